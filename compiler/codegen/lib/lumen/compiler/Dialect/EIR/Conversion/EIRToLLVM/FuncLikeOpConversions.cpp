@@ -87,12 +87,7 @@ struct ClosureOpConversion : public EIROpConversion<ClosureOp> {
 
     auto loc = ctx.getLoc();
 
-    assert(op.isAnonymous() && "expected anonymous closures only");
-
     unsigned arity = op.arity();
-    unsigned index = op.index();
-    unsigned oldUnique = op.oldUnique();
-    StringRef unique = op.unique();
     FlatSymbolRefAttr callee = op.calleeAttr();
 
     LLVMType termTy = ctx.getUsizeType();
@@ -117,6 +112,14 @@ struct ClosureOpConversion : public EIROpConversion<ClosureOp> {
     LLVMType uniquePtrTy = uniqueTy.getPointerTo();
     LLVMType defTy = ctx.targetInfo.getClosureDefinitionType();
 
+    // Closures are always called with the closure as the first argument, but
+    // functions without an environment don't take one, so those are called
+    // through a trampoline which drops it
+    if (envLen == 0) {
+      target = getOrInsertTrampoline(ctx, target, closureTy.getPointerTo(),
+                                     argTypes);
+    }
+
     // Allocate closure header block
     auto boxedClosureTy = BoxType::get(rewriter.getType<ClosureType>());
     Value arityConst = llvm_constant(termTy, ctx.getIntegerAttr(arity));
@@ -151,30 +154,51 @@ struct ClosureOpConversion : public EIROpConversion<ClosureOp> {
     Value defTypeIdx = llvm_constant(i32Ty, ctx.getI32Attr(0));
     ArrayRef<Value> defTypeIndices({zero, defIdx, defTypeIdx});
     Value definitionTypePtrGep = llvm_gep(i8PtrTy, valRef, defTypeIndices);
-    Value anonTypeConst = llvm_constant(i8Ty, ctx.getI8Attr(1));
-    llvm_store(anonTypeConst, definitionTypePtrGep);
-
-    // Definition - index
-    Value defIndexIdx = llvm_constant(i32Ty, ctx.getI32Attr(1));
-    ArrayRef<Value> defIndexIndices({zero, defIdx, defIndexIdx});
-    Value definitionIndexGep = llvm_gep(termPtrTy, valRef, defIndexIndices);
-    Value indexConst = llvm_constant(termTy, ctx.getIntegerAttr(index));
-    llvm_store(indexConst, definitionIndexGep);
-
-    // Definition - unique
-    Value defUniqueIdx = llvm_constant(i32Ty, ctx.getI32Attr(2));
-    ArrayRef<Value> defUniqueIndices({zero, defIdx, defUniqueIdx});
-    Value definitionUniqueGep = llvm_gep(uniquePtrTy, valRef, defUniqueIndices);
-    Value uniqueConst = llvm_constant(uniqueTy, ctx.getStringAttr(unique));
-    llvm_store(uniqueConst, definitionUniqueGep);
-
-    // Definition - old_unique
-    Value defOldUniqueIdx = llvm_constant(i32Ty, ctx.getI32Attr(3));
-    ArrayRef<Value> defOldUniqueIndices({zero, defIdx, defOldUniqueIdx});
-    Value definitionOldUniqueGep =
-        llvm_gep(i32PtrTy, valRef, defOldUniqueIndices);
-    Value oldUniqueConst = llvm_constant(i32Ty, ctx.getI32Attr(oldUnique));
-    llvm_store(oldUniqueConst, definitionOldUniqueGep);
+
+    if (op.isAnonymous()) {
+      unsigned index = op.index();
+      unsigned oldUnique = op.oldUnique();
+      StringRef unique = op.unique();
+
+      Value anonTypeConst = llvm_constant(i8Ty, ctx.getI8Attr(1));
+      llvm_store(anonTypeConst, definitionTypePtrGep);
+
+      // Definition - index
+      Value defIndexIdx = llvm_constant(i32Ty, ctx.getI32Attr(1));
+      ArrayRef<Value> defIndexIndices({zero, defIdx, defIndexIdx});
+      Value definitionIndexGep = llvm_gep(termPtrTy, valRef, defIndexIndices);
+      Value indexConst = llvm_constant(termTy, ctx.getIntegerAttr(index));
+      llvm_store(indexConst, definitionIndexGep);
+
+      // Definition - unique
+      Value defUniqueIdx = llvm_constant(i32Ty, ctx.getI32Attr(2));
+      ArrayRef<Value> defUniqueIndices({zero, defIdx, defUniqueIdx});
+      Value definitionUniqueGep =
+          llvm_gep(uniquePtrTy, valRef, defUniqueIndices);
+      Value uniqueConst = llvm_constant(uniqueTy, ctx.getStringAttr(unique));
+      llvm_store(uniqueConst, definitionUniqueGep);
+
+      // Definition - old_unique
+      Value defOldUniqueIdx = llvm_constant(i32Ty, ctx.getI32Attr(3));
+      ArrayRef<Value> defOldUniqueIndices({zero, defIdx, defOldUniqueIdx});
+      Value definitionOldUniqueGep =
+          llvm_gep(i32PtrTy, valRef, defOldUniqueIndices);
+      Value oldUniqueConst = llvm_constant(i32Ty, ctx.getI32Attr(oldUnique));
+      llvm_store(oldUniqueConst, definitionOldUniqueGep);
+    } else {
+      Value exportTypeConst = llvm_constant(i8Ty, ctx.getI8Attr(0));
+      llvm_store(exportTypeConst, definitionTypePtrGep);
+
+      // Definition - function
+      Value defFunctionIdx = llvm_constant(i32Ty, ctx.getI32Attr(1));
+      ArrayRef<Value> defFunctionIndices({zero, defIdx, defFunctionIdx});
+      Value definitionFunctionGep =
+          llvm_gep(termPtrTy, valRef, defFunctionIndices);
+      Value functionConst = llvm_constant(
+          termTy,
+          ctx.getIntegerAttr(op.function().getValue().getLimitedValue()));
+      llvm_store(functionConst, definitionFunctionGep);
+    }
 
     // Arity
     // arity: u8,
@@ -205,6 +229,7 @@ struct ClosureOpConversion : public EIROpConversion<ClosureOp> {
         ArrayRef<Value> opIndices({zero, envIdx, opIdx});
         Value opPtrGep = llvm_gep(termPtrTy, valRef, opIndices);
         llvm_store(operand, opPtrGep);
+        opIndex++;
       }
     }
 
@@ -214,6 +239,50 @@ struct ClosureOpConversion : public EIROpConversion<ClosureOp> {
     rewriter.replaceOp(op, boxed);
     return success();
   }
+
+ private:
+  // Returns a function which takes the closure followed by the arguments of
+  // `target`, and tail calls `target` with just the arguments.
+  //
+  // The trampoline is linkonce_odr so that every module capturing the same
+  // function shares one code address, which closure equality depends on
+  static Operation *getOrInsertTrampoline(
+      RewritePatternContext<ClosureOp> &ctx, Operation *target,
+      LLVMType closurePtrTy, ArrayRef<LLVMType> argTypes) {
+    ModuleOp mod = ctx.getModule();
+    auto targetName = SymbolTable::getSymbolName(target);
+    auto trampolineName = (targetName + "$trampoline").str();
+    if (auto trampoline = mod.lookupSymbol<LLVM::LLVMFuncOp>(trampolineName))
+      return trampoline;
+
+    auto &rewriter = ctx.rewriter;
+    auto loc = mod.getLoc();
+    LLVMType termTy = ctx.getUsizeType();
+
+    SmallVector<LLVMType, 3> trampolineArgTypes;
+    trampolineArgTypes.push_back(closurePtrTy);
+    trampolineArgTypes.append(argTypes.begin(), argTypes.end());
+    auto fnTy =
+        LLVMType::getFunctionTy(termTy, trampolineArgTypes, /*isVarArg=*/false);
+
+    PatternRewriter::InsertionGuard insertGuard(rewriter);
+    rewriter.setInsertionPointToStart(mod.getBody());
+    auto trampoline = rewriter.create<LLVM::LLVMFuncOp>(
+        loc, trampolineName, fnTy, LLVM::Linkage::LinkonceODR);
+
+    Block *entry = trampoline.addEntryBlock();
+    rewriter.setInsertionPointToStart(entry);
+    SmallVector<Value, 2> args(std::next(entry->args_begin()),
+                               entry->args_end());
+    auto calleeAttr = rewriter.getNamedAttr(
+        "callee", FlatSymbolRefAttr::get(targetName, rewriter.getContext()));
+    auto callOp = rewriter.create<LLVM::CallOp>(
+        loc, termTy, args, ArrayRef<NamedAttribute>{calleeAttr});
+    callOp.setAttr("tail", rewriter.getUnitAttr());
+    rewriter.create<LLVM::ReturnOp>(loc, callOp.getResult(0));
+
+    return trampoline;
+  }
 };
 
 struct UnpackEnvOpConversion : public EIROpConversion<UnpackEnvOp> {
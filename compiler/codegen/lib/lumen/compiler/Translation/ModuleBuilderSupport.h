@@ -120,6 +120,8 @@ struct KeyValuePair {
 struct Closure {
   MLIRLocationRef loc;
   MLIRAttributeRef module;
+  // The function atom of an export closure, null for anonymous closures
+  MLIRAttributeRef function;
   char *name;
   uint8_t arity;
   uint32_t index;
@@ -10,18 +10,19 @@ pub(super) mod value;
 use std::cell::RefCell;
 use std::collections::HashSet;
 use std::ffi::{CStr, CString};
+use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::anyhow;
 
 use log::debug;
 
-use libeir_diagnostics::{ByteIndex, FileMap};
+use libeir_diagnostics::{ByteIndex, ByteSpan, FileMap};
 use libeir_intern::Symbol;
 use libeir_ir as ir;
 
 use liblumen_core::symbols::FunctionSymbol;
-use liblumen_session::Options;
+use liblumen_session::{IRModule, Options};
 
 use liblumen_llvm::target::{TargetMachine, TargetMachineRef};
 use liblumen_mlir::{Context, Dialect, Module};
@@ -36,11 +37,13 @@ pub struct GeneratedModule {
     pub module: Module,
     pub atoms: HashSet<Symbol>,
     pub symbols: HashSet<FunctionSymbol>,
+    /// The arities of the dynamic calls made by the module
+    pub dynamic_calls: HashSet<u8>,
 }
 
 /// Constructs an MLIR module from an EIR module, using the provided context and options
 pub fn build(
-    module: &ir::Module,
+    module: &IRModule,
     filemap: Arc<FileMap>,
     context: &Context,
     options: &Options,
@@ -48,15 +51,46 @@ pub fn build(
 ) -> Result<GeneratedModule> {
     debug!("building mlir module for {}", module.name());
 
-    let builder = ModuleBuilder::new(module, filemap, context, target_machine.as_ref());
+    // The file name is embedded in debug info, so it is remapped here
+    let source_filename = options.remap_path(Path::new(&filemap.name().to_string()));
+    let builder = ModuleBuilder::new(
+        module,
+        module.remote_captures().clone(),
+        filemap,
+        &source_filename.to_string_lossy(),
+        context,
+        target_machine.as_ref(),
+    );
     return builder.build(options);
 }
 
+/// Interns the symbols which building `module` would otherwise intern
+///
+/// The id of an atom is the index of its interned symbol, so for atom ids to be the same from one
+/// build to the next, symbols must be interned in the same order. This is called for each module
+/// in turn before they are built in parallel, which would otherwise intern the names of lifted
+/// closures in whatever order the worker threads get to them.
+pub fn intern_symbols(module: &ir::Module) {
+    for def in module.function_iter() {
+        let f = def.function();
+        let ident = f.ident();
+        let analysis = libeir_lowerutils::analyze(f);
+        let root_block = f.block_entry();
+        for (index, (entry_block, _data)) in analysis.functions.iter().enumerate() {
+            if *entry_block != root_block {
+                let arity = f.block_args(*entry_block).len() - 2;
+                function::closure_ident(ident, index, arity);
+            }
+        }
+    }
+}
+
 /// This builder holds the state necessary to build an MLIR module
 /// from an EIR module.
 ///
-/// It maintains a module-local atom table, and a table of
-/// function symbols created during the build. These are later
+/// It maintains a module-local atom table, a table of
+/// function symbols created during the build, and the arities
+/// of the dynamic calls made by the module. These are later
 /// combined with the same tables of other modules to form a
 /// global set of atoms and symbols.
 pub struct ModuleBuilder<'m> {
@@ -64,6 +98,8 @@ pub struct ModuleBuilder<'m> {
     module: &'m ir::Module,
     atoms: RefCell<HashSet<Symbol>>,
     symbols: RefCell<HashSet<FunctionSymbol>>,
+    dynamic_calls: RefCell<HashSet<u8>>,
+    remote_captures: Arc<HashSet<ByteSpan>>,
     filemap: Arc<FileMap>,
     source_filename: CString,
 }
@@ -77,13 +113,15 @@ impl<'m> ModuleBuilder<'m> {
     /// Creates a new builder for the given EIR module, using the provided MLIR context
     pub fn new(
         module: &'m ir::Module,
+        remote_captures: Arc<HashSet<ByteSpan>>,
         filemap: Arc<FileMap>,
+        source_filename: &str,
         context: &Context,
         target_machine: TargetMachineRef,
     ) -> Self {
         use ffi::MLIRCreateModuleBuilder;
 
-        let source_filename = CString::new(filemap.name().to_string()).unwrap();
+        let source_filename = CString::new(source_filename).unwrap();
         let (li, ci) = filemap
             .location(module.span().start())
             .expect("expected source filename for module");
@@ -111,11 +149,19 @@ impl<'m> ModuleBuilder<'m> {
             module,
             atoms: RefCell::new(atoms),
             symbols: RefCell::new(HashSet::new()),
+            dynamic_calls: RefCell::new(HashSet::new()),
+            remote_captures,
             filemap,
             source_filename,
         }
     }
 
+    /// The spans of the function captures in the module which name a module
+    #[inline]
+    pub fn remote_captures(&self) -> &Arc<HashSet<ByteSpan>> {
+        &self.remote_captures
+    }
+
     #[inline]
     pub fn filename(&self) -> &CStr {
         self.source_filename.as_c_str()
@@ -167,6 +213,7 @@ impl<'m> ModuleBuilder<'m> {
             module: Module::new(result, Dialect::EIR),
             atoms: self.atoms.into_inner(),
             symbols: self.symbols.into_inner(),
+            dynamic_calls: self.dynamic_calls.into_inner(),
         })
     }
 
@@ -189,4 +236,9 @@ impl<'m> ModuleBuilder<'m> {
     pub fn symbols_mut(&self) -> core::cell::RefMut<HashSet<FunctionSymbol>> {
         self.symbols.borrow_mut()
     }
+
+    /// Returns the set of arities of the dynamic calls found in this module, mutably
+    pub fn dynamic_calls_mut(&self) -> core::cell::RefMut<HashSet<u8>> {
+        self.dynamic_calls.borrow_mut()
+    }
 }
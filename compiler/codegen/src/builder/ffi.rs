@@ -73,6 +73,8 @@ pub struct FunctionDeclResult {
 pub struct Closure {
     pub loc: LocationRef,
     pub module: AttributeRef,
+    /// The function atom of an export closure, null for anonymous closures
+    pub function: AttributeRef,
     pub name: *const libc::c_char,
     pub arity: u8,
     pub index: u32,
@@ -1,6 +1,8 @@
 mod function;
 pub use self::function::*;
 
+use std::collections::HashSet;
+use std::convert::TryFrom;
 use std::mem;
 use std::ptr;
 use std::sync::Arc;
@@ -9,10 +11,10 @@ use anyhow::anyhow;
 
 use log::debug;
 
-use libeir_diagnostics::{ByteIndex, FileMap};
+use libeir_diagnostics::{ByteIndex, ByteSpan, FileMap};
 use libeir_intern::{Ident, Symbol};
 use libeir_ir as ir;
-use libeir_ir::{AtomTerm, AtomicTerm, ConstKind, FunctionIdent};
+use libeir_ir::{AtomTerm, AtomicTerm, ConstKind, FunctionIdent, IntTerm};
 use libeir_lowerutils::{FunctionData, LowerData};
 use libeir_util_datastructures::pooled_entity_set::BoundEntitySet;
 
@@ -63,6 +65,18 @@ impl<'a, 'm, 'f> FunctionBuilder<'a, 'm, 'f> {
             }
         }
 
+        // Gather the arities of the dynamic calls in this function, for which dispatch functions
+        // are generated with the symbol table
+        for (_, data) in analysis.functions.iter() {
+            for block in data.scope.iter().copied() {
+                for value in f.block_reads(block).iter().copied() {
+                    if let Some(arity) = dynamic_call_arity(f, value)? {
+                        self.builder.dynamic_calls_mut().insert(arity);
+                    }
+                }
+            }
+        }
+
         let root_block = f.block_entry();
         for (index, (entry_block, data)) in analysis.functions.iter().enumerate() {
             let entry_block = *entry_block;
@@ -71,12 +85,7 @@ impl<'a, 'm, 'f> FunctionBuilder<'a, 'm, 'f> {
                     .and_then(|scope| scope.build())?
             } else {
                 let arity = f.block_args(entry_block).len() - 2;
-                let fun = Ident::from_str(&format!("{}-fun-{}-{}", ident.name, index, arity));
-                let fi = FunctionIdent {
-                    module: ident.module.clone(),
-                    name: fun,
-                    arity,
-                };
+                let fi = closure_ident(ident, index, arity);
                 {
                     self.builder.atoms_mut().insert(fi.name.name);
                 }
@@ -163,6 +172,7 @@ impl<'a, 'm, 'f> FunctionBuilder<'a, 'm, 'f> {
         Ok(ScopedFunctionBuilder {
             filemap: self.builder.filemap().clone(),
             filename: self.builder.filename().as_ptr(),
+            remote_captures: self.builder.remote_captures().clone(),
             func,
             eir,
             mlir,
@@ -183,6 +193,7 @@ impl<'a, 'm, 'f> FunctionBuilder<'a, 'm, 'f> {
 pub struct ScopedFunctionBuilder<'f, 'o> {
     filename: *const libc::c_char,
     filemap: Arc<FileMap>,
+    remote_captures: Arc<HashSet<ByteSpan>>,
     func: Function,
     eir: &'f ir::Function,
     mlir: FunctionOpRef,
@@ -289,15 +300,7 @@ impl<'f, 'o> ScopedFunctionBuilder<'f, 'o> {
                 let containing_ident = self.eir.ident();
                 let arity = self.eir.block_args(entry_block).len() - 2;
                 let index = i as u32;
-                let fun = Ident::from_str(&format!(
-                    "{}-fun-{}-{}",
-                    containing_ident.name, index, arity
-                ));
-                let ident = FunctionIdent {
-                    module: containing_ident.module.clone(),
-                    name: fun,
-                    arity,
-                };
+                let ident = closure_ident(containing_ident, i, arity);
                 let unique = unsafe {
                     mem::transmute::<[u64; 2], [u8; 16]>([
                         fxhash::hash64(&ident),
@@ -316,6 +319,26 @@ impl<'f, 'o> ScopedFunctionBuilder<'f, 'o> {
         panic!("expected block to correspond to the entry block in this functions' scope");
     }
 
+    /// Computes the closure definition used when a local function is captured by
+    /// reference, i.e. `fun foo/1`.
+    ///
+    /// Like `block_to_closure_info`, the result is derived purely from the identifier
+    /// of the captured function, so every capture of the same function produces a
+    /// closure that compares equal to the others.
+    pub fn function_ref_closure_info(&self, callee: &FunctionIdent) -> ClosureInfo {
+        let index = fxhash::hash32(callee);
+        let unique = unsafe {
+            mem::transmute::<[u64; 2], [u8; 16]>([fxhash::hash64(callee), fxhash::hash64(&index)])
+        };
+        let old_unique = fxhash::hash32(&unique);
+        ClosureInfo {
+            ident: *callee,
+            index,
+            old_unique,
+            unique,
+        }
+    }
+
     /// Finds the EIR value the given value represents
     ///
     /// Panics if the value does not have an EIR representation
@@ -526,6 +549,16 @@ impl<'f, 'o> ScopedFunctionBuilder<'f, 'o> {
         self.func.name().module.name == m
     }
 
+    /// Returns true if the given function capture names a module, i.e. it is `fun m:f/1` or
+    /// `fun ?MODULE:f/1` rather than `fun f/1`
+    pub fn is_remote_capture(&self, ir_value: ir::Value) -> bool {
+        self.eir
+            .value_locations(ir_value)
+            .and_then(|locs| locs.first())
+            .map(|span| self.remote_captures.contains(span))
+            .unwrap_or(false)
+    }
+
     /// Returns the current block arguments as values
     #[inline]
     pub fn block_args(&self, block: Block) -> Vec<Value> {
@@ -1395,6 +1428,52 @@ impl Position {
     }
 }
 
+/// Returns the identifier of the function lifted from the `index`th scope of `containing`
+pub(super) fn closure_ident(
+    containing: &FunctionIdent,
+    index: usize,
+    arity: usize,
+) -> FunctionIdent {
+    let fun = Ident::from_str(&format!("{}-fun-{}-{}", containing.name, index, arity));
+    FunctionIdent {
+        module: containing.module.clone(),
+        name: fun,
+        arity,
+    }
+}
+
+/// Returns the arity of the function captured by `value` if its module or function is only known
+/// at runtime, as a call to it is made through the dispatch function for that arity
+///
+/// Fails if the arity is more than the 255 arguments a function can have, as there is no dispatch
+/// function for it.
+fn dynamic_call_arity(function: &ir::Function, value: ir::Value) -> Result<Option<u8>> {
+    let primop = match function.value_primop(value) {
+        Some(primop) => primop,
+        None => return Ok(None),
+    };
+    if *function.primop_kind(primop) != ir::PrimOpKind::CaptureFunction {
+        return Ok(None);
+    }
+    let reads = function.primop_reads(primop);
+    let is_const = |value: ir::Value| function.value_const(value).is_some();
+    if is_const(reads[0]) && is_const(reads[1]) {
+        return Ok(None);
+    }
+    match value_to_const_kind(function, reads[2]) {
+        ConstKind::Atomic(AtomicTerm::Int(IntTerm(arity))) => match u8::try_from(*arity) {
+            Ok(arity) => Ok(Some(arity)),
+            Err(_) => Err(anyhow!(
+                "invalid dynamic call in {}: arity {} is more than the maximum of {}",
+                function.ident(),
+                arity,
+                u8::MAX
+            )),
+        },
+        _ => Ok(None),
+    }
+}
+
 /// Shared helper to map an EIR value to its constant kind
 pub(super) fn value_to_const_kind<'f>(
     function: &'f ir::Function,
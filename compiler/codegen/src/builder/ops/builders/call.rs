@@ -1,7 +1,11 @@
 use std::ffi::CString;
+use std::ptr;
 
 use super::*;
 
+use crate::builder::traits::*;
+use crate::generators::dynamic_call_symbol;
+
 pub struct CallBuilder;
 
 impl CallBuilder {
@@ -43,7 +47,7 @@ impl CallBuilder {
         };
         builder.debug(&format!("call err args: {:?}", err_args.as_slice()));
 
-        match op.callee {
+        let (name, args) = match op.callee {
             Callee::ClosureDynamic(closure) => {
                 builder.debug("call target is closure");
 
@@ -65,48 +69,153 @@ impl CallBuilder {
                     );
                 }
 
-                Ok(None)
+                return Ok(None);
             }
             Callee::Static(ref ident) => {
                 builder.debug(&format!("static call target is {}", ident));
 
-                let name = CString::new(ident.to_string()).unwrap();
-                unsafe {
-                    MLIRBuildStaticCall(
-                        builder.as_ref(),
-                        op.loc,
-                        name.as_ptr(),
-                        args.as_ptr(),
-                        args.len() as libc::c_uint,
-                        op.is_tail,
-                        ok_block,
-                        ok_args.as_ptr(),
-                        ok_args.len() as libc::c_uint,
-                        err_block,
-                        err_args.as_ptr(),
-                        err_args.len() as libc::c_uint,
-                    );
-                }
+                (CString::new(ident.to_string()).unwrap(), args)
+            }
+            // Calls where the module and/or function are only known at runtime are
+            // static calls to the dispatch function for the arity generated with the
+            // symbol table, which takes the module and function ahead of the arguments.
+            // It resolves the callee in the runtime dispatch table, raising `undef` if
+            // it doesn't exist.
+            Callee::LocalDynamic {
+                module,
+                function,
+                arity,
+            } => {
+                builder.debug(&format!("dynamic call target is {}:_/{}", module, arity));
 
-                Ok(None)
+                let module_ref =
+                    module
+                        .name
+                        .as_value_ref(op.loc, builder.as_ref(), builder.options())?;
+                let function_ref = builder.value_ref(function);
+                let name = CString::new(dynamic_call_symbol(arity)).unwrap();
+                (name, dynamic_call_args(module_ref, function_ref, args))
             }
-            callee => todo!("unimplemented call type {:?}", callee),
+            Callee::GlobalDynamic {
+                module,
+                function,
+                arity,
+            } => {
+                builder.debug(&format!("dynamic call target is _:_/{}", arity));
+
+                let module_ref = builder.value_ref(module);
+                let function_ref = builder.value_ref(function);
+                let name = CString::new(dynamic_call_symbol(arity)).unwrap();
+                (name, dynamic_call_args(module_ref, function_ref, args))
+            }
+        };
+
+        unsafe {
+            MLIRBuildStaticCall(
+                builder.as_ref(),
+                op.loc,
+                name.as_ptr(),
+                args.as_ptr(),
+                args.len() as libc::c_uint,
+                op.is_tail,
+                ok_block,
+                ok_args.as_ptr(),
+                ok_args.len() as libc::c_uint,
+                err_block,
+                err_args.as_ptr(),
+                err_args.len() as libc::c_uint,
+            );
         }
+
+        Ok(None)
     }
 }
 
+fn dynamic_call_args(module: ValueRef, function: ValueRef, args: Vec<ValueRef>) -> Vec<ValueRef> {
+    let mut dispatch_args = Vec::with_capacity(args.len() + 2);
+    dispatch_args.push(module);
+    dispatch_args.push(function);
+    dispatch_args.extend(args);
+    dispatch_args
+}
+
 pub struct CalleeBuilder;
 
 impl CalleeBuilder {
+    /// Lowers a function reference, i.e. `fun foo/1` or `fun lists:map/2`, to a closure.
+    ///
+    /// Local captures become anonymous closures with an empty environment, while
+    /// remote captures become export closures. In both cases the closure is callable
+    /// through the usual closure call path.
+    ///
+    /// A capture naming its own module, e.g. `fun ?MODULE:foo/1`, is still a remote
+    /// capture, as in BEAM, so it is an export closure that doesn't compare equal to
+    /// `fun foo/1`. EIR lowers both to the same capture, so which captures name a
+    /// module is recorded when the module is lowered.
     pub fn build<'f, 'o>(
-        _builder: &mut ScopedFunctionBuilder<'f, 'o>,
+        builder: &mut ScopedFunctionBuilder<'f, 'o>,
         ir_value: Option<ir::Value>,
         op: FunctionRef,
     ) -> Result<Option<Value>> {
-        todo!(
-            "build function reference constant for {:?} = {:?}",
-            ir_value,
-            &op.callee
-        );
+        let ident = match op.callee {
+            Callee::Static(ident) => ident,
+            ref callee => {
+                return Err(anyhow!(
+                    "unsupported function reference to dynamic callee {:?}",
+                    callee
+                ))
+            }
+        };
+        builder.debug(&format!("function reference to {}", &ident));
+
+        let name = CString::new(ident.to_string()).unwrap();
+        let builder_ref = builder.as_ref();
+        let module_ref =
+            ident
+                .module
+                .name
+                .as_attribute_ref(op.loc, builder_ref, builder.options())?;
+
+        let is_local = builder.is_current_module(ident.module.name)
+            && !ir_value
+                .map(|v| builder.is_remote_capture(v))
+                .unwrap_or(false);
+        let closure = if is_local {
+            let info = builder.function_ref_closure_info(&ident);
+            Closure {
+                loc: op.loc,
+                module: module_ref,
+                function: Default::default(),
+                name: name.as_ptr(),
+                arity: ident.arity as u8,
+                index: info.index,
+                old_unique: info.old_unique,
+                unique: info.unique,
+                env: ptr::null(),
+                env_len: 0,
+            }
+        } else {
+            let function_ref =
+                ident
+                    .name
+                    .name
+                    .as_attribute_ref(op.loc, builder_ref, builder.options())?;
+            Closure {
+                loc: op.loc,
+                module: module_ref,
+                function: function_ref,
+                name: name.as_ptr(),
+                arity: ident.arity as u8,
+                index: 0,
+                old_unique: 0,
+                unique: [0; 16],
+                env: ptr::null(),
+                env_len: 0,
+            }
+        };
+
+        let result_ref = unsafe { MLIRBuildClosure(builder_ref, &closure) };
+        let result = builder.new_value(ir_value, result_ref, ValueDef::Result(0));
+        Ok(Some(result))
     }
 }
@@ -35,6 +35,7 @@ impl ClosureBuilder {
         let closure = Closure {
             loc,
             module: module_ref,
+            function: Default::default(),
             name: name.as_ptr(),
             arity: ident.arity as u8,
             index: info.index,
@@ -13,6 +13,8 @@ use liblumen_llvm::Context;
 use crate::meta::CodegenResults;
 use crate::Result;
 
+pub use self::symbol_table::{dynamic_call_symbol, symbol_of};
+
 pub fn run(
     result: &mut CodegenResults,
     context: &Context,
@@ -20,11 +22,13 @@ pub fn run(
     output_dir: &Path,
     atoms: HashSet<Symbol>,
     symbols: HashSet<FunctionSymbol>,
+    dynamic_calls: HashSet<u8>,
 ) -> Result<()> {
     let atom_table = atom_table::generate(context, target_machine, atoms, output_dir)?;
     result.modules.push(atom_table);
 
-    let symbol_table = symbol_table::generate(context, target_machine, symbols, output_dir)?;
+    let symbol_table =
+        symbol_table::generate(context, target_machine, symbols, dynamic_calls, output_dir)?;
     result.modules.push(symbol_table);
 
     Ok(())
@@ -60,6 +60,10 @@ pub fn generate(
         Ok(constant)
     }
 
+    // The table is ordered by id, so that its layout is the same from one build to the next
+    let mut atoms = atoms.drain().collect::<Vec<_>>();
+    atoms.sort_by_key(|atom| atom.as_usize());
+
     // Generate globals/constants for each atom
     let mut values = Vec::with_capacity(atoms.len());
     for atom in atoms.iter().copied() {
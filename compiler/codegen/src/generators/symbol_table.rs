@@ -17,6 +17,17 @@ use liblumen_llvm::target::TargetMachine;
 use crate::meta::CompiledModule;
 use crate::Result;
 
+/// Returns the name of the function generated by this module which dispatches dynamic
+/// calls to functions of the given arity
+pub fn dynamic_call_symbol(arity: usize) -> String {
+    format!("__lumen_dynamic_call_{}", arity)
+}
+
+/// Returns the symbol with the given id, as found in a `FunctionSymbol`
+pub fn symbol_of(id: usize) -> Symbol {
+    unsafe { mem::transmute::<u32, Symbol>(id as u32) }
+}
+
 /// Generates an LLVM module containing the raw symbol table data for the current build
 ///
 /// This is similar to the atom table generation, but simpler, in that we just generate
@@ -28,6 +39,7 @@ pub fn generate(
     context: &llvm::Context,
     target_machine: &TargetMachine,
     symbols: HashSet<FunctionSymbol>,
+    dynamic_calls: HashSet<u8>,
     output_dir: &Path,
 ) -> Result<Arc<CompiledModule>> {
     const NAME: &'static str = "liblumen_crt_dispatch";
@@ -38,11 +50,9 @@ pub fn generate(
         builder: &ModuleBuilder<'ctx>,
         symbol: &FunctionSymbol,
     ) -> Result<llvm::Value> {
-        let ms = unsafe { mem::transmute::<u32, Symbol>(symbol.module as u32) };
-        let fs = unsafe { mem::transmute::<u32, Symbol>(symbol.function as u32) };
         let ident = FunctionIdent {
-            module: Ident::with_empty_span(ms),
-            name: Ident::with_empty_span(fs),
+            module: Ident::with_empty_span(symbol_of(symbol.module)),
+            name: Ident::with_empty_span(symbol_of(symbol.function)),
             arity: symbol.arity as usize,
         };
         let name = CString::new(ident.to_string()).unwrap();
@@ -59,6 +69,16 @@ pub fn generate(
         &[usize_type, usize_type, i8_type, fn_ptr_type],
     );
 
+    // The table is ordered by name, so that its layout is the same from one build to the next
+    let mut symbols = symbols.into_iter().collect::<Vec<_>>();
+    symbols.sort_by_cached_key(|symbol| {
+        (
+            symbol_of(symbol.module).as_str().get().to_string(),
+            symbol_of(symbol.function).as_str().get().to_string(),
+            symbol.arity,
+        )
+    });
+
     // Build values for array
     let mut functions = Vec::with_capacity(symbols.len());
     for symbol in symbols.iter() {
@@ -113,6 +133,81 @@ pub fn generate(
     builder.set_linkage(reduction_count_global, Linkage::External);
     builder.set_alignment(reduction_count_global, 8);
 
+    // Generate the functions used to dispatch dynamic calls, i.e. `M:F(Args)` where
+    // `M` or `F` are only known at runtime. There is one for each arity called this way
+    // in the build, taking the module and function followed by the arguments. Each
+    // resolves the callee in the dispatch table and tail calls it with the arguments.
+    // When the callee doesn't exist, it instead passes the module, function and
+    // arguments to the runtime to raise `undef`, with the arguments in an array, as
+    // there is no function to call with them.
+    let resolve_name = CString::new("__lumen_builtin_resolve").unwrap();
+    let resolve_ty = builder.get_function_type(
+        fn_ptr_type,
+        &[usize_type, usize_type, i8_type],
+        /* variadic= */ false,
+    );
+    let resolve_fn_decl = builder.build_function(&resolve_name, resolve_ty);
+    builder.set_linkage(resolve_fn_decl, Linkage::External);
+    let term_type = builder.get_term_type();
+    let raise_undef_name = CString::new("__lumen_builtin_raise_undef").unwrap();
+    let raise_undef_ty = builder.get_function_type(
+        builder.get_void_type(),
+        &[
+            term_type,
+            term_type,
+            usize_type,
+            builder.get_pointer_type(term_type),
+        ],
+        /* variadic= */ false,
+    );
+    let raise_undef_fn_decl = builder.build_function(&raise_undef_name, raise_undef_ty);
+    builder.set_linkage(raise_undef_fn_decl, Linkage::External);
+    let mut dynamic_calls = dynamic_calls.into_iter().collect::<Vec<_>>();
+    dynamic_calls.sort();
+    for arity in dynamic_calls {
+        let arity = arity as usize;
+        let dispatch_name = CString::new(dynamic_call_symbol(arity)).unwrap();
+        let dispatch_ty = builder.get_erlang_function_type(arity + 2);
+        let dispatch_fn = builder.build_function(&dispatch_name, dispatch_ty);
+        builder.set_linkage(dispatch_fn, Linkage::External);
+        let entry_block = builder.build_entry_block(dispatch_fn);
+        let call_block = builder.build_block(dispatch_fn, "call");
+        let undef_block = builder.build_block(dispatch_fn, "undef");
+        builder.position_at_end(entry_block);
+        let dispatch_args = builder.get_function_params(dispatch_fn);
+        let arity_const = builder.build_constant_uint(i8_type, arity);
+        let resolved = builder.build_call(
+            resolve_fn_decl,
+            resolve_ty,
+            &[dispatch_args[0], dispatch_args[1], arity_const],
+        );
+        let is_undef = builder.build_is_null(resolved);
+        builder.build_condbr(is_undef, undef_block, call_block);
+
+        builder.position_at_end(undef_block);
+        let argv_ty = builder.get_array_type(arity, term_type);
+        let argv = builder.build_alloca(argv_ty);
+        for (i, arg) in dispatch_args[2..].iter().copied().enumerate() {
+            let arg_ptr = builder.build_inbounds_gep(argv_ty, argv, &[0, i]);
+            builder.build_store(arg, arg_ptr);
+        }
+        let argv_ptr = builder.build_inbounds_gep(argv_ty, argv, &[0, 0]);
+        let argc = builder.build_constant_uint(usize_type, arity);
+        builder.build_call(
+            raise_undef_fn_decl,
+            raise_undef_ty,
+            &[dispatch_args[0], dispatch_args[1], argc, argv_ptr],
+        );
+        builder.build_unreachable();
+
+        builder.position_at_end(call_block);
+        let callee_ty = builder.get_erlang_function_type(arity);
+        let callee = builder.build_bitcast(resolved, builder.get_pointer_type(callee_ty));
+        let call = builder.build_call(callee, callee_ty, &dispatch_args[2..]);
+        builder.set_is_tail(call, true);
+        builder.build_return(call);
+    }
+
     // We have to build a shim for the Rust libstd `lang_start_internal`
     // function to start the Rust runtime. Since that symbol is internal,
     // we locate the mangled symbol name at build time and build a shim
@@ -396,24 +396,17 @@ fn use_system_linker(
             if !prog.status.success() {
                 let mut output = prog.stderr.clone();
                 output.extend_from_slice(&prog.stdout);
-                diagnostics.error_str(&format!(
-                    "linking with `{}` failed: {}\n\
-                     \n\
-                     {:?}\n\
-                     \n\
-                     {}",
-                    pname.display(),
-                    prog.status,
-                    &cmd,
-                    &escape_string(&output)
-                ));
+                diagnostics.error_with_notes(
+                    &format!("linking with `{}` failed: {}", pname.display(), prog.status),
+                    &[format!("{:?}", &cmd), escape_string(&output)],
+                );
                 diagnostics.abort_if_errors();
             }
         }
         Err(e) => {
             let linker_not_found = e.kind() == io::ErrorKind::NotFound;
 
-            let mut linker_error = {
+            let linker_error = {
                 if linker_not_found {
                     format!("linker `{}` not found", pname.display())
                 } else {
@@ -421,13 +414,13 @@ fn use_system_linker(
                 }
             };
 
-            linker_error = format!("{}\n\n{}", linker_error, &e.to_string());
+            let mut notes = vec![e.to_string()];
 
             if !linker_not_found {
-                linker_error = format!("{}\n\n{:?}", linker_error, &cmd);
+                notes.push(format!("{:?}", &cmd));
             }
 
-            diagnostics.error_str(&linker_error);
+            diagnostics.error_with_notes(&linker_error, &notes);
 
             if options.target.options.is_like_msvc && linker_not_found {
                 warn!(
@@ -1,13 +1,165 @@
 extern crate which;
 
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
+/// The crates whose modules each keep their native functions in a directory named for the module
+const NATIVE_FUNCTION_CRATES: &[&str] = &[
+    "native_implemented_functions/otp",
+    "native_implemented_functions/web",
+];
+
+/// The runtime whose native functions are exported under their `module:function/arity` symbol
+const NATIVE_SYMBOL_CRATE: &str = "runtimes/minimal";
+
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
 
     let (hash, hash_date) = git_version();
     println!("cargo:rustc-env=LUMEN_COMMIT_HASH={}", hash);
     println!("cargo:rustc-env=LUMEN_COMMIT_DATE={}", hash_date);
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    write_native_functions(&out_dir.join("native_functions.rs"));
+}
+
+type FunctionArity = (String, usize);
+
+/// Writes the functions implemented natively by the runtimes to `path`, as a slice of
+/// `(module, function, arity)` for `xref` to include
+fn write_native_functions(path: &Path) {
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("../..");
+    let mut functions = BTreeSet::new();
+
+    for krate in NATIVE_FUNCTION_CRATES {
+        let src = root.join(krate).join("src");
+        println!("cargo:rerun-if-changed={}", src.display());
+        for module_dir in read_dir(&src).into_iter().filter(|path| path.is_dir()) {
+            let module = match read(&module_dir.with_extension("rs")).and_then(|s| module_name(&s))
+            {
+                Some(module) => module,
+                None => continue,
+            };
+            let files = read_dir(&module_dir)
+                .into_iter()
+                .filter(|path| path.extension().map(|ext| ext == "rs").unwrap_or(false));
+            for file in files {
+                let source = read(&file).unwrap_or_default();
+                let attributes = native_attributes(&source);
+                // Functions generated by a macro are named by the file, like `ceil_1.rs`
+                let function_arities = if attributes.is_empty() {
+                    file_function_arity(&file).into_iter().collect()
+                } else {
+                    attributes
+                };
+                for (function, arity) in function_arities {
+                    functions.insert((module.clone(), function, arity));
+                }
+            }
+        }
+    }
+
+    let src = root.join(NATIVE_SYMBOL_CRATE).join("src");
+    println!("cargo:rerun-if-changed={}", src.display());
+    let mut pending = vec![src];
+    while let Some(dir) = pending.pop() {
+        for path in read_dir(&dir) {
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Some(source) = read(&path) {
+                functions.extend(exported_symbols(&source));
+            }
+        }
+    }
+
+    let mut output = String::from("&[\n");
+    for (module, function, arity) in functions {
+        output.push_str(&format!("    ({:?}, {:?}, {}),\n", module, function, arity));
+    }
+    output.push_str("]\n");
+    fs::write(path, output).unwrap_or_else(|e| fail(&format!("failed to write {:?}: {}", path, e)));
+}
+
+/// The paths in `dir`, sorted so the output is the same on every build
+fn read_dir(dir: &Path) -> Vec<PathBuf> {
+    let mut paths = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>(),
+        Err(_) => Vec::new(),
+    };
+    paths.sort();
+    paths
+}
+
+fn read(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok()
+}
+
+/// The name returned by `fn module() -> Atom` in the source of a module
+fn module_name(source: &str) -> Option<String> {
+    let body = &source[source.find("fn module()")?..];
+    quoted_after(body, "try_from_str(\"")
+}
+
+/// The functions named by `#[native_implemented_function(function/arity)]` attributes
+fn native_attributes(source: &str) -> Vec<FunctionArity> {
+    const ATTRIBUTE: &str = "#[native_implemented_function(";
+
+    source
+        .match_indices(ATTRIBUTE)
+        .filter_map(|(i, _)| {
+            let rest = &source[(i + ATTRIBUTE.len())..];
+            let function_arity = &rest[..rest.find(")]")?];
+            let mut parts = function_arity.rsplitn(2, '/');
+            let arity = parts.next()?.trim().parse().ok()?;
+            let function = parts.next()?.trim();
+            // `$name` is a parameter of a macro which generates functions
+            if function.starts_with('$') {
+                None
+            } else {
+                Some((function.to_string(), arity))
+            }
+        })
+        .collect()
+}
+
+/// The function and arity in a file name like `function_arity.rs`
+fn file_function_arity(path: &Path) -> Option<FunctionArity> {
+    let stem = path.file_stem()?.to_str()?;
+    let mut parts = stem.rsplitn(2, '_');
+    let arity = parts.next()?.parse().ok()?;
+    let function = parts.next()?;
+    Some((function.to_string(), arity))
+}
+
+/// The functions exported as `#[export_name = "module:function/arity"]`
+fn exported_symbols(source: &str) -> Vec<(String, String, usize)> {
+    source
+        .match_indices("#[export_name = \"")
+        .filter_map(|(i, _)| {
+            let symbol = quoted_after(&source[i..], "\"")?;
+            let colon = symbol.find(':')?;
+            let slash = symbol.rfind('/')?;
+            let arity = symbol[(slash + 1)..].parse().ok()?;
+            Some((
+                symbol[..colon].to_string(),
+                symbol[(colon + 1)..slash].to_string(),
+                arity,
+            ))
+        })
+        .collect()
+}
+
+/// The contents of the string which follows the first `prefix` in `source`
+fn quoted_after(source: &str, prefix: &str) -> Option<String> {
+    let start = source.find(prefix)? + prefix.len();
+    let len = source[start..].find('"')?;
+    Some(source[start..(start + len)].to_string())
 }
 
 pub fn git_version() -> (String, String) {
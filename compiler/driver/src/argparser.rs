@@ -31,6 +31,9 @@ pub fn parser<'a, 'b>() -> App<'a, 'b> {
         )
         .subcommand(print_command())
         .subcommand(compile_command())
+        .subcommand(shell_command())
+        .subcommand(lsp_command())
+        .subcommand(xref_command())
 }
 
 pub fn print_print_help() {
@@ -43,6 +46,18 @@ pub fn print_compile_help() {
         .expect("unable to print help");
 }
 
+pub fn print_shell_help() {
+    shell_command().print_help().expect("unable to print help");
+}
+
+pub fn print_lsp_help() {
+    lsp_command().print_help().expect("unable to print help");
+}
+
+pub fn print_xref_help() {
+    xref_command().print_help().expect("unable to print help");
+}
+
 fn print_command<'a, 'b>() -> App<'a, 'b> {
     let target = self::target_arg();
     App::new("print")
@@ -69,6 +84,15 @@ fn print_command<'a, 'b>() -> App<'a, 'b> {
                 .about("Prints the available architectures for the current target")
                 .arg(target.clone().help("The target to list architectures for")),
         )
+        .subcommand(
+            App::new("target-spec-json")
+                .about("Prints the specification of a target as JSON, to base a custom target on")
+                .arg(
+                    target
+                        .clone()
+                        .help("The target to print the specification of"),
+                ),
+        )
         .subcommand(App::new("project-name").about("Prints the current project name"))
         .subcommand(
             App::new("passes").about("Prints the LLVM passes registered with the pass manager"),
@@ -135,11 +159,9 @@ fn compile_command<'a, 'b>() -> App<'a, 'b> {
                 .short("O")
                 .long("optimize"),
         )
-        .arg(
-            target
-                .clone()
-                .help("The target triple to compile against (e.g. x86_64-linux-gnu)"),
-        )
+        .arg(target.clone().help(
+            "The target triple (e.g. x86_64-linux-gnu) or target specification file to compile for",
+        ))
         .arg(
             Arg::with_name("color")
                 .help("Configure coloring of output")
@@ -149,11 +171,21 @@ fn compile_command<'a, 'b>() -> App<'a, 'b> {
                 .default_value("auto"),
         )
         .arg(
-            Arg::with_name("source-map-prefix")
-                .help("Remap source paths in all output (i.e. FROM/foo => TO/foo)")
-                .long("source-map-prefix")
-                .hidden(true)
+            Arg::with_name("error-format")
+                .help("Configure the format of diagnostics, `json` prints one object per line")
+                .next_line_help(true)
+                .long("error-format")
+                .possible_values(&["human", "json"])
+                .default_value("human"),
+        )
+        .arg(
+            Arg::with_name("remap-path-prefix")
+                .help("Remap source paths embedded in the output (i.e. FROM/foo => TO/foo)")
+                .next_line_help(true)
+                .long("remap-path-prefix")
                 .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
                 .value_name("FROM=TO"),
         )
         .arg(
@@ -177,6 +209,15 @@ fn compile_command<'a, 'b>() -> App<'a, 'b> {
                 .long("no-warn")
                 .conflicts_with("warnings-as-errors"),
         )
+        .arg(
+            Arg::with_name("xref")
+                .help(
+                    "Check the calls between modules before linking, as `lumen xref` does.\n\
+                     Calls to undefined functions are errors",
+                )
+                .next_line_help(true)
+                .long("xref"),
+        )
         .arg(
             Arg::with_name("verbose")
                 .help("Set verbosity level")
@@ -232,6 +273,20 @@ fn compile_command<'a, 'b>() -> App<'a, 'b> {
                 .multiple(true)
                 .number_of_values(1),
         )
+        .arg(
+            Arg::with_name("include-path")
+                .help(
+                    "Add a directory to the search path for -include files.\n\
+                     -include_lib applications are searched for in the code path and ERL_LIBS",
+                )
+                .next_line_help(true)
+                .short("I")
+                .long("include-path")
+                .value_name("DIR")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
         .arg(
             Arg::with_name("emit")
                 .help(OutputType::help())
@@ -244,6 +299,113 @@ fn compile_command<'a, 'b>() -> App<'a, 'b> {
         )
 }
 
+fn shell_command<'a, 'b>() -> App<'a, 'b> {
+    App::new("shell")
+        .about("Starts an interactive shell that interprets Erlang expressions")
+        .arg(
+            Arg::with_name("input")
+                .index(1)
+                .help("Erlang source files to load before starting the shell")
+                .takes_value(true)
+                .multiple(true)
+                .value_name("FILE"),
+        )
+}
+
+fn lsp_command<'a, 'b>() -> App<'a, 'b> {
+    App::new("lsp").about(
+        "Starts a language server for Erlang sources, which speaks the Language Server Protocol \
+         over stdio",
+    )
+}
+
+fn xref_command<'a, 'b>() -> App<'a, 'b> {
+    App::new("xref")
+        .about(
+            "Checks the calls between modules, reporting calls to undefined functions, \
+             unused exports, and calls to deprecated functions",
+        )
+        .setting(AppSettings::DeriveDisplayOrder)
+        .arg(
+            Arg::with_name("input")
+                .index(1)
+                .help(
+                    "Path to the source file or directory to check.\n\
+                     If not provided, the current directory is checked.",
+                )
+                .next_line_help(true)
+                .takes_value(true)
+                .value_name("PATH"),
+        )
+        .arg(
+            Arg::with_name("color")
+                .help("Configure coloring of output")
+                .next_line_help(true)
+                .long("color")
+                .possible_values(&["never", "always", "auto"])
+                .default_value("auto"),
+        )
+        .arg(
+            Arg::with_name("error-format")
+                .help("Configure the format of diagnostics, `json` prints one object per line")
+                .next_line_help(true)
+                .long("error-format")
+                .possible_values(&["human", "json"])
+                .default_value("human"),
+        )
+        .arg(
+            Arg::with_name("define")
+                .help("Define a macro, e.g. -D TEST or -D FOO=BAR")
+                .short("D")
+                .long("define")
+                .takes_value(true)
+                .value_name("NAME[=VALUE]")
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("warnings-as-errors")
+                .help("Causes unused exports and deprecated calls to be treated as errors")
+                .long("warnings-as-errors"),
+        )
+        .arg(
+            Arg::with_name("no-warn")
+                .help("Only report calls to undefined functions")
+                .long("no-warn")
+                .conflicts_with("warnings-as-errors"),
+        )
+        .arg(
+            Arg::with_name("append-path")
+                .help("Appends a path to the Erlang code path")
+                .long("append-path")
+                .short("p")
+                .value_name("PATH")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("prepend-path")
+                .help("Prepends a path to the Erlang code path")
+                .long("prepend-path")
+                .short("P")
+                .value_name("PATH")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("include-path")
+                .help("Add a directory to the search path for -include files")
+                .short("I")
+                .long("include-path")
+                .value_name("DIR")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+}
+
 fn target_arg<'a, 'b>() -> Arg<'a, 'b> {
     Arg::with_name("target")
         .short("t")
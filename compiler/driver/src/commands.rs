@@ -1,5 +1,8 @@
 pub(crate) mod compile;
+pub(crate) mod lsp;
 pub(crate) mod print;
+pub(crate) mod shell;
+pub(crate) mod xref;
 
 use std::sync::{Arc, RwLock};
 
@@ -24,6 +27,7 @@ pub(super) fn create_diagnostics_handler(
     let config = DiagnosticsConfig {
         warnings_as_errors: options.warnings_as_errors,
         no_warn: options.no_warn,
+        error_format: options.error_format,
     };
     DiagnosticsHandler::new(config, codemap, emitter)
 }
@@ -1,3 +1,4 @@
+use std::fs;
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
@@ -15,7 +16,8 @@ use libeir_diagnostics::{CodeMap, Emitter};
 use liblumen_codegen as codegen;
 use liblumen_codegen::linker::{self, LinkerInfo};
 use liblumen_codegen::meta::{CodegenResults, ProjectInfo};
-use liblumen_session::{CodegenOptions, DebuggingOptions, Options};
+use liblumen_incremental::{InternedInput, InternerDatabase};
+use liblumen_session::{Application, CodegenOptions, DebuggingOptions, Input, Options};
 use liblumen_util::time::HumanDuration;
 
 use crate::commands::*;
@@ -58,14 +60,65 @@ pub fn handle_command<'a>(
     }
 
     let start = Instant::now();
-    let mut tasks = inputs
+    let options = db.options();
+    let diagnostics = db.diagnostics();
+
+    // The resource file of an application is checked against its sources before compiling them
+    let app_modules = options
+        .app
+        .as_ref()
+        .map(|app| check_application(&db, app, &inputs));
+
+    // Modules that haven't changed since they were last compiled are taken from the
+    // incremental cache, only the rest need to be compiled
+    //
+    // The id of an atom is the index of its interned symbol, so to get the same atom ids in every
+    // build, the symbols of each module are interned here, in input order, rather than by the
+    // worker threads in whatever order they happen to run. The symbols of a cached module are
+    // interned again from its cache entry, in the same order as parsing it would, so only the
+    // modules that miss are parsed here.
+    //
+    // Either way, the compiled modules are kept in input order, so that objects are
+    // always linked in the same order
+    let cache = IncrementalCache::new(&options, db.output_dir().as_path());
+    let mut compiled_modules = vec![None; num_inputs];
+    let mut uncached = Vec::new();
+    for (index, input) in inputs.iter().cloned().enumerate() {
+        let input_info = db.lookup_intern_input(input);
+        let interning = InterningStart::new(index);
+        let hit = match input_info {
+            Input::File(ref path) => cache.lookup(path, &interning),
+            Input::Str { .. } => None,
+        };
+        match hit {
+            Some((compiled, symbols)) => {
+                debug!("using cached module for {:?}", input);
+                if options.debugging_opts.incremental_info {
+                    diagnostics.success("Cached", input_info.source_name());
+                }
+                db.add_atoms(symbols.atoms.iter());
+                db.add_symbols(symbols.symbols.iter());
+                db.add_dynamic_calls(symbols.dynamic_calls.iter());
+                compiled_modules[index] = Some(Arc::new(compiled));
+                interning.finish();
+            }
+            None => {
+                // Parse errors are reported here, and the input then fails to compile below
+                if let Ok(module) = db.input_eir(input) {
+                    codegen::builder::intern_symbols(&module);
+                }
+                uncached.push((index, input, interning.finish()));
+            }
+        }
+    }
+
+    let mut tasks = uncached
         .iter()
-        .cloned()
-        .map(|input| {
+        .map(|(_, input, _)| {
+            let input = *input;
             debug!("spawning worker for {:?}", input);
             let snapshot = db.snapshot();
             task::spawn(async move {
-                use liblumen_incremental::InternerDatabase;
                 let result = snapshot.compile(input);
                 if result.is_err() {
                     let diagnostics = snapshot.diagnostics();
@@ -77,7 +130,6 @@ pub fn handle_command<'a>(
         })
         .collect::<Vec<_>>();
 
-    let options = db.options();
     let mut codegen_results = CodegenResults {
         project_name: options.project_name.clone(),
         modules: Vec::with_capacity(num_inputs),
@@ -88,16 +140,43 @@ pub fn handle_command<'a>(
 
     debug!("awaiting results from workers ({} units)", num_inputs);
 
-    let diagnostics = db.diagnostics();
-    for task in tasks.drain(..) {
+    for ((index, input, interned), task) in uncached.into_iter().zip(tasks.drain(..)) {
         if let Ok(compiled) = task::join(task).unwrap() {
-            codegen_results.modules.push(compiled);
+            if let Input::File(ref path) = db.lookup_intern_input(input) {
+                if let Some(symbols) = db.take_module_symbols(input) {
+                    if let Err(err) = cache.store(path, &compiled, &interned, &symbols) {
+                        diagnostics.warn(format!("failed to update incremental cache: {}", err));
+                    }
+                }
+            }
+            compiled_modules[index] = Some(compiled);
         }
     }
+    codegen_results
+        .modules
+        .extend(compiled_modules.into_iter().flatten());
+
+    if options.debugging_opts.incremental_info {
+        diagnostics.success(
+            "Incremental",
+            format!("{} hits, {} misses", cache.hits(), cache.misses()),
+        );
+    }
 
     // Do not proceed to linking if there were compilation errors
     diagnostics.abort_if_errors();
 
+    if let (Some(app), Some(modules)) = (options.app.as_ref(), app_modules.as_ref()) {
+        let resource_file = db.output_dir().join(format!("{}.app", app.name));
+        fs::write(&resource_file, app.resource(modules)).map_err(|err| {
+            anyhow!(
+                "could not write application resource file {}: {}",
+                resource_file.display(),
+                err
+            )
+        })?;
+    }
+
     // Generate LLVM module containing atom table data
     //
     // NOTE: This does not go through the query system, since atoms
@@ -108,7 +187,21 @@ pub fn handle_command<'a>(
     let target_machine = db.get_target_machine(thread_id);
     let atoms = db.take_atoms();
     let symbols = db.take_symbols();
+    let dynamic_calls = db.take_dynamic_calls();
     let output_dir = db.output_dir();
+
+    // Undefined functions would otherwise only be found by the linker, or at runtime
+    if options.xref {
+        let mut xref = Xref::new(options.project_type, options.app.as_ref());
+        xref.add_symbols(symbols.iter());
+        for input in inputs.iter().cloned() {
+            // Parse errors have already been reported
+            let _ = xref.add_input(&db, input);
+        }
+        let diagnostics = db.diagnostics();
+        xref.check(diagnostics);
+        diagnostics.abort_if_errors();
+    }
     codegen::generators::run(
         &mut codegen_results,
         context.deref(),
@@ -116,6 +209,7 @@ pub fn handle_command<'a>(
         output_dir.as_path(),
         atoms,
         symbols,
+        dynamic_calls,
     )?;
 
     // Link all compiled objects
@@ -132,3 +226,29 @@ pub fn handle_command<'a>(
     );
     Ok(())
 }
+
+/// Checks the resource file of `app` against the application sources in `inputs`
+///
+/// Returns the names of the modules compiled from the application sources.
+fn check_application(
+    db: &CompilerDatabase,
+    app: &Application,
+    inputs: &[InternedInput],
+) -> Vec<String> {
+    let mut sources = Vec::new();
+    for input in inputs.iter().cloned() {
+        // Generated modules are virtual, and are not part of the application
+        if let Input::File(ref path) = db.lookup_intern_input(input) {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let source = fs::read_to_string(path).unwrap_or_default();
+            sources.push((name, source));
+        }
+    }
+    sources.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let diagnostics = db.diagnostics();
+    if !app.validate(diagnostics, &sources) {
+        diagnostics.abort_if_errors();
+    }
+    sources.into_iter().map(|(name, _)| name).collect()
+}
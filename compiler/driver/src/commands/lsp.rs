@@ -0,0 +1,575 @@
+mod database;
+mod document;
+mod index;
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use clap::ArgMatches;
+use log::debug;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as NotificationTrait, PublishDiagnostics,
+};
+use lsp_types::request::Request as RequestTrait;
+use lsp_types::request::{DocumentSymbolRequest, GotoDefinition, HoverRequest};
+use lsp_types::{
+    DiagnosticRelatedInformation, DiagnosticSeverity, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DocumentSymbol, DocumentSymbolParams,
+    DocumentSymbolResponse, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents,
+    HoverParams, InitializeParams, LanguageString, Location, MarkedString, NumberOrString,
+    PublishDiagnosticsParams, ServerCapabilities, SymbolKind, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url,
+};
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use libeir_diagnostics::{CodeMap, Diagnostic, Emitter, Label, LabelStyle, NullEmitter, Severity};
+
+use liblumen_incremental::{InternedInput, InternerDatabase, ParserDatabase};
+use liblumen_session::{CodegenOptions, DebuggingOptions, Input, InputType, Options};
+
+use crate::commands::create_diagnostics_handler;
+
+use self::database::{Database, LanguageServerDatabase};
+use self::document::Document;
+use self::index::{Reference, Span};
+
+/// The main entry point for the 'lsp' command
+pub fn handle_command<'a>(
+    c_opts: CodegenOptions,
+    z_opts: DebuggingOptions,
+    matches: &ArgMatches<'a>,
+    cwd: PathBuf,
+    emitter: Option<Arc<dyn Emitter>>,
+) -> anyhow::Result<()> {
+    let options = Options::new_with_defaults(c_opts, z_opts, cwd.clone(), matches)?;
+
+    // Stdout carries the protocol, so diagnostics are only ever sent to the client
+    let emitter = emitter.unwrap_or_else(|| Arc::new(NullEmitter::new()));
+    let codemap = Arc::new(RwLock::new(CodeMap::new()));
+    let diagnostics = create_diagnostics_handler(&options, codemap.clone(), Some(emitter));
+    let db = Database::new(options, codemap.clone(), diagnostics);
+
+    let (connection, io_threads) = Connection::stdio();
+    let params = connection.initialize(serde_json::to_value(capabilities())?)?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+    let root = params
+        .root_uri
+        .and_then(|uri| uri.to_file_path().ok())
+        .unwrap_or(cwd);
+
+    let mut server = Server {
+        connection,
+        db,
+        codemap,
+        root,
+        documents: HashMap::new(),
+        reported: HashMap::new(),
+    };
+    server.run()?;
+
+    io_threads.join()?;
+    Ok(())
+}
+
+fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::Full)),
+        hover_provider: Some(true),
+        definition_provider: Some(true),
+        document_symbol_provider: Some(true),
+        ..Default::default()
+    }
+}
+
+struct Server {
+    connection: Connection,
+    db: Database,
+    codemap: Arc<RwLock<CodeMap>>,
+    root: PathBuf,
+    documents: HashMap<Url, Document>,
+    /// The diagnostics reported for each input, as queries only report them when first run
+    reported: HashMap<InternedInput, Vec<Diagnostic>>,
+}
+impl Server {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let receiver = self.connection.receiver.clone();
+        for message in &receiver {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    self.handle_request(request)?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&mut self, request: Request) -> anyhow::Result<()> {
+        debug!("received request: {}", request.method);
+
+        let request = match request.extract::<GotoDefinitionParams>(GotoDefinition::METHOD) {
+            Ok((id, params)) => {
+                let result = self.goto_definition(params);
+                return self.respond(id, result);
+            }
+            Err(request) => request,
+        };
+        let request = match request.extract::<DocumentSymbolParams>(DocumentSymbolRequest::METHOD) {
+            Ok((id, params)) => return self.respond(id, self.document_symbols(params)),
+            Err(request) => request,
+        };
+        let request = match request.extract::<HoverParams>(HoverRequest::METHOD) {
+            Ok((id, params)) => return self.respond(id, self.hover(params)),
+            Err(request) => request,
+        };
+
+        let response = Response::new_err(
+            request.id,
+            ErrorCode::MethodNotFound as i32,
+            format!("unsupported request: {}", request.method),
+        );
+        self.send(Message::Response(response))
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> anyhow::Result<()> {
+        debug!("received notification: {}", notification.method);
+
+        let notification =
+            match notification.extract::<DidOpenTextDocumentParams>(DidOpenTextDocument::METHOD) {
+                Ok(params) => {
+                    let document = params.text_document;
+                    return self.update(document.uri, Some(document.version), document.text);
+                }
+                Err(notification) => notification,
+            };
+        let notification = match notification
+            .extract::<DidChangeTextDocumentParams>(DidChangeTextDocument::METHOD)
+        {
+            Ok(params) => {
+                // Changes are synchronized in full, so the last change is the new text
+                return match params.content_changes.into_iter().last() {
+                    Some(change) => self.update(
+                        params.text_document.uri,
+                        params.text_document.version,
+                        change.text,
+                    ),
+                    None => Ok(()),
+                };
+            }
+            Err(notification) => notification,
+        };
+        if let Ok(params) =
+            notification.extract::<DidCloseTextDocumentParams>(DidCloseTextDocument::METHOD)
+        {
+            let uri = params.text_document.uri;
+            self.documents.remove(&uri);
+            return self.publish_diagnostics(uri, Vec::new(), None);
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the text of the document at `uri`, and publishes its diagnostics
+    fn update(&mut self, uri: Url, version: Option<i64>, text: String) -> anyhow::Result<()> {
+        let document = self.document(&uri, version, text);
+        let diagnostics = self.diagnostics(&uri, &document);
+        self.documents.insert(uri.clone(), document);
+        self.publish_diagnostics(uri, diagnostics, version)
+    }
+
+    /// Sets the text of the document at `uri`.
+    ///
+    /// Every version of a document is the same input, so the queries for it are only recomputed
+    /// when its text changes, and then only those that depend on the text.
+    fn document(&mut self, uri: &Url, version: Option<i64>, text: String) -> Document {
+        let name = match uri.to_file_path() {
+            Ok(path) => path.display().to_string(),
+            Err(_) => uri.to_string(),
+        };
+        // The text is set as a query input rather than interned, which would add an input for
+        // every edit
+        let input = self.db.intern_input(Input::new(name, ""));
+        let unchanged = self
+            .documents
+            .get(uri)
+            .map(|document| document.text == text)
+            .unwrap_or(false);
+        if !unchanged {
+            self.db.set_document_text(input, Arc::new(text.clone()));
+            self.reported.remove(&input);
+        }
+        Document::new(input, version, text)
+    }
+
+    /// Parses and lowers `document`, returning the diagnostics for it
+    fn diagnostics(&mut self, uri: &Url, document: &Document) -> Vec<lsp_types::Diagnostic> {
+        let input = document.input;
+        // Header files are only meaningful when included
+        if self.db.input_type(input) != InputType::Erlang {
+            return Vec::new();
+        }
+
+        self.db.take_diagnostics();
+        let _ = self.db.input_eir(input);
+        let reported = self.db.take_diagnostics();
+        // Nothing is reported if the results of the queries for this input were reused, in which
+        // case the diagnostics from when its text was last set still apply
+        self.reported.entry(input).or_insert(reported);
+
+        let codemap = self.codemap.read().unwrap();
+        self.reported[&input]
+            .iter()
+            .map(|diagnostic| convert_diagnostic(&codemap, uri, document, diagnostic))
+            .collect()
+    }
+
+    fn publish_diagnostics(
+        &self,
+        uri: Url,
+        diagnostics: Vec<lsp_types::Diagnostic>,
+        version: Option<i64>,
+    ) -> anyhow::Result<()> {
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, version);
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.send(Message::Notification(notification))
+    }
+
+    fn goto_definition(&mut self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let params = params.text_document_position_params;
+        let uri = params.text_document.uri;
+        let document = self.documents.get(&uri)?.clone();
+        let index = self.db.document_index(document.input);
+        let offset = document.offset(params.position);
+
+        let location = match &index.reference_at(offset)?.1 {
+            Reference::Function {
+                module: Some(module),
+                name,
+                arity,
+            } if index.module.as_ref() != Some(module) => {
+                let (uri, document) = self.find_module(module)?;
+                let index = self.db.document_index(document.input);
+                let function = index.function(name, *arity)?;
+                Location::new(uri, document.range(&function.name_span))
+            }
+            Reference::Function { name, arity, .. } => {
+                let function = index.function(name, *arity)?;
+                Location::new(uri.clone(), document.range(&function.name_span))
+            }
+            Reference::Record(name) => {
+                let record = index.record(name)?;
+                Location::new(uri.clone(), document.range(&record.name_span))
+            }
+            Reference::Macro(name) => {
+                let definition = index.find_macro(name)?;
+                Location::new(uri.clone(), document.range(&definition.name_span))
+            }
+        };
+
+        Some(GotoDefinitionResponse::Scalar(location))
+    }
+
+    fn document_symbols(&self, params: DocumentSymbolParams) -> Option<DocumentSymbolResponse> {
+        let document = self.documents.get(&params.text_document.uri)?;
+        let index = self.db.document_index(document.input);
+
+        let symbol = |name: String, detail: &str, kind, span: &Span, name_span: &Span| {
+            let symbol = DocumentSymbol {
+                name,
+                detail: Some(detail.to_string()),
+                kind,
+                deprecated: None,
+                range: document.range(span),
+                selection_range: document.range(name_span),
+                children: None,
+            };
+            (span.start, symbol)
+        };
+
+        let mut symbols = Vec::new();
+        for function in index.functions.iter() {
+            symbols.push(symbol(
+                format!("{}/{}", function.name, function.arity),
+                "function",
+                SymbolKind::Function,
+                &function.span,
+                &function.name_span,
+            ));
+        }
+        for record in index.records.iter() {
+            symbols.push(symbol(
+                record.name.clone(),
+                "record",
+                SymbolKind::Struct,
+                &record.span,
+                &record.name_span,
+            ));
+        }
+        for definition in index.macros.iter() {
+            symbols.push(symbol(
+                definition.name.clone(),
+                "macro",
+                SymbolKind::Constant,
+                &definition.span,
+                &definition.name_span,
+            ));
+        }
+        symbols.sort_by_key(|(start, _)| *start);
+
+        Some(DocumentSymbolResponse::Nested(
+            symbols.into_iter().map(|(_, symbol)| symbol).collect(),
+        ))
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let params = params.text_document_position_params;
+        let document = self.documents.get(&params.text_document.uri)?;
+        let index = self.db.document_index(document.input);
+        let offset = document.offset(params.position);
+
+        let (value, span) = match index.reference_at(offset) {
+            Some((
+                span,
+                Reference::Function {
+                    module,
+                    name,
+                    arity,
+                },
+            )) => {
+                // Local calls to functions that aren't defined here are to imports or BIFs
+                let module = module
+                    .as_ref()
+                    .or_else(|| index.function(name, *arity).and(index.module.as_ref()));
+                match module {
+                    Some(module) => (format!("{}:{}/{}", module, name, arity), span),
+                    None => (format!("{}/{}", name, arity), span),
+                }
+            }
+            _ => {
+                let function = index.function_at(offset)?;
+                let value = match index.module {
+                    Some(ref module) => {
+                        format!("{}:{}/{}", module, function.name, function.arity)
+                    }
+                    None => format!("{}/{}", function.name, function.arity),
+                };
+                (value, &function.name_span)
+            }
+        };
+
+        Some(Hover {
+            contents: HoverContents::Scalar(MarkedString::LanguageString(LanguageString {
+                language: "erlang".to_string(),
+                value,
+            })),
+            range: Some(document.range(span)),
+        })
+    }
+
+    /// Finds the source of `module`, preferring open documents to files under the root
+    fn find_module(&mut self, module: &str) -> Option<(Url, Document)> {
+        for (uri, document) in self.documents.iter() {
+            let index = self.db.document_index(document.input);
+            if index.module.as_ref().map(|m| m.as_str()) == Some(module) {
+                return Some((uri.clone(), document.clone()));
+            }
+        }
+
+        let file_name = format!("{}.erl", module);
+        let path = WalkDir::new(&self.root)
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !is_ignored(entry.path()))
+            .filter_map(|entry| entry.ok())
+            .find(|entry| entry.file_name() == OsStr::new(&file_name))?
+            .into_path();
+        let text = fs::read_to_string(&path).ok()?;
+        let uri = Url::from_file_path(&path).ok()?;
+        let document = self.document(&uri, None, text);
+        Some((uri, document))
+    }
+
+    fn respond<R: Serialize>(&self, id: RequestId, result: R) -> anyhow::Result<()> {
+        self.send(Message::Response(Response::new_ok(id, result)))
+    }
+
+    fn send(&self, message: Message) -> anyhow::Result<()> {
+        self.connection.sender.send(message)?;
+        Ok(())
+    }
+}
+
+/// Hidden directories and build output are not searched for modules
+fn is_ignored(path: &Path) -> bool {
+    match path.file_name().and_then(|name| name.to_str()) {
+        Some(name) => name.starts_with('.') || name == "_build",
+        None => false,
+    }
+}
+
+fn convert_diagnostic(
+    codemap: &CodeMap,
+    uri: &Url,
+    document: &Document,
+    diagnostic: &Diagnostic,
+) -> lsp_types::Diagnostic {
+    let severity = match diagnostic.severity {
+        Severity::Bug | Severity::Error => DiagnosticSeverity::Error,
+        Severity::Warning => DiagnosticSeverity::Warning,
+        Severity::Note => DiagnosticSeverity::Information,
+        Severity::Help => DiagnosticSeverity::Hint,
+    };
+
+    // Labels in other files, such as included headers, can't be shown in this document
+    let labels = diagnostic
+        .labels
+        .iter()
+        .filter_map(|label| {
+            let file = codemap.find_file(label.span.start())?;
+            if file.src() != document.text {
+                return None;
+            }
+            let offset = |index| {
+                let (line, column) = file.location(index).ok()?;
+                Some(
+                    document
+                        .line_offset(line.number().to_usize() - 1, column.number().to_usize() - 1),
+                )
+            };
+            let span = offset(label.span.start())?..offset(label.span.end())?;
+            Some((label, document.range(&span)))
+        })
+        .collect::<Vec<_>>();
+
+    let primary = labels
+        .iter()
+        .find(|(label, _)| is_primary(label))
+        .or_else(|| labels.first());
+    let (range, message) = match primary {
+        Some((label, range)) => match label.message {
+            Some(ref message) => (*range, format!("{}\n{}", diagnostic.message, message)),
+            None => (*range, diagnostic.message.clone()),
+        },
+        // Diagnostics without a location in this document are shown at the start of it
+        None => (document.range(&(0..0)), diagnostic.message.clone()),
+    };
+
+    let related = labels
+        .iter()
+        .filter(|(label, _)| !is_primary(label))
+        .filter_map(|(label, range)| {
+            Some(DiagnosticRelatedInformation {
+                location: Location::new(uri.clone(), *range),
+                message: label.message.clone()?,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    lsp_types::Diagnostic::new(
+        range,
+        Some(severity),
+        diagnostic.code.clone().map(NumberOrString::String),
+        Some("lumen".to_string()),
+        message,
+        if related.is_empty() {
+            None
+        } else {
+            Some(related)
+        },
+        None,
+    )
+}
+
+fn is_primary(label: &Label) -> bool {
+    match label.style {
+        LabelStyle::Primary => true,
+        LabelStyle::Secondary => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::argparser;
+
+    fn server() -> (Server, Connection) {
+        let matches = argparser::parser().get_matches_from(vec!["lumen", "lsp"]);
+        let cwd = std::env::temp_dir();
+        let options = Options::new_with_defaults(
+            Default::default(),
+            Default::default(),
+            cwd.clone(),
+            matches.subcommand_matches("lsp").unwrap(),
+        )
+        .unwrap();
+
+        let emitter: Arc<dyn Emitter> = Arc::new(NullEmitter::new());
+        let codemap = Arc::new(RwLock::new(CodeMap::new()));
+        let diagnostics = create_diagnostics_handler(&options, codemap.clone(), Some(emitter));
+        let (connection, client) = Connection::memory();
+        let server = Server {
+            connection,
+            db: Database::new(options, codemap.clone(), diagnostics),
+            codemap,
+            root: cwd,
+            documents: HashMap::new(),
+            reported: HashMap::new(),
+        };
+        (server, client)
+    }
+
+    fn published(client: &Connection) -> PublishDiagnosticsParams {
+        match client.receiver.recv().unwrap() {
+            Message::Notification(notification) => {
+                assert_eq!(notification.method, PublishDiagnostics::METHOD);
+                serde_json::from_value(notification.params).unwrap()
+            }
+            message => panic!("expected diagnostics to be published, got {:?}", message),
+        }
+    }
+
+    #[test]
+    fn edits_set_the_text_of_one_input_per_document() {
+        let (mut server, client) = server();
+        let uri = Url::from_file_path(std::env::temp_dir().join("example.erl")).unwrap();
+
+        let broken = "-module(example).\nrun() -> .\n";
+        server
+            .update(uri.clone(), Some(1), broken.to_string())
+            .unwrap();
+        let input = server.documents[&uri].input;
+        assert!(!published(&client).diagnostics.is_empty());
+
+        let fixed = "-module(example).\nrun(Name) -> Name.\n";
+        server
+            .update(uri.clone(), Some(2), fixed.to_string())
+            .unwrap();
+        assert_eq!(server.documents[&uri].input, input);
+        assert!(published(&client).diagnostics.is_empty());
+        let index = server.db.document_index(input);
+        assert!(index.function("run", 0).is_none());
+        assert!(index.function("run", 1).is_some());
+
+        server
+            .update(uri.clone(), Some(3), broken.to_string())
+            .unwrap();
+        let diagnostics = published(&client).diagnostics;
+        assert!(!diagnostics.is_empty());
+        // The queries aren't recomputed for the same text, but its diagnostics are still published
+        server
+            .update(uri.clone(), Some(4), broken.to_string())
+            .unwrap();
+        assert_eq!(published(&client).diagnostics, diagnostics);
+        assert_eq!(server.documents[&uri].input, input);
+    }
+}
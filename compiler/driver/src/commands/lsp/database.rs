@@ -0,0 +1,133 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+
+use libeir_diagnostics::{CodeMap, Diagnostic};
+
+use liblumen_incremental::{InternedInput, InternerStorage, QueryResult};
+use liblumen_incremental::{ParserDatabase, ParserDatabaseBase, ParserStorage};
+use liblumen_session::{DiagnosticsHandler, Emit, Options, OutputType};
+
+use super::index::DocumentIndex;
+
+#[salsa::query_group(LanguageServerStorage)]
+pub trait LanguageServerDatabase: ParserDatabase {
+    /// The current text of a document, which is set whenever the client changes it
+    #[salsa::input]
+    fn document_text(&self, input: InternedInput) -> Arc<String>;
+
+    #[salsa::invoke(document_index)]
+    fn document_index(&self, input: InternedInput) -> Arc<DocumentIndex>;
+}
+
+fn document_index<D>(db: &D, input: InternedInput) -> Arc<DocumentIndex>
+where
+    D: LanguageServerDatabase,
+{
+    Arc::new(DocumentIndex::new(&db.document_text(input)))
+}
+
+/// The query database backing the language server.
+///
+/// Each document is a single input whose text is set when it changes, so an edit only
+/// recomputes the queries for the document that changed.
+#[salsa::database(LanguageServerStorage, ParserStorage, InternerStorage)]
+pub struct Database {
+    runtime: salsa::Runtime<Database>,
+    diagnostics: DiagnosticsHandler,
+    codemap: Arc<RwLock<CodeMap>>,
+    reported: Arc<Mutex<Vec<Diagnostic>>>,
+}
+impl Database {
+    pub fn new(
+        options: Options,
+        codemap: Arc<RwLock<CodeMap>>,
+        diagnostics: DiagnosticsHandler,
+    ) -> Self {
+        let mut db = Self {
+            runtime: Default::default(),
+            diagnostics,
+            codemap,
+            reported: Arc::new(Mutex::new(Vec::new())),
+        };
+        db.set_options(Arc::new(options));
+        db
+    }
+
+    /// Takes the diagnostics reported by queries since the last call
+    pub fn take_diagnostics(&self) -> Vec<Diagnostic> {
+        let mut reported = self.reported.lock().unwrap();
+        std::mem::replace(&mut *reported, Vec::new())
+    }
+}
+impl salsa::Database for Database {
+    fn salsa_runtime(&self) -> &salsa::Runtime<Self> {
+        &self.runtime
+    }
+
+    fn salsa_runtime_mut(&mut self) -> &mut salsa::Runtime<Self> {
+        &mut self.runtime
+    }
+}
+
+// Nothing is written to disk on behalf of the client, so none of the emit functions emit
+impl ParserDatabaseBase for Database {
+    fn diagnostics(&self) -> &DiagnosticsHandler {
+        &self.diagnostics
+    }
+
+    fn diagnostic(&self, diagnostic: &Diagnostic) {
+        self.reported.lock().unwrap().push(diagnostic.clone());
+    }
+
+    fn codemap(&self) -> &Arc<RwLock<CodeMap>> {
+        &self.codemap
+    }
+
+    fn input_text(&self, input: InternedInput) -> Option<Arc<String>> {
+        Some(self.document_text(input))
+    }
+
+    fn maybe_emit_file<E>(&self, _input: InternedInput, _output: &E) -> QueryResult<Option<PathBuf>>
+    where
+        E: Emit,
+    {
+        Ok(None)
+    }
+
+    fn maybe_emit_file_with_opts<E>(
+        &self,
+        _options: &Options,
+        _input: InternedInput,
+        _output: &E,
+    ) -> QueryResult<Option<PathBuf>>
+    where
+        E: Emit,
+    {
+        Ok(None)
+    }
+
+    fn maybe_emit_file_with_callback<F>(
+        &self,
+        _input: InternedInput,
+        _output_type: OutputType,
+        _callback: F,
+    ) -> QueryResult<Option<PathBuf>>
+    where
+        F: FnOnce(&mut std::fs::File) -> anyhow::Result<()>,
+    {
+        Ok(None)
+    }
+
+    fn maybe_emit_file_with_callback_and_opts<F>(
+        &self,
+        _options: &Options,
+        _input: InternedInput,
+        _output_type: OutputType,
+        _callback: F,
+    ) -> QueryResult<Option<PathBuf>>
+    where
+        F: FnOnce(&mut std::fs::File) -> anyhow::Result<()>,
+    {
+        Ok(None)
+    }
+}
@@ -0,0 +1,75 @@
+use lsp_types::{Position, Range};
+
+use liblumen_incremental::InternedInput;
+
+use super::index::Span;
+
+/// A version of a source file known to the language server
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub input: InternedInput,
+    pub version: Option<i64>,
+    pub text: String,
+    line_starts: Vec<usize>,
+}
+impl Document {
+    pub fn new(input: InternedInput, version: Option<i64>, text: String) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            input,
+            version,
+            text,
+            line_starts,
+        }
+    }
+
+    /// Converts a byte offset into a position, whose character is counted in UTF-16 code units
+    pub fn position(&self, offset: usize) -> Position {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+        let character = self.text[self.line_starts[line]..offset]
+            .encode_utf16()
+            .count();
+        Position::new(line as _, character as _)
+    }
+
+    /// Converts a position into a byte offset, clamping it to the end of its line
+    pub fn offset(&self, position: Position) -> usize {
+        let line = (position.line as usize).min(self.line_starts.len() - 1);
+        let start = self.line_starts[line];
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .cloned()
+            .unwrap_or_else(|| self.text.len());
+
+        let mut character = 0;
+        for (i, c) in self.text[start..end].char_indices() {
+            if character >= position.character as usize {
+                return start + i;
+            }
+            character += c.len_utf16();
+        }
+        end
+    }
+
+    /// Converts a zero-based line and a byte offset into that line into a byte offset
+    pub fn line_offset(&self, line: usize, column: usize) -> usize {
+        match self.line_starts.get(line) {
+            Some(start) => (start + column).min(self.text.len()),
+            None => self.text.len(),
+        }
+    }
+
+    pub fn range(&self, span: &Span) -> Range {
+        Range::new(self.position(span.start), self.position(span.end))
+    }
+}
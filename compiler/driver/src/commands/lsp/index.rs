@@ -0,0 +1,484 @@
+//! An index of the definitions and references in an Erlang source file.
+//!
+//! The index is built from the lexer's tokens rather than from the parsed module, so that it is
+//! still available while a file is being edited and doesn't parse, and so that macros and
+//! records, which are expanded away by the preprocessor and parser, can be found.
+use std::ops::Range;
+
+use libeir_diagnostics::{CodeMap, FileName};
+
+/// A range of byte offsets into a source file
+pub type Span = Range<usize>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Function {
+    pub name: String,
+    pub arity: usize,
+    /// The name in the head of the first clause
+    pub name_span: Span,
+    /// All of the clauses of the function
+    pub span: Span,
+}
+
+/// A record or macro definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Definition {
+    pub name: String,
+    pub name_span: Span,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reference {
+    /// A call or `fun` reference; `module` is `None` for local functions
+    Function {
+        module: Option<String>,
+        name: String,
+        arity: usize,
+    },
+    Record(String),
+    Macro(String),
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DocumentIndex {
+    pub module: Option<String>,
+    pub functions: Vec<Function>,
+    pub records: Vec<Definition>,
+    pub macros: Vec<Definition>,
+    pub references: Vec<(Span, Reference)>,
+}
+impl DocumentIndex {
+    pub fn new(source: &str) -> Self {
+        let tokens = tokenize(source);
+        let mut index = Self::default();
+
+        let mut start = 0;
+        for (i, (token, _)) in tokens.iter().enumerate() {
+            if *token == Token::Dot {
+                index.add_form(&tokens[start..=i]);
+                start = i + 1;
+            }
+        }
+        // The last form may be incomplete while it is being written
+        if start < tokens.len() {
+            index.add_form(&tokens[start..]);
+        }
+
+        index
+    }
+
+    pub fn function(&self, name: &str, arity: usize) -> Option<&Function> {
+        self.functions
+            .iter()
+            .find(|f| f.name == name && f.arity == arity)
+    }
+
+    pub fn record(&self, name: &str) -> Option<&Definition> {
+        self.records.iter().find(|r| r.name == name)
+    }
+
+    pub fn find_macro(&self, name: &str) -> Option<&Definition> {
+        self.macros.iter().find(|m| m.name == name)
+    }
+
+    /// Returns the reference which contains `offset`
+    pub fn reference_at(&self, offset: usize) -> Option<&(Span, Reference)> {
+        self.references
+            .iter()
+            .find(|(span, _)| span.start <= offset && offset <= span.end)
+    }
+
+    /// Returns the function whose name in the head of its first clause contains `offset`
+    pub fn function_at(&self, offset: usize) -> Option<&Function> {
+        self.functions
+            .iter()
+            .find(|f| f.name_span.start <= offset && offset <= f.name_span.end)
+    }
+
+    fn add_form(&mut self, form: &[(Token, Span)]) {
+        let span = form[0].1.start..form[form.len() - 1].1.end;
+        match form {
+            [(Token::Punct("-"), _), (Token::Atom(attribute), _), (Token::Punct("("), _), (name, name_span), ..] =>
+            {
+                let definition = |name: &str| Definition {
+                    name: name.to_string(),
+                    name_span: name_span.clone(),
+                    span: span.clone(),
+                };
+                match (attribute.as_str(), name) {
+                    ("module", Token::Atom(name)) => self.module = Some(name.clone()),
+                    ("record", Token::Atom(name)) => self.records.push(definition(name)),
+                    ("define", Token::Atom(name)) | ("define", Token::Var(name)) => {
+                        self.macros.push(definition(name))
+                    }
+                    _ => (),
+                }
+            }
+            [(Token::Atom(name), name_span), (Token::Punct("("), _), ..] => {
+                if let Some((arity, _)) = count_args(form, 1) {
+                    self.functions.push(Function {
+                        name: name.clone(),
+                        arity,
+                        name_span: name_span.clone(),
+                        span,
+                    });
+                }
+                self.add_references(form);
+            }
+            _ => (),
+        }
+    }
+
+    fn add_references(&mut self, tokens: &[(Token, Span)]) {
+        for i in 0..tokens.len() {
+            let previous = if i > 0 { Some(&tokens[i - 1].0) } else { None };
+            match &tokens[i..] {
+                // m:f(...) and ?MODULE:f(...)
+                [(module, module_span), (Token::Punct(":"), _), (Token::Atom(name), name_span), (Token::Punct("("), _), ..] =>
+                {
+                    let module = match module {
+                        Token::Atom(module) => Some(module.clone()),
+                        Token::Macro(m) if m == "MODULE" => None,
+                        _ => continue,
+                    };
+                    if let Some((arity, _)) = count_args(tokens, i + 3) {
+                        let reference = Reference::Function {
+                            module,
+                            name: name.clone(),
+                            arity,
+                        };
+                        self.references
+                            .push((module_span.start..name_span.end, reference));
+                    }
+                }
+                // fun m:f/a
+                [(Token::Atom(fun), _), (Token::Atom(module), module_span), (Token::Punct(":"), _), (Token::Atom(name), _), (Token::Punct("/"), _), (Token::Number(arity), arity_span), ..]
+                    if fun == "fun" =>
+                {
+                    if let Ok(arity) = arity.parse() {
+                        let reference = Reference::Function {
+                            module: Some(module.clone()),
+                            name: name.clone(),
+                            arity,
+                        };
+                        self.references
+                            .push((module_span.start..arity_span.end, reference));
+                    }
+                }
+                // fun f/a
+                [(Token::Atom(fun), _), (Token::Atom(name), name_span), (Token::Punct("/"), _), (Token::Number(arity), arity_span), ..]
+                    if fun == "fun" =>
+                {
+                    if let Ok(arity) = arity.parse() {
+                        let reference = Reference::Function {
+                            module: None,
+                            name: name.clone(),
+                            arity,
+                        };
+                        self.references
+                            .push((name_span.start..arity_span.end, reference));
+                    }
+                }
+                // f(...)
+                [(Token::Atom(name), name_span), (Token::Punct("("), _), ..]
+                    if !is_keyword(name) =>
+                {
+                    match previous {
+                        Some(Token::Punct(":")) | Some(Token::Punct("#")) => continue,
+                        _ => (),
+                    }
+                    if let Some((arity, _)) = count_args(tokens, i + 1) {
+                        let reference = Reference::Function {
+                            module: None,
+                            name: name.clone(),
+                            arity,
+                        };
+                        self.references.push((name_span.clone(), reference));
+                    }
+                }
+                // #record{...} and Var#record.field
+                [(Token::Punct("#"), hash_span), (Token::Atom(name), name_span), ..] => {
+                    self.references.push((
+                        hash_span.start..name_span.end,
+                        Reference::Record(name.clone()),
+                    ));
+                }
+                [(Token::Macro(name), span), ..] => {
+                    self.references
+                        .push((span.clone(), Reference::Macro(name.clone())));
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Counts the arguments between the parenthesis at `open` and its matching close, returning
+/// the count and the index of the close, or `None` if the parenthesis is unbalanced
+fn count_args(tokens: &[(Token, Span)], open: usize) -> Option<(usize, usize)> {
+    let mut depth = 0usize;
+    let mut commas = 0;
+    for i in (open + 1)..tokens.len() {
+        match &tokens[i].0 {
+            Token::Punct(")") if depth == 0 => {
+                let arity = if i == open + 1 { 0 } else { commas + 1 };
+                return Some((arity, i));
+            }
+            Token::Punct("(") | Token::Punct("[") | Token::Punct("{") | Token::Punct("<<") => {
+                depth += 1
+            }
+            Token::Punct(")") | Token::Punct("]") | Token::Punct("}") | Token::Punct(">>") => {
+                depth = depth.checked_sub(1)?
+            }
+            Token::Atom(keyword) if opens_block(keyword, &tokens[(i + 1)..]) => depth += 1,
+            Token::Atom(keyword) if keyword == "end" => depth = depth.checked_sub(1)?,
+            Token::Punct(",") if depth == 0 => commas += 1,
+            Token::Dot => return None,
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Returns true if `keyword` starts an expression which is closed by `end`
+fn opens_block(keyword: &str, rest: &[(Token, Span)]) -> bool {
+    match keyword {
+        "begin" | "case" | "if" | "maybe" | "receive" | "try" => true,
+        // Anonymous and named funs have clauses, but `fun f/1` and `fun m:f/1` don't
+        "fun" => match rest {
+            [(Token::Punct("("), _), ..] => true,
+            [(Token::Var(_), _), (Token::Punct("("), _), ..] => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn is_keyword(atom: &str) -> bool {
+    match atom {
+        "after" | "and" | "andalso" | "band" | "begin" | "bnot" | "bor" | "bsl" | "bsr"
+        | "bxor" | "case" | "catch" | "cond" | "div" | "else" | "end" | "fun" | "if" | "let"
+        | "maybe" | "not" | "of" | "or" | "orelse" | "receive" | "rem" | "try" | "when" | "xor" => {
+            true
+        }
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Atom(String),
+    Var(String),
+    Number(String),
+    /// `?NAME`
+    Macro(String),
+    Punct(&'static str),
+    /// The `.` that ends a form
+    Dot,
+    /// Strings, characters and anything else the index doesn't care about
+    Other,
+}
+
+const PUNCTUATION: &[&str] = &[
+    "=:=", "=/=", "->", "::", ":=", "=>", "<-", "<=", "||", "<<", ">>", "==", "/=", "=<", ">=",
+    "++", "--", "??", "(", ")", "[", "]", "{", "}", ",", ";", ":", "/", "#", "-", "+", "*", "=",
+    "<", ">", "!", "|", "?", ".",
+];
+
+/// Lexes `source` with the Erlang lexer, reducing its tokens to those the index looks at
+fn tokenize(source: &str) -> Vec<(Token, Span)> {
+    use libeir_syntax_erl::{FileMapSource, Lexer, LexicalToken, Scanner, Token as LexToken};
+
+    // The index is rebuilt on every edit, so the source isn't added to the shared code map
+    let mut codemap = CodeMap::new();
+    let filemap = codemap.add_filemap(FileName::Virtual("nofile".into()), source.to_string());
+    let base = filemap.span().start().to_usize();
+    let lexer = Lexer::new(Scanner::new(FileMapSource::new(filemap)));
+
+    let mut tokens: Vec<(Token, Span)> = Vec::new();
+    // Invalid tokens are skipped, as they are likely in the middle of being written
+    for LexicalToken(start, token, end) in lexer.filter_map(Result::ok) {
+        let span = (start.to_usize() - base)..(end.to_usize() - base);
+        let token = match token {
+            // Quoted atoms are unquoted
+            LexToken::Atom(name) => Token::Atom(name.to_string()),
+            _ => classify(source, &span),
+        };
+
+        // `?NAME` and `??Arg` are lexed as separate tokens
+        let question = match tokens.last() {
+            Some((Token::Punct(p), question_span))
+                if p.starts_with('?') && question_span.end == span.start =>
+            {
+                Some(question_span.start)
+            }
+            _ => None,
+        };
+        match (question, &token) {
+            (Some(question), Token::Atom(name)) | (Some(question), Token::Var(name)) => {
+                let name = Token::Macro(name.clone());
+                *tokens.last_mut().unwrap() = (name, question..span.end);
+            }
+            _ => tokens.push((token, span)),
+        }
+    }
+
+    tokens
+}
+
+/// Reduces the lexed token at `span` to the kind of token the index looks at by its text, as
+/// keywords and attribute names are all identifiers to the index
+fn classify(source: &str, span: &Span) -> Token {
+    let text = &source[span.clone()];
+    match text.chars().next() {
+        Some('.') if text == "." && is_form_end(source.as_bytes(), span.end) => Token::Dot,
+        Some(c) if c.is_ascii_digit() => Token::Number(text.to_string()),
+        Some(c) if c.is_uppercase() || c == '_' => Token::Var(text.to_string()),
+        Some(c) if c.is_lowercase() => Token::Atom(text.to_string()),
+        _ => match PUNCTUATION.iter().find(|p| **p == text) {
+            Some(p) => Token::Punct(p),
+            None => Token::Other,
+        },
+    }
+}
+
+fn is_form_end(bytes: &[u8], i: usize) -> bool {
+    i >= bytes.len() || bytes[i].is_ascii_whitespace() || bytes[i] == b'%'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"-module(example).
+-export([run/1]).
+
+-define(TIMEOUT, 5000).
+-record(state, {name, count = 0}).
+
+%% ignored(1) is a comment
+run(Name) ->
+    State = #state{name = Name},
+    loop(State, ?TIMEOUT).
+
+loop(#state{count = Count} = State, Timeout) when Count > 1.5 ->
+    lists:map(fun format/1, [State#state.name, "not_a_call(1)"]),
+    ?MODULE:loop(State, Timeout);
+loop(State, _Timeout) ->
+    fun other:loop/2,
+    fun (X) -> X end.
+
+incomplete(A, B) ->
+    case A of
+"#;
+
+    fn offset(pattern: &str) -> usize {
+        SOURCE.find(pattern).unwrap()
+    }
+
+    fn span(pattern: &str) -> Span {
+        let start = offset(pattern);
+        start..(start + pattern.len())
+    }
+
+    /// The span of `name` in the first occurrence of `context`
+    fn span_in(name: &str, context: &str) -> Span {
+        let start = offset(context) + context.find(name).unwrap();
+        start..(start + name.len())
+    }
+
+    fn function(module: Option<&str>, name: &str, arity: usize) -> Reference {
+        Reference::Function {
+            module: module.map(|m| m.to_string()),
+            name: name.to_string(),
+            arity,
+        }
+    }
+
+    #[test]
+    fn finds_module_and_definitions() {
+        let index = DocumentIndex::new(SOURCE);
+        assert_eq!(index.module.as_ref().map(|m| m.as_str()), Some("example"));
+
+        let functions = index
+            .functions
+            .iter()
+            .map(|f| (f.name.as_str(), f.arity))
+            .collect::<Vec<_>>();
+        assert_eq!(functions, [("run", 1), ("loop", 2), ("incomplete", 2)]);
+
+        let run = index.function("run", 1).unwrap();
+        assert_eq!(run.name_span, span_in("run", "run(Name)"));
+        assert_eq!(run.span.start, offset("run(Name)"));
+        assert_eq!(run.span.end, offset("?TIMEOUT).") + "?TIMEOUT).".len());
+
+        let loop_ = index.function("loop", 2).unwrap();
+        assert_eq!(loop_.span.end, offset("X end.") + "X end.".len());
+
+        let record = index.record("state").unwrap();
+        assert_eq!(record.name_span, span_in("state", "state, {"));
+        let definition = index.find_macro("TIMEOUT").unwrap();
+        assert_eq!(definition.name_span, span_in("TIMEOUT", "TIMEOUT, 5000"));
+    }
+
+    #[test]
+    fn finds_references() {
+        let index = DocumentIndex::new(SOURCE);
+        let reference = |pattern: &str| {
+            let start = offset(pattern);
+            index
+                .reference_at(start)
+                .map(|(span, reference)| (span.clone(), reference.clone()))
+        };
+
+        assert_eq!(
+            reference("loop(State, ?TIMEOUT)"),
+            Some((
+                span_in("loop", "loop(State, ?TIMEOUT)"),
+                function(None, "loop", 2)
+            ))
+        );
+        assert_eq!(
+            reference("?TIMEOUT"),
+            Some((span("?TIMEOUT"), Reference::Macro("TIMEOUT".to_string())))
+        );
+        assert_eq!(
+            reference("#state{name"),
+            Some((span("#state"), Reference::Record("state".to_string())))
+        );
+        assert_eq!(
+            reference("lists:map"),
+            Some((span("lists:map"), function(Some("lists"), "map", 2)))
+        );
+        assert_eq!(
+            reference("format/1"),
+            Some((span("format/1"), function(None, "format", 1)))
+        );
+        assert_eq!(
+            reference("?MODULE:loop"),
+            Some((span("?MODULE:loop"), function(None, "loop", 2)))
+        );
+        assert_eq!(
+            reference("other:loop/2"),
+            Some((span("other:loop/2"), function(Some("other"), "loop", 2)))
+        );
+    }
+
+    #[test]
+    fn ignores_comments_strings_and_keywords() {
+        let index = DocumentIndex::new(SOURCE);
+        let names = index
+            .references
+            .iter()
+            .filter_map(|(_, reference)| match reference {
+                Reference::Function { name, .. } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(!names.contains(&"ignored"));
+        assert!(!names.contains(&"not_a_call"));
+        assert!(!names.contains(&"fun"));
+        assert!(!names.contains(&"when"));
+    }
+}
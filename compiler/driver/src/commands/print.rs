@@ -58,6 +58,13 @@ pub fn handle_command<'a>(
             codegen::init(&options)?;
             llvm::target::print_target_cpus(&options, &diagnostics);
         }
+        ("target-spec-json", subcommand_matches) => {
+            let target = match subcommand_matches.and_then(|m| m.value_of("target")) {
+                Some(target) => Target::search(target)?,
+                None => Target::search(target::host_triple())?,
+            };
+            println!("{}", serde_json::to_string_pretty(&target.to_json())?);
+        }
         ("passes", _subcommand_matches) => {
             llvm::passes::print();
         }
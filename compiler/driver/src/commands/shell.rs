@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::ArgMatches;
+
+use libeir_diagnostics::Emitter;
+
+use liblumen_session::{CodegenOptions, DebuggingOptions};
+
+use lumen_interpreter::shell;
+
+/// The main entry point for the 'shell' command
+pub fn handle_command<'a>(
+    _c_opts: CodegenOptions,
+    _z_opts: DebuggingOptions,
+    matches: &ArgMatches<'a>,
+    cwd: PathBuf,
+    _emitter: Option<Arc<dyn Emitter>>,
+) -> anyhow::Result<()> {
+    // Modules are interpreted, so there is nothing to configure for codegen
+    let paths: Vec<PathBuf> = matches
+        .values_of("input")
+        .map(|values| values.map(|value| cwd.join(value)).collect())
+        .unwrap_or_default();
+
+    shell::run(&paths)
+}
@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use anyhow::anyhow;
+
+use clap::ArgMatches;
+
+use libeir_diagnostics::{CodeMap, Emitter};
+
+use liblumen_session::{CodegenOptions, DebuggingOptions, Options};
+
+use crate::commands::*;
+use crate::compiler::{prelude::*, *};
+
+/// The main entry point for the 'xref' command
+pub fn handle_command<'a>(
+    c_opts: CodegenOptions,
+    z_opts: DebuggingOptions,
+    matches: &ArgMatches<'a>,
+    cwd: PathBuf,
+    emitter: Option<Arc<dyn Emitter>>,
+) -> anyhow::Result<()> {
+    let options = Options::new(c_opts, z_opts, cwd, &matches)?;
+    let codemap = Arc::new(RwLock::new(CodeMap::new()));
+    let diagnostics = create_diagnostics_handler(&options, codemap.clone(), emitter);
+
+    // Only the parser queries are used, so no codegen backend is needed
+    let mut db = CompilerDatabase::new(codemap, diagnostics);
+    db.set_options(Arc::new(options));
+
+    let inputs = db.inputs().unwrap_or_else(abort_on_err);
+    if inputs.is_empty() {
+        db.diagnostics()
+            .fatal_str("No input sources found!")
+            .raise();
+    }
+
+    let options = db.options();
+    let mut xref = Xref::new(options.project_type, options.app.as_ref());
+    for input in inputs.iter().cloned() {
+        // Parse errors have already been reported
+        let _ = xref.add_input(&db, input);
+    }
+    let diagnostics = db.diagnostics();
+    diagnostics.abort_if_errors();
+
+    let summary = xref.check(diagnostics);
+    diagnostics.success(
+        "Checked",
+        format!(
+            "{} modules: {} undefined calls, {} unused exports, {} deprecated calls",
+            inputs.len(),
+            summary.undefined,
+            summary.unused,
+            summary.deprecated
+        ),
+    );
+    if diagnostics.has_errors() {
+        return Err(anyhow!("xref found errors"));
+    }
+    Ok(())
+}
@@ -1,8 +1,10 @@
+mod cache;
 mod intern;
 mod queries;
 mod query_groups;
+mod xref;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex, RwLock};
 
@@ -24,6 +26,9 @@ pub(crate) mod prelude {
     pub use super::query_groups::*;
 }
 
+pub(crate) use self::cache::{IncrementalCache, InterningStart, ModuleSymbols};
+pub(crate) use self::xref::Xref;
+
 use self::prelude::*;
 
 #[salsa::database(CodegenStorage, ParserStorage, InternerStorage, StringInternerStorage)]
@@ -33,6 +38,8 @@ pub struct CompilerDatabase {
     codemap: Arc<RwLock<CodeMap>>,
     atoms: Arc<Mutex<HashSet<Symbol>>>,
     symbols: Arc<Mutex<HashSet<FunctionSymbol>>>,
+    dynamic_calls: Arc<Mutex<HashSet<u8>>>,
+    module_symbols: Arc<Mutex<HashMap<InternedInput, ModuleSymbols>>>,
 }
 impl CompilerDatabase {
     pub fn new(codemap: Arc<RwLock<CodeMap>>, diagnostics: DiagnosticsHandler) -> Self {
@@ -45,6 +52,8 @@ impl CompilerDatabase {
             codemap,
             atoms: Arc::new(Mutex::new(atoms)),
             symbols: Arc::new(Mutex::new(HashSet::default())),
+            dynamic_calls: Arc::new(Mutex::new(HashSet::default())),
+            module_symbols: Arc::new(Mutex::new(HashMap::default())),
         }
     }
 }
@@ -65,6 +74,8 @@ impl salsa::ParallelDatabase for CompilerDatabase {
             codemap: self.codemap.clone(),
             atoms: self.atoms.clone(),
             symbols: self.symbols.clone(),
+            dynamic_calls: self.dynamic_calls.clone(),
+            module_symbols: self.module_symbols.clone(),
         })
     }
 }
@@ -182,4 +193,33 @@ impl CodegenDatabaseBase for CompilerDatabase {
             locked.insert(*i);
         }
     }
+
+    fn take_dynamic_calls(&mut self) -> HashSet<u8> {
+        let dynamic_calls = Arc::get_mut(&mut self.dynamic_calls)
+            .unwrap()
+            .get_mut()
+            .unwrap();
+        let empty = HashSet::default();
+        core::mem::replace(dynamic_calls, empty)
+    }
+
+    fn add_dynamic_calls<'a, I>(&self, arities: I)
+    where
+        I: Iterator<Item = &'a u8>,
+    {
+        let mut locked = self.dynamic_calls.lock().unwrap();
+        for i in arities {
+            locked.insert(*i);
+        }
+    }
+
+    fn add_module_symbols(&self, input: InternedInput, symbols: ModuleSymbols) {
+        let mut locked = self.module_symbols.lock().unwrap();
+        locked.insert(input, symbols);
+    }
+
+    fn take_module_symbols(&self, input: InternedInput) -> Option<ModuleSymbols> {
+        let mut locked = self.module_symbols.lock().unwrap();
+        locked.remove(&input)
+    }
 }
@@ -0,0 +1,547 @@
+//! A persistent cache of compiled modules, so that unchanged modules are not recompiled.
+//!
+//! The cache lives in the `incremental` directory under the output directory. Each input file
+//! has an entry recording the key it was compiled with, a copy of the object file, and the atoms,
+//! function symbols and dynamic call arities codegen gathered for it, which are needed to
+//! generate the atom and symbol tables at link time.
+//!
+//! The key is a hash of the compiler version, the options that affect code generation, and the
+//! contents of the source file and any files it includes, so a change to any of them is a miss.
+//! It is computed with SHA-256 rather than the standard library's hasher, whose algorithm is not
+//! guaranteed to be the same across releases of Rust.
+//!
+//! Atom ids are compiled into the object file, and the id of an atom is the index of its symbol in
+//! the interner, so it depends on every symbol interned before it, not just on other atoms. Each
+//! input is parsed between two marker symbols which are interned in every build, and the entry
+//! records every symbol interned between them. Using an entry interns those symbols again, in the
+//! same order, so that the following inputs get the same ids whether or not this one was parsed.
+//! The id of each atom is recorded as well, and an entry is a miss if its start marker or any of
+//! its symbols has been given a different id in this build, such as when an earlier input changed.
+use std::collections::HashSet;
+use std::convert::TryInto;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use sha2::{Digest, Sha256};
+
+use libeir_intern::Symbol;
+
+use liblumen_codegen::generators::symbol_of;
+use liblumen_codegen::meta::CompiledModule;
+use liblumen_core::symbols::FunctionSymbol;
+use liblumen_session::include_search::scan_includes;
+use liblumen_session::{IncludeSearch, Options};
+
+/// Marks the start of the symbols interned for the input at `index`
+pub struct InterningStart {
+    index: usize,
+    marker: Symbol,
+}
+impl InterningStart {
+    /// Interns the start marker, which must be done before the input is looked up or parsed
+    pub fn new(index: usize) -> Self {
+        Self {
+            index,
+            marker: Symbol::intern(&format!("\0lumen-incremental-start-{}", index)),
+        }
+    }
+
+    /// Interns the end marker and returns the symbols interned since the start marker
+    pub fn finish(self) -> InternedSymbols {
+        let end = Symbol::intern(&format!("\0lumen-incremental-end-{}", self.index));
+        let symbols = ((self.marker.as_usize() + 1)..end.as_usize())
+            .map(symbol_of)
+            .collect();
+
+        InternedSymbols {
+            start: self.marker,
+            symbols,
+        }
+    }
+}
+
+/// The symbols interned for an input, in the order they were interned
+pub struct InternedSymbols {
+    start: Symbol,
+    symbols: Vec<Symbol>,
+}
+
+/// The atoms, function symbols and dynamic call arities generated for a module
+#[derive(Clone, Default)]
+pub struct ModuleSymbols {
+    pub atoms: HashSet<Symbol>,
+    pub symbols: HashSet<FunctionSymbol>,
+    pub dynamic_calls: HashSet<u8>,
+}
+
+pub struct IncrementalCache {
+    dir: PathBuf,
+    options_hash: u64,
+    include_search: IncludeSearch,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+impl IncrementalCache {
+    pub fn new(options: &Options, output_dir: &Path) -> Self {
+        // `-Z incremental-info` only changes what is reported, so it shouldn't invalidate the cache
+        let mut debugging_opts = options.debugging_opts.clone();
+        debugging_opts.incremental_info = false;
+
+        let mut defines = options.defines.iter().collect::<Vec<_>>();
+        defines.sort();
+
+        let mut hasher = StableHasher::new();
+        crate::LUMEN_RELEASE.hash(&mut hasher);
+        crate::LUMEN_COMMIT_HASH.hash(&mut hasher);
+        format!("{:?}", options.project_type).hash(&mut hasher);
+        format!("{:?}", options.target).hash(&mut hasher);
+        format!("{:?}", options.opt_level).hash(&mut hasher);
+        format!("{:?}", options.debug_info).hash(&mut hasher);
+        options.debug_assertions.hash(&mut hasher);
+        options.warnings_as_errors.hash(&mut hasher);
+        format!("{:?}", options.codegen_opts).hash(&mut hasher);
+        format!("{:?}", debugging_opts).hash(&mut hasher);
+        defines.hash(&mut hasher);
+        options.include_path.hash(&mut hasher);
+        options.code_path.hash(&mut hasher);
+        options.lib_path.hash(&mut hasher);
+        options.remap_path_prefix.hash(&mut hasher);
+
+        Self {
+            dir: output_dir.join("incremental"),
+            options_hash: hasher.finish(),
+            include_search: IncludeSearch::new(options),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Returns the cached module compiled from the source file at `path`, and the symbols
+    /// generated for it, if the cached module is up to date
+    ///
+    /// The symbols that parsing the file interned are interned again, so this must be called
+    /// between `start` and its `finish`.
+    pub fn lookup(
+        &self,
+        path: &Path,
+        start: &InterningStart,
+    ) -> Option<(CompiledModule, ModuleSymbols)> {
+        let result = self
+            .key(path)
+            .ok()
+            .and_then(|key| self.read_entry(path, key, start));
+        if result.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    /// Stores the object file compiled from the source file at `path`, along with the symbols
+    /// interned for it and generated for it
+    pub fn store(
+        &self,
+        path: &Path,
+        compiled: &CompiledModule,
+        interned: &InternedSymbols,
+        symbols: &ModuleSymbols,
+    ) -> anyhow::Result<()> {
+        let object = match compiled.object() {
+            Some(object) => object,
+            None => return Ok(()),
+        };
+        let key = self.key(path)?;
+
+        fs::create_dir_all(&self.dir)?;
+        let cached_object = self.dir.join(format!("{}-{:016x}.o", compiled.name(), key));
+        fs::copy(object, &cached_object)?;
+
+        let mut entry = String::new();
+        entry.push_str(&format!("key\t{:016x}\n", key));
+        entry.push_str(&format!("name\t{}\n", escape(compiled.name())));
+        entry.push_str(&format!(
+            "object\t{}\n",
+            escape(&cached_object.file_name().unwrap().to_string_lossy())
+        ));
+        entry.push_str(&format!("start\t{}\n", interned.start.as_usize()));
+        for symbol in interned.symbols.iter() {
+            entry.push_str(&format!("interned\t{}\n", escape(symbol.as_str().get())));
+        }
+        // Atoms are interned in the order they are read, so they are written in id order
+        let mut atoms = symbols.atoms.iter().collect::<Vec<_>>();
+        atoms.sort_by_key(|atom| atom.as_usize());
+        for atom in atoms {
+            entry.push_str(&format!(
+                "atom\t{}\t{}\n",
+                atom.as_usize(),
+                escape(atom.as_str().get())
+            ));
+        }
+        for symbol in symbols.symbols.iter() {
+            entry.push_str(&format!(
+                "symbol\t{}\t{}\t{}\n",
+                escape(symbol_of(symbol.module).as_str().get()),
+                escape(symbol_of(symbol.function).as_str().get()),
+                symbol.arity
+            ));
+        }
+        let mut dynamic_calls = symbols.dynamic_calls.iter().collect::<Vec<_>>();
+        dynamic_calls.sort();
+        for arity in dynamic_calls {
+            entry.push_str(&format!("dynamic_call\t{}\n", arity));
+        }
+
+        fs::write(self.entry_path(path), entry)?;
+        Ok(())
+    }
+
+    fn read_entry(
+        &self,
+        path: &Path,
+        key: u64,
+        start: &InterningStart,
+    ) -> Option<(CompiledModule, ModuleSymbols)> {
+        let entry = fs::read_to_string(self.entry_path(path)).ok()?;
+
+        let mut next_id = None;
+        let mut name = None;
+        let mut object = None;
+        let mut symbols = ModuleSymbols::default();
+        for line in entry.lines() {
+            let fields = line.split('\t').map(unescape).collect::<Vec<_>>();
+            match fields.as_slice() {
+                [tag, value] if tag == "key" => {
+                    if u64::from_str_radix(value, 16).ok()? != key {
+                        return None;
+                    }
+                }
+                [tag, value] if tag == "name" => name = Some(value.clone()),
+                [tag, value] if tag == "object" => object = Some(self.dir.join(value)),
+                [tag, id] if tag == "start" => {
+                    if id.parse::<usize>().ok()? != start.marker.as_usize() {
+                        return None;
+                    }
+                    next_id = Some(start.marker.as_usize() + 1);
+                }
+                [tag, value] if tag == "interned" => {
+                    let id = next_id.as_mut()?;
+                    if Symbol::intern(value).as_usize() != *id {
+                        return None;
+                    }
+                    *id += 1;
+                }
+                [tag, id, value] if tag == "atom" => {
+                    let atom = Symbol::intern(value);
+                    if id.parse::<usize>().ok()? != atom.as_usize() {
+                        return None;
+                    }
+                    symbols.atoms.insert(atom);
+                }
+                [tag, module, function, arity] if tag == "symbol" => {
+                    symbols.symbols.insert(FunctionSymbol {
+                        module: Symbol::intern(module).as_usize(),
+                        function: Symbol::intern(function).as_usize(),
+                        arity: arity.parse().ok()?,
+                        ptr: std::ptr::null(),
+                    });
+                }
+                [tag, arity] if tag == "dynamic_call" => {
+                    symbols.dynamic_calls.insert(arity.parse().ok()?);
+                }
+                _ => return None,
+            }
+        }
+
+        // Without the start marker, the symbols of the entry can't have been interned
+        next_id?;
+        let object = object.filter(|object| object.exists())?;
+        Some((CompiledModule::new(name?, Some(object), None), symbols))
+    }
+
+    fn entry_path(&self, path: &Path) -> PathBuf {
+        let mut hasher = StableHasher::new();
+        path.hash(&mut hasher);
+        self.dir.join(format!("{:016x}.entry", hasher.finish()))
+    }
+
+    /// Computes the key of the source file at `path`, which covers the options, the contents of
+    /// the file, and the contents of the files it includes
+    fn key(&self, path: &Path) -> io::Result<u64> {
+        let mut hasher = StableHasher::new();
+        self.options_hash.hash(&mut hasher);
+        path.hash(&mut hasher);
+
+        let mut visited = HashSet::new();
+        self.hash_file(path, &mut hasher, &mut visited)?;
+
+        Ok(hasher.finish())
+    }
+
+    fn hash_file(
+        &self,
+        path: &Path,
+        hasher: &mut StableHasher,
+        visited: &mut HashSet<PathBuf>,
+    ) -> io::Result<()> {
+        if !visited.insert(path.to_path_buf()) {
+            return Ok(());
+        }
+
+        let bytes = fs::read(path)?;
+        bytes.hash(hasher);
+
+        let source = String::from_utf8_lossy(&bytes);
+        for include in scan_includes(&source) {
+            // An include that can't be found is either conditional or fails compilation
+            if let Some(include_path) =
+                self.include_search
+                    .resolve(include.kind, &include.path, path)
+            {
+                include_path.hash(hasher);
+                self.hash_file(&include_path, hasher, visited)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A `Hasher` which feeds everything written to it into SHA-256, so that keys stay the same
+/// across builds of the compiler
+struct StableHasher(Sha256);
+impl StableHasher {
+    fn new() -> Self {
+        Self(Sha256::new())
+    }
+}
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.input(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let digest = self.0.clone().result();
+        u64::from_be_bytes(digest[..8].try_into().unwrap())
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(s: &str) -> String {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('t') => unescaped.push('\t'),
+                Some('n') => unescaped.push('\n'),
+                Some('r') => unescaped.push('\r'),
+                Some(c) => unescaped.push(c),
+                None => (),
+            }
+        } else {
+            unescaped.push(c);
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::process::Command;
+
+    use crate::argparser;
+
+    const DIR_VAR: &str = "LUMEN_INCREMENTAL_CACHE_TEST_DIR";
+    /// Printed by `build_in_this_process` before whether its build was a hit or a miss
+    const BUILD_RESULT: &str = "incremental cache test build: ";
+
+    /// Creates an empty directory under the temporary directory for the test named `name`
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join("lumen_incremental_cache").join(name);
+        if dir.exists() {
+            fs::remove_dir_all(&dir).unwrap();
+        }
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn cache(dir: &Path) -> IncrementalCache {
+        let matches = argparser::parser().get_matches_from(vec!["lumen", "compile"]);
+        let options = Options::new(
+            Default::default(),
+            Default::default(),
+            dir.to_path_buf(),
+            matches.subcommand_matches("compile").unwrap(),
+        )
+        .unwrap();
+        IncrementalCache::new(&options, &dir.join("_build"))
+    }
+
+    /// The symbols generated for `m.erl`, which must be interned after its symbols are
+    fn module_symbols() -> ModuleSymbols {
+        let module = Symbol::intern("m");
+        let function = Symbol::intern("f\tg");
+        let mut symbols = ModuleSymbols::default();
+        symbols.atoms.insert(module);
+        symbols.atoms.insert(function);
+        symbols.symbols.insert(FunctionSymbol {
+            module: module.as_usize(),
+            function: function.as_usize(),
+            arity: 1,
+            ptr: std::ptr::null(),
+        });
+        symbols.dynamic_calls.insert(2);
+        symbols
+    }
+
+    /// Looks up `m.erl` in the cache in `dir`, as the compile command does, and on a miss stands
+    /// in for compiling it and stores the result. Returns whether it was a hit.
+    fn build(dir: &Path) -> bool {
+        let cache = cache(dir);
+        let source = dir.join("m.erl");
+        let interning = InterningStart::new(0);
+
+        match cache.lookup(&source, &interning) {
+            Some((compiled, found)) => {
+                interning.finish();
+                let symbols = module_symbols();
+
+                assert_eq!(compiled.name(), "m");
+                assert_eq!(fs::read(compiled.object().unwrap()).unwrap(), b"object");
+                assert_eq!(found.atoms, symbols.atoms);
+                assert!(found.symbols == symbols.symbols);
+                assert_eq!(found.dynamic_calls, symbols.dynamic_calls);
+
+                true
+            }
+            None => {
+                // Parsing interns variables and strings between the atoms of a module
+                Symbol::intern("m");
+                Symbol::intern("X");
+                Symbol::intern("f\tg");
+                Symbol::intern("a string\r\n");
+                let interned = interning.finish();
+
+                let object = dir.join("m.o");
+                fs::write(&object, b"object").unwrap();
+                let compiled = CompiledModule::new("m".to_string(), Some(object), None);
+                cache
+                    .store(&source, &compiled, &interned, &module_symbols())
+                    .unwrap();
+
+                false
+            }
+        }
+    }
+
+    /// Runs `build` on `dir` in a new process, so that it starts with an empty interner the same
+    /// as a new run of the compiler, and returns whether it was a hit
+    fn build_in_new_process(dir: &Path) -> bool {
+        let module_path = module_path!();
+        let test_name = format!(
+            "{}::build_in_this_process",
+            &module_path[(module_path.find("::").unwrap() + 2)..]
+        );
+        let output = Command::new(env::current_exe().unwrap())
+            .args(&[&test_name, "--exact", "--nocapture"])
+            .env(DIR_VAR, dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if stdout.contains(&format!("{}hit", BUILD_RESULT)) {
+            true
+        } else {
+            assert!(
+                stdout.contains(&format!("{}miss", BUILD_RESULT)),
+                "{}",
+                stdout
+            );
+            false
+        }
+    }
+
+    /// Only builds when run by `build_in_new_process`
+    #[test]
+    fn build_in_this_process() {
+        if let Some(dir) = env::var_os(DIR_VAR) {
+            let hit = build(Path::new(&dir));
+            println!("{}{}", BUILD_RESULT, if hit { "hit" } else { "miss" });
+        }
+    }
+
+    #[test]
+    fn keys_are_hashed_with_sha256() {
+        let mut hasher = StableHasher::new();
+        "abc".hash(&mut hasher);
+
+        // The first 8 bytes of the SHA-256 digest of `abc` followed by the `str` terminator
+        assert_eq!(hasher.finish(), 0x8e3b08dc1236880b);
+    }
+
+    #[test]
+    fn entries_stored_by_one_process_are_found_by_another() {
+        let dir = test_dir("entries_stored_by_one_process_are_found_by_another");
+        fs::write(dir.join("m.erl"), "-module(m).\n").unwrap();
+
+        assert!(!build_in_new_process(&dir));
+        assert!(build_in_new_process(&dir));
+        assert!(build_in_new_process(&dir));
+    }
+
+    #[test]
+    fn changing_an_included_file_is_a_miss() {
+        let dir = test_dir("changing_an_included_file_is_a_miss");
+        fs::write(dir.join("m.erl"), "-module(m).\n-include(\"m.hrl\").\n").unwrap();
+        fs::write(dir.join("m.hrl"), "-define(X, 1).\n").unwrap();
+
+        assert!(!build_in_new_process(&dir));
+        assert!(build_in_new_process(&dir));
+
+        fs::write(dir.join("m.hrl"), "-define(X, 2).\n").unwrap();
+
+        assert!(!build_in_new_process(&dir));
+    }
+
+    #[test]
+    fn entry_interned_after_other_symbols_is_a_miss() {
+        let dir = test_dir("entry_interned_after_other_symbols_is_a_miss");
+        fs::write(dir.join("m.erl"), "-module(m).\n").unwrap();
+        assert!(!build_in_new_process(&dir));
+
+        // An extra symbol interned before the start marker shifts the ids of every symbol after
+        // it, the same as a change to an earlier input does
+        Symbol::intern("entry_interned_after_other_symbols_is_a_miss");
+        let cache = cache(&dir);
+        let interning = InterningStart::new(0);
+
+        assert!(cache.lookup(&dir.join("m.erl"), &interning).is_none());
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+    }
+}
@@ -14,6 +14,7 @@ use liblumen_mlir as mlir;
 use liblumen_session::{Input, InputType, OutputType};
 
 use crate::compiler::query_groups::*;
+use crate::compiler::ModuleSymbols;
 
 macro_rules! to_query_result {
     ($db:expr, $val:expr) => {
@@ -96,9 +97,19 @@ where
             module: mlir_module,
             atoms,
             symbols,
+            dynamic_calls,
         }) => {
             db.add_atoms(atoms.iter());
             db.add_symbols(symbols.iter());
+            db.add_dynamic_calls(dynamic_calls.iter());
+            db.add_module_symbols(
+                input,
+                ModuleSymbols {
+                    atoms,
+                    symbols,
+                    dynamic_calls,
+                },
+            );
             db.maybe_emit_file_with_opts(&options, input, &mlir_module)?;
             Ok(Arc::new(mlir_module))
         }
@@ -119,7 +130,7 @@ where
     C: CodegenDatabase,
 {
     match db.input_type(input) {
-        InputType::Erlang | InputType::AbstractErlang | InputType::EIR => {
+        InputType::Erlang | InputType::AbstractErlang | InputType::BEAM | InputType::EIR => {
             debug!("input {:?} is erlang", input);
             Ok(db.generate_mlir(thread_id, input)?)
         }
@@ -291,10 +302,11 @@ fn get_input_source_name<C>(db: &C, input: InternedInput) -> Option<String>
 where
     C: CodegenDatabase,
 {
+    let options = db.options();
     let input_info = db.lookup_intern_input(input);
 
     match input_info {
-        Input::File(ref path) => Some(path.to_string_lossy().into_owned()),
+        Input::File(ref path) => Some(options.remap_path(path).to_string_lossy().into_owned()),
         Input::Str { ref name, .. } => Some(name.clone()),
     }
 }
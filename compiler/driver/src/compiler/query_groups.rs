@@ -11,6 +11,7 @@ use liblumen_mlir as mlir;
 
 use crate::compiler::intern::InternedString;
 use crate::compiler::queries;
+use crate::compiler::ModuleSymbols;
 
 #[salsa::query_group(CodegenStorage)]
 pub trait CodegenDatabase: CodegenDatabaseBase {
@@ -77,4 +78,11 @@ pub trait CodegenDatabaseBase: ParserDatabase + StringInternerDatabase {
     fn add_symbols<'a, I>(&self, symbols: I)
     where
         I: Iterator<Item = &'a FunctionSymbol>;
+    fn take_dynamic_calls(&mut self) -> HashSet<u8>;
+    fn add_dynamic_calls<'a, I>(&self, arities: I)
+    where
+        I: Iterator<Item = &'a u8>;
+    /// Records the atoms and symbols generated for a single input, for the incremental cache
+    fn add_module_symbols(&self, input: InternedInput, symbols: ModuleSymbols);
+    fn take_module_symbols(&self, input: InternedInput) -> Option<ModuleSymbols>;
 }
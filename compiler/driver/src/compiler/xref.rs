@@ -0,0 +1,679 @@
+//! Cross-reference analysis of the modules in a project.
+//!
+//! Remote calls and `fun M:F/A` captures are gathered from the EIR of each module, so calls
+//! produced by macros are seen, and their spans point at the call site. Exports and
+//! `-deprecated` attributes are read from the module source, since they are not kept in EIR.
+//!
+//! The analysis reports:
+//!
+//! * calls to functions which are not defined by any module in the project, and which are not
+//!   implemented natively by the runtime
+//! * exported functions which are not called from any other module, for executables only, since
+//!   the exports of a library are its interface
+//! * calls to functions which are deprecated, either by a `-deprecated` attribute in the project,
+//!   or in OTP
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+
+use libeir_diagnostics::{ByteIndex, ByteSpan, Diagnostic, FileMap, Label, Severity};
+use libeir_intern::Symbol;
+use libeir_ir as ir;
+use libeir_ir::{AtomTerm, AtomicTerm, ConstKind, IntTerm};
+
+use liblumen_codegen::generators::symbol_of;
+use liblumen_core::symbols::FunctionSymbol;
+use liblumen_incremental::{InternedInput, ParserDatabase, QueryResult};
+use liblumen_session::app::term::{self, Term};
+use liblumen_session::{Application, DiagnosticsHandler, InputType, ProjectType};
+
+/// The functions implemented natively by the runtime, in `liblumen_otp`, `liblumen_web` and the
+/// minimal runtime, as found in their sources by the build script
+const NATIVE_FUNCTIONS: &[(&str, &str, usize)] =
+    include!(concat!(env!("OUT_DIR"), "/native_functions.rs"));
+
+lazy_static! {
+    static ref NATIVE_FUNCTION_SET: HashSet<(&'static str, &'static str, usize)> =
+        NATIVE_FUNCTIONS.iter().cloned().collect();
+}
+
+/// Functions deprecated in OTP, `None` matches any function or arity
+const DEPRECATED: &[(&str, Option<&str>, Option<usize>, &str)] = &[
+    (
+        "calendar",
+        Some("local_time_to_universal_time"),
+        Some(1),
+        "use calendar:local_time_to_universal_time_dst/1 instead",
+    ),
+    (
+        "crypto",
+        Some("rand_bytes"),
+        Some(1),
+        "use crypto:strong_rand_bytes/1 instead",
+    ),
+    (
+        "erlang",
+        Some("get_stacktrace"),
+        Some(0),
+        "use the new try/catch syntax for retrieving the stack backtrace",
+    ),
+    (
+        "erlang",
+        Some("hash"),
+        Some(2),
+        "use erlang:phash2/2 instead",
+    ),
+    (
+        "erlang",
+        Some("now"),
+        Some(0),
+        "use erlang:monotonic_time/0 or erlang:timestamp/0 instead",
+    ),
+    ("random", None, None, "use the 'rand' module instead"),
+];
+
+/// The callbacks of the OTP behaviours, which are called by the behaviour module rather than
+/// by a module in the project
+const BEHAVIOUR_CALLBACKS: &[(&str, &[(&str, usize)])] = &[
+    (
+        "application",
+        &[
+            ("start", 2),
+            ("stop", 1),
+            ("prep_stop", 1),
+            ("start_phase", 3),
+            ("config_change", 3),
+        ],
+    ),
+    ("supervisor", &[("init", 1)]),
+    (
+        "gen_server",
+        &[
+            ("init", 1),
+            ("handle_call", 3),
+            ("handle_cast", 2),
+            ("handle_info", 2),
+            ("handle_continue", 2),
+            ("terminate", 2),
+            ("code_change", 3),
+            ("format_status", 2),
+        ],
+    ),
+    (
+        "gen_statem",
+        &[
+            ("init", 1),
+            ("callback_mode", 0),
+            ("terminate", 3),
+            ("code_change", 4),
+            ("format_status", 2),
+        ],
+    ),
+    (
+        "gen_event",
+        &[
+            ("init", 1),
+            ("handle_event", 2),
+            ("handle_call", 2),
+            ("handle_info", 2),
+            ("terminate", 2),
+            ("code_change", 3),
+            ("format_status", 2),
+        ],
+    ),
+];
+
+/// A function, as `(module, function, arity)`
+type Mfa = (Symbol, Symbol, usize);
+
+/// A remote call, or a `fun M:F/A` capture
+#[derive(Debug, Clone)]
+struct Call {
+    caller: Mfa,
+    callee: Mfa,
+    span: Option<ByteSpan>,
+}
+
+/// An exported function, and the span of its name in the `-export` attribute
+#[derive(Debug, Clone)]
+struct Export {
+    function: Symbol,
+    arity: usize,
+    span: ByteSpan,
+}
+
+/// A function deprecated by a `-deprecated` attribute, `None` matches any function or arity
+#[derive(Debug, Clone)]
+struct Deprecation {
+    function: Option<String>,
+    arity: Option<usize>,
+    description: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct ModuleXref {
+    name: Symbol,
+    exports: Vec<Export>,
+    behaviours: Vec<String>,
+    deprecations: Vec<Deprecation>,
+    calls: Vec<Call>,
+}
+
+/// The number of problems of each kind found by `Xref::check`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct XrefSummary {
+    pub undefined: usize,
+    pub unused: usize,
+    pub deprecated: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct Xref {
+    modules: Vec<ModuleXref>,
+    defined: HashSet<Mfa>,
+    /// Functions which are called by the runtime, rather than by a module in the project
+    entry_points: HashSet<Mfa>,
+}
+impl Xref {
+    pub fn new(project_type: ProjectType, app: Option<&Application>) -> Self {
+        let mut xref = Self::default();
+        if project_type == ProjectType::Executable {
+            xref.add_entry_point("init", "start", 0);
+            if app.is_some() {
+                xref.add_entry_point(liblumen_session::app::INIT_MODULE, "start", 0);
+            }
+            if let Some((module, _)) = app.and_then(|app| app.mod_callback()) {
+                xref.add_entry_point(module, "start", 2);
+                xref.add_entry_point(module, "stop", 1);
+            }
+        }
+        xref
+    }
+
+    fn add_entry_point(&mut self, module: &str, function: &str, arity: usize) {
+        self.entry_points
+            .insert((Symbol::intern(module), Symbol::intern(function), arity));
+    }
+
+    /// Adds the module compiled from `input` to the analysis
+    ///
+    /// Inputs which are not Erlang, such as MLIR, are skipped.
+    pub fn add_input<D>(&mut self, db: &D, input: InternedInput) -> QueryResult<()>
+    where
+        D: ParserDatabase,
+    {
+        match db.input_type(input) {
+            InputType::Erlang | InputType::AbstractErlang => (),
+            _ => return Ok(()),
+        }
+        let module = db.input_eir(input)?;
+        let file = {
+            let codemap = db.codemap().read().unwrap();
+            codemap.find_file(module.span().start()).cloned()
+        };
+        if let Some(file) = file {
+            self.add_module(&module, &file);
+        }
+        Ok(())
+    }
+
+    /// Adds the functions defined by, and the calls made by, `module` to the analysis
+    ///
+    /// `file` is the source file of the module, which is used to find its exports.
+    pub fn add_module(&mut self, module: &ir::Module, file: &Arc<FileMap>) {
+        let name = module.name().name;
+        let mut calls = Vec::new();
+        for definition in module.function_iter() {
+            let function = definition.function();
+            let ident = function.ident();
+            let caller = (name, ident.name.name, ident.arity);
+            self.defined.insert(caller);
+            gather_calls(function, caller, &mut calls);
+        }
+
+        let base = file.span().start().to_usize();
+        let span = |start: usize, end: usize| {
+            ByteSpan::new(
+                ByteIndex::from((base + start) as u32),
+                ByteIndex::from((base + end) as u32),
+            )
+        };
+        let mut exports = Vec::new();
+        let mut behaviours = Vec::new();
+        let mut deprecations = Vec::new();
+        for attribute in scan_attributes(file.src()) {
+            match attribute.name {
+                "export" => {
+                    for (function, arity, start, end) in parse_export_list(attribute.args) {
+                        exports.push(Export {
+                            function: Symbol::intern(&function),
+                            arity,
+                            span: span(attribute.offset + start, attribute.offset + end),
+                        });
+                    }
+                }
+                "behaviour" | "behavior" => behaviours.push(attribute.args.trim().to_string()),
+                "deprecated" => deprecations.extend(parse_deprecations(attribute.args)),
+                _ => (),
+            }
+        }
+
+        self.modules.push(ModuleXref {
+            name,
+            exports,
+            behaviours,
+            deprecations,
+            calls,
+        });
+    }
+
+    /// Adds the functions in the symbol table to the set of defined functions
+    pub fn add_symbols<'a, I>(&mut self, symbols: I)
+    where
+        I: Iterator<Item = &'a FunctionSymbol>,
+    {
+        for symbol in symbols {
+            let module = symbol_of(symbol.module);
+            let function = symbol_of(symbol.function);
+            self.defined
+                .insert((module, function, symbol.arity as usize));
+        }
+    }
+
+    /// Reports the problems found by the analysis to `diagnostics`
+    ///
+    /// Undefined calls are errors, unused exports and deprecated calls are warnings.
+    pub fn check(&self, diagnostics: &DiagnosticsHandler) -> XrefSummary {
+        let mut summary = XrefSummary::default();
+        let project_modules = self
+            .modules
+            .iter()
+            .map(|m| m.name)
+            .chain(self.defined.iter().map(|(m, _, _)| *m))
+            .collect::<HashSet<_>>();
+
+        let mut called = HashSet::new();
+        for module in self.modules.iter() {
+            for call in module.calls.iter() {
+                // Calls within a module don't make an export used
+                if call.callee.0 != module.name {
+                    called.insert(call.callee);
+                }
+
+                if !self.defined.contains(&call.callee) && !is_native(call.callee) {
+                    let message = if project_modules.contains(&call.callee.0) {
+                        format!("call to undefined function {}", format_mfa(call.callee))
+                    } else {
+                        format!(
+                            "call to {}, but module '{}' is not part of the project",
+                            format_mfa(call.callee),
+                            call.callee.0
+                        )
+                    };
+                    self.report(diagnostics, Severity::Error, message, call, None);
+                    summary.undefined += 1;
+                }
+
+                if let Some(description) = self.deprecation(call.callee) {
+                    let message = format!("{} is deprecated", format_mfa(call.callee));
+                    self.report(diagnostics, Severity::Warning, message, call, description);
+                    summary.deprecated += 1;
+                }
+            }
+        }
+
+        // The exports of a library are its interface, so are used by definition
+        if self.entry_points.is_empty() {
+            return summary;
+        }
+        for module in self.modules.iter() {
+            let callbacks = behaviour_callbacks(&module.behaviours);
+            for export in module.exports.iter() {
+                let mfa = (module.name, export.function, export.arity);
+                let function = export.function.as_str().get().to_string();
+                let is_used = called.contains(&mfa)
+                    || self.entry_points.contains(&mfa)
+                    || function == "module_info"
+                    || callbacks.contains(&(function.as_str(), export.arity));
+                if !is_used {
+                    let label = Label::new_primary(export.span)
+                        .with_message("not called from any other module");
+                    diagnostics.diagnostic(
+                        &Diagnostic::new(
+                            Severity::Warning,
+                            format!("{} is exported but unused", format_mfa(mfa)),
+                        )
+                        .with_label(label),
+                    );
+                    summary.unused += 1;
+                }
+            }
+        }
+
+        summary
+    }
+
+    fn report(
+        &self,
+        diagnostics: &DiagnosticsHandler,
+        severity: Severity,
+        message: String,
+        call: &Call,
+        note: Option<String>,
+    ) {
+        let mut diagnostic = Diagnostic::new(severity, message);
+        if let Some(span) = call.span {
+            let label =
+                Label::new_primary(span).with_message(format!("in {}", format_mfa(call.caller)));
+            diagnostic = diagnostic.with_label(label);
+        }
+        let notes = note.into_iter().collect::<Vec<_>>();
+        diagnostics.diagnostic_with(&diagnostic, &notes, &[]);
+    }
+
+    /// Returns the description of the deprecation of `mfa`, if it is deprecated
+    fn deprecation(&self, mfa: Mfa) -> Option<Option<String>> {
+        let (module, function, arity) = mfa;
+        let function = function.as_str().get().to_string();
+        let matches = |f: Option<&str>, a: Option<usize>| {
+            f.map(|f| f == function).unwrap_or(true) && a.map(|a| a == arity).unwrap_or(true)
+        };
+
+        if let Some(m) = self.modules.iter().find(|m| m.name == module) {
+            return m
+                .deprecations
+                .iter()
+                .find(|d| matches(d.function.as_ref().map(|f| f.as_str()), d.arity))
+                .map(|d| d.description.clone());
+        }
+
+        let module = module.as_str().get().to_string();
+        DEPRECATED
+            .iter()
+            .find(|(m, f, a, _)| *m == module && matches(*f, *a))
+            .map(|(_, _, _, description)| Some(description.to_string()))
+    }
+}
+
+fn is_native(mfa: Mfa) -> bool {
+    let (module, function, arity) = mfa;
+    let module = module.as_str().get().to_string();
+    let function = function.as_str().get().to_string();
+    NATIVE_FUNCTION_SET.contains(&(module.as_str(), function.as_str(), arity))
+}
+
+fn behaviour_callbacks(behaviours: &[String]) -> Vec<(&'static str, usize)> {
+    BEHAVIOUR_CALLBACKS
+        .iter()
+        .filter(|(behaviour, _)| behaviours.iter().any(|b| b == behaviour))
+        .flat_map(|(_, callbacks)| callbacks.iter().cloned())
+        .collect()
+}
+
+fn format_mfa(mfa: Mfa) -> String {
+    format!("{}:{}/{}", mfa.0, mfa.1, mfa.2)
+}
+
+/// Gathers the calls to, and captures of, statically known remote functions in `function`
+fn gather_calls(function: &ir::Function, caller: Mfa, calls: &mut Vec<Call>) {
+    let analysis = libeir_lowerutils::analyze(function);
+    let mut visited = HashSet::new();
+    for (_, data) in analysis.functions.iter() {
+        for block in data.scope.iter().copied() {
+            for value in function.block_reads(block).iter().copied() {
+                gather_value_calls(function, caller, value, &mut visited, calls);
+            }
+        }
+    }
+}
+
+fn gather_value_calls(
+    function: &ir::Function,
+    caller: Mfa,
+    value: ir::Value,
+    visited: &mut HashSet<ir::Value>,
+    calls: &mut Vec<Call>,
+) {
+    if !visited.insert(value) {
+        return;
+    }
+    let primop = match function.value_primop(value) {
+        Some(primop) => primop,
+        None => return,
+    };
+    let reads = function.primop_reads(primop);
+    if let ir::PrimOpKind::CaptureFunction = function.primop_kind(primop) {
+        let constant = |value: ir::Value| {
+            function
+                .value_const(value)
+                .map(|constant| function.const_kind(constant))
+        };
+        let callee = match (constant(reads[0]), constant(reads[1]), constant(reads[2])) {
+            (
+                Some(ConstKind::Atomic(AtomicTerm::Atom(AtomTerm(module)))),
+                Some(ConstKind::Atomic(AtomicTerm::Atom(AtomTerm(name)))),
+                Some(ConstKind::Atomic(AtomicTerm::Int(IntTerm(arity)))),
+            ) => (*module, *name, *arity as usize),
+            // Dynamic calls can't be checked
+            _ => return,
+        };
+        let span = function.value_locations(value).map(|locs| locs[0]);
+        calls.push(Call {
+            caller,
+            callee,
+            span,
+        });
+        return;
+    }
+    for read in reads.iter().copied() {
+        gather_value_calls(function, caller, read, visited, calls);
+    }
+}
+
+/// A `-name(args).` attribute in a source file
+struct Attribute<'a> {
+    name: &'a str,
+    args: &'a str,
+    /// The byte offset of `args` in the source
+    offset: usize,
+}
+
+/// Finds the attributes in `source` which begin at the start of a line
+fn scan_attributes(source: &str) -> Vec<Attribute<'_>> {
+    let mut attributes = Vec::new();
+    let mut line_start = 0;
+    for line in source.split('\n') {
+        let start = line_start;
+        line_start += line.len() + 1;
+
+        let trimmed = line.trim_start();
+        if !trimmed.starts_with('-') {
+            continue;
+        }
+        let name_start = start + (line.len() - trimmed.len()) + 1;
+        let name_len = source[name_start..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(source.len() - name_start);
+        let name = &source[name_start..(name_start + name_len)];
+        let rest = &source[(name_start + name_len)..];
+        let open = match rest.find(|c: char| !c.is_whitespace()) {
+            Some(i) if rest[i..].starts_with('(') => name_start + name_len + i,
+            _ => continue,
+        };
+        if let Some(close) = find_closing_paren(source, open) {
+            attributes.push(Attribute {
+                name,
+                args: &source[(open + 1)..close],
+                offset: open + 1,
+            });
+        }
+    }
+    attributes
+}
+
+/// Finds the `)` which closes the `(` at `open`, skipping strings, quoted atoms and comments
+fn find_closing_paren(source: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut chars = source[open..].char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            '"' | '\'' => {
+                while let Some((_, d)) = chars.next() {
+                    if d == '\\' {
+                        chars.next();
+                    } else if d == c {
+                        break;
+                    }
+                }
+            }
+            '$' => {
+                chars.next();
+            }
+            '%' => {
+                while let Some((_, d)) = chars.next() {
+                    if d == '\n' {
+                        break;
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Parses the `[f/1, g/2]` list of an `-export` attribute
+///
+/// Returns the name and arity of each function, with the byte range of the entry in `args`.
+fn parse_export_list(args: &str) -> Vec<(String, usize, usize, usize)> {
+    let open = match args.find('[') {
+        Some(open) => open,
+        None => return Vec::new(),
+    };
+    let close = args.rfind(']').unwrap_or_else(|| args.len());
+    let mut exports = Vec::new();
+    let mut offset = open + 1;
+    for entry in args[(open + 1)..close].split(',') {
+        let entry_offset = offset;
+        offset += entry.len() + 1;
+
+        // Skip whitespace and comments before the entry, and comments after it
+        let mut start = 0;
+        loop {
+            let rest = &entry[start..];
+            let trimmed = rest.trim_start();
+            start += rest.len() - trimmed.len();
+            if !trimmed.starts_with('%') {
+                break;
+            }
+            start += trimmed.find('\n').unwrap_or_else(|| trimmed.len());
+        }
+        let trimmed = entry[start..].split('%').next().unwrap().trim_end();
+        let start = entry_offset + start;
+        let mut parts = trimmed.rsplitn(2, '/');
+        let arity = parts.next().and_then(|a| a.trim().parse::<usize>().ok());
+        let name = parts.next().map(|n| n.trim().trim_matches('\''));
+        if let (Some(name), Some(arity)) = (name, arity) {
+            exports.push((name.to_string(), arity, start, start + trimmed.len()));
+        }
+    }
+    exports
+}
+
+/// Parses the argument of a `-deprecated` attribute
+fn parse_deprecations(args: &str) -> Vec<Deprecation> {
+    let term = match term::consult(&format!("{}.", args)) {
+        Ok(mut terms) if terms.len() == 1 => terms.pop().unwrap(),
+        _ => return Vec::new(),
+    };
+    let entries = match term {
+        Term::List(entries, None) => entries,
+        entry => vec![entry],
+    };
+    let wildcard = |term: &Term| term.as_atom() == Some("_");
+    entries
+        .iter()
+        .filter_map(|entry| match entry {
+            // `-deprecated(module).`
+            Term::Atom(ref a) if a == "module" => Some(Deprecation {
+                function: None,
+                arity: None,
+                description: None,
+            }),
+            Term::Tuple(ref elements) if elements.len() == 2 || elements.len() == 3 => {
+                let function = if wildcard(&elements[0]) {
+                    None
+                } else {
+                    Some(elements[0].as_atom()?.to_string())
+                };
+                let arity = match elements[1] {
+                    Term::Integer(ref i) => Some(i.parse().ok()?),
+                    ref a if wildcard(a) => None,
+                    _ => return None,
+                };
+                let description = elements.get(2).map(|d| match d.as_string() {
+                    Some(s) => s,
+                    None => format!("will be removed {}", d),
+                });
+                Some(Deprecation {
+                    function,
+                    arity,
+                    description,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mfa(module: &str, function: &str, arity: usize) -> Mfa {
+        (Symbol::intern(module), Symbol::intern(function), arity)
+    }
+
+    #[test]
+    fn native_functions_include_every_native_module() {
+        assert!(is_native(mfa("atomics", "add_get", 3)));
+        assert!(is_native(mfa("counters", "new", 2)));
+        assert!(is_native(mfa("crypto", "hash", 2)));
+        assert!(is_native(mfa("persistent_term", "get", 1)));
+        assert!(is_native(mfa("rand", "uniform", 1)));
+        assert!(is_native(mfa("ets", "foldl", 3)));
+        assert!(is_native(mfa("Elixir.Lumen.Web.Document", "body", 1)));
+    }
+
+    #[test]
+    fn native_functions_are_named_by_attribute_or_file() {
+        assert!(is_native(mfa("erlang", "+", 2)));
+        assert!(is_native(mfa("erlang", "=:=", 2)));
+        assert!(is_native(mfa("maps", "remove", 2)));
+        // Generated by a macro in `ceil_1.rs`
+        assert!(is_native(mfa("erlang", "ceil", 1)));
+    }
+
+    #[test]
+    fn native_functions_include_symbols_exported_by_the_runtime() {
+        assert!(is_native(mfa("io", "format", 2)));
+        assert!(is_native(mfa("init", "get_plain_arguments", 0)));
+        assert!(is_native(mfa("erlang", "halt", 1)));
+    }
+
+    #[test]
+    fn native_functions_are_matched_by_arity() {
+        assert!(!is_native(mfa("erlang", "+", 3)));
+        assert!(!is_native(mfa("lists", "reverse", 5)));
+        assert!(!is_native(mfa("rand", "not_a_function", 0)));
+        assert!(!is_native(mfa("io", "format", 3)));
+    }
+}
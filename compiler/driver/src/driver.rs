@@ -45,6 +45,23 @@ pub fn run_compiler_with_emitter(
             cwd,
             emitter,
         ),
+        ("shell", subcommand_matches) => commands::shell::handle_command(
+            c_opts,
+            z_opts,
+            subcommand_matches.unwrap(),
+            cwd,
+            emitter,
+        ),
+        ("lsp", subcommand_matches) => {
+            commands::lsp::handle_command(c_opts, z_opts, subcommand_matches.unwrap(), cwd, emitter)
+        }
+        ("xref", subcommand_matches) => commands::xref::handle_command(
+            c_opts,
+            z_opts,
+            subcommand_matches.unwrap(),
+            cwd,
+            emitter,
+        ),
         (subcommand, _) => Err(anyhow!(format!("Unrecognized subcommand '{}'", subcommand))),
     }
 }
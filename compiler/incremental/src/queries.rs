@@ -1,10 +1,14 @@
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use liblumen_session::{IRModule, Input, InputType};
+use liblumen_session::{
+    find_remote_captures, Application, IRModule, IncludeKind, IncludeSearch, Input, InputType,
+    Suggestion,
+};
 use liblumen_util::{seq, seq::Seq};
 
-use libeir_diagnostics::FileName;
+use libeir_diagnostics::{Diagnostic, FileName, Label, Severity};
 use libeir_frontend::{AnyFrontend, DynFrontend};
 use libeir_syntax_erl::ParseConfig;
 
@@ -28,6 +32,11 @@ where
 
     let options = db.options();
 
+    // An application is compiled from its `src` directory
+    if let Some(ref app) = options.app {
+        return app_sources(db, app);
+    }
+
     // Handle case where input is empty, indicating to compile the current working directory
     if options.input_file.is_none() {
         return find_sources(db, &options.current_dir);
@@ -80,6 +89,26 @@ where
     }
 }
 
+/// The sources of an OTP application, followed by the modules generated to embed it
+fn app_sources<P>(db: &P, app: &Application) -> QueryResult<Arc<Seq<InternedInput>>>
+where
+    P: ParserDatabase,
+{
+    let sources = find_sources(db, app.src_dir())?;
+    let modules = sources
+        .iter()
+        .map(|input| {
+            let input_info = db.lookup_intern_input(*input);
+            input_info.file_stem().to_string_lossy().into_owned()
+        })
+        .collect::<Vec<_>>();
+    let mut inputs = sources.to_vec();
+    for input in app.runtime_modules(&modules) {
+        inputs.push(db.intern_input(input));
+    }
+    Ok(Arc::new(inputs.into()))
+}
+
 pub(crate) fn input_type<P>(db: &P, input: InternedInput) -> InputType
 where
     P: ParserDatabase,
@@ -97,7 +126,15 @@ where
     parse_config.warnings_as_errors = options.warnings_as_errors;
     parse_config.no_warn = options.no_warn;
     parse_config.include_paths = options.include_path.clone();
-    parse_config.code_paths = options.code_path.clone();
+    // `-include_lib` applications are found in the code path, `ERL_LIBS`, and their `lib` dirs,
+    // which are given after the resolved directory of each application found in them, so that
+    // versioned directories and `ebin` code paths are found the same as by the compiler
+    let search = IncludeSearch::new(&options);
+    parse_config.code_paths = search
+        .app_dirs()
+        .into_iter()
+        .chain(search.lib_dirs())
+        .collect();
     parse_config
 }
 
@@ -109,9 +146,10 @@ where
     use libeir_frontend::eir::EirFrontend;
     use libeir_frontend::erlang::ErlangFrontend;
 
-    let frontend: AnyFrontend = match db.input_type(input) {
+    let input_type = db.input_type(input);
+    let frontend: AnyFrontend = match input_type {
         InputType::Erlang => ErlangFrontend::new(db.parse_config()).into(),
-        InputType::AbstractErlang => AbstrErlangFrontend::new().into(),
+        InputType::AbstractErlang | InputType::BEAM => AbstrErlangFrontend::new().into(),
         InputType::EIR => EirFrontend::new().into(),
         _ => unreachable!(),
     };
@@ -119,8 +157,16 @@ where
     let codemap = db.codemap().clone();
 
     let (result, diags) = match db.lookup_intern_input(input) {
+        // The abstract code in a BEAM file is lowered the same as an `.abstr` file
+        Input::File(ref path) if input_type == InputType::BEAM => {
+            let listing = beam_abstract_code(db, path)?;
+            frontend.parse_string_dyn(codemap, &listing)
+        }
         Input::File(ref path) => frontend.parse_file_dyn(codemap, path),
-        Input::Str { ref input, .. } => frontend.parse_string_dyn(codemap, input),
+        Input::Str { .. } => {
+            let text = db.input_text(input).unwrap_or_default();
+            frontend.parse_string_dyn(codemap, text.as_str())
+        }
     };
 
     for ref diagnostic in diags.iter() {
@@ -131,9 +177,113 @@ where
         Ok(module) => {
             let options = db.options();
             db.maybe_emit_file_with_opts(&options, input, &module)?;
-            Ok(module.into())
+            let remote_captures = match input_type {
+                InputType::Erlang => find_remote_captures(&db.codemap().read().unwrap(), &module),
+                _ => HashSet::new(),
+            };
+            Ok(IRModule::from(module).with_remote_captures(Arc::new(remote_captures)))
+        }
+        Err(_) => {
+            if let Input::File(ref path) = db.lookup_intern_input(input) {
+                if input_type == InputType::Erlang {
+                    report_missing_includes(db, path);
+                }
+            }
+            Err(())
+        }
+    }
+}
+
+/// Reports the includes in the source file at `path` that can't be found, with the directories
+/// that were searched, as the preprocessor doesn't say where it looked
+fn report_missing_includes<P>(db: &P, path: &Path)
+where
+    P: ParserDatabase,
+{
+    use liblumen_session::include_search::{find_includes, ERL_LIBS};
+
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(_) => return,
+    };
+    let file = db
+        .codemap()
+        .write()
+        .unwrap()
+        .add_filemap(FileName::Real(path.to_path_buf()), source);
+    let search = IncludeSearch::new(&db.options());
+
+    // Conditional includes are found too, but the parse failed, so they may be why
+    for include in find_includes(&file) {
+        if search.resolve(include.kind, &include.path, path).is_some() {
+            continue;
+        }
+
+        let mut notes = Vec::new();
+        if include.kind == IncludeKind::IncludeLib {
+            let app = include.path.split('/').next().unwrap_or_default();
+            if search.app_dir(app).is_none() {
+                notes.push(format!(
+                    "no directory for the application `{}` was found in the code path or {}",
+                    app, ERL_LIBS
+                ));
+            }
+        }
+        let searched = search
+            .searched_dirs(include.kind, path)
+            .iter()
+            .map(|dir| format!("    {}", dir.display()))
+            .collect::<Vec<_>>();
+        notes.push(format!("searched in:\n{}", searched.join("\n")));
+
+        let diagnostic = Diagnostic::new(
+            Severity::Error,
+            format!(
+                "cannot find {} file \"{}\"",
+                include.kind.attribute(),
+                include.path
+            ),
+        )
+        .with_label(Label::new_primary(include.span).with_message("included here"));
+        // An application's include file is often named in an `-include` by mistake
+        let suggestions = match include.kind {
+            IncludeKind::Include if search.resolve_include_lib(&include.path, path).is_some() => {
+                vec![Suggestion::new(
+                    "the file is in an application, so is included with `-include_lib`",
+                    include.span,
+                    format!("-include_lib(\"{}\")", include.path),
+                )]
+            }
+            _ => Vec::new(),
+        };
+        db.diagnostics()
+            .diagnostic_with(&diagnostic, &notes, &suggestions);
+    }
+}
+
+/// Reads the abstract code from the `Abst` or `Dbgi` chunk of the BEAM file at `path` as the
+/// forms of an `.abstr` file.
+fn beam_abstract_code<P>(db: &P, path: &Path) -> QueryResult<String>
+where
+    P: ParserDatabase,
+{
+    use liblumen_beam::syntax::ast::format::raw_abstract_v1::AbstractCode;
+    use liblumen_beam::FromBeamError;
+
+    match AbstractCode::from_beam_file(path).and_then(|code| code.to_listing()) {
+        Ok(listing) => Ok(listing),
+        Err(FromBeamError::NoDebugInfo) => {
+            db.diagnostics().error_str(&format!(
+                "{} cannot be compiled because it has no debug info, recompile it with +debug_info",
+                path.display()
+            ));
+            Err(())
+        }
+        Err(err) => {
+            db.diagnostics()
+                .error_str(&format!("{} cannot be compiled: {}", path.display(), err));
+            Err(())
         }
-        Err(_) => Err(()),
     }
 }
 
@@ -149,7 +299,8 @@ where
     let mut pass_manager = PassManager::default();
     pass_manager.run(&mut ir_module);
 
-    let new_module = IRModule::new(ir_module);
+    let new_module =
+        IRModule::new(ir_module).with_remote_captures(module.remote_captures().clone());
     db.maybe_emit_file(input, &new_module)?;
     Ok(new_module)
 }
@@ -181,7 +332,11 @@ where
         InputType::is_valid(entry.path())
     }
 
-    let walker = WalkDir::new(dir.as_ref()).follow_links(false).into_iter();
+    // Entries are sorted so that the order of inputs, and so of the compiled output, is stable
+    let walker = WalkDir::new(dir.as_ref())
+        .follow_links(false)
+        .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+        .into_iter();
 
     let mut inputs = Vec::new();
 
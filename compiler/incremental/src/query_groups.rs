@@ -51,6 +51,17 @@ pub trait ParserDatabaseBase: InternerDatabase {
 
     fn codemap(&self) -> &Arc<RwLock<CodeMap>>;
 
+    /// Returns the source text of `input`, or `None` if it is read from a file.
+    ///
+    /// Databases whose sources are edited while they are open override this with an input
+    /// query, so that queries which read the text are recomputed when it is set.
+    fn input_text(&self, input: InternedInput) -> Option<Arc<String>> {
+        match self.lookup_intern_input(input) {
+            Input::File(_) => None,
+            Input::Str { input, .. } => Some(Arc::new(input.into_owned())),
+        }
+    }
+
     fn maybe_emit_file<E>(&self, input: InternedInput, emit: &E) -> QueryResult<Option<PathBuf>>
     where
         E: Emit;
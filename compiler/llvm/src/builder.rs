@@ -251,6 +251,13 @@ impl<'ctx> ModuleBuilder<'ctx> {
         unsafe { LLVMAppendBasicBlock(fun, entry_name.as_ptr()) }
     }
 
+    pub fn build_block(&self, fun: Value, name: &str) -> Block {
+        use llvm_sys::core::LLVMAppendBasicBlock;
+
+        let name = CString::new(name).unwrap();
+        unsafe { LLVMAppendBasicBlock(fun, name.as_ptr()) }
+    }
+
     pub fn position_at_end(&self, block: Block) {
         use llvm_sys::core::LLVMPositionBuilderAtEnd;
 
@@ -273,6 +280,57 @@ impl<'ctx> ModuleBuilder<'ctx> {
         unsafe { LLVMSetTailCall(call, is_tail as LLVMBool) }
     }
 
+    pub fn build_is_null(&self, value: Value) -> Value {
+        use llvm_sys::core::LLVMBuildIsNull;
+
+        unsafe { LLVMBuildIsNull(self.builder, value, UNNAMED) }
+    }
+
+    pub fn build_condbr(&self, cond: Value, then_block: Block, else_block: Block) -> Value {
+        use llvm_sys::core::LLVMBuildCondBr;
+
+        unsafe { LLVMBuildCondBr(self.builder, cond, then_block, else_block) }
+    }
+
+    pub fn build_alloca(&self, ty: Type) -> Value {
+        use llvm_sys::core::LLVMBuildAlloca;
+
+        unsafe { LLVMBuildAlloca(self.builder, ty, UNNAMED) }
+    }
+
+    pub fn build_store(&self, value: Value, ptr: Value) -> Value {
+        use llvm_sys::core::LLVMBuildStore;
+
+        unsafe { LLVMBuildStore(self.builder, value, ptr) }
+    }
+
+    pub fn build_inbounds_gep(&self, ty: Type, ptr: Value, indices: &[usize]) -> Value {
+        use llvm_sys::core::LLVMBuildInBoundsGEP2;
+
+        let i32_type = self.get_i32_type();
+        let indices_values = indices
+            .iter()
+            .map(|i| self.build_constant_uint(i32_type, *i))
+            .collect::<Vec<_>>();
+        let num_indices = indices_values.len() as libc::c_uint;
+        unsafe {
+            LLVMBuildInBoundsGEP2(
+                self.builder,
+                ty,
+                ptr,
+                indices_values.as_ptr() as *mut _,
+                num_indices,
+                UNNAMED,
+            )
+        }
+    }
+
+    pub fn build_unreachable(&self) -> Value {
+        use llvm_sys::core::LLVMBuildUnreachable;
+
+        unsafe { LLVMBuildUnreachable(self.builder) }
+    }
+
     pub fn build_return(&self, ret: Value) -> Value {
         use llvm_sys::core::LLVMBuildRet;
 
@@ -338,6 +396,12 @@ impl<'ctx> ModuleBuilder<'ctx> {
         unsafe { LLVMConstPointerCast(value, ty) }
     }
 
+    pub fn build_bitcast(&self, value: Value, ty: Type) -> Value {
+        use llvm_sys::core::LLVMBuildBitCast;
+
+        unsafe { LLVMBuildBitCast(self.builder, value, ty, UNNAMED) }
+    }
+
     pub fn build_const_inbounds_gep(&self, value: Value, indices: &[usize]) -> Value {
         use llvm_sys::core::LLVMConstInBoundsGEP;
 
@@ -0,0 +1,747 @@
+//! Support for compiling OTP application directories.
+//!
+//! An application directory is recognized by the `src/<name>.app.src` resource file it contains,
+//! e.g.:
+//!
+//! ```text
+//! myapp/
+//!     include/
+//!     src/
+//!         myapp.app.src
+//!         myapp_app.erl
+//!         myapp_sup.erl
+//! ```
+//!
+//! When compiling an application, the modules in `src` are compiled with `include` on the
+//! include path, the `modules` and `registered` keys of the resource file are checked against the
+//! sources, and `<name>.app` is written to the output directory with the `modules` list filled
+//! in from the sources.
+//!
+//! The application metadata is embedded in the executable as a generated `lumen_application`
+//! module. Its `start/1` starts the `applications` the application depends on, in order, and then
+//! calls the `mod` callback, and it provides `get_env` and `get_key` for the application
+//! environment and resource keys. A `lumen_init` module is also generated, whose `start/0` starts
+//! the application when the runtime boots, in place of `init:start/0`. The generated modules have
+//! names of their own so that they don't shadow the OTP modules of the same purpose.
+pub mod term;
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+
+use crate::{DiagnosticsHandler, Input};
+
+use self::term::Term;
+
+/// The extension of application resource files in `src`
+pub const APP_SRC_EXTENSION: &str = ".app.src";
+
+/// The name of the generated module which embeds the application metadata
+pub const APPLICATION_MODULE: &str = "lumen_application";
+
+/// The name of the generated module whose `start/0` the runtime calls to start the application
+pub const INIT_MODULE: &str = "lumen_init";
+
+/// An OTP application, as described by its `.app.src` resource file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Application {
+    /// The application directory, i.e. the directory containing `src`
+    pub dir: PathBuf,
+    /// The path of the `.app.src` file this application was loaded from
+    pub resource_file: PathBuf,
+    pub name: String,
+    /// The keys of the resource file, in the order they were written
+    pub properties: Vec<(String, Term)>,
+}
+impl Application {
+    /// Loads the application in `dir`, if it is an application directory
+    pub fn load(dir: &Path) -> anyhow::Result<Option<Self>> {
+        match Self::find_resource_file(dir)? {
+            None => Ok(None),
+            Some(resource_file) => Self::load_resource_file(dir, resource_file).map(Some),
+        }
+    }
+
+    /// Finds `src/*.app.src` in `dir`
+    pub fn find_resource_file(dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+        let src = dir.join("src");
+        if !src.is_dir() {
+            return Ok(None);
+        }
+        let mut found = fs::read_dir(&src)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .map(|name| name.ends_with(APP_SRC_EXTENSION))
+                        .unwrap_or(false)
+            })
+            .collect::<Vec<_>>();
+        found.sort();
+        match found.len() {
+            0 => Ok(None),
+            1 => Ok(found.pop()),
+            _ => Err(anyhow!(
+                "found more than one application resource file in {}",
+                src.display()
+            )),
+        }
+    }
+
+    fn load_resource_file(dir: &Path, resource_file: PathBuf) -> anyhow::Result<Self> {
+        let source = fs::read_to_string(&resource_file)
+            .map_err(|err| anyhow!("could not read {}: {}", resource_file.display(), err))?;
+        let invalid = |message: String| anyhow!("{}: {}", resource_file.display(), message);
+
+        let mut terms = term::consult(&source).map_err(|err| invalid(err.to_string()))?;
+        if terms.len() != 1 {
+            return Err(invalid(format!(
+                "expected a single application specification, found {} terms",
+                terms.len()
+            )));
+        }
+        let (name, properties) = match terms.pop().unwrap() {
+            Term::Tuple(mut elements) if elements.len() == 3 => {
+                let properties = elements.pop().unwrap();
+                let name = elements.pop().unwrap();
+                match (elements[0].as_atom(), name) {
+                    (Some("application"), Term::Atom(name)) => (name, properties),
+                    _ => {
+                        return Err(invalid(
+                            "expected `{application, Name, Properties}`".to_string(),
+                        ))
+                    }
+                }
+            }
+            _ => {
+                return Err(invalid(
+                    "expected `{application, Name, Properties}`".to_string(),
+                ))
+            }
+        };
+
+        let expected_name = resource_file
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| &name[..(name.len() - APP_SRC_EXTENSION.len())])
+            .unwrap_or_default();
+        if name != expected_name {
+            return Err(invalid(format!(
+                "the application is named `{}`, but the file is named for `{}`",
+                name, expected_name
+            )));
+        }
+
+        let properties = match properties {
+            Term::List(elements, None) => elements
+                .into_iter()
+                .map(|element| match element {
+                    Term::Tuple(mut pair) if pair.len() == 2 && pair[0].as_atom().is_some() => {
+                        let value = pair.pop().unwrap();
+                        let key = pair.pop().unwrap().as_atom().unwrap().to_string();
+                        Ok((key, value))
+                    }
+                    other => Err(invalid(format!(
+                        "expected a `{{Key, Value}}` application property, found `{}`",
+                        other
+                    ))),
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            _ => {
+                return Err(invalid(
+                    "expected a list of application properties".to_string(),
+                ))
+            }
+        };
+
+        let app = Self {
+            dir: dir.to_path_buf(),
+            resource_file: resource_file.clone(),
+            name,
+            properties,
+        };
+        app.check_properties().map_err(invalid)?;
+        Ok(app)
+    }
+
+    /// Checks the types of the properties the compiler relies on
+    fn check_properties(&self) -> Result<(), String> {
+        for key in [
+            "modules",
+            "registered",
+            "applications",
+            "included_applications",
+        ]
+        .iter()
+        {
+            if let Some(value) = self.get(key) {
+                let is_atom_list = value
+                    .as_list()
+                    .map(|elements| elements.iter().all(|e| e.as_atom().is_some()))
+                    .unwrap_or(false);
+                if !is_atom_list {
+                    return Err(format!("expected `{}` to be a list of atoms", key));
+                }
+            }
+        }
+        if let Some(value) = self.get("description") {
+            if value.as_string().is_none() {
+                return Err("expected `description` to be a string".to_string());
+            }
+        }
+        if let Some(value) = self.get("mod") {
+            match value {
+                Term::Tuple(ref elements)
+                    if elements.len() == 2 && elements[0].as_atom().is_some() => {}
+                _ => return Err("expected `mod` to be a `{Module, StartArgs}` tuple".to_string()),
+            }
+        }
+        if let Some(value) = self.get("env") {
+            let is_proplist = value
+                .as_list()
+                .map(|elements| {
+                    elements.iter().all(|element| match element {
+                        Term::Tuple(ref pair) => pair.len() == 2 && pair[0].as_atom().is_some(),
+                        _ => false,
+                    })
+                })
+                .unwrap_or(false);
+            if !is_proplist {
+                return Err("expected `env` to be a list of `{Par, Val}` tuples".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn src_dir(&self) -> PathBuf {
+        self.dir.join("src")
+    }
+
+    pub fn include_dir(&self) -> PathBuf {
+        self.dir.join("include")
+    }
+
+    /// Returns the value of the property `key`
+    pub fn get(&self, key: &str) -> Option<&Term> {
+        self.properties
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+
+    fn atoms(&self, key: &str) -> Vec<String> {
+        self.get(key)
+            .and_then(|value| value.as_list())
+            .map(|elements| {
+                elements
+                    .iter()
+                    .filter_map(|e| e.as_atom().map(|a| a.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The modules listed in the resource file
+    pub fn modules(&self) -> Vec<String> {
+        self.atoms("modules")
+    }
+
+    /// The registered process names listed in the resource file
+    pub fn registered(&self) -> Vec<String> {
+        self.atoms("registered")
+    }
+
+    /// The application callback module and its start arguments
+    pub fn mod_callback(&self) -> Option<(&str, &Term)> {
+        match self.get("mod") {
+            Some(Term::Tuple(ref elements)) => Some((elements[0].as_atom()?, &elements[1])),
+            _ => None,
+        }
+    }
+
+    /// The application environment, as a list of `{Par, Val}` tuples
+    pub fn env(&self) -> Term {
+        self.get("env")
+            .cloned()
+            .unwrap_or_else(|| Term::List(Vec::new(), None))
+    }
+
+    /// Checks the resource file against `sources`, the module names and source text of each
+    /// module in the application, reporting any problems to `diagnostics`
+    ///
+    /// Returns `false` if any errors were reported.
+    pub fn validate(&self, diagnostics: &DiagnosticsHandler, sources: &[(String, String)]) -> bool {
+        let file = self.resource_file.display();
+        let mut valid = true;
+
+        if self.get("modules").is_some() {
+            let listed = self.modules();
+            for module in listed.iter() {
+                if !sources.iter().any(|(name, _)| name == module) {
+                    diagnostics.error_str(&format!(
+                        "{}: module `{}` is listed in `modules`, but there is no source for it",
+                        file, module
+                    ));
+                    valid = false;
+                }
+            }
+            for (name, _) in sources.iter() {
+                if !listed.contains(name) {
+                    diagnostics.warn(format!(
+                        "{}: module `{}` is not listed in `modules`, it will be added to {}.app",
+                        file, name, self.name
+                    ));
+                }
+            }
+        }
+
+        let mut registered = self.registered();
+        let registered_by_sources = sources
+            .iter()
+            .flat_map(|(module, source)| registered_names(module, source))
+            .collect::<Vec<_>>();
+        for name in registered.iter() {
+            if !registered_by_sources.contains(name) {
+                diagnostics.warn(format!(
+                    "{}: `{}` is listed in `registered`, but no module registers a process with \
+                     that name",
+                    file, name
+                ));
+            }
+        }
+        registered.sort();
+        for pair in registered.windows(2) {
+            if pair[0] == pair[1] {
+                diagnostics.error_str(&format!(
+                    "{}: `{}` is listed in `registered` more than once",
+                    file, pair[0]
+                ));
+                valid = false;
+            }
+        }
+
+        if let Some((module, _)) = self.mod_callback() {
+            if !sources.iter().any(|(name, _)| name == module) {
+                diagnostics.error_str(&format!(
+                    "{}: the application callback module `{}` has no source",
+                    file, module
+                ));
+                valid = false;
+            }
+        }
+
+        valid
+    }
+
+    /// The properties of the `.app` file, with `modules` set to `modules`
+    pub fn resource_properties(&self, modules: &[String]) -> Vec<(String, Term)> {
+        let modules = Term::List(
+            modules.iter().map(|m| Term::Atom(m.clone())).collect(),
+            None,
+        );
+        let mut properties = self.properties.clone();
+        match properties.iter_mut().find(|(key, _)| key == "modules") {
+            Some((_, value)) => *value = modules,
+            None => properties.push(("modules".to_string(), modules)),
+        }
+        properties
+    }
+
+    /// Renders the `.app` resource file for an application containing `modules`
+    pub fn resource(&self, modules: &[String]) -> String {
+        let mut resource = String::new();
+        writeln!(resource, "{{application,{},", Term::Atom(self.name.clone())).unwrap();
+        let properties = self.resource_properties(modules);
+        for (i, (key, value)) in properties.iter().enumerate() {
+            let open = if i == 0 { "[" } else { " " };
+            let close = if i + 1 == properties.len() {
+                "]}."
+            } else {
+                ","
+            };
+            writeln!(
+                resource,
+                "    {}{{{},{}}}{}",
+                open,
+                Term::Atom(key.clone()),
+                value,
+                close
+            )
+            .unwrap();
+        }
+        if properties.is_empty() {
+            resource.push_str("    []}.\n");
+        }
+        resource
+    }
+
+    /// The generated modules which embed the application in the executable
+    ///
+    /// `modules` are the modules compiled from the application sources.
+    pub fn runtime_modules(&self, modules: &[String]) -> Vec<Input> {
+        vec![
+            Input::new(
+                format!("{}.erl", APPLICATION_MODULE),
+                self.application_module(modules),
+            ),
+            Input::new(format!("{}.erl", INIT_MODULE), self.init_module()),
+        ]
+    }
+
+    fn application_module(&self, modules: &[String]) -> String {
+        let name = Term::Atom(self.name.clone()).to_string();
+        let properties = Term::List(
+            self.resource_properties(modules)
+                .into_iter()
+                .map(|(key, value)| Term::Tuple(vec![Term::Atom(key), value]))
+                .collect(),
+            None,
+        );
+        let start_callback = match self.mod_callback() {
+            None => "start_callback() ->\n    ok.\n".to_string(),
+            Some((module, args)) => {
+                let module = Term::Atom(module.to_string());
+                format!(
+                    r#"start_callback() ->
+    case {module}:start(normal, {args}) of
+        {{ok, _Pid}} ->
+            ok;
+        {{ok, _Pid, _State}} ->
+            ok;
+        {{error, Reason}} ->
+            {{error, {{Reason, {{{module}, start, [normal, {args}]}}}}}};
+        Other ->
+            {{error, {{bad_return, {{{{{module}, start, [normal, {args}]}}, Other}}}}}}
+    end.
+"#,
+                    name = name,
+                    module = module,
+                    args = args
+                )
+            }
+        };
+        format!(
+            r#"%% Generated by lumen from {file}, do not edit.
+-module({module}).
+
+-export([start/1, start/2]).
+-export([get_env/1, get_env/2, get_env/3, get_all_env/0, get_all_env/1]).
+-export([get_key/1, get_key/2, get_all_key/0, get_all_key/1]).
+
+%% kernel and stdlib are provided by the runtime
+start(kernel) ->
+    ok;
+start(stdlib) ->
+    ok;
+start({name}) ->
+    case start_applications({applications}) of
+        ok ->
+            start_callback();
+        {{error, Reason}} ->
+            {{error, Reason}}
+    end;
+start(Application) ->
+    {{error, {{not_loaded, Application}}}}.
+
+start(Application, _Type) ->
+    start(Application).
+
+start_applications([]) ->
+    ok;
+start_applications([Application | Rest]) ->
+    case start(Application) of
+        ok ->
+            start_applications(Rest);
+        {{error, Reason}} ->
+            {{error, {{Application, Reason}}}}
+    end.
+
+{start_callback}
+get_env(Par) ->
+    get_env({name}, Par).
+
+get_env(Application, Par) ->
+    find(Par, get_all_env(Application)).
+
+get_env(Application, Par, Default) ->
+    case get_env(Application, Par) of
+        {{ok, Val}} ->
+            Val;
+        undefined ->
+            Default
+    end.
+
+get_all_env() ->
+    get_all_env({name}).
+
+get_all_env({name}) ->
+    {env};
+get_all_env(_Application) ->
+    [].
+
+get_key(Key) ->
+    get_key({name}, Key).
+
+get_key({name}, Key) ->
+    find(Key, {properties});
+get_key(_Application, _Key) ->
+    undefined.
+
+get_all_key() ->
+    get_all_key({name}).
+
+get_all_key({name}) ->
+    {{ok, {properties}}};
+get_all_key(_Application) ->
+    undefined.
+
+find(_Key, []) ->
+    undefined;
+find(Key, [{{Key, Val}} | _Rest]) ->
+    {{ok, Val}};
+find(Key, [_ | Rest]) ->
+    find(Key, Rest).
+"#,
+            file = self.resource_file_name(),
+            module = APPLICATION_MODULE,
+            applications = Term::List(
+                self.atoms("applications")
+                    .into_iter()
+                    .map(Term::Atom)
+                    .collect(),
+                None
+            ),
+            start_callback = start_callback,
+            name = name,
+            env = self.env(),
+            properties = properties,
+        )
+    }
+
+    fn resource_file_name(&self) -> String {
+        format!("src/{}{}", self.name, APP_SRC_EXTENSION)
+    }
+
+    fn init_module(&self) -> String {
+        format!(
+            r#"%% Generated by lumen from {file}, do not edit.
+-module({module}).
+
+-export([start/0]).
+
+start() ->
+    case {application}:start({name}) of
+        ok ->
+            %% Like the init process of an OTP release, this process lives
+            %% for as long as the system is running
+            receive
+                stop -> ok
+            end;
+        {{error, Reason}} ->
+            exit({{application_start_failure, {name}, Reason}})
+    end.
+"#,
+            file = self.resource_file_name(),
+            module = INIT_MODULE,
+            application = APPLICATION_MODULE,
+            name = Term::Atom(self.name.clone()),
+        )
+    }
+}
+
+/// The names of the processes registered by the module named `module` with `source`, i.e. the
+/// names given to `register/2`, and the `{local, Name}` names given to `gen_server:start_link/4`
+/// and the like
+///
+/// A name may be written as a macro if it is `?MODULE`, or a macro defined as an atom or as
+/// `?MODULE`.
+fn registered_names(module: &str, source: &str) -> Vec<String> {
+    use libeir_diagnostics::{CodeMap, FileName};
+    use libeir_syntax_erl::{FileMapSource, Lexer, LexicalToken, Scanner, Token as LexToken};
+
+    enum Token {
+        Atom(String),
+        Macro(String),
+        /// Any other token, such as a variable, keyword or punctuation
+        Other(String),
+    }
+    let is = |token: &Token, text: &str| match token {
+        Token::Atom(s) | Token::Other(s) => s == text,
+        Token::Macro(_) => false,
+    };
+
+    let mut codemap = CodeMap::new();
+    let file = codemap.add_filemap(FileName::Virtual(module.into()), source.to_string());
+    let base = file.span().start().to_usize();
+    let mut tokens: Vec<Token> = Vec::new();
+    for LexicalToken(start, token, end) in
+        Lexer::new(Scanner::new(FileMapSource::new(file.clone()))).filter_map(Result::ok)
+    {
+        let token = match token {
+            LexToken::Atom(name) => Token::Atom(name.to_string()),
+            _ => {
+                Token::Other(source[(start.to_usize() - base)..(end.to_usize() - base)].to_string())
+            }
+        };
+        // `?NAME` is lexed as two tokens
+        let follows_question = tokens.last().map(|last| is(last, "?")).unwrap_or(false);
+        match token {
+            Token::Atom(name) | Token::Other(name) if follows_question => {
+                *tokens.last_mut().unwrap() = Token::Macro(name);
+            }
+            token => tokens.push(token),
+        }
+    }
+
+    let mut macros = HashMap::new();
+    macros.insert("MODULE".to_string(), module.to_string());
+    for define in tokens.windows(7) {
+        if let [dash, name, open, Token::Other(ref macro_name), comma, value, close] = define {
+            let is_define = is(dash, "-")
+                && is(name, "define")
+                && is(open, "(")
+                && is(comma, ",")
+                && is(close, ")");
+            if !is_define {
+                continue;
+            }
+            let value = match value {
+                Token::Atom(atom) => atom.clone(),
+                Token::Macro(value) => match macros.get(value) {
+                    Some(atom) => atom.clone(),
+                    None => continue,
+                },
+                Token::Other(_) => continue,
+            };
+            macros.insert(macro_name.clone(), value);
+        }
+    }
+    let name_of = |token: &Token| match token {
+        Token::Atom(atom) => Some(atom.clone()),
+        Token::Macro(name) => macros.get(name).cloned(),
+        Token::Other(_) => None,
+    };
+
+    let mut names = Vec::new();
+    for (i, window) in tokens.windows(5).enumerate() {
+        let name = match window {
+            // `register(Name, Pid)` or `erlang:register(Name, Pid)`
+            [function, open, name, comma, _] if is(function, "register") => {
+                let is_remote = i > 0 && is(&tokens[i - 1], ":");
+                let is_erlang = i > 1 && is(&tokens[i - 2], "erlang");
+                if !is(open, "(") || !is(comma, ",") || (is_remote && !is_erlang) {
+                    continue;
+                }
+                name
+            }
+            // `{local, Name}`
+            [open, scope, comma, name, close] if is(open, "{") && is(scope, "local") => {
+                if !is(comma, ",") || !is(close, "}") {
+                    continue;
+                }
+                name
+            }
+            _ => continue,
+        };
+        names.extend(name_of(name));
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(properties: Vec<(&str, Term)>) -> Application {
+        Application {
+            dir: PathBuf::from("myapp"),
+            resource_file: PathBuf::from("myapp/src/myapp.app.src"),
+            name: "myapp".to_string(),
+            properties: properties
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        }
+    }
+
+    fn atoms(atoms: &[&str]) -> Term {
+        Term::List(
+            atoms
+                .iter()
+                .map(|atom| Term::Atom(atom.to_string()))
+                .collect(),
+            None,
+        )
+    }
+
+    #[test]
+    fn generated_modules_have_private_names() {
+        let modules = app(Vec::new()).runtime_modules(&["application".to_string()]);
+
+        let names = modules
+            .iter()
+            .map(|input| input.file_stem().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["lumen_application", "lumen_init"]);
+    }
+
+    #[test]
+    fn dependencies_are_started_in_order_before_the_application() {
+        let app = app(vec![
+            (
+                "applications",
+                atoms(&["kernel", "stdlib", "dep_a", "dep_b"]),
+            ),
+            (
+                "mod",
+                Term::Tuple(vec![
+                    Term::Atom("myapp_app".to_string()),
+                    Term::List(Vec::new(), None),
+                ]),
+            ),
+        ]);
+
+        let module = app.application_module(&[]);
+
+        assert!(module.contains("-module(lumen_application)."));
+        assert!(module.contains(
+            "start(myapp) ->\n    case start_applications([kernel,stdlib,dep_a,dep_b]) of\n"
+        ));
+        let dependencies = module.find("start_applications([kernel").unwrap();
+        let callback = module.find("myapp_app:start(normal, [])").unwrap();
+        assert!(dependencies < callback);
+    }
+
+    #[test]
+    fn registered_names_are_found_where_processes_are_registered() {
+        let source = r#"-module(myapp_sup).
+-define(SERVER, ?MODULE).
+-define(OTHER, other_server).
+
+start_link() ->
+    supervisor:start_link({local, ?SERVER}, ?MODULE, []).
+
+start_other() ->
+    gen_server:start_link({local, ?OTHER}, other, [], []).
+
+start_registered() ->
+    erlang:register(by_bif, self()),
+    register('quoted name', self()),
+    my:register(not_a_bif, self()).
+
+mentions() ->
+    [mentioned_only, {local, Variable}].
+"#;
+
+        assert_eq!(
+            registered_names("myapp_sup", source),
+            vec!["myapp_sup", "other_server", "by_bif", "quoted name"]
+        );
+    }
+}
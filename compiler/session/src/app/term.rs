@@ -0,0 +1,573 @@
+//! A parser for the literal Erlang terms found in `.app.src` and `.app` files.
+//!
+//! Only the subset of the term syntax which can appear in a consultable file is supported, i.e.
+//! atoms, numbers, characters, strings, binaries, lists, tuples and maps; variables, operators
+//! (other than a leading `-` on numbers), and function calls are rejected.
+use std::fmt::{self, Write};
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    Atom(String),
+    /// An integer, kept in its source form so that bignums round-trip
+    Integer(String),
+    /// A float, kept in its source form so that it round-trips exactly
+    Float(String),
+    String(String),
+    Binary(String),
+    List(Vec<Term>, Option<Box<Term>>),
+    Tuple(Vec<Term>),
+    Map(Vec<(Term, Term)>),
+}
+impl Term {
+    pub fn as_atom(&self) -> Option<&str> {
+        match self {
+            Self::Atom(ref name) => Some(name.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of a proper list
+    pub fn as_list(&self) -> Option<&[Term]> {
+        match self {
+            Self::List(ref elements, None) => Some(elements.as_slice()),
+            _ => None,
+        }
+    }
+
+    /// Returns the characters of a string, which may be written as a list of character codes
+    pub fn as_string(&self) -> Option<String> {
+        match self {
+            Self::String(ref s) => Some(s.clone()),
+            Self::List(ref elements, None) => elements
+                .iter()
+                .map(|element| match element {
+                    Self::Integer(ref i) => i.parse::<u32>().ok().and_then(std::char::from_u32),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+
+    /// Looks up `key` in a proplist, i.e. a list of `{Key, Value}` tuples
+    pub fn proplist_get(&self, key: &str) -> Option<&Term> {
+        self.as_list()?.iter().find_map(|element| match element {
+            Self::Tuple(ref elements) if elements.len() == 2 => {
+                if elements[0].as_atom() == Some(key) {
+                    Some(&elements[1])
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        })
+    }
+}
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Atom(ref name) => write_atom(f, name),
+            Self::Integer(ref i) => f.write_str(i),
+            Self::Float(ref n) => f.write_str(n),
+            Self::String(ref s) => write_quoted(f, s, '"'),
+            Self::Binary(ref s) => {
+                f.write_str("<<")?;
+                write_quoted(f, s, '"')?;
+                f.write_str(">>")
+            }
+            Self::List(ref elements, ref tail) => {
+                f.write_char('[')?;
+                write_elements(f, elements)?;
+                if let Some(tail) = tail {
+                    write!(f, "|{}", tail)?;
+                }
+                f.write_char(']')
+            }
+            Self::Tuple(ref elements) => {
+                f.write_char('{')?;
+                write_elements(f, elements)?;
+                f.write_char('}')
+            }
+            Self::Map(ref pairs) => {
+                f.write_str("#{")?;
+                for (i, (key, value)) in pairs.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{} => {}", key, value)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_elements(f: &mut fmt::Formatter<'_>, elements: &[Term]) -> fmt::Result {
+    for (i, element) in elements.iter().enumerate() {
+        if i > 0 {
+            f.write_char(',')?;
+        }
+        write!(f, "{}", element)?;
+    }
+    Ok(())
+}
+
+fn write_atom(f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+    let mut chars = name.chars();
+    let bare = match chars.next() {
+        Some(c) if c.is_ascii_lowercase() => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '@')
+        }
+        _ => false,
+    };
+    if bare && !is_reserved_word(name) {
+        f.write_str(name)
+    } else {
+        write_quoted(f, name, '\'')
+    }
+}
+
+fn write_quoted(f: &mut fmt::Formatter<'_>, s: &str, quote: char) -> fmt::Result {
+    f.write_char(quote)?;
+    for c in s.chars() {
+        match c {
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c == quote => {
+                f.write_char('\\')?;
+                f.write_char(c)?;
+            }
+            c if c.is_control() => write!(f, "\\x{{{:X}}}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char(quote)
+}
+
+fn is_reserved_word(name: &str) -> bool {
+    match name {
+        "after" | "and" | "andalso" | "band" | "begin" | "bnot" | "bor" | "bsl" | "bsr"
+        | "bxor" | "case" | "catch" | "cond" | "div" | "end" | "fun" | "if" | "let" | "not"
+        | "of" | "or" | "orelse" | "receive" | "rem" | "try" | "when" | "xor" => true,
+        _ => false,
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("{line}:{column}: {message}")]
+pub struct TermParseError {
+    /// The one-based line at which the error occurred
+    pub line: usize,
+    /// The one-based column at which the error occurred
+    pub column: usize,
+    pub message: String,
+}
+
+/// Parses all of the terms in `source`, each of which must be followed by a `.`
+pub fn consult(source: &str) -> Result<Vec<Term>, TermParseError> {
+    let mut parser = Parser::new(source);
+    let mut terms = Vec::new();
+    loop {
+        parser.skip_whitespace();
+        if parser.peek().is_none() {
+            return Ok(terms);
+        }
+        terms.push(parser.parse_term()?);
+        parser.skip_whitespace();
+        parser.expect('.')?;
+    }
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self { source, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.source[self.pos..].chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error<T, S: Into<String>>(&self, message: S) -> Result<T, TermParseError> {
+        let before = &self.source[..self.pos];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        Err(TermParseError {
+            line,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+        })
+    }
+
+    fn unexpected<T>(&self, expected: &str) -> Result<T, TermParseError> {
+        match self.peek() {
+            None => self.error(format!("unexpected end of input, expected {}", expected)),
+            Some(c) => self.error(format!("unexpected `{}`, expected {}", c, expected)),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), TermParseError> {
+        if self.peek() == Some(expected) {
+            self.bump();
+            Ok(())
+        } else {
+            self.unexpected(&format!("`{}`", expected))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '%' {
+                while let Some(c) = self.bump() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            } else if c.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn parse_term(&mut self) -> Result<Term, TermParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => {
+                self.bump();
+                let elements = self.parse_elements('}')?;
+                Ok(Term::Tuple(elements))
+            }
+            Some('[') => self.parse_list(),
+            Some('#') => self.parse_map(),
+            Some('<') => self.parse_binary(),
+            Some('"') => Ok(Term::String(self.parse_quoted('"')?)),
+            Some('\'') => Ok(Term::Atom(self.parse_quoted('\'')?)),
+            Some('$') => {
+                self.bump();
+                let c = match self.bump() {
+                    Some('\\') => self.parse_escape()?,
+                    Some(c) => c,
+                    None => return self.unexpected("a character"),
+                };
+                Ok(Term::Integer((c as u32).to_string()))
+            }
+            Some(c) if c == '-' || c == '+' || c.is_ascii_digit() => self.parse_number(),
+            Some(c) if c.is_lowercase() => {
+                let start = self.pos;
+                while let Some(c) = self.peek() {
+                    if c.is_alphanumeric() || c == '_' || c == '@' {
+                        self.bump();
+                    } else {
+                        break;
+                    }
+                }
+                Ok(Term::Atom(self.source[start..self.pos].to_string()))
+            }
+            Some(c) if c.is_uppercase() || c == '_' => {
+                self.error("variables are not allowed in a term")
+            }
+            _ => self.unexpected("a term"),
+        }
+    }
+
+    fn parse_elements(&mut self, close: char) -> Result<Vec<Term>, TermParseError> {
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(close) {
+            self.bump();
+            return Ok(elements);
+        }
+        loop {
+            elements.push(self.parse_term()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                }
+                Some(c) if c == close => {
+                    self.bump();
+                    return Ok(elements);
+                }
+                _ => return self.unexpected(&format!("`,` or `{}`", close)),
+            }
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<Term, TermParseError> {
+        self.expect('[')?;
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.bump();
+            return Ok(Term::List(elements, None));
+        }
+        loop {
+            elements.push(self.parse_term()?);
+            self.skip_whitespace();
+            match self.bump() {
+                Some(',') => continue,
+                Some(']') => return Ok(Term::List(elements, None)),
+                Some('|') => {
+                    let tail = self.parse_term()?;
+                    self.skip_whitespace();
+                    self.expect(']')?;
+                    // Normalize `[a|[b]]` to `[a,b]`
+                    return Ok(match tail {
+                        Term::List(rest, tail) => {
+                            elements.extend(rest);
+                            Term::List(elements, tail)
+                        }
+                        Term::String(ref s) => {
+                            elements
+                                .extend(s.chars().map(|c| Term::Integer((c as u32).to_string())));
+                            Term::List(elements, None)
+                        }
+                        tail => Term::List(elements, Some(Box::new(tail))),
+                    });
+                }
+                _ => {
+                    self.pos -= 1;
+                    return self.unexpected("`,`, `|` or `]`");
+                }
+            }
+        }
+    }
+
+    fn parse_map(&mut self) -> Result<Term, TermParseError> {
+        self.expect('#')?;
+        self.expect('{')?;
+        let mut pairs = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.bump();
+            return Ok(Term::Map(pairs));
+        }
+        loop {
+            let key = self.parse_term()?;
+            self.skip_whitespace();
+            if !self.source[self.pos..].starts_with("=>") {
+                return self.unexpected("`=>`");
+            }
+            self.pos += 2;
+            let value = self.parse_term()?;
+            pairs.push((key, value));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                }
+                Some('}') => {
+                    self.bump();
+                    return Ok(Term::Map(pairs));
+                }
+                _ => return self.unexpected("`,` or `}`"),
+            }
+        }
+    }
+
+    fn parse_binary(&mut self) -> Result<Term, TermParseError> {
+        if !self.source[self.pos..].starts_with("<<") {
+            return self.unexpected("`<<`");
+        }
+        self.pos += 2;
+        self.skip_whitespace();
+        let mut contents = String::new();
+        if self.source[self.pos..].starts_with(">>") {
+            self.pos += 2;
+            return Ok(Term::Binary(contents));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return self.error("only string segments are supported in binaries");
+            }
+            contents.push_str(&self.parse_quoted('"')?);
+            self.skip_whitespace();
+            if self.peek() == Some(',') {
+                self.bump();
+            } else if self.source[self.pos..].starts_with(">>") {
+                self.pos += 2;
+                return Ok(Term::Binary(contents));
+            } else {
+                return self.unexpected("`,` or `>>`");
+            }
+        }
+    }
+
+    fn parse_quoted(&mut self, quote: char) -> Result<String, TermParseError> {
+        self.expect(quote)?;
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                None => return self.error(format!("unterminated {}", describe_quote(quote))),
+                Some('\\') => value.push(self.parse_escape()?),
+                Some(c) if c == quote => break,
+                Some(c) => value.push(c),
+            }
+        }
+        // Adjacent strings are concatenated, e.g. `"foo" "bar"`
+        if quote == '"' {
+            self.skip_whitespace();
+            if self.peek() == Some('"') {
+                value.push_str(&self.parse_quoted(quote)?);
+            }
+        }
+        Ok(value)
+    }
+
+    fn parse_escape(&mut self) -> Result<char, TermParseError> {
+        let c = match self.bump() {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('v') => '\u{0B}',
+            Some('b') => '\u{08}',
+            Some('f') => '\u{0C}',
+            Some('e') => '\u{1B}',
+            Some('s') => ' ',
+            Some('d') => '\u{7F}',
+            Some('^') => match self.bump() {
+                Some(c) => ((c as u32) % 32) as u8 as char,
+                None => return self.unexpected("a control character"),
+            },
+            Some('x') => {
+                let digits = if self.peek() == Some('{') {
+                    self.bump();
+                    let start = self.pos;
+                    while self.peek().map(|c| c != '}').unwrap_or(false) {
+                        self.bump();
+                    }
+                    let digits = &self.source[start..self.pos];
+                    self.expect('}')?;
+                    digits
+                } else {
+                    let start = self.pos;
+                    for _ in 0..2 {
+                        if self.peek().map(|c| c.is_ascii_hexdigit()).unwrap_or(false) {
+                            self.bump();
+                        }
+                    }
+                    &self.source[start..self.pos]
+                };
+                match u32::from_str_radix(digits, 16)
+                    .ok()
+                    .and_then(std::char::from_u32)
+                {
+                    Some(c) => c,
+                    None => return self.error("invalid hexadecimal escape"),
+                }
+            }
+            Some(c) if c.is_digit(8) => {
+                let mut value = c.to_digit(8).unwrap();
+                for _ in 0..2 {
+                    match self.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            self.bump();
+                            value = value * 8 + digit;
+                        }
+                        None => break,
+                    }
+                }
+                std::char::from_u32(value).unwrap()
+            }
+            Some(c) => c,
+            None => return self.unexpected("an escape sequence"),
+        };
+        Ok(c)
+    }
+
+    fn parse_number(&mut self) -> Result<Term, TermParseError> {
+        let start = self.pos;
+        if self.peek() == Some('-') || self.peek() == Some('+') {
+            self.bump();
+            self.skip_whitespace();
+        }
+        let sign = self.source[start..self.pos].trim_end().to_string();
+        let digits_start = self.pos;
+        self.skip_digits(10);
+        if digits_start == self.pos {
+            return self.unexpected("a number");
+        }
+        // Integers in another base, e.g. `16#FF`
+        if self.peek() == Some('#') {
+            let base = match self.source[digits_start..self.pos].parse::<u32>() {
+                Ok(base) if base >= 2 && base <= 36 => base,
+                _ => return self.error("invalid integer base"),
+            };
+            self.bump();
+            let value_start = self.pos;
+            self.skip_digits(base);
+            let digits = self.source[value_start..self.pos].replace('_', "");
+            return match u128::from_str_radix(&digits, base) {
+                Ok(value) => Ok(Term::Integer(format!(
+                    "{}{}",
+                    sign.trim_start_matches('+'),
+                    value
+                ))),
+                Err(_) => self.error("invalid integer"),
+            };
+        }
+        let mut is_float = false;
+        if self.peek() == Some('.')
+            && self
+                .peek_nth(1)
+                .map(|c| c.is_ascii_digit())
+                .unwrap_or(false)
+        {
+            is_float = true;
+            self.bump();
+            self.skip_digits(10);
+            if self.peek() == Some('e') || self.peek() == Some('E') {
+                self.bump();
+                if self.peek() == Some('-') || self.peek() == Some('+') {
+                    self.bump();
+                }
+                self.skip_digits(10);
+            }
+        }
+        let digits = self.source[digits_start..self.pos].replace('_', "");
+        let sign = sign.trim_start_matches('+');
+        if is_float {
+            Ok(Term::Float(format!("{}{}", sign, digits)))
+        } else {
+            Ok(Term::Integer(format!("{}{}", sign, digits)))
+        }
+    }
+
+    fn skip_digits(&mut self, base: u32) {
+        while let Some(c) = self.peek() {
+            if c.is_digit(base) || c == '_' {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+fn describe_quote(quote: char) -> &'static str {
+    if quote == '"' {
+        "string"
+    } else {
+        "quoted atom"
+    }
+}
@@ -1,6 +1,7 @@
 //! Contains infrastructure for configuring the compiler, including parsing
 //! command-line options.
 mod debug;
+mod error_format;
 mod input;
 mod optimization;
 mod options;
@@ -9,6 +10,7 @@ mod project;
 mod sanitizer;
 
 pub use self::debug::DebugInfo;
+pub use self::error_format::ErrorFormat;
 pub use self::input::{Input, InputType};
 pub use self::optimization::{LinkerPluginLto, Lto, LtoCli, OptLevel, Passes};
 pub use self::options::{
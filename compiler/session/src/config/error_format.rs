@@ -0,0 +1,49 @@
+use std::fmt;
+use std::str::FromStr;
+
+use clap::ArgMatches;
+
+use crate::config::options::invalid_value;
+use crate::config::options::{OptionInfo, ParseOption};
+
+/// The format in which diagnostics are printed
+#[derive(Copy, PartialEq, Clone, Eq, Hash, Debug)]
+pub enum ErrorFormat {
+    /// Colored, human-readable text with source snippets
+    Human,
+    /// One JSON object per diagnostic, one per line on stderr
+    Json,
+}
+impl Default for ErrorFormat {
+    fn default() -> Self {
+        ErrorFormat::Human
+    }
+}
+impl fmt::Display for ErrorFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ErrorFormat::Human => "human".fmt(f),
+            ErrorFormat::Json => "json".fmt(f),
+        }
+    }
+}
+impl FromStr for ErrorFormat {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(()),
+        }
+    }
+}
+impl ParseOption for ErrorFormat {
+    fn parse_option<'a>(info: &OptionInfo, matches: &ArgMatches<'a>) -> clap::Result<Self> {
+        match matches.value_of(info.name) {
+            None => Ok(Self::default()),
+            Some(s) => s
+                .parse()
+                .map_err(|_| invalid_value(info, &format!("unknown error format: `{}`", s))),
+        }
+    }
+}
@@ -9,6 +9,7 @@ use libeir_diagnostics::FileName;
 pub enum InputType {
     Erlang,
     AbstractErlang,
+    BEAM,
     EIR,
     MLIR,
     Unknown(Option<String>),
@@ -17,6 +18,7 @@ impl InputType {
     const TYPES: &'static [InputType] = &[
         InputType::Erlang,
         InputType::AbstractErlang,
+        InputType::BEAM,
         InputType::EIR,
         InputType::MLIR,
     ];
@@ -30,6 +32,7 @@ impl InputType {
             Some("erl") => true,
             Some("eir") => true,
             Some("abstr") => true,
+            Some("beam") => true,
             Some("mlir") => true,
             Some(_) => false,
         }
@@ -48,6 +51,7 @@ impl fmt::Display for InputType {
         match self {
             Self::Erlang => f.write_str("erl"),
             Self::AbstractErlang => f.write_str("abstr"),
+            Self::BEAM => f.write_str("beam"),
             Self::EIR => f.write_str("eir"),
             Self::MLIR => f.write_str("mlir"),
             Self::Unknown(None) => f.write_str("unknown (no extension)"),
@@ -82,6 +86,7 @@ impl Input {
             Input::File(ref file) => match file.extension().and_then(|ext| ext.to_str()) {
                 Some("erl") => InputType::Erlang,
                 Some("abstr") => InputType::AbstractErlang,
+                Some("beam") => InputType::BEAM,
                 Some("eir") => InputType::EIR,
                 Some("mlir") => InputType::MLIR,
                 Some(t) => InputType::Unknown(Some(t.to_string())),
@@ -92,6 +97,8 @@ impl Input {
                     InputType::Erlang
                 } else if name.ends_with(".abstr") {
                     InputType::AbstractErlang
+                } else if name.ends_with(".beam") {
+                    InputType::BEAM
                 } else if name.ends_with(".eir") {
                     InputType::EIR
                 } else if name.ends_with(".mlir") {
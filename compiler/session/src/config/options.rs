@@ -24,7 +24,9 @@ use liblumen_util::error::{HelpRequested, Verbosity};
 use liblumen_util::fs::NativeLibraryKind;
 
 use super::*;
+use crate::app::Application;
 use crate::filesearch;
+use crate::include_search;
 use crate::search_paths::SearchPath;
 
 /// Represents user-defined configuration, e.g. `-D KEY[=VALUE]`
@@ -47,8 +49,11 @@ pub struct Options {
     pub project_type: ProjectType,
     pub output_types: OutputTypes,
     pub use_color: UseColors,
+    pub error_format: ErrorFormat,
     pub warnings_as_errors: bool,
     pub no_warn: bool,
+    /// Check the calls between modules before linking
+    pub xref: bool,
     pub verbosity: Verbosity,
 
     pub host: Target,
@@ -67,13 +72,17 @@ pub struct Options {
 
     pub current_dir: PathBuf,
     pub input_file: Option<FileName>,
+    /// The OTP application being compiled, when the input is an application directory
+    pub app: Option<Application>,
     pub output_file: Option<PathBuf>,
     pub output_dir: Option<PathBuf>,
-    // Remap source path prefixes in all output (messages, object files, debug, etc.).
-    pub source_path_prefix: Vec<(PathBuf, PathBuf)>,
+    /// Source path prefixes to remap in the file names embedded in the output, as `(from, to)`
+    pub remap_path_prefix: Vec<(PathBuf, PathBuf)>,
     pub search_paths: Vec<SearchPath>,
     pub include_path: VecDeque<PathBuf>,
     pub code_path: VecDeque<PathBuf>,
+    /// The library directories from `ERL_LIBS`, searched for `-include_lib` applications
+    pub lib_path: Vec<PathBuf>,
     pub link_libraries: Vec<(String, Option<String>, Option<NativeLibraryKind>)>,
     pub defines: HashMap<String, Option<String>>,
 
@@ -108,12 +117,20 @@ impl Options {
             }
         }
 
-        let project_name = detect_project_name(args, cwd.as_path(), input_file.as_ref());
+        let app = match input_file {
+            None => Application::load(&cwd)?,
+            Some(FileName::Real(ref path)) if path.is_dir() => Application::load(path)?,
+            Some(_) => None,
+        };
+
+        let project_name =
+            detect_project_name(args, cwd.as_path(), input_file.as_ref(), app.as_ref());
         let project_type_opt: Option<ProjectType> =
             ParseOption::parse_option(&option!("project-type"), &args)?;
         let project_type = project_type_opt.unwrap_or(ProjectType::Executable);
         let output_types = OutputTypes::parse_option(&option!("emit"), &args)?;
         let use_color = UseColors::parse_option(&option!("color"), &args)?;
+        let error_format = ErrorFormat::parse_option(&option!("error-format"), &args)?;
 
         let maybe_sysroot: Option<PathBuf> = ParseOption::parse_option(&option!("sysroot"), &args)?;
         let sysroot = match &maybe_sysroot {
@@ -196,7 +213,7 @@ impl Options {
         }
 
         let link_libraries = parse_link_libraries(&args)?;
-        let source_path_prefix = parse_source_path_prefix(&args)?;
+        let remap_path_prefix = parse_remap_path_prefix(&args)?;
 
         let output_file = args.value_of_os("output").map(PathBuf::from);
         let output_dir = args.value_of_os("output-dir").map(PathBuf::from);
@@ -211,8 +228,18 @@ impl Options {
         }
         let warnings_as_errors = args.is_present("warnings-as-errors");
         let no_warn = args.is_present("no-warn");
+        let xref = args.is_present("xref");
         let verbosity = Verbosity::from_level(args.occurrences_of("verbose") as isize);
-        let include_path = VecDeque::new();
+        let mut include_path = VecDeque::new();
+        if let Some(values) = args.values_of_os("include-path") {
+            for value in values {
+                include_path.push_back(PathBuf::from(value));
+            }
+        }
+        // Like `erlc -I include`, as used by OTP build tools
+        if let Some(ref app) = app {
+            include_path.push_back(app.include_dir());
+        }
         let mut code_path = VecDeque::new();
         if let Some(values) = args.values_of_os("prepend-path") {
             for value in values {
@@ -232,8 +259,10 @@ impl Options {
             project_type,
             output_types,
             use_color,
+            error_format,
             warnings_as_errors,
             no_warn,
+            xref,
             verbosity,
             host,
             target,
@@ -248,12 +277,14 @@ impl Options {
             debugging_opts,
             current_dir: cwd,
             input_file,
+            app,
             output_file,
             output_dir,
-            source_path_prefix,
+            remap_path_prefix,
             search_paths,
             include_path,
             code_path,
+            lib_path: include_search::erl_libs(),
             link_libraries,
             defines,
             cli_forced_thinlto_off: false,
@@ -310,8 +341,10 @@ impl Options {
             project_type: ProjectType::Executable,
             output_types: OutputTypes::default(),
             use_color: UseColors(ColorChoice::Auto),
+            error_format: ErrorFormat::default(),
             warnings_as_errors: false,
             no_warn: false,
+            xref: false,
             verbosity: Verbosity::from_level(0),
             host,
             target,
@@ -326,12 +359,14 @@ impl Options {
             debugging_opts,
             current_dir: cwd,
             input_file: None,
+            app: None,
             output_file: None,
             output_dir: None,
-            source_path_prefix: vec![],
+            remap_path_prefix: vec![],
             search_paths: Default::default(),
             include_path: Default::default(),
             code_path: Default::default(),
+            lib_path: include_search::erl_libs(),
             link_libraries: Default::default(),
             defines,
             cli_forced_thinlto_off: false,
@@ -425,17 +460,40 @@ impl Options {
         self.host = target;
     }
 
+    /// Applies `--remap-path-prefix` to `path`
+    ///
+    /// When more than one prefix matches, the one given last on the command line wins.
+    pub fn remap_path(&self, path: &Path) -> PathBuf {
+        for (from, to) in self.remap_path_prefix.iter().rev() {
+            match path.strip_prefix(from) {
+                Ok(rest) if rest.as_os_str().is_empty() => return to.clone(),
+                Ok(rest) => return to.join(rest),
+                Err(_) => continue,
+            }
+        }
+        path.to_path_buf()
+    }
+
     /// Returns `true` if there will be an output file generated.
     pub fn will_create_output_file(&self) -> bool {
         !self.debugging_opts.parse_only // The file is just being parsed
     }
 }
 
-fn detect_project_name<'a>(args: &ArgMatches<'a>, cwd: &Path, input: Option<&FileName>) -> String {
+fn detect_project_name<'a>(
+    args: &ArgMatches<'a>,
+    cwd: &Path,
+    input: Option<&FileName>,
+    app: Option<&Application>,
+) -> String {
     // If explicitly set, use the provided name
     if let Some(name) = args.value_of("name") {
         return name.to_owned();
     }
+    // If we're compiling an application, name the project after it
+    if let Some(app) = app {
+        return app.name.clone();
+    }
     match input {
         // If we have a single input file, name the project after it
         Some(FileName::Real(ref path)) if path.exists() && path.is_file() => path
@@ -531,10 +589,10 @@ fn parse_link_libraries<'a>(
     }
 }
 
-fn parse_source_path_prefix<'a>(
+fn parse_remap_path_prefix<'a>(
     matches: &ArgMatches<'a>,
 ) -> Result<Vec<(PathBuf, PathBuf)>, clap::Error> {
-    match matches.values_of("source-path-prefix") {
+    match matches.values_of("remap-path-prefix") {
         None => return Ok(Vec::new()),
         Some(values) => {
             let mut source_maps = Vec::new();
@@ -548,8 +606,8 @@ fn parse_source_path_prefix<'a>(
                     }
                     _ => {
                         return Err(str_to_clap_err(
-                            "source-path-prefix",
-                            "invalid argument format, expected `PATH=PREFIX`",
+                            "remap-path-prefix",
+                            "invalid argument format, expected `FROM=TO`",
                         ))
                     }
                 }
@@ -37,6 +37,9 @@ pub struct DebuggingOptions {
     #[option]
     /// Prints the LLVM optimization passes being run
     pub print_llvm_passes: bool,
+    #[option]
+    /// Print the hits and misses of the incremental compilation cache
+    pub incremental_info: bool,
     #[option(default_value("1"), takes_value(true), value_name("N"))]
     /// Use a thread pool with N threads
     pub threads: u64,
@@ -145,6 +145,11 @@ impl ParseOption for Target {
                 message: format!("unable to load target: {}", desc),
                 info: Some(vec![info.name.to_string()]),
             }),
+            Err(err) => Err(clap::Error {
+                kind: ErrorKind::ValueValidation,
+                message: err.to_string(),
+                info: Some(vec![info.name.to_string()]),
+            }),
         }
     }
 }
@@ -1,18 +1,45 @@
+mod json;
+
 use std::error::Error;
 use std::ffi::CString;
 use std::fmt::Display;
+use std::io::{self, Write};
 use std::ops::Deref;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 
 use libeir_diagnostics::emitter::{cyan, green_bold, red_bold, white, yellow, yellow_bold};
-use libeir_diagnostics::{ByteSpan, CodeMap, ColorSpec, Diagnostic, Emitter, Severity};
+use libeir_diagnostics::{ByteSpan, CodeMap, ColorSpec, Diagnostic, Emitter, Label, Severity};
 use liblumen_util::error::{FatalError, Verbosity};
 
+use crate::config::ErrorFormat;
+
 #[derive(Debug, Copy, Clone)]
 pub struct DiagnosticsConfig {
     pub warnings_as_errors: bool,
     pub no_warn: bool,
+    pub error_format: ErrorFormat,
+}
+
+/// A suggested fix for a diagnostic, replacing the source in `span` with `replacement`
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub message: String,
+    pub span: ByteSpan,
+    pub replacement: String,
+}
+impl Suggestion {
+    pub fn new<M: Into<String>, R: Into<String>>(
+        message: M,
+        span: ByteSpan,
+        replacement: R,
+    ) -> Self {
+        Self {
+            message: message.into(),
+            span,
+            replacement: replacement.into(),
+        }
+    }
 }
 
 #[repr(C)]
@@ -33,6 +60,7 @@ pub struct DiagnosticsHandler {
     codemap: Arc<RwLock<CodeMap>>,
     warnings_as_errors: bool,
     no_warn: bool,
+    error_format: ErrorFormat,
     err_count: Arc<AtomicUsize>,
 }
 // We can safely implement these traits for DiagnosticsHandler,
@@ -50,6 +78,7 @@ impl DiagnosticsHandler {
             codemap,
             warnings_as_errors: config.warnings_as_errors,
             no_warn: config.no_warn,
+            error_format: config.error_format,
             err_count: Arc::new(AtomicUsize::new(0)),
         }
     }
@@ -93,9 +122,7 @@ impl DiagnosticsHandler {
     }
 
     pub fn fatal_str(&self, err: &str) -> FatalError {
-        self.emitter
-            .diagnostic(&Diagnostic::new(Severity::Error, err.to_string()))
-            .unwrap();
+        self.write_diagnostic(&Diagnostic::new(Severity::Error, err.to_string()), &[], &[]);
         FatalError
     }
 
@@ -115,19 +142,53 @@ impl DiagnosticsHandler {
 
     pub fn error_str(&self, err: &str) {
         self.err_count.fetch_add(1, Ordering::Relaxed);
-        self.emitter
-            .diagnostic(&Diagnostic::new(Severity::Error, err.to_string()))
-            .unwrap();
+        self.write_diagnostic(&Diagnostic::new(Severity::Error, err.to_string()), &[], &[]);
+    }
+
+    /// Reports an error with additional details, such as the output of a failed command.
+    ///
+    /// The notes follow the message in human-readable output, and are kept separate from it
+    /// in JSON output.
+    pub fn error_with_notes(&self, err: &str, notes: &[String]) {
+        self.err_count.fetch_add(1, Ordering::Relaxed);
+        match self.error_format {
+            ErrorFormat::Human => {
+                let mut message = err.to_string();
+                for note in notes {
+                    message.push_str("\n\n");
+                    message.push_str(note);
+                }
+                self.emitter
+                    .diagnostic(&Diagnostic::new(Severity::Error, message))
+                    .unwrap();
+            }
+            ErrorFormat::Json => self.write_json(
+                &Diagnostic::new(Severity::Error, err.to_string()),
+                notes,
+                &[],
+            ),
+        }
     }
 
     pub fn warn<M: Display>(&self, message: M) {
         if self.warnings_as_errors {
-            self.emitter
-                .diagnostic(&Diagnostic::new(Severity::Error, message.to_string()))
-                .unwrap();
+            self.write_diagnostic(
+                &Diagnostic::new(Severity::Error, message.to_string()),
+                &[],
+                &[],
+            );
         } else if !self.no_warn {
-            self.write_warning(yellow_bold(), "WARN: ");
-            self.write_warning(yellow(), message);
+            match self.error_format {
+                ErrorFormat::Human => {
+                    self.write_warning(yellow_bold(), "WARN: ");
+                    self.write_warning(yellow(), message);
+                }
+                ErrorFormat::Json => self.write_json(
+                    &Diagnostic::new(Severity::Warning, message.to_string()),
+                    &[],
+                    &[],
+                ),
+            }
         }
     }
 
@@ -149,14 +210,94 @@ impl DiagnosticsHandler {
     }
 
     pub fn diagnostic(&self, diagnostic: &Diagnostic) {
-        self.emitter.diagnostic(diagnostic).unwrap();
+        self.diagnostic_with(diagnostic, &[], &[]);
+    }
+
+    /// Emits `diagnostic` along with notes and suggested fixes for it
+    ///
+    /// Warnings are subject to `--no-warn` and `--warnings-as-errors`, and errors are counted.
+    pub fn diagnostic_with(
+        &self,
+        diagnostic: &Diagnostic,
+        notes: &[String],
+        suggestions: &[Suggestion],
+    ) {
+        match diagnostic.severity {
+            Severity::Warning if self.warnings_as_errors => {
+                self.err_count.fetch_add(1, Ordering::Relaxed);
+                let mut diagnostic = diagnostic.clone();
+                diagnostic.severity = Severity::Error;
+                self.write_diagnostic(&diagnostic, notes, suggestions);
+            }
+            Severity::Warning if self.no_warn => (),
+            Severity::Bug | Severity::Error => {
+                self.err_count.fetch_add(1, Ordering::Relaxed);
+                self.write_diagnostic(diagnostic, notes, suggestions);
+            }
+            _ => self.write_diagnostic(diagnostic, notes, suggestions),
+        }
     }
 
     fn write_error<E>(&self, err: E)
     where
         E: Deref<Target = (dyn Error + Send + Sync + 'static)>,
     {
-        self.emitter.error(err.deref()).unwrap();
+        match self.error_format {
+            ErrorFormat::Human => self.emitter.error(err.deref()).unwrap(),
+            ErrorFormat::Json => {
+                // The chain of causes becomes the notes of the diagnostic
+                let mut notes = Vec::new();
+                let mut source = err.source();
+                while let Some(cause) = source {
+                    notes.push(cause.to_string());
+                    source = cause.source();
+                }
+                self.write_json(
+                    &Diagnostic::new(Severity::Error, err.to_string()),
+                    &notes,
+                    &[],
+                );
+            }
+        }
+    }
+
+    fn write_diagnostic(
+        &self,
+        diagnostic: &Diagnostic,
+        notes: &[String],
+        suggestions: &[Suggestion],
+    ) {
+        match self.error_format {
+            ErrorFormat::Human => {
+                self.emitter.diagnostic(diagnostic).unwrap();
+                for note in notes {
+                    self.emitter
+                        .diagnostic(&Diagnostic::new(Severity::Note, note.clone()))
+                        .unwrap();
+                }
+                for suggestion in suggestions {
+                    let label = Label::new_primary(suggestion.span)
+                        .with_message(format!("replace with `{}`", suggestion.replacement));
+                    self.emitter
+                        .diagnostic(
+                            &Diagnostic::new(Severity::Help, suggestion.message.clone())
+                                .with_label(label),
+                        )
+                        .unwrap();
+                }
+            }
+            ErrorFormat::Json => self.write_json(diagnostic, notes, suggestions),
+        }
+    }
+
+    fn write_json(&self, diagnostic: &Diagnostic, notes: &[String], suggestions: &[Suggestion]) {
+        let rendered = {
+            let codemap = self.codemap.read().unwrap();
+            json::render(&codemap, diagnostic, notes, suggestions)
+        };
+        let stderr = io::stderr();
+        let mut handle = stderr.lock();
+        writeln!(handle, "{}", rendered).unwrap();
     }
 
     fn write_warning<M: Display>(&self, color: ColorSpec, message: M) {
@@ -0,0 +1,178 @@
+//! Renders diagnostics as JSON for `--error-format=json`.
+//!
+//! Each diagnostic is written as a single object on its own line, with the fields always in the
+//! same order, so that the output can be consumed by tools and compared in snapshot tests:
+//!
+//! ```json
+//! {"severity":"error","message":"...","code":null,"spans":[{"file":"foo.erl","line_start":1,
+//!  "column_start":1,"line_end":1,"column_end":4,"is_primary":true,"label":null}],
+//!  "notes":[],"suggestions":[{"message":"...","replacement":"...","span":{...}}]}
+//! ```
+//!
+//! Lines and columns are one-based. Spans which do not belong to a file in the `CodeMap` are
+//! omitted.
+use serde::Serialize;
+
+use libeir_diagnostics::{ByteSpan, CodeMap, Diagnostic, LabelStyle, Severity};
+
+use super::Suggestion;
+
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    severity: &'static str,
+    message: &'a str,
+    code: Option<&'a str>,
+    spans: Vec<JsonSpan<'a>>,
+    notes: &'a [String],
+    suggestions: Vec<JsonSuggestion<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonSpan<'a> {
+    file: String,
+    line_start: usize,
+    column_start: usize,
+    line_end: usize,
+    column_end: usize,
+    is_primary: bool,
+    label: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct JsonSuggestion<'a> {
+    message: &'a str,
+    replacement: &'a str,
+    span: Option<JsonSpan<'a>>,
+}
+
+pub(super) fn render(
+    codemap: &CodeMap,
+    diagnostic: &Diagnostic,
+    notes: &[String],
+    suggestions: &[Suggestion],
+) -> String {
+    let spans = diagnostic
+        .labels
+        .iter()
+        .filter_map(|label| {
+            let is_primary = match label.style {
+                LabelStyle::Primary => true,
+                LabelStyle::Secondary => false,
+            };
+            span(
+                codemap,
+                label.span,
+                is_primary,
+                label.message.as_ref().map(|m| m.as_str()),
+            )
+        })
+        .collect();
+    let suggestions = suggestions
+        .iter()
+        .map(|suggestion| JsonSuggestion {
+            message: &suggestion.message,
+            replacement: &suggestion.replacement,
+            span: span(codemap, suggestion.span, true, None),
+        })
+        .collect();
+
+    serde_json::to_string(&JsonDiagnostic {
+        severity: severity(diagnostic.severity),
+        message: &diagnostic.message,
+        code: diagnostic.code.as_ref().map(|c| c.as_str()),
+        spans,
+        notes,
+        suggestions,
+    })
+    .unwrap()
+}
+
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug => "bug",
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+        Severity::Help => "help",
+    }
+}
+
+/// Returns `None` if the span is not in a file known to `codemap`
+fn span<'a>(
+    codemap: &CodeMap,
+    span: ByteSpan,
+    is_primary: bool,
+    label: Option<&'a str>,
+) -> Option<JsonSpan<'a>> {
+    let file = codemap.find_file(span.start())?;
+    let (line_start, column_start) = file.location(span.start()).ok()?;
+    let (line_end, column_end) = file
+        .location(span.end())
+        .unwrap_or((line_start, column_start));
+
+    Some(JsonSpan {
+        file: file.name.to_string(),
+        line_start: line_start.number().to_usize(),
+        column_start: column_start.number().to_usize(),
+        line_end: line_end.number().to_usize(),
+        column_end: column_end.number().to_usize(),
+        is_primary,
+        label,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use libeir_diagnostics::{ByteIndex, FileName, Label};
+
+    use super::*;
+
+    /// Returns a code map holding a file named `foo.erl`, and the span of `text` in it
+    fn codemap_with(text: &str) -> (CodeMap, ByteSpan) {
+        let source = "-module(foo).\n\nbar() -> baz:qux().\n";
+        let mut codemap = CodeMap::new();
+        let file = codemap.add_filemap(FileName::Virtual("foo.erl".into()), source.to_string());
+        let start = file.span().start().to_usize() + source.find(text).unwrap();
+        let span = ByteSpan::new(
+            ByteIndex::from(start as u32),
+            ByteIndex::from((start + text.len()) as u32),
+        );
+        (codemap, span)
+    }
+
+    #[test]
+    fn renders_a_message_without_spans() {
+        let codemap = CodeMap::new();
+        let diagnostic = Diagnostic::new(Severity::Warning, "a \"quoted\"\nmessage".to_string());
+
+        assert_eq!(
+            render(&codemap, &diagnostic, &[], &[]),
+            r#"{"severity":"warning","message":"a \"quoted\"\nmessage","code":null,"spans":[],"notes":[],"suggestions":[]}"#
+        );
+    }
+
+    #[test]
+    fn renders_labels_and_notes() {
+        let (codemap, span) = codemap_with("baz:qux()");
+        let diagnostic = Diagnostic::new(Severity::Error, "undefined".to_string())
+            .with_label(Label::new_primary(span).with_message("in foo:bar/0"));
+        let notes = vec!["a note".to_string()];
+
+        assert_eq!(
+            render(&codemap, &diagnostic, &notes, &[]),
+            r#"{"severity":"error","message":"undefined","code":null,"spans":[{"file":"foo.erl","line_start":3,"column_start":10,"line_end":3,"column_end":19,"is_primary":true,"label":"in foo:bar/0"}],"notes":["a note"],"suggestions":[]}"#
+        );
+    }
+
+    #[test]
+    fn renders_suggestions() {
+        let (codemap, span) = codemap_with("qux");
+        let diagnostic = Diagnostic::new(Severity::Error, "undefined".to_string());
+        let suggestions = vec![Suggestion::new("did you mean", span, "quux")];
+
+        assert_eq!(
+            render(&codemap, &diagnostic, &[], &suggestions),
+            r#"{"severity":"error","message":"undefined","code":null,"spans":[],"notes":[],"suggestions":[{"message":"did you mean","replacement":"quux","span":{"file":"foo.erl","line_start":3,"column_start":14,"line_end":3,"column_end":17,"is_primary":true,"label":null}}]}"#
+        );
+    }
+}
@@ -0,0 +1,390 @@
+//! Resolution of the files named by `-include` and `-include_lib` attributes.
+//!
+//! An `-include` path is searched for relative to the directory of the including file, the
+//! current directory, and then each `-I` include path, in that order.
+//!
+//! An `-include_lib` path is first tried as an `-include` path, as `epp` does. Otherwise, its
+//! first component names an application, which is searched for in each code path and `ERL_LIBS`
+//! directory, either directly or in a `lib` subdirectory, and with or without a version suffix,
+//! e.g. `include_lib("stdlib/include/ms_transform.hrl")` may be found in
+//! `<root>/lib/stdlib-3.12/include/ms_transform.hrl`. When more than one version of an
+//! application is present, the highest version is used.
+use std::cmp::Ordering;
+use std::env;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use libeir_diagnostics::{ByteSpan, CodeMap, FileMap, FileName};
+
+use crate::Options;
+
+/// The environment variable holding the library directories to search for applications
+pub const ERL_LIBS: &str = "ERL_LIBS";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IncludeKind {
+    Include,
+    IncludeLib,
+}
+impl IncludeKind {
+    pub fn attribute(&self) -> &'static str {
+        match self {
+            Self::Include => "include",
+            Self::IncludeLib => "include_lib",
+        }
+    }
+}
+
+/// An `-include` or `-include_lib` attribute found in a source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncludeDirective {
+    pub kind: IncludeKind,
+    pub path: String,
+    /// The span of the attribute, from the `-` to the closing parenthesis
+    pub span: ByteSpan,
+}
+
+/// Finds the `-include` and `-include_lib` attributes in `file` from the tokens of the same
+/// lexer the preprocessor reads
+///
+/// Conditional includes are returned even when the condition is false, as the macros aren't
+/// expanded.
+pub fn find_includes(file: &Arc<FileMap>) -> Vec<IncludeDirective> {
+    use libeir_syntax_erl::{FileMapSource, Lexer, LexicalToken, Scanner, Token};
+
+    let source = file.src();
+    let base = file.span().start().to_usize();
+    let text =
+        |token: &LexicalToken| &source[(token.0.to_usize() - base)..(token.2.to_usize() - base)];
+
+    let tokens = Lexer::new(Scanner::new(FileMapSource::new(file.clone())))
+        .filter_map(Result::ok)
+        .collect::<Vec<_>>();
+    let mut includes = Vec::new();
+    for (i, window) in tokens.windows(5).enumerate() {
+        // An attribute starts a form
+        if i > 0 && text(&tokens[i - 1]) != "." {
+            continue;
+        }
+        if let [minus, name, open, LexicalToken(_, Token::String(path), _), close] = window {
+            let kind = match text(name) {
+                "include" => IncludeKind::Include,
+                "include_lib" => IncludeKind::IncludeLib,
+                _ => continue,
+            };
+            if text(minus) == "-" && text(open) == "(" && text(close) == ")" {
+                includes.push(IncludeDirective {
+                    kind,
+                    path: path.to_string(),
+                    span: ByteSpan::new(minus.0, close.2),
+                });
+            }
+        }
+    }
+    includes
+}
+
+/// Finds the `-include` and `-include_lib` attributes in `source`, for when only their paths
+/// are needed, as the spans are in a code map of their own
+pub fn scan_includes(source: &str) -> Vec<IncludeDirective> {
+    let mut codemap = CodeMap::new();
+    let file = codemap.add_filemap(FileName::Virtual("nofile".into()), source.to_string());
+    find_includes(&file)
+}
+
+/// Searches for included files using the directories given by the compiler options
+#[derive(Debug, Clone)]
+pub struct IncludeSearch {
+    current_dir: PathBuf,
+    include_paths: Vec<PathBuf>,
+    lib_roots: Vec<PathBuf>,
+}
+impl IncludeSearch {
+    pub fn new(options: &Options) -> Self {
+        Self {
+            current_dir: options.current_dir.clone(),
+            include_paths: options.include_path.iter().cloned().collect(),
+            lib_roots: options
+                .code_path
+                .iter()
+                .chain(options.lib_path.iter())
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// The existing directories in which applications are searched for
+    pub fn lib_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        for root in self.lib_roots.iter() {
+            for dir in [root.clone(), root.join("lib")].iter() {
+                if dir.is_dir() && !dirs.contains(dir) {
+                    dirs.push(dir.clone());
+                }
+            }
+        }
+        dirs
+    }
+
+    /// The directory of every application in the library directories, resolved as by `app_dir`,
+    /// so only the highest version of each is included
+    pub fn app_dirs(&self) -> Vec<PathBuf> {
+        let mut apps = Vec::new();
+        for root in self.lib_roots.iter() {
+            if root.file_name().and_then(|n| n.to_str()) == Some("ebin") {
+                if let Some(name) = root.parent().and_then(|dir| dir.file_name()) {
+                    apps.extend(name.to_str().map(app_name));
+                }
+            }
+        }
+        for dir in self.lib_dirs() {
+            let entries = match fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            apps.extend(
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().is_dir())
+                    .filter_map(|entry| entry.file_name().to_str().map(app_name)),
+            );
+        }
+
+        let mut dirs = Vec::new();
+        for app in apps {
+            if let Some(dir) = self.app_dir(&app) {
+                if !dirs.contains(&dir) {
+                    dirs.push(dir);
+                }
+            }
+        }
+        dirs
+    }
+
+    /// Resolves `path` from an attribute in the file at `from`
+    pub fn resolve(&self, kind: IncludeKind, path: &str, from: &Path) -> Option<PathBuf> {
+        match kind {
+            IncludeKind::Include => self.resolve_include(path, from),
+            IncludeKind::IncludeLib => self.resolve_include_lib(path, from),
+        }
+    }
+
+    pub fn resolve_include(&self, path: &str, from: &Path) -> Option<PathBuf> {
+        let path = substitute_path_variables(path);
+        if path.is_absolute() {
+            return Some(path).filter(|p| p.is_file());
+        }
+        self.include_dirs(from)
+            .into_iter()
+            .map(|dir| dir.join(&path))
+            .find(|p| p.is_file())
+    }
+
+    pub fn resolve_include_lib(&self, path: &str, from: &Path) -> Option<PathBuf> {
+        if let Some(found) = self.resolve_include(path, from) {
+            return Some(found);
+        }
+        let path = substitute_path_variables(path);
+        let mut components = path.components();
+        let app = match components.next() {
+            Some(Component::Normal(app)) => app.to_str()?,
+            _ => return None,
+        };
+        let found = self.app_dir(app)?.join(components.as_path());
+        Some(found).filter(|p| p.is_file())
+    }
+
+    /// Finds the directory of the application named `app`
+    pub fn app_dir(&self, app: &str) -> Option<PathBuf> {
+        let versioned = format!("{}-", app);
+        for root in self.lib_roots.iter() {
+            // A code path may be the `ebin` directory of the application itself
+            if root.file_name().and_then(|n| n.to_str()) == Some("ebin") {
+                if let Some(dir) = root.parent() {
+                    match dir.file_name().and_then(|n| n.to_str()) {
+                        Some(name) if name == app || name.starts_with(&versioned) => {
+                            return Some(dir.to_path_buf());
+                        }
+                        _ => (),
+                    }
+                }
+            }
+
+            for dir in [root.clone(), root.join("lib")].iter() {
+                let unversioned = dir.join(app);
+                if unversioned.is_dir() {
+                    return Some(unversioned);
+                }
+                let entries = match fs::read_dir(dir) {
+                    Ok(entries) => entries,
+                    Err(_) => continue,
+                };
+                let newest = entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().is_dir())
+                    .filter_map(|entry| {
+                        let name = entry.file_name().to_str()?.to_string();
+                        if name.starts_with(&versioned) {
+                            Some((name[versioned.len()..].to_string(), entry.path()))
+                        } else {
+                            None
+                        }
+                    })
+                    .max_by(|(a, _), (b, _)| compare_versions(a, b));
+                if let Some((_, path)) = newest {
+                    return Some(path);
+                }
+            }
+        }
+        None
+    }
+
+    /// The directories searched for an include of `kind` in the file at `from`, for diagnostics
+    pub fn searched_dirs(&self, kind: IncludeKind, from: &Path) -> Vec<PathBuf> {
+        let mut dirs = self.include_dirs(from);
+        if kind == IncludeKind::IncludeLib {
+            dirs.extend(self.lib_dirs());
+        }
+        dirs
+    }
+
+    fn include_dirs(&self, from: &Path) -> Vec<PathBuf> {
+        from.parent()
+            .map(|dir| dir.to_path_buf())
+            .into_iter()
+            .chain(std::iter::once(self.current_dir.clone()))
+            .chain(self.include_paths.iter().cloned())
+            .collect()
+    }
+}
+
+/// Parses the directories in the `ERL_LIBS` environment variable
+pub fn erl_libs() -> Vec<PathBuf> {
+    env::var_os(ERL_LIBS)
+        .map(|value| env::split_paths(&value).collect())
+        .unwrap_or_default()
+}
+
+/// Replaces a leading `$VAR` component with the value of the environment variable `VAR`
+fn substitute_path_variables(path: &str) -> PathBuf {
+    if path.starts_with('$') {
+        let (var, rest) = match path.find('/') {
+            Some(i) => (&path[1..i], &path[(i + 1)..]),
+            None => (&path[1..], ""),
+        };
+        if let Some(value) = env::var_os(var) {
+            return PathBuf::from(value).join(rest);
+        }
+    }
+    PathBuf::from(path)
+}
+
+/// Strips the version suffix from an application directory name, e.g. `stdlib-3.12`
+fn app_name(dir_name: &str) -> String {
+    match dir_name.find('-') {
+        Some(i) => dir_name[..i].to_string(),
+        None => dir_name.to_string(),
+    }
+}
+
+/// Compares versions such as `3.12.1`, comparing numeric parts as numbers
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parts = |v: &str| {
+        v.split('.')
+            .map(|part| (part.parse::<u64>().ok(), part.to_string()))
+            .collect::<Vec<_>>()
+    };
+    parts(a).cmp(&parts(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search(lib_roots: Vec<PathBuf>) -> IncludeSearch {
+        IncludeSearch {
+            current_dir: env::temp_dir(),
+            include_paths: Vec::new(),
+            lib_roots,
+        }
+    }
+
+    /// Creates an empty directory under the temporary directory for the test named `name`
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join("lumen_include_search").join(name);
+        if dir.exists() {
+            fs::remove_dir_all(&dir).unwrap();
+        }
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn finds_include_attributes_with_their_spans() {
+        let source = "-module(m).\n\
+                      % -include(\"commented.hrl\").\n\
+                      -include(\"m.hrl\").\n\
+                      -include_lib(\"stdlib/include/ms_transform.hrl\").\n\
+                      f() -> \"-include(\\\"string.hrl\\\").\".\n";
+        let mut codemap = CodeMap::new();
+        let file = codemap.add_filemap(FileName::Virtual("nofile".into()), source.to_string());
+        let base = file.span().start().to_usize();
+
+        let includes = find_includes(&file);
+
+        assert_eq!(
+            includes
+                .iter()
+                .map(|include| (include.kind, include.path.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (IncludeKind::Include, "m.hrl"),
+                (IncludeKind::IncludeLib, "stdlib/include/ms_transform.hrl")
+            ]
+        );
+        let start = includes[0].span.start().to_usize() - base;
+        let end = includes[0].span.end().to_usize() - base;
+        assert_eq!(&source[start..end], "-include(\"m.hrl\")");
+    }
+
+    #[test]
+    fn resolves_the_highest_version_of_an_application() {
+        let lib = test_dir("highest_version");
+        for app in ["stdlib-3.9", "stdlib-3.12", "kernel"].iter() {
+            fs::create_dir_all(lib.join(app).join("include")).unwrap();
+        }
+        fs::write(lib.join("stdlib-3.12/include/ms_transform.hrl"), "").unwrap();
+        let search = search(vec![lib.clone()]);
+
+        assert_eq!(search.app_dir("stdlib"), Some(lib.join("stdlib-3.12")));
+        assert_eq!(search.app_dir("kernel"), Some(lib.join("kernel")));
+        assert_eq!(search.app_dir("compiler"), None);
+        assert_eq!(
+            search.resolve_include_lib("stdlib/include/ms_transform.hrl", &lib.join("m.erl")),
+            Some(lib.join("stdlib-3.12/include/ms_transform.hrl"))
+        );
+
+        let mut app_dirs = search.app_dirs();
+        app_dirs.sort();
+        assert_eq!(app_dirs, vec![lib.join("kernel"), lib.join("stdlib-3.12")]);
+    }
+
+    #[test]
+    fn resolves_an_application_from_its_ebin_directory() {
+        let root = test_dir("ebin");
+        let ebin = root.join("myapp-1.0.0").join("ebin");
+        fs::create_dir_all(&ebin).unwrap();
+        let search = search(vec![ebin]);
+
+        assert_eq!(search.app_dir("myapp"), Some(root.join("myapp-1.0.0")));
+        assert_eq!(search.app_dirs(), vec![root.join("myapp-1.0.0")]);
+    }
+
+    #[test]
+    fn compares_version_parts_as_numbers() {
+        assert_eq!(compare_versions("3.12", "3.9"), Ordering::Greater);
+        assert_eq!(compare_versions("3.12.1", "3.12"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0", "1.0"), Ordering::Equal);
+    }
+}
@@ -1,11 +1,17 @@
+pub mod app;
 mod config;
 pub mod diagnostics;
 pub mod filesearch;
+pub mod include_search;
 pub mod search_paths;
 mod types;
 
+pub use self::app::Application;
 pub use self::config::*;
-pub use self::diagnostics::{verbosity_to_severity, DiagnosticsConfig, DiagnosticsHandler};
+pub use self::diagnostics::{
+    verbosity_to_severity, DiagnosticsConfig, DiagnosticsHandler, Suggestion,
+};
 pub use self::filesearch::{FileMatch, FileSearch};
+pub use self::include_search::{IncludeKind, IncludeSearch};
 pub use self::search_paths::{PathKind, SearchPath};
-pub use self::types::{IRModule, ParsedModule};
+pub use self::types::{find_remote_captures, IRModule, ParsedModule};
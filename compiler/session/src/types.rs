@@ -1,9 +1,11 @@
+use std::collections::{HashMap, HashSet};
 use std::convert::{AsRef, From};
 use std::fmt;
 use std::ops::Deref;
 use std::ptr;
 use std::sync::Arc;
 
+use libeir_diagnostics::{ByteSpan, CodeMap, FileMap};
 use libeir_ir as eir;
 use libeir_syntax_erl as syntax;
 
@@ -67,14 +69,31 @@ impl From<syntax::ast::Module> for ParsedModule {
 #[derive(Clone)]
 pub struct IRModule {
     module: Arc<eir::Module>,
+    remote_captures: Arc<HashSet<ByteSpan>>,
 }
 impl IRModule {
     #[inline]
     pub fn new(module: eir::Module) -> Self {
         Self {
             module: Arc::new(module),
+            remote_captures: Default::default(),
         }
     }
+
+    /// Records the spans of the function captures in this module which name a module
+    pub fn with_remote_captures(mut self, remote_captures: Arc<HashSet<ByteSpan>>) -> Self {
+        self.remote_captures = remote_captures;
+        self
+    }
+
+    /// The spans of the function captures in this module which name a module, i.e.
+    /// `fun m:f/1` or `fun ?MODULE:f/1` rather than `fun f/1`
+    ///
+    /// Both are lowered to the same `CaptureFunction` primop, but a capture naming its own
+    /// module is of the exported function, not the local one, so they are told apart by these.
+    pub fn remote_captures(&self) -> &Arc<HashSet<ByteSpan>> {
+        &self.remote_captures
+    }
 }
 impl fmt::Debug for IRModule {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -107,9 +126,7 @@ impl AsRef<eir::Module> for IRModule {
 }
 impl From<eir::Module> for IRModule {
     fn from(module: eir::Module) -> Self {
-        Self {
-            module: Arc::new(module),
-        }
+        Self::new(module)
     }
 }
 impl Emit for IRModule {
@@ -119,3 +136,154 @@ impl Emit for IRModule {
         self.module.emit(f)
     }
 }
+
+/// Finds the spans of the function captures in `module` which name a module, where `module` was
+/// lowered from the Erlang source files in `codemap`
+///
+/// Each capture is looked up in the tokens of the file its span is in, which may be an included
+/// file, or the file defining the macro it was expanded from. A capture names a module when a `:`
+/// comes before the `/` of its arity.
+pub fn find_remote_captures(codemap: &CodeMap, module: &eir::Module) -> HashSet<ByteSpan> {
+    let mut tokens = FileTokens::new(codemap);
+    let mut remote_captures = HashSet::new();
+    for definition in module.function_iter() {
+        for span in capture_spans(definition.function()) {
+            if tokens.is_remote_capture(span) {
+                remote_captures.insert(span);
+            }
+        }
+    }
+    remote_captures
+}
+
+/// Returns the spans of the `CaptureFunction` primops in `function`
+fn capture_spans(function: &eir::Function) -> Vec<ByteSpan> {
+    let mut spans = Vec::new();
+    let mut blocks = vec![function.block_entry()];
+    let mut visited_blocks = HashSet::new();
+    let mut visited_values = HashSet::new();
+    while let Some(block) = blocks.pop() {
+        if !visited_blocks.insert(block) {
+            continue;
+        }
+        let mut values = function.block_reads(block).to_vec();
+        while let Some(value) = values.pop() {
+            if !visited_values.insert(value) {
+                continue;
+            }
+            if let Some(block) = function.value_block(value) {
+                blocks.push(block);
+            } else if let Some(primop) = function.value_primop(value) {
+                if *function.primop_kind(primop) == eir::PrimOpKind::CaptureFunction {
+                    let span = function
+                        .value_locations(value)
+                        .and_then(|locs| locs.first().copied());
+                    spans.extend(span);
+                }
+                values.extend(function.primop_reads(primop).iter().copied());
+            }
+        }
+    }
+    spans
+}
+
+/// The tokens of the files in a code map, lexed the first time a span in each file is looked up
+struct FileTokens<'a> {
+    codemap: &'a CodeMap,
+    /// The file and its tokens, by the start of the file
+    files: HashMap<usize, (Arc<FileMap>, Vec<syntax::LexicalToken>)>,
+}
+impl<'a> FileTokens<'a> {
+    fn new(codemap: &'a CodeMap) -> Self {
+        Self {
+            codemap,
+            files: HashMap::new(),
+        }
+    }
+
+    /// Returns true if the capture at `span` names a module
+    fn is_remote_capture(&mut self, span: ByteSpan) -> bool {
+        use syntax::{FileMapSource, Lexer, Scanner};
+
+        let file = match self.codemap.find_file(span.start()) {
+            Some(file) => file.clone(),
+            None => return false,
+        };
+        let (file, tokens) = self
+            .files
+            .entry(file.span().start().to_usize())
+            .or_insert_with(|| {
+                let tokens = Lexer::new(Scanner::new(FileMapSource::new(file.clone())))
+                    .filter_map(Result::ok)
+                    .collect();
+                (file, tokens)
+            });
+
+        let source = file.src();
+        let base = file.span().start().to_usize();
+        for syntax::LexicalToken(start, _, end) in tokens.iter() {
+            if *start < span.start() {
+                continue;
+            }
+            if *end > span.end() {
+                break;
+            }
+            match &source[(start.to_usize() - base)..(end.to_usize() - base)] {
+                ":" => return true,
+                "/" => return false,
+                _ => (),
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use libeir_diagnostics::{ByteIndex, FileName};
+
+    /// Returns the span of `text` in `file`, which must occur in it once
+    fn span_of(file: &FileMap, text: &str) -> ByteSpan {
+        let start = file.span().start().to_usize() + file.src().find(text).unwrap();
+        ByteSpan::new(
+            ByteIndex::from(start as u32),
+            ByteIndex::from((start + text.len()) as u32),
+        )
+    }
+
+    #[test]
+    fn captures_naming_a_module_are_remote() {
+        let mut codemap = CodeMap::new();
+        let file = codemap.add_filemap(
+            FileName::Virtual("m.erl".into()),
+            "-module(m).\n\
+             f() -> {fun g/0, fun 'h:i'/0, fun ?MODULE:g/0, fun lists : map / 2}.\n"
+                .to_string(),
+        );
+        let mut tokens = FileTokens::new(&codemap);
+
+        assert!(!tokens.is_remote_capture(span_of(&file, "fun g/0")));
+        assert!(!tokens.is_remote_capture(span_of(&file, "fun 'h:i'/0")));
+        assert!(tokens.is_remote_capture(span_of(&file, "fun ?MODULE:g/0")));
+        assert!(tokens.is_remote_capture(span_of(&file, "fun lists : map / 2")));
+    }
+
+    #[test]
+    fn captures_are_looked_up_in_the_file_they_are_in() {
+        let mut codemap = CodeMap::new();
+        let module = codemap.add_filemap(
+            FileName::Virtual("m.erl".into()),
+            "-module(m).\n-include(\"m.hrl\").\nf() -> {?F, fun g/0}.\n".to_string(),
+        );
+        let header = codemap.add_filemap(
+            FileName::Virtual("m.hrl".into()),
+            "-define(F, fun m:g/0).\n".to_string(),
+        );
+        let mut tokens = FileTokens::new(&codemap);
+
+        assert!(tokens.is_remote_capture(span_of(&header, "fun m:g/0")));
+        assert!(!tokens.is_remote_capture(span_of(&module, "fun g/0")));
+    }
+}
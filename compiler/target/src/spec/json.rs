@@ -0,0 +1,627 @@
+//! Custom target specifications, loaded from JSON files
+//!
+//! A specification is a single JSON object, using the kebab-case names of the fields of `Target`
+//! and `TargetOptions` as keys, e.g. `llvm-target` or `pre-link-args`. The fields of `Target` are
+//! required, any of the fields of `TargetOptions` which are not given take their default value.
+//!
+//! The easiest way to write one is to start from the specification of a built-in target, as
+//! printed by `lumen print target-spec-json --target <triple>`.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+
+use super::abi::{self, Abi};
+use super::*;
+
+/// The environment variable holding the directories searched for `<target>.json`
+pub const LUMEN_TARGET_PATH: &str = "LUMEN_TARGET_PATH";
+
+/// Finds the specification file for `target`, which is either a path to the file itself,
+/// or the name of a file `<target>.json` in one of the directories in `LUMEN_TARGET_PATH`
+pub(super) fn find(target: &str) -> Option<PathBuf> {
+    let path = Path::new(target);
+    if path.extension().map(|ext| ext == "json").unwrap_or(false) || path.is_file() {
+        return Some(path.to_path_buf());
+    }
+    let file_name = format!("{}.json", target);
+    env::var_os(LUMEN_TARGET_PATH)
+        .map(|dirs| env::split_paths(&dirs).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|dir| dir.join(&file_name))
+        .find(|path| path.is_file())
+}
+
+/// Loads the target specification in the file at `path`
+pub(super) fn load(path: &Path) -> Result<Target, TargetError> {
+    let source =
+        fs::read_to_string(path).map_err(|err| TargetError::Io(path.to_path_buf(), err))?;
+    parse(&source).map_err(|message| TargetError::InvalidSpec(path.to_path_buf(), message))
+}
+
+/// Parses a target specification, returning a description of the problem if it is invalid
+pub(super) fn parse(source: &str) -> Result<Target, String> {
+    let json: Value = serde_json::from_str(source).map_err(|err| err.to_string())?;
+    let object = json
+        .as_object()
+        .ok_or_else(|| "expected a JSON object".to_string())?;
+    from_object(object).map_err(
+        |InvalidField { key, message }| match field_line(source, &key) {
+            Some(line) => format!("`{}` at line {}: {}", key, line, message),
+            None => format!("`{}`: {}", key, message),
+        },
+    )
+}
+
+/// Converts `target` to the JSON specification it would be loaded from
+pub(super) fn to_json(target: &Target) -> Value {
+    Value::Object(to_object(target))
+}
+
+struct InvalidField {
+    key: String,
+    message: String,
+}
+impl InvalidField {
+    fn new<S: Into<String>>(key: &str, message: S) -> Self {
+        Self {
+            key: key.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+macro_rules! target_spec {
+    (
+        target { $($field:ident => $key:literal,)* }
+        options { $($option:ident => $option_key:literal,)* }
+    ) => {
+        const KEYS: &[&str] = &[$($key,)* $($option_key,)*];
+
+        fn from_object(object: &Map<String, Value>) -> Result<Target, InvalidField> {
+            // Misspelled fields would otherwise silently take their default value
+            if let Some(key) = object.keys().find(|key| !KEYS.contains(&key.as_str())) {
+                return Err(InvalidField::new(key, "unknown field"));
+            }
+
+            let mut options = TargetOptions::default();
+            $(
+                if let Some(value) = object.get($option_key) {
+                    options.$option = FromJson::from_json(value)
+                        .map_err(|message| InvalidField::new($option_key, message))?;
+                }
+            )*
+            validate_options(&options)?;
+
+            Ok(Target {
+                $(
+                    $field: match object.get($key) {
+                        Some(value) => FromJson::from_json(value)
+                            .map_err(|message| InvalidField::new($key, message))?,
+                        None => return Err(InvalidField::new($key, "this field is required")),
+                    },
+                )*
+                options,
+            })
+        }
+
+        fn to_object(target: &Target) -> Map<String, Value> {
+            let mut object = Map::new();
+            $(object.insert($key.to_string(), target.$field.to_json());)*
+            $(object.insert($option_key.to_string(), target.options.$option.to_json());)*
+            object
+        }
+    };
+}
+
+target_spec! {
+    target {
+        llvm_target => "llvm-target",
+        target_endian => "target-endian",
+        target_pointer_width => "target-pointer-width",
+        target_c_int_width => "target-c-int-width",
+        target_os => "os",
+        target_env => "env",
+        target_vendor => "vendor",
+        arch => "arch",
+        data_layout => "data-layout",
+        linker_flavor => "linker-flavor",
+    }
+    options {
+        encoding => "encoding",
+        linker => "linker",
+        lld_flavor => "lld-flavor",
+        pre_link_args => "pre-link-args",
+        pre_link_args_crt => "pre-link-args-crt",
+        pre_link_objects_exe => "pre-link-objects-exe",
+        pre_link_objects_exe_crt => "pre-link-objects-exe-crt",
+        pre_link_objects_dll => "pre-link-objects-dll",
+        late_link_args => "late-link-args",
+        late_link_args_dynamic => "late-link-args-dynamic",
+        late_link_args_static => "late-link-args-static",
+        post_link_objects => "post-link-objects",
+        post_link_objects_crt => "post-link-objects-crt",
+        post_link_args => "post-link-args",
+        link_env => "link-env",
+        link_env_remove => "link-env-remove",
+        asm_args => "asm-args",
+        cpu => "cpu",
+        features => "features",
+        dynamic_linking => "dynamic-linking",
+        only_cdylib => "only-cdylib",
+        executables => "executables",
+        relocation_model => "relocation-model",
+        code_model => "code-model",
+        tls_model => "tls-model",
+        disable_redzone => "disable-redzone",
+        eliminate_frame_pointer => "eliminate-frame-pointer",
+        function_sections => "function-sections",
+        dll_prefix => "dll-prefix",
+        dll_suffix => "dll-suffix",
+        exe_suffix => "exe-suffix",
+        staticlib_prefix => "staticlib-prefix",
+        staticlib_suffix => "staticlib-suffix",
+        target_family => "target-family",
+        abi_return_struct_as_int => "abi-return-struct-as-int",
+        is_like_osx => "is-like-osx",
+        is_like_solaris => "is-like-solaris",
+        is_like_windows => "is-like-windows",
+        is_like_msvc => "is-like-msvc",
+        is_like_android => "is-like-android",
+        is_like_emscripten => "is-like-emscripten",
+        is_like_fuchsia => "is-like-fuchsia",
+        linker_is_gnu => "linker-is-gnu",
+        allows_weak_linkage => "allows-weak-linkage",
+        has_rpath => "has-rpath",
+        no_default_libraries => "no-default-libraries",
+        position_independent_executables => "position-independent-executables",
+        needs_plt => "needs-plt",
+        relro_level => "relro-level",
+        archive_format => "archive-format",
+        allow_asm => "allow-asm",
+        main_needs_argc_argv => "main-needs-argc-argv",
+        has_elf_tls => "has-elf-tls",
+        obj_is_bitcode => "obj-is-bitcode",
+        min_atomic_width => "min-atomic-width",
+        max_atomic_width => "max-atomic-width",
+        atomic_cas => "atomic-cas",
+        panic_strategy => "panic-strategy",
+        abi_blacklist => "abi-blacklist",
+        crt_static_allows_dylibs => "crt-static-allows-dylibs",
+        crt_static_default => "crt-static-default",
+        crt_static_respected => "crt-static-respected",
+        stack_probes => "stack-probes",
+        min_global_align => "min-global-align",
+        default_codegen_units => "default-codegen-units",
+        trap_unreachable => "trap-unreachable",
+        requires_lto => "requires-lto",
+        singlethread => "singlethread",
+        no_builtins => "no-builtins",
+        codegen_backend => "codegen-backend",
+        default_hidden_visibility => "default-hidden-visibility",
+        emit_debug_gdb_scripts => "emit-debug-gdb-scripts",
+        requires_uwtable => "requires-uwtable",
+        simd_types_indirect => "simd-types-indirect",
+        limit_rdylib_exports => "limit-rdylib-exports",
+        override_export_symbols => "override-export-symbols",
+        merge_functions => "merge-functions",
+        target_mcount => "target-mcount",
+        llvm_abiname => "llvm-abiname",
+        relax_elf_relocations => "relax-elf-relocations",
+        llvm_args => "llvm-args",
+    }
+}
+
+/// Checks the options whose values are strings with a fixed set of valid values
+fn validate_options(options: &TargetOptions) -> Result<(), InvalidField> {
+    if options.relocation_model.parse::<RelocMode>().is_err() {
+        return Err(InvalidField::new(
+            "relocation-model",
+            format!(
+                "unrecognized relocation model '{}', expected one of: default, static, pic, \
+                 dynamic-no-pic, ropi, rwpi, ropi-rwpi",
+                options.relocation_model
+            ),
+        ));
+    }
+    if let Some(ref code_model) = options.code_model {
+        if code_model.parse::<CodeModel>().is_err() {
+            return Err(InvalidField::new(
+                "code-model",
+                format!(
+                    "unrecognized code model '{}', expected one of: small, kernel, medium, large",
+                    code_model
+                ),
+            ));
+        }
+    }
+    if options.tls_model.parse::<ThreadLocalMode>().is_err() {
+        return Err(InvalidField::new(
+            "tls-model",
+            format!(
+                "unrecognized TLS model '{}', expected one of: global-dynamic, local-dynamic, \
+                 initial-exec, local-exec",
+                options.tls_model
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the one-based line on which `key` is defined in `source`
+fn field_line(source: &str, key: &str) -> Option<usize> {
+    let quoted = format!("\"{}\"", key);
+    source
+        .lines()
+        .position(|line| match line.find(&quoted) {
+            Some(i) => line[(i + quoted.len())..].trim_start().starts_with(':'),
+            None => false,
+        })
+        .map(|i| i + 1)
+}
+
+trait FromJson: Sized {
+    fn from_json(json: &Value) -> Result<Self, String>;
+}
+
+trait ToJson {
+    fn to_json(&self) -> Value;
+}
+
+fn describe(json: &Value) -> &'static str {
+    match json {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+fn expected<T>(what: &str, json: &Value) -> Result<T, String> {
+    Err(format!("expected {}, found {}", what, describe(json)))
+}
+
+/// Parses a string naming one of the variants of an enum
+fn parse_name<T: FromStr>(json: &Value, what: &str, valid: &str) -> Result<T, String> {
+    let s = String::from_json(json)?;
+    s.parse()
+        .map_err(|_| format!("unrecognized {} '{}', expected {}", what, s, valid))
+}
+
+impl FromJson for String {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        match json {
+            Value::String(s) => Ok(s.clone()),
+            json => expected("a string", json),
+        }
+    }
+}
+impl ToJson for String {
+    fn to_json(&self) -> Value {
+        Value::String(self.clone())
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        match json {
+            Value::Bool(b) => Ok(*b),
+            json => expected("a boolean", json),
+        }
+    }
+}
+impl ToJson for bool {
+    fn to_json(&self) -> Value {
+        Value::Bool(*self)
+    }
+}
+
+impl FromJson for u64 {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        match json.as_u64() {
+            Some(n) => Ok(n),
+            None => expected("a non-negative integer", json),
+        }
+    }
+}
+impl ToJson for u64 {
+    fn to_json(&self) -> Value {
+        Value::from(*self)
+    }
+}
+
+// This is only used for the pointer width, which is written as a string like the C int width,
+// but may also be given as a number
+impl FromJson for usize {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        match json {
+            Value::String(s) => s
+                .parse()
+                .map_err(|_| format!("expected an integer, found '{}'", s)),
+            json => u64::from_json(json).map(|n| n as usize),
+        }
+    }
+}
+impl ToJson for usize {
+    fn to_json(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        match json {
+            Value::Null => Ok(None),
+            json => T::from_json(json).map(Some),
+        }
+    }
+}
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> Value {
+        match self {
+            None => Value::Null,
+            Some(value) => value.to_json(),
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        match json {
+            Value::Array(elements) => elements
+                .iter()
+                .enumerate()
+                .map(|(i, element)| {
+                    T::from_json(element).map_err(|err| format!("element {}: {}", i, err))
+                })
+                .collect(),
+            json => expected("an array", json),
+        }
+    }
+}
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> Value {
+        Value::Array(self.iter().map(ToJson::to_json).collect())
+    }
+}
+
+/// Environment variables are written as `KEY=VALUE`
+impl FromJson for (String, String) {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        let s = String::from_json(json)?;
+        let mut parts = s.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some(key), Some(value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+            _ => Err(format!(
+                "expected a string of the form KEY=VALUE, found '{}'",
+                s
+            )),
+        }
+    }
+}
+impl ToJson for (String, String) {
+    fn to_json(&self) -> Value {
+        Value::String(format!("{}={}", self.0, self.1))
+    }
+}
+
+/// Linker arguments are written as an object with an array of arguments per linker flavor
+impl FromJson for LinkArgs {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        let object = match json {
+            Value::Object(object) => object,
+            json => return expected("an object", json),
+        };
+        let mut args = LinkArgs::new();
+        for (flavor, value) in object.iter() {
+            let flavor = flavor.parse::<LinkerFlavor>().map_err(|err| {
+                format!("{}, expected {}", err, LinkerFlavor::one_of().trim_end())
+            })?;
+            let value = Vec::<String>::from_json(value)
+                .map_err(|err| format!("{}: {}", flavor.desc(), err))?;
+            args.insert(flavor, value);
+        }
+        Ok(args)
+    }
+}
+impl ToJson for LinkArgs {
+    fn to_json(&self) -> Value {
+        Value::Object(
+            self.iter()
+                .map(|(flavor, args)| (flavor.desc().to_string(), args.to_json()))
+                .collect(),
+        )
+    }
+}
+
+impl FromJson for LinkerFlavor {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        parse_name(json, "linker flavor", LinkerFlavor::one_of().trim_end())
+    }
+}
+impl ToJson for LinkerFlavor {
+    fn to_json(&self) -> Value {
+        Value::String(self.desc().to_string())
+    }
+}
+
+impl FromJson for LldFlavor {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        parse_name(
+            json,
+            "LLD flavor",
+            "one of: wasm-ld, ld64.lld, ld.lld, link",
+        )
+    }
+}
+impl ToJson for LldFlavor {
+    fn to_json(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl FromJson for Endianness {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        match String::from_json(json)?.as_str() {
+            "native" => Ok(Self::Native),
+            "big" => Ok(Self::Big),
+            "little" => Ok(Self::Little),
+            s => Err(format!(
+                "unrecognized endianness '{}', expected one of: native, big, little",
+                s
+            )),
+        }
+    }
+}
+impl ToJson for Endianness {
+    fn to_json(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl FromJson for EncodingType {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        match String::from_json(json)?.as_str() {
+            "default" => Ok(Self::Default),
+            "32" => Ok(Self::Encoding32),
+            "64" => Ok(Self::Encoding64),
+            "64-nanboxed" => Ok(Self::Encoding64Nanboxed),
+            s => Err(format!(
+                "unrecognized encoding '{}', expected one of: default, 32, 64, 64-nanboxed",
+                s
+            )),
+        }
+    }
+}
+impl ToJson for EncodingType {
+    fn to_json(&self) -> Value {
+        let name = match self {
+            Self::Default => "default",
+            Self::Encoding32 => "32",
+            Self::Encoding64 => "64",
+            Self::Encoding64Nanboxed => "64-nanboxed",
+        };
+        Value::String(name.to_string())
+    }
+}
+
+impl FromJson for RelroLevel {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        parse_name(json, "RELRO level", "one of: full, partial, off, none")
+    }
+}
+impl ToJson for RelroLevel {
+    fn to_json(&self) -> Value {
+        Value::String(self.desc().to_string())
+    }
+}
+
+impl FromJson for PanicStrategy {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        parse_name(json, "panic strategy", "one of: unwind, abort")
+    }
+}
+impl ToJson for PanicStrategy {
+    fn to_json(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl FromJson for MergeFunctions {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        parse_name(
+            json,
+            "merge functions setting",
+            "one of: disabled, trampolines, aliases",
+        )
+    }
+}
+impl ToJson for MergeFunctions {
+    fn to_json(&self) -> Value {
+        Value::String(self.desc().to_string())
+    }
+}
+
+impl FromJson for Abi {
+    fn from_json(json: &Value) -> Result<Self, String> {
+        let name = String::from_json(json)?;
+        abi::lookup(&name).ok_or_else(|| {
+            format!(
+                "unrecognized ABI '{}', expected one of: {}",
+                name,
+                abi::all_names().join(", ")
+            )
+        })
+    }
+}
+impl ToJson for Abi {
+    fn to_json(&self) -> Value {
+        Value::String(self.name().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_round_trips_through_to_json() {
+        for triple in Target::all() {
+            let target = super::super::search(&triple).unwrap();
+            let source = serde_json::to_string_pretty(&to_json(&target)).unwrap();
+
+            assert_eq!(parse(&source), Ok(target), "{}", triple);
+        }
+    }
+
+    #[test]
+    fn parse_rejects_unknown_field() {
+        let target = super::super::search("x86_64-unknown-linux-gnu").unwrap();
+        let mut object = to_object(&target);
+        object.insert("relocation-modle".to_string(), Value::from("pic"));
+        let source = serde_json::to_string_pretty(&Value::Object(object)).unwrap();
+
+        let message = parse(&source).unwrap_err();
+
+        assert!(
+            message.starts_with("`relocation-modle`") && message.ends_with("unknown field"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn parse_reports_line_of_invalid_field() {
+        let target = super::super::search("x86_64-unknown-linux-gnu").unwrap();
+        let mut object = to_object(&target);
+        object.insert("tls-model".to_string(), Value::from("thread-local"));
+        let source = serde_json::to_string_pretty(&Value::Object(object)).unwrap();
+        let line = source
+            .lines()
+            .position(|line| line.trim_start().starts_with("\"tls-model\""))
+            .unwrap()
+            + 1;
+
+        assert_eq!(
+            parse(&source),
+            Err(format!(
+                "`tls-model` at line {}: unrecognized TLS model 'thread-local', expected one of: \
+                 global-dynamic, local-dynamic, initial-exec, local-exec",
+                line
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_reports_missing_required_field_without_line() {
+        assert_eq!(
+            parse("{}"),
+            Err("`llvm-target`: this field is required".to_string())
+        );
+    }
+}
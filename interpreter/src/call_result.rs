@@ -178,7 +178,8 @@ fn return_ok(arc_process: &Arc<Process>) -> code::Result {
     let sender_any: Resource = sender_resource.into();
     let sender: &ProcessResultSender = sender_any.downcast_ref().unwrap();
 
-    let mut fragment = HeapFragment::new_from_word_size(100).unwrap();
+    // Sized to the result, so that large results, such as those kept by the shell, fit
+    let mut fragment = HeapFragment::new_from_word_size(argument_vec[0].size_in_words()).unwrap();
     let frag_mut = unsafe { fragment.as_mut() };
     let ret = argument_vec[0].clone_to_heap(frag_mut).unwrap();
 
@@ -367,20 +367,20 @@ impl CallExecutor {
             }
             ConstKind::Atomic(AtomicTerm::Nil) => Ok(Term::NIL),
             ConstKind::Map { keys, values } => {
-                let mut map = HashMap::new();
+                let mut entries = Vec::new();
 
                 for (k, v) in keys
                     .as_slice(&fun.fun.cons().const_pool)
                     .iter()
                     .zip(values.as_slice(&fun.fun.cons().const_pool).iter())
                 {
-                    map.insert(
+                    entries.push((
                         self.make_const_term(proc, fun, *k)?,
                         self.make_const_term(proc, fun, *v)?,
-                    );
+                    ));
                 }
 
-                Ok(proc.map_from_hash_map(map)?)
+                Ok(proc.map_from_slice(&entries)?)
             }
             kind => unimplemented!("{:?}", kind),
         };
@@ -516,6 +516,35 @@ impl CallExecutor {
         }
     }
 
+    /// `M#{K := V}` raises `{badkey, K}` when `K` is not in `M`.
+    ///
+    /// The error continuation of `MapPut` takes no arguments, so it cannot be given the key.
+    /// Instead, the raise the lowering put in the error continuation, a call of the exception
+    /// handler in scope with `(Class, Reason, Trace)`, is made directly with `{badkey, K}` as the
+    /// `Reason`.
+    fn map_put_badkey(
+        &mut self,
+        proc: &Arc<Process>,
+        fun: &ErlangFunction,
+        err: Value,
+        key: Term,
+    ) -> Result<OpResult, SystemException> {
+        if let ValueKind::Block(err_block) = fun.fun.value_kind(err) {
+            let reads = fun.fun.block_reads(err_block);
+
+            if let (Some(OpKind::Call(_)), 4) = (fun.fun.block_kind(err_block), reads.len()) {
+                let class = self.make_term(proc, fun, reads[1])?;
+                let reason = proc.tuple_from_slice(&[atom!("badkey"), key])?;
+                let trace = self.make_term(proc, fun, reads[3])?;
+                self.next_args.extend_from_slice(&[class, reason, trace]);
+
+                return self.val_call(proc, fun, reads[0]);
+            }
+        }
+
+        self.val_call(proc, fun, err)
+    }
+
     fn run_erlang_op(
         &mut self,
         _vm: &VMState,
@@ -608,9 +637,7 @@ impl CallExecutor {
             }
             OpKind::Match { branches } => self::r#match::match_op(self, proc, fun, branches, block),
             OpKind::MapPut { action } => {
-                let map_term: Boxed<Map> = self.make_term(proc, fun, reads[2])?.try_into().unwrap();
-                let hashmap_ref: &HashMap<Term, Term> = map_term.as_ref().as_ref();
-                let mut hashmap = hashmap_ref.clone();
+                let mut map: Boxed<Map> = self.make_term(proc, fun, reads[2])?.try_into().unwrap();
 
                 let mut idx = 3;
                 for action in action.iter() {
@@ -618,20 +645,27 @@ impl CallExecutor {
                     let val = self.make_term(proc, fun, reads[idx + 1])?;
                     idx += 2;
 
-                    match action {
-                        MapPutUpdate::Put => {
-                            hashmap.insert(key, val);
-                        }
-                        MapPutUpdate::Update => {
-                            if hashmap.contains_key(&key) {
-                                unimplemented!()
+                    let mut heap = proc.acquire_heap();
+
+                    // Each step only copies the path to `key`, so the rest of the map is shared
+                    let updated_map = match action {
+                        MapPutUpdate::Put => map.put(&mut heap, key, val)?,
+                        MapPutUpdate::Update => match map.update(&mut heap, key, val)? {
+                            Some(updated_map) => Some(updated_map),
+                            None => {
+                                std::mem::drop(heap);
+
+                                return self.map_put_badkey(proc, fun, reads[1], key);
                             }
-                            hashmap.insert(key, val);
-                        }
+                        },
+                    };
+
+                    if let Some(updated_map) = updated_map {
+                        map = updated_map;
                     }
                 }
 
-                self.next_args.push(proc.map_from_hash_map(hashmap)?);
+                self.next_args.push(map.into());
                 return self.val_call(proc, fun, reads[0]);
             }
             OpKind::Intrinsic(name) if *name == Symbol::intern("receive_start") => {
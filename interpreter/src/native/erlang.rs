@@ -92,6 +92,16 @@ pub fn make_erlang() -> NativeModule {
         2,
         |proc, args| erlang::process_flag_2::native(proc, args[0], args[1]),
     );
+    native.add_simple(
+        Atom::try_from_str("process_info").unwrap(),
+        1,
+        |proc, args| erlang::process_info_1::native(proc, args[0]),
+    );
+    native.add_simple(
+        Atom::try_from_str("process_info").unwrap(),
+        2,
+        |proc, args| erlang::process_info_2::native(proc, args[0], args[1]),
+    );
 
     let send_2_module_function_arity = erlang::send_2::module_function_arity();
     native.add_simple(
@@ -172,6 +182,11 @@ pub fn make_erlang() -> NativeModule {
         0,
         |proc, _args| erlang::monotonic_time_0::native(proc),
     );
+    native.add_simple(
+        Atom::try_from_str("statistics").unwrap(),
+        1,
+        |proc, args| erlang::statistics_1::native(proc, args[0]),
+    );
 
     native.add_yielding(Atom::try_from_str("apply").unwrap(), 3, |proc, args| {
         let inner_args = proc.cons(args[0], proc.cons(args[1], args[4])?)?;
@@ -0,0 +1,65 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use liblumen_otp::ets;
+
+use crate::module::NativeModule;
+
+pub fn make_ets() -> NativeModule {
+    let mut native = NativeModule::new(Atom::try_from_str("ets").unwrap());
+
+    native.add_simple(Atom::try_from_str("delete").unwrap(), 1, |proc, args| {
+        ets::delete_1::native(proc, args[0])
+    });
+
+    native.add_simple(Atom::try_from_str("delete").unwrap(), 2, |proc, args| {
+        ets::delete_2::native(proc, args[0], args[1])
+    });
+
+    native.add_simple(Atom::try_from_str("info").unwrap(), 2, |proc, args| {
+        ets::info_2::native(proc, args[0], args[1])
+    });
+
+    native.add_simple(Atom::try_from_str("insert").unwrap(), 2, |proc, args| {
+        ets::insert_2::native(proc, args[0], args[1])
+    });
+
+    native.add_simple(
+        Atom::try_from_str("insert_new").unwrap(),
+        2,
+        |proc, args| ets::insert_new_2::native(proc, args[0], args[1]),
+    );
+
+    native.add_simple(Atom::try_from_str("lookup").unwrap(), 2, |proc, args| {
+        ets::lookup_2::native(proc, args[0], args[1])
+    });
+
+    native.add_simple(Atom::try_from_str("match").unwrap(), 2, |proc, args| {
+        ets::match_2::native(proc, args[0], args[1])
+    });
+
+    native.add_simple(
+        Atom::try_from_str("match_object").unwrap(),
+        2,
+        |proc, args| ets::match_object_2::native(proc, args[0], args[1]),
+    );
+
+    native.add_simple(Atom::try_from_str("new").unwrap(), 2, |proc, args| {
+        ets::new_2::native(proc, args[0], args[1])
+    });
+
+    native.add_simple(Atom::try_from_str("select").unwrap(), 2, |proc, args| {
+        ets::select_2::native(proc, args[0], args[1])
+    });
+
+    native.add_simple(Atom::try_from_str("tab2list").unwrap(), 1, |proc, args| {
+        ets::tab2list_1::native(proc, args[0])
+    });
+
+    native.add_simple(
+        Atom::try_from_str("update_counter").unwrap(),
+        3,
+        |proc, args| ets::update_counter_3::native(proc, args[0], args[1], args[2]),
+    );
+
+    native
+}
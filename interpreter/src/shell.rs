@@ -0,0 +1,419 @@
+//! An interactive shell that evaluates Erlang expressions with the interpreter.
+//!
+//! Each line is wrapped in a function of a fresh module that takes the current bindings as
+//! arguments and returns the value of the expressions along with any variables they bound, so
+//! that bindings are kept between lines the same way as in `erl`.
+use std::collections::BTreeSet;
+use std::convert::TryInto;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::ptr::{self, NonNull};
+use std::sync::Arc;
+
+use anyhow::*;
+
+use libeir_ir::Module;
+
+use libeir_passes::PassManager;
+
+use libeir_syntax_erl::ast::Module as ErlAstModule;
+use libeir_syntax_erl::lower_module;
+use libeir_syntax_erl::{ParseConfig, Parser};
+
+use libeir_util_parse::{ArcCodemap, Errors};
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::HeapFragment;
+
+use lumen_rt_full::scheduler::Scheduler;
+
+use crate::call_result::{call_run_erlang, ProcessResult};
+use crate::VM;
+
+/// Loads the Erlang source files at `paths`, then runs the shell on stdin and stdout until `q().`
+/// or the end of input.
+pub fn run<P: AsRef<Path>>(paths: &[P]) -> anyhow::Result<()> {
+    let mut shell = Shell::new();
+
+    for path in paths {
+        load_file(path)?;
+    }
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut input = String::new();
+
+    loop {
+        if input.is_empty() {
+            print!("{}> ", shell.count + 1);
+        } else {
+            print!(".. ");
+        }
+        io::stdout().flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+        input.push_str(&line);
+        input.push('\n');
+
+        // Like `erl`, keep reading until the expressions are terminated with `.`
+        if !input.trim_end().ends_with('.') {
+            continue;
+        }
+
+        let command = input.trim().trim_end_matches('.').trim().to_string();
+        input.clear();
+
+        match shell.eval_command(&command) {
+            Ok(Some(output)) => println!("{}", output),
+            Ok(None) => break,
+            Err(err) => println!("* {:#}", err),
+        }
+    }
+
+    Ok(())
+}
+
+pub struct Shell {
+    /// Number of evaluated lines, used for the prompt and to name each line's module
+    count: usize,
+    /// Variables bound by previous lines in the order they were bound
+    bindings: Vec<(String, Term)>,
+    /// The fragments holding the values of `bindings` and the results of previous lines
+    fragments: Vec<NonNull<HeapFragment>>,
+    init: Arc<Process>,
+}
+
+impl Shell {
+    pub fn new() -> Self {
+        &*VM;
+
+        liblumen_otp::erlang::apply_3::export();
+
+        let init = Scheduler::current().spawn_init(0).unwrap();
+
+        Self {
+            count: 0,
+            bindings: Vec::new(),
+            fragments: Vec::new(),
+            init,
+        }
+    }
+
+    /// Evaluates `command`, the expressions on a line without the terminating `.`, returning the
+    /// text to print or `None` if the shell should quit.
+    ///
+    /// Besides expressions, the shell commands `b()` to print bindings, `c(File)` to load a module,
+    /// `f()` and `f(Variable)` to forget bindings, and `q()` to quit are supported.
+    pub fn eval_command(&mut self, command: &str) -> anyhow::Result<Option<String>> {
+        if command.is_empty() {
+            return Ok(Some(String::new()));
+        }
+
+        let output = match command {
+            "b()" => self
+                .bindings
+                .iter()
+                .map(|(name, value)| format!("{} = {}", name, value))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "f()" => {
+                self.bindings.clear();
+
+                "ok".to_string()
+            }
+            "q()" => return Ok(None),
+            _ => {
+                if let Some(argument) = shell_command_argument(command, "c") {
+                    let path = argument.trim_matches(|c| c == '"' || c == '\'');
+                    let module_name = load_file(path)?;
+
+                    format!("{{ok,{}}}", module_name)
+                } else if let Some(name) = shell_command_argument(command, "f") {
+                    self.bindings.retain(|(bound, _)| bound != name);
+
+                    "ok".to_string()
+                } else {
+                    self.eval(command)?
+                }
+            }
+        };
+
+        Ok(Some(output))
+    }
+
+    /// Evaluates the expressions in `exprs` with the current bindings, keeping any variables
+    /// they bind for later lines.
+    pub fn eval(&mut self, exprs: &str) -> anyhow::Result<String> {
+        self.count += 1;
+
+        let module_name = format!("lumen_shell_{}", self.count);
+        let bound: BTreeSet<&str> = self
+            .bindings
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        let candidates: Vec<String> = variables(exprs)
+            .into_iter()
+            .filter(|name| !bound.contains(name.as_str()))
+            .collect();
+
+        // Variables that only appear in `fun`s or are used without being bound can't be returned,
+        // so when the line doesn't lower with all of the candidates, find the candidates it
+        // lowers with one at a time.
+        let (eir_module, new_names) = match self.lower_line(&module_name, exprs, &candidates, false)
+        {
+            Some(eir_module) => (eir_module, candidates),
+            None => {
+                let new_names: Vec<String> = candidates
+                    .into_iter()
+                    .filter(|name| {
+                        self.lower_line(&module_name, exprs, &[name.clone()], false)
+                            .is_some()
+                    })
+                    .collect();
+
+                match self.lower_line(&module_name, exprs, &new_names, true) {
+                    Some(eir_module) => (eir_module, new_names),
+                    None => return Err(anyhow!("expressions could not be compiled")),
+                }
+            }
+        };
+
+        VM.modules
+            .write()
+            .unwrap()
+            .register_erlang_module(eir_module);
+
+        let module = Atom::try_from_str(&module_name).unwrap();
+        let function = Atom::try_from_str("eval").unwrap();
+        let arguments: Vec<Term> = self.bindings.iter().map(|(_, value)| *value).collect();
+
+        let ProcessResult { heap, result } =
+            call_run_erlang(self.init.clone(), module, function, &arguments);
+        self.fragments.push(heap);
+
+        match result {
+            Ok(value_bindings) => {
+                let tuple: Boxed<Tuple> = value_bindings.try_into().unwrap();
+                let elements = tuple.elements();
+                let value = elements[0];
+                let new_values: Boxed<Tuple> = elements[1].try_into().unwrap();
+
+                for (name, value) in new_names.into_iter().zip(new_values.iter()) {
+                    self.bindings.push((name, *value));
+                }
+
+                Ok(value.to_string())
+            }
+            Err((class, reason, _stacktrace)) => Ok(format!("** exception {}: {}", class, reason)),
+        }
+    }
+
+    /// Lowers `exprs` in a module named `module_name` with `eval/N`, where `N` is the number of
+    /// bindings, which returns `{Value, {NewVariable...}}` for `new_names`.  Errors are only
+    /// printed when `print_errors`, so that candidate bindings can be checked quietly.
+    fn lower_line(
+        &self,
+        module_name: &str,
+        exprs: &str,
+        new_names: &[String],
+        print_errors: bool,
+    ) -> Option<Module> {
+        let parameters: Vec<&str> = self
+            .bindings
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        let source = format!(
+            "-module({}).\n\
+             -export([eval/{}]).\n\
+             eval({}) ->\n\
+             LumenShellValue__ = begin\n{}\nend,\n\
+             {{LumenShellValue__, {{{}}}}}.\n",
+            module_name,
+            parameters.len(),
+            parameters.join(", "),
+            exprs,
+            new_names.join(", ")
+        );
+
+        let parser = Parser::new(ParseConfig::default());
+        let mut errors = Errors::new();
+        let codemap: ArcCodemap = Default::default();
+
+        let parsed: ErlAstModule =
+            match parser.parse_string::<&str, ErlAstModule>(&mut errors, &codemap, &source) {
+                Ok(parsed) => parsed,
+                Err(_) => {
+                    if print_errors {
+                        errors.print(&codemap);
+                    }
+
+                    return None;
+                }
+            };
+
+        let mut errors = Errors::new();
+
+        match lower_module(&mut errors, &codemap, &parsed) {
+            Ok(eir_module) => Some(run_passes(eir_module)),
+            Err(()) => {
+                if print_errors {
+                    errors.print(&codemap);
+                }
+
+                None
+            }
+        }
+    }
+}
+
+impl Drop for Shell {
+    fn drop(&mut self) {
+        for fragment in self.fragments.drain(..) {
+            unsafe { ptr::drop_in_place(fragment.as_ptr()) };
+        }
+    }
+}
+
+/// Parses, lowers and registers the module in the Erlang source file at `path`, returning its
+/// name.
+pub fn load_file<P: AsRef<Path>>(path: P) -> anyhow::Result<String> {
+    let path = path.as_ref();
+    let parser = Parser::new(ParseConfig::default());
+    let mut errors = Errors::new();
+    let codemap: ArcCodemap = Default::default();
+
+    let parsed: ErlAstModule =
+        match parser.parse_file::<_, ErlAstModule>(&mut errors, &codemap, path) {
+            Ok(parsed) => parsed,
+            Err(_) => {
+                errors.print(&codemap);
+
+                return Err(anyhow!("{} could not be parsed", path.display()));
+            }
+        };
+
+    let mut errors = Errors::new();
+    let eir_module = match lower_module(&mut errors, &codemap, &parsed) {
+        Ok(eir_module) => run_passes(eir_module),
+        Err(()) => {
+            errors.print(&codemap);
+
+            return Err(anyhow!("{} could not be compiled", path.display()));
+        }
+    };
+    let name = eir_module.name().as_str().to_string();
+
+    VM.modules
+        .write()
+        .unwrap()
+        .register_erlang_module(eir_module);
+
+    Ok(name)
+}
+
+// Private
+
+fn run_passes(mut eir_module: Module) -> Module {
+    for fun_def in eir_module.function_iter() {
+        let fun = fun_def.function();
+        fun.graph_validate_global();
+    }
+
+    let mut pass_manager = PassManager::default();
+    pass_manager.run(&mut eir_module);
+
+    eir_module
+}
+
+/// The argument of `command` if it is a call to the shell command `name` with one argument.
+fn shell_command_argument<'a>(command: &'a str, name: &str) -> Option<&'a str> {
+    if !command.starts_with(name) {
+        return None;
+    }
+
+    let call = command[name.len()..].trim_start();
+
+    if !(call.starts_with('(') && call.ends_with(')')) {
+        return None;
+    }
+
+    let argument = call[1..call.len() - 1].trim();
+
+    if argument.is_empty() {
+        None
+    } else {
+        Some(argument)
+    }
+}
+
+/// The names of the variables in `exprs` in order of first appearance, skipping `_` and anything
+/// in strings, quoted atoms, character literals and comments.
+fn variables(exprs: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut chars = exprs.chars().peekable();
+    let mut previous = ' ';
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                let quote = c;
+
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        c if c == quote => break,
+                        _ => (),
+                    }
+                }
+            }
+            '$' => {
+                if let Some('\\') = chars.next() {
+                    chars.next();
+                }
+            }
+            '%' => {
+                while let Some(c) = chars.next() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut name = c.to_string();
+
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || c == '_' || c == '@' {
+                        name.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+
+                // `?MACRO` is not a variable
+                let is_variable = (c.is_ascii_uppercase() || c == '_') && previous != '?';
+
+                if is_variable && name != "_" && !names.contains(&name) {
+                    names.push(name);
+                }
+
+                previous = 'a';
+
+                continue;
+            }
+            _ => (),
+        }
+
+        previous = c;
+    }
+
+    names
+}
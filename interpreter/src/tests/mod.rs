@@ -143,6 +143,83 @@ a() -> 1 + a.
     }
 }
 
+#[test]
+fn map_update_without_key_errors_badkey() {
+    &*VM;
+
+    let arc_scheduler = Scheduler::current();
+    let init_arc_process = arc_scheduler.spawn_init(0).unwrap();
+
+    liblumen_otp::erlang::apply_3::export();
+
+    let module = Atom::try_from_str("map_update_badkey_test").unwrap();
+    let function = Atom::try_from_str("run").unwrap();
+
+    let eir_mod = compile(
+        "
+-module(map_update_badkey_test).
+
+run(M) -> M#{b := 2}.
+",
+    );
+
+    VM.modules.write().unwrap().register_erlang_module(eir_mod);
+
+    let map = init_arc_process
+        .map_from_slice(&[(Atom::str_to_term("a"), init_arc_process.integer(1).unwrap())])
+        .unwrap();
+    let res =
+        crate::call_result::call_run_erlang(init_arc_process.clone(), module, function, &[map]);
+
+    assert!(res.result.is_err());
+    if let Err((typ, reason, _trace)) = res.result {
+        let expected_reason = init_arc_process
+            .tuple_from_slice(&[Atom::str_to_term("badkey"), Atom::str_to_term("b")])
+            .unwrap();
+
+        assert!(typ == Atom::str_to_term("error"));
+        assert!(reason == expected_reason);
+    }
+}
+
+#[test]
+fn map_update_without_key_is_caught_as_badkey() {
+    &*VM;
+
+    let arc_scheduler = Scheduler::current();
+    let init_arc_process = arc_scheduler.spawn_init(0).unwrap();
+
+    liblumen_otp::erlang::apply_3::export();
+
+    let module = Atom::try_from_str("map_update_catch_badkey_test").unwrap();
+    let function = Atom::try_from_str("run").unwrap();
+
+    let eir_mod = compile(
+        "
+-module(map_update_catch_badkey_test).
+
+run(M) ->
+    try M#{b := 2}
+    catch
+        error:Reason -> Reason
+    end.
+",
+    );
+
+    VM.modules.write().unwrap().register_erlang_module(eir_mod);
+
+    let map = init_arc_process
+        .map_from_slice(&[(Atom::str_to_term("a"), init_arc_process.integer(1).unwrap())])
+        .unwrap();
+    let res =
+        crate::call_result::call_run_erlang(init_arc_process.clone(), module, function, &[map]);
+
+    let expected_reason = init_arc_process
+        .tuple_from_slice(&[Atom::str_to_term("badkey"), Atom::str_to_term("b")])
+        .unwrap();
+    assert!(res.result == Ok(expected_reason));
+}
+
 #[test]
 fn fib_gc() {
     &*VM;
@@ -303,3 +380,26 @@ run(N) -> this_proc(N, 0).
     println!("{:?}", res.result);
     //assert!(res.result == Ok(100));
 }
+
+#[test]
+fn shell_keeps_bindings_between_lines() {
+    let mut shell = crate::shell::Shell::new();
+
+    assert_eq!(shell.eval_command("X = 1 + 2").unwrap().unwrap(), "3");
+    assert_eq!(
+        shell
+            .eval_command("Double = fun(Y) -> Y * 2 end, Double(X)")
+            .unwrap()
+            .unwrap(),
+        "6"
+    );
+    // `Y` is only bound in the `fun`, so it is not kept
+    assert!(shell
+        .eval_command("b()")
+        .unwrap()
+        .unwrap()
+        .starts_with("X = 3\nDouble = "));
+    assert_eq!(shell.eval_command("f(X)").unwrap().unwrap(), "ok");
+    assert_eq!(shell.eval_command("X = 4").unwrap().unwrap(), "4");
+    assert_eq!(shell.eval_command("q()").unwrap(), None);
+}
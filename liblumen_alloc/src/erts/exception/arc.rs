@@ -4,6 +4,7 @@ use core::ops::Deref;
 use alloc::sync::Arc;
 
 use crate::erts::term::pid::InvalidPidError;
+use crate::erts::term::port::InvalidPortError;
 use crate::erts::term::prelude::{TermDecodingError, TermEncodingError, TypeError};
 
 #[derive(Clone)]
@@ -59,6 +60,11 @@ impl From<InvalidPidError> for ArcError {
         Self::from_err(err)
     }
 }
+impl From<InvalidPortError> for ArcError {
+    fn from(err: InvalidPortError) -> Self {
+        Self::from_err(err)
+    }
+}
 impl From<TermDecodingError> for ArcError {
     fn from(err: TermDecodingError) -> Self {
         Self::from_err(err)
@@ -1,6 +1,7 @@
 use thiserror::Error;
 
 use crate::erts::term::pid::InvalidPidError;
+use crate::erts::term::port::InvalidPortError;
 
 use super::{Alloc, ArcError, SystemException};
 use crate::erts::term::prelude::{TermDecodingError, TermEncodingError};
@@ -29,6 +30,11 @@ impl From<InvalidPidError> for InternalException {
         Self::Internal(err.into())
     }
 }
+impl From<InvalidPortError> for InternalException {
+    fn from(err: InvalidPortError) -> Self {
+        Self::Internal(err.into())
+    }
+}
 impl From<TermDecodingError> for InternalException {
     fn from(err: TermDecodingError) -> Self {
         Self::Internal(err.into())
@@ -5,6 +5,8 @@ mod flags;
 pub mod gc;
 mod heap;
 mod mailbox;
+pub mod max_heap_size;
+pub mod message_queue_data;
 mod monitor;
 pub mod priority;
 
@@ -18,11 +20,11 @@ use core::ptr::{self, NonNull};
 use core::str::Chars;
 use core::sync::atomic::{AtomicU16, AtomicU64, AtomicUsize, Ordering};
 
+use ::alloc::collections::VecDeque;
 use ::alloc::sync::Arc;
 
 use anyhow::*;
 use dashmap::{DashMap, DashSet};
-use hashbrown::HashMap;
 use intrusive_collections::{LinkedList, UnsafeRef};
 
 use liblumen_core::alloc::Layout;
@@ -48,6 +50,8 @@ use self::gc::{GcError, RootSet};
 pub use self::flags::*;
 pub use self::heap::ProcessHeap;
 pub use self::mailbox::*;
+pub use self::max_heap_size::MaxHeapSize;
+pub use self::message_queue_data::MessageQueueData;
 pub use self::monitor::Monitor;
 pub use self::priority::Priority;
 
@@ -89,15 +93,15 @@ pub struct Process {
     /// ID of the scheduler that is running the process
     scheduler_id: Mutex<Option<scheduler::ID>>,
     /// The priority of the process in `scheduler`.
-    pub priority: Priority,
+    priority: RwLock<Priority>,
     /// Process flags, e.g. `Process.flag/1`
     flags: AtomicProcessFlags,
     /// Minimum size of the heap that this process will start with
-    min_heap_size: usize,
+    min_heap_size: AtomicUsize,
     /// The maximum size of the heap allowed for this process
-    max_heap_size: usize,
+    max_heap_size: RwLock<MaxHeapSize>,
     /// Minimum virtual heap size for this process
-    min_vheap_size: usize,
+    min_vheap_size: AtomicUsize,
     /// The percentage of used to unused space at which a collection is triggered
     gc_threshold: f64,
     /// The maximum number of minor collections before a full sweep occurs
@@ -107,6 +111,12 @@ pub struct Process {
     off_heap_size: AtomicUsize,
     /// Process dictionary
     dictionary: DashMap<Term, Term>,
+    /// The module called when an undefined function is called by this process
+    error_handler: RwLock<Atom>,
+    /// The maximum number of calls saved in `saved_calls`.  `0` disables saving calls.
+    save_calls: AtomicUsize,
+    /// The most recent calls made by this process, most recent first.
+    saved_calls: Mutex<VecDeque<Arc<ModuleFunctionArity>>>,
     /// The `pid` of the process that `spawn`ed this process.
     parent_pid: Option<Pid>,
     /// The `pid` of the process that does I/O on this process's behalf.
@@ -159,14 +169,17 @@ impl Process {
 
         Self {
             flags: AtomicProcessFlags::new(ProcessFlags::Default),
-            min_heap_size: heap_size,
-            max_heap_size: 0,
-            min_vheap_size: 0,
+            min_heap_size: AtomicUsize::new(heap_size),
+            max_heap_size: Default::default(),
+            min_vheap_size: Default::default(),
             gc_threshold: 0.75,
             max_gen_gcs: 65535,
             off_heap,
             off_heap_size: AtomicUsize::new(0),
             dictionary: Default::default(),
+            error_handler: RwLock::new(Atom::from_str("error_handler")),
+            save_calls: Default::default(),
+            saved_calls: Default::default(),
             pid,
             status: Default::default(),
             mailbox: Default::default(),
@@ -175,7 +188,7 @@ impl Process {
             registers: Default::default(),
             code_stack: Default::default(),
             scheduler_id: Mutex::new(None),
-            priority,
+            priority: RwLock::new(priority),
             parent_pid,
             group_leader_pid: Mutex::new(group_leader_pid),
             initial_module_function_arity,
@@ -216,6 +229,16 @@ impl Process {
         *self.scheduler_id.lock() = Some(scheduler_id);
     }
 
+    pub fn priority(&self) -> Priority {
+        *self.priority.read()
+    }
+
+    /// Changes the priority used the next time this process is enqueued in a run queue.  Returns
+    /// the old priority.
+    pub fn set_priority(&self, priority: Priority) -> Priority {
+        mem::replace(&mut *self.priority.write(), priority)
+    }
+
     // Flags
 
     pub fn are_flags_set(&self, flags: ProcessFlags) -> bool {
@@ -250,6 +273,74 @@ impl Process {
         self.are_flags_set(ProcessFlags::TrapExit)
     }
 
+    pub fn message_queue_data(&self) -> MessageQueueData {
+        if self.are_flags_set(ProcessFlags::OffHeapMessageQueue) {
+            MessageQueueData::OffHeap
+        } else {
+            MessageQueueData::OnHeap
+        }
+    }
+
+    /// Returns the old `MessageQueueData`.  Messages already in the mailbox stay where they are.
+    pub fn set_message_queue_data(&self, message_queue_data: MessageQueueData) -> MessageQueueData {
+        let flag = ProcessFlags::OffHeapMessageQueue;
+
+        let old_flags = match message_queue_data {
+            MessageQueueData::OffHeap => self.set_flags(flag),
+            MessageQueueData::OnHeap => self.clear_flags(flag),
+        };
+
+        if old_flags.are_set(flag) {
+            MessageQueueData::OffHeap
+        } else {
+            MessageQueueData::OnHeap
+        }
+    }
+
+    pub fn error_handler(&self) -> Atom {
+        *self.error_handler.read()
+    }
+
+    /// Returns the old error handler module.
+    pub fn set_error_handler(&self, module: Atom) -> Atom {
+        mem::replace(&mut *self.error_handler.write(), module)
+    }
+
+    /// The maximum number of calls saved by `save_call`
+    pub fn save_calls(&self) -> usize {
+        self.save_calls.load(Ordering::Acquire)
+    }
+
+    /// Sets the maximum number of calls saved by `save_call`, dropping any saved calls if
+    /// disabled.  Returns the old maximum.
+    pub fn set_save_calls(&self, save_calls: usize) -> usize {
+        let old_save_calls = self.save_calls.swap(save_calls, Ordering::AcqRel);
+
+        let mut saved_calls = self.saved_calls.lock();
+        saved_calls.truncate(save_calls);
+
+        old_save_calls
+    }
+
+    /// The most recent calls, most recent first, if saving calls is enabled.
+    pub fn saved_calls(&self) -> Option<Vec<Arc<ModuleFunctionArity>>> {
+        if 0 < self.save_calls() {
+            Some(self.saved_calls.lock().iter().cloned().collect())
+        } else {
+            None
+        }
+    }
+
+    fn save_call(&self, module_function_arity: Arc<ModuleFunctionArity>) {
+        let save_calls = self.save_calls();
+
+        if 0 < save_calls {
+            let mut saved_calls = self.saved_calls.lock();
+            saved_calls.truncate(save_calls - 1);
+            saved_calls.push_front(module_function_arity);
+        }
+    }
+
     // Alloc
 
     /// Acquires exclusive access to the process heap, blocking the current thread until it is able
@@ -497,7 +588,12 @@ impl Process {
 
     /// Returns `true` if the process should stop waiting and be rescheduled as runnable.
     pub fn send_from_other(&self, data: Term) -> AllocResult<bool> {
-        match self.heap.try_lock() {
+        let option_heap = match self.message_queue_data() {
+            MessageQueueData::OnHeap => self.heap.try_lock(),
+            MessageQueueData::OffHeap => None,
+        };
+
+        match option_heap {
             Some(ref mut destination_heap) => match data.clone_to_heap(destination_heap) {
                 Ok(destination_data) => {
                     self.send_message(Message::Process(message::Process {
@@ -663,12 +759,6 @@ impl Process {
         }
     }
 
-    pub fn map_from_hash_map(&self, hash_map: HashMap<Term, Term>) -> AllocResult<Term> {
-        self.acquire_heap()
-            .map_from_hash_map(hash_map)
-            .map(|map| map.into())
-    }
-
     pub fn map_from_slice(&self, slice: &[(Term, Term)]) -> AllocResult<Term> {
         self.acquire_heap()
             .map_from_slice(slice)
@@ -805,6 +895,15 @@ impl Process {
         }
     }
 
+    /// Returns all key/value pairs from the process dictionary without allocating them on the
+    /// heap, such as when they need to be copied to another process.
+    pub fn dictionary_entries(&self) -> Vec<(Term, Term)> {
+        self.dictionary
+            .iter()
+            .map(|entry| (*entry.key(), *entry.value()))
+            .collect()
+    }
+
     /// Returns list of all keys from the process dictionary.
     pub fn get_keys(&self) -> AllocResult<Term> {
         let mut heap = self.heap.lock();
@@ -907,8 +1006,68 @@ impl Process {
         heap.should_collect(self.gc_threshold)
     }
 
+    /// The size of the young generation of the heap, in words
+    pub fn heap_size(&self) -> usize {
+        self.heap.lock().heap_size()
+    }
+
+    /// The size of all generations of the heap, the stack and any heap fragments, in words
+    pub fn total_heap_size(&self) -> usize {
+        let heap = self.heap.lock();
+
+        heap.total_heap_size() + heap.stack_size() + self.off_heap_size()
+    }
+
+    /// The size of the stack, in words
+    pub fn stack_size(&self) -> usize {
+        self.heap.lock().stack_size()
+    }
+
+    /// The minimum size of the heap, in words
+    pub fn min_heap_size(&self) -> usize {
+        self.min_heap_size.load(Ordering::Acquire)
+    }
+
+    /// Sets the minimum size of the heap, in words, used the next time the heap is resized.
+    /// Returns the old minimum.
+    pub fn set_min_heap_size(&self, min_heap_size: usize) -> usize {
+        self.min_heap_size.swap(min_heap_size, Ordering::AcqRel)
+    }
+
+    /// The maximum size of the heap
+    pub fn max_heap_size(&self) -> MaxHeapSize {
+        *self.max_heap_size.read()
+    }
+
+    /// Sets the maximum size of the heap, which is enforced at the next garbage collection.
+    /// Returns the old maximum.
+    pub fn set_max_heap_size(&self, max_heap_size: MaxHeapSize) -> MaxHeapSize {
+        mem::replace(&mut *self.max_heap_size.write(), max_heap_size)
+    }
+
+    /// The minimum size of the virtual binary heap, in words
+    pub fn min_vheap_size(&self) -> usize {
+        self.min_vheap_size.load(Ordering::Acquire)
+    }
+
+    /// Sets the minimum size of the virtual binary heap, in words.  Returns the old minimum.
+    pub fn set_min_vheap_size(&self, min_vheap_size: usize) -> usize {
+        self.min_vheap_size.swap(min_vheap_size, Ordering::AcqRel)
+    }
+
+    /// The maximum number of minor collections before a full sweep occurs
+    pub fn max_gen_gcs(&self) -> usize {
+        self.max_gen_gcs
+    }
+
+    /// The number of minor collections since the last full sweep
+    pub fn minor_gcs(&self) -> usize {
+        self.heap.lock().gen_gc_count()
+    }
+
+    /// The size of all heap fragments, in words
     #[inline(always)]
-    fn off_heap_size(&self) -> usize {
+    pub fn off_heap_size(&self) -> usize {
         self.off_heap_size.load(Ordering::Acquire)
     }
 
@@ -1136,10 +1295,12 @@ impl Process {
     }
 
     pub fn push_frame(&self, frame: Frame) {
+        self.save_call(frame.module_function_arity());
         self.code_stack.lock().push(frame)
     }
 
     pub fn replace_frame(&self, frame: Frame) {
+        self.save_call(frame.module_function_arity());
         let mut locked_code_stack = self.code_stack.lock();
 
         // unwrap to ensure there is a frame to replace
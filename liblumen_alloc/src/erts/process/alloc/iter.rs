@@ -85,6 +85,11 @@ where
                         self.pos = unsafe { pos.add(1) };
                         // Shift to first element
                         return Some(term);
+                    } else if term.is_map() {
+                        // Map header is word-sized, followed by the length and the root
+                        // of its entries, which are walked like tuple elements
+                        self.pos = unsafe { pos.add(1) };
+                        return Some(term);
                     } else if term.is_function() {
                         let closure_box = unsafe { Closure::from_raw_term(pos) };
                         let closure = closure_box.as_ref();
@@ -8,8 +8,6 @@ use core::str::Chars;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 
-use hashbrown::HashMap;
-
 use liblumen_core::util::reference::bytes;
 use liblumen_core::util::reference::str::inherit_lifetime as inherit_str_lifetime;
 
@@ -262,23 +260,8 @@ pub trait TermAlloc: Heap {
     }
 
     /// Constructs a map and associated with the given process.
-    fn map_from_hash_map(&mut self, hash_map: HashMap<Term, Term>) -> AllocResult<Boxed<Map>>
-    where
-        Self: Sized,
-    {
-        let boxed = Map::from_hash_map(hash_map).clone_to_heap(self)?;
-        let ptr: Boxed<Map> = boxed.dyn_cast();
-        Ok(ptr)
-    }
-
-    /// Constructs a map and associated with the given process.
-    fn map_from_slice(&mut self, slice: &[(Term, Term)]) -> AllocResult<Boxed<Map>>
-    where
-        Self: Sized,
-    {
-        let boxed = Map::from_slice(slice).clone_to_heap(self)?;
-        let ptr: Boxed<Map> = boxed.dyn_cast();
-        Ok(ptr)
+    fn map_from_slice(&mut self, slice: &[(Term, Term)]) -> AllocResult<Boxed<Map>> {
+        Map::from_slice(self, slice)
     }
 
     #[inline]
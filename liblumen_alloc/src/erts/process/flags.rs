@@ -26,6 +26,9 @@ impl ProcessFlags {
     /// This flag indicates the processes linked to this process should send exit messages instead
     /// of causing this process to exit when they exit
     pub const TrapExit: Self = Self(1 << 6);
+    /// This flag indicates that messages sent to this process should always be stored in heap
+    /// fragments instead of being copied directly to the process heap
+    pub const OffHeapMessageQueue: Self = Self(1 << 7);
 
     pub fn are_set(&self, flags: ProcessFlags) -> bool {
         (*self & flags) == flags
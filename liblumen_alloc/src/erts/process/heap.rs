@@ -1,7 +1,7 @@
 use core::alloc::Layout;
 use core::ptr::NonNull;
 
-use log::trace;
+use log::{error, trace};
 
 use liblumen_core::util::pointer::distance_absolute;
 
@@ -40,6 +40,18 @@ impl ProcessHeap {
         self.heap.should_collect(gc_threshold)
     }
 
+    /// Returns the size, in words, of the young and old generations combined
+    #[inline]
+    pub fn total_heap_size(&self) -> usize {
+        self.heap.young_generation().heap_size() + self.heap.old_generation().heap_size()
+    }
+
+    /// Returns the number of minor collections since the last full sweep
+    #[inline]
+    pub fn gen_gc_count(&self) -> usize {
+        self.gen_gc_count
+    }
+
     #[cfg(test)]
     pub(super) fn heap(&self) -> &SemispaceProcessHeap {
         &self.heap
@@ -118,9 +130,7 @@ impl ProcessHeap {
 
         // Verify that our projected heap size is not going to blow the max heap size, if set
         // NOTE: When this happens, we will be left with no choice but to kill the process
-        if process.max_heap_size > 0 && process.max_heap_size < new_heap_size {
-            return Err(GcError::MaxHeapSizeExceeded);
-        }
+        Self::check_max_heap_size(process, new_heap_size)?;
 
         // Unset heap_grow and need_fullsweep flags, because we are doing both
         process
@@ -198,13 +208,13 @@ impl ProcessHeap {
 
         // Check if the needed space consumes less than 25% of the new heap,
         // and if so, shrink the new heap immediately to free the unused space
-        if total_size > needed_after * 4 && process.min_heap_size < total_size {
+        if total_size > needed_after * 4 && process.min_heap_size() < total_size {
             // Shrink to double our estimated need
             let mut estimate = needed_after * 2;
             // If our estimated need is too low, round up to the min heap size;
             // otherwise, calculate the next heap size bucket our need falls in
-            if estimate < process.min_heap_size {
-                estimate = process.min_heap_size;
+            if estimate < process.min_heap_size() {
+                estimate = process.min_heap_size();
             } else {
                 estimate = alloc::next_heap_size(estimate);
             }
@@ -226,6 +236,35 @@ impl ProcessHeap {
         }
     }
 
+    /// Enforces the `max_heap_size` process flag against the projected `heap_size`, in words.
+    ///
+    /// When the limit is exceeded an error report is logged if `error_logger` is set.  The
+    /// collection is only aborted with `GcError::MaxHeapSizeExceeded`, so that the caller kills
+    /// the process, if `kill` is set; otherwise the heap is allowed to grow past the limit.
+    fn check_max_heap_size(process: &Process, heap_size: usize) -> Result<(), GcError> {
+        let max_heap_size = process.max_heap_size();
+
+        if max_heap_size.is_exceeded_by(heap_size) {
+            if max_heap_size.error_logger {
+                error!(
+                    "Process: {}\nContext: maximum heap size reached\nMax Heap Size: {}\n\
+                     Total Heap Size: {}\nKill: {}\nError Logger: {}",
+                    process,
+                    max_heap_size.size,
+                    heap_size,
+                    max_heap_size.kill,
+                    max_heap_size.error_logger
+                );
+            }
+
+            if max_heap_size.kill {
+                return Err(GcError::MaxHeapSizeExceeded);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Handles the specific details required to initialize and execute a minor garbage collection
     fn collect_minor(
         &mut self,
@@ -248,7 +287,8 @@ impl ProcessHeap {
         // the max heap size, if one was configured.
         //
         // If a max heap size is set, make sure we're not going to exceed it
-        if process.max_heap_size > 0 {
+        let max_heap_size = process.max_heap_size();
+        if max_heap_size.size > 0 {
             // First, check if we have exceeded the max heap size
             let mut heap_size = size_before;
             // In this estimate, our stack size includes unused area between stack and heap
@@ -268,9 +308,10 @@ impl ProcessHeap {
             let baseline_size = stack_size + size_before + needed;
             heap_size += alloc::next_heap_size(baseline_size);
 
-            // When this error type is returned, a full sweep will be triggered
-            if heap_size > process.max_heap_size {
-                return Err(GcError::MaxHeapSizeExceeded);
+            // This is a worst case estimate, so rather than enforcing the limit here, a full
+            // sweep is triggered, which checks the limit against the actual size of live data
+            if max_heap_size.is_exceeded_by(heap_size) {
+                return Err(GcError::FullsweepRequired);
             }
         }
 
@@ -356,8 +397,8 @@ impl ProcessHeap {
 
             // If the new estimate is less than the min heap size, then round up;
             // otherwise, round the estimate up to the nearest heap size bucket
-            if estimate < process.min_heap_size {
-                estimate = process.min_heap_size;
+            if estimate < process.min_heap_size() {
+                estimate = process.min_heap_size();
             } else {
                 estimate = alloc::next_heap_size(estimate);
             }
@@ -0,0 +1,73 @@
+use core::convert::{TryFrom, TryInto};
+
+use anyhow::Context;
+
+use crate::erts::term::prelude::*;
+
+/// The `max_heap_size` process flag, which limits the total size of the heap, in words.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MaxHeapSize {
+    /// The maximum total heap size, in words.  `0` disables the limit.
+    pub size: usize,
+    /// Whether the process is sent an untrappable `kill` exit when `size` is exceeded
+    pub kill: bool,
+    /// Whether an error report is logged when `size` is exceeded
+    pub error_logger: bool,
+}
+
+impl MaxHeapSize {
+    pub fn is_exceeded_by(&self, heap_size: usize) -> bool {
+        0 < self.size && self.size < heap_size
+    }
+}
+
+impl Default for MaxHeapSize {
+    fn default() -> MaxHeapSize {
+        MaxHeapSize {
+            size: 0,
+            kill: true,
+            error_logger: true,
+        }
+    }
+}
+
+const SUPPORTED_MAX_HEAP_SIZE_CONTEXT: &str = "max_heap_size must be a non-negative integer or a \
+                                               map with size, kill, and error_logger keys";
+
+impl TryFrom<Term> for MaxHeapSize {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let mut max_heap_size: MaxHeapSize = Default::default();
+
+        match term.decode().unwrap() {
+            TypedTerm::Map(map) => {
+                for (key, value) in map.iter() {
+                    let key_atom: Atom = key.try_into().context(SUPPORTED_MAX_HEAP_SIZE_CONTEXT)?;
+
+                    match key_atom.name() {
+                        "size" => {
+                            max_heap_size.size = value.try_into().context("size")?;
+                        }
+                        "kill" => {
+                            max_heap_size.kill = value.try_into().context("kill")?;
+                        }
+                        "error_logger" => {
+                            max_heap_size.error_logger =
+                                value.try_into().context("error_logger")?;
+                        }
+                        name => {
+                            return Err(TryAtomFromTermError(name))
+                                .context(SUPPORTED_MAX_HEAP_SIZE_CONTEXT)
+                        }
+                    }
+                }
+            }
+            _ => {
+                max_heap_size.size = term.try_into().context(SUPPORTED_MAX_HEAP_SIZE_CONTEXT)?;
+            }
+        }
+
+        Ok(max_heap_size)
+    }
+}
@@ -1,12 +1,16 @@
-use std::convert::{TryFrom, TryInto};
+use core::convert::{TryFrom, TryInto};
 
 use anyhow::Context;
 
-use liblumen_alloc::erts::term::prelude::*;
+use crate::erts::term::prelude::*;
 
-#[derive(Clone, Copy, Debug)]
+/// Where messages sent to a process are stored until they are received
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum MessageQueueData {
+    /// Messages are copied directly onto the receiving process's heap when its heap is not
+    /// locked
     OnHeap,
+    /// Messages are always stored in heap fragments until they are received
     OffHeap,
 }
 
@@ -32,3 +36,14 @@ impl TryFrom<Term> for MessageQueueData {
         }
     }
 }
+
+impl From<MessageQueueData> for Atom {
+    fn from(message_queue_data: MessageQueueData) -> Self {
+        let name = match message_queue_data {
+            MessageQueueData::OnHeap => "on_heap",
+            MessageQueueData::OffHeap => "off_heap",
+        };
+
+        Atom::from_str(name)
+    }
+}
@@ -11,7 +11,7 @@ mod integer;
 pub mod list;
 mod map;
 pub(super) mod pid;
-mod port;
+pub(super) mod port;
 pub(super) mod reference;
 mod release;
 mod resource;
@@ -47,7 +47,7 @@ pub mod prelude {
     };
     pub use super::map::Map;
     pub use super::pid::{AnyPid, ExternalPid, InvalidPidError, Pid};
-    pub use super::port::{ExternalPort, Port};
+    pub use super::port::{ExternalPort, InvalidPortError, Port};
     pub use super::reference::{ExternalReference, Reference, ReferenceNumber};
     pub use super::resource::Resource;
     pub use super::tuple::Tuple;
@@ -1,4 +1,3 @@
-use core::alloc::Layout;
 use core::convert::TryInto;
 use core::fmt::{self, Debug};
 use core::marker::PhantomData;
@@ -9,7 +8,6 @@ use alloc::sync::Arc;
 
 use std::backtrace::Backtrace;
 
-use hashbrown::HashMap;
 use thiserror::Error;
 
 use liblumen_term::{Encoding as TermEncoding, Tag};
@@ -243,20 +241,6 @@ impl<T: ?Sized> Debug for Header<T> {
     }
 }
 const_assert_eq!(mem::size_of::<Header<usize>>(), mem::size_of::<usize>());
-impl Header<Map> {
-    pub fn from_map(map: &HashMap<Term, Term>) -> Self {
-        // NOTE: This size only accounts for the HashMap header, not the values
-        let layout = Layout::for_value(map);
-        let map_size = layout.size();
-        let arity = Self::static_arity() + Self::to_word_size(map_size);
-        let value = Term::encode_header(arity.try_into().unwrap(), Term::HEADER_MAP);
-        Self {
-            value,
-            _phantom: PhantomData,
-        }
-    }
-}
-
 /// This is a marker trait for dynamically-sized types which have headers
 pub trait DynamicHeader {
     /// The header tag associated with this type
@@ -3,50 +3,129 @@ use core::cmp;
 use core::convert::{TryFrom, TryInto};
 use core::fmt::{self, Debug, Display, Write};
 use core::hash::{Hash, Hasher};
-use core::mem;
-use core::ptr;
+use core::marker::PhantomData;
 
 use alloc::vec::Vec;
 
 use anyhow::*;
-use hashbrown::HashMap;
 
+use crate::borrow::CloneToProcess;
+use crate::erts;
 use crate::erts::exception::{AllocResult, InternalResult};
 use crate::erts::process::alloc::TermAlloc;
 
 use super::prelude::*;
 
-#[derive(Clone)]
+/// Maps with at most this many entries are stored as a flat tuple of keys followed by values,
+/// sorted by key.  Larger maps are stored as a hash array mapped trie (HAMT), which is the same
+/// split C-BEAM makes between flatmaps and hashmaps.
+pub const MAX_FLAT_LEN: usize = 32;
+
+/// Number of hash bits consumed at each level of the HAMT, which makes each node at most 16-way.
+/// Bitmaps of 16 bits fit in a small integer on every architecture.
+const BITS_PER_LEVEL: u32 = 4;
+const LEVEL_MASK: u64 = (1 << BITS_PER_LEVEL) - 1;
+const HASH_BITS: u32 = 64;
+
+/// Represents a map term in memory.
+///
+/// A map is a fixed-size header, followed by its length and the root of its entries.  All of the
+/// entries are allocated on the same heap as the map itself as ordinary terms, so the garbage
+/// collector moves and walks them the same way it does tuples and cons cells:
+///
+/// * An empty map has a root of `[]`.
+/// * A flat map (`len <= MAX_FLAT_LEN`) has a root tuple of `{K1, ..., Kn, V1, ..., Vn}`, with the
+///   keys in ascending term order.
+/// * A hash map has a root node in the HAMT.  A node is either a bitmap node,
+///   `{Bitmap, Child1, ..., ChildN}`, where `Bitmap` is a small integer with one bit set for each
+///   occupied slot; or a collision node `{Leaf1, ..., LeafN}`, used only when all 64 bits of the
+///   key hashes are exhausted.  A child is either another node or a leaf, which is an improper
+///   cons cell of `[Key | Value]`.
+///
+/// Updates never modify an existing map.  Instead, only the path from the root to the changed
+/// entry is copied, and every other node is shared with the original map.
+#[derive(Clone, Copy)]
 #[repr(C)]
 pub struct Map {
     header: Header<Map>,
-    value: HashMap<Term, Term>,
+    len: Term,
+    root: Term,
 }
+impl_static_header!(Map, Term::HEADER_MAP);
 
 impl Map {
-    pub(in crate::erts) fn from_hash_map(value: HashMap<Term, Term>) -> Self {
-        Self {
-            header: Header::from_map(&value),
-            value,
-        }
+    /// Constructs an empty map on `heap`
+    pub fn new<A>(heap: &mut A) -> AllocResult<Boxed<Map>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        Self::from_root(heap, 0, Term::NIL)
     }
 
-    pub(in crate::erts) fn from_slice(slice: &[(Term, Term)]) -> Self {
-        let mut value: HashMap<Term, Term> = HashMap::with_capacity(slice.len());
+    /// Constructs a map from `slice` on `heap`.  If a key appears more than once, the last value
+    /// wins, the same as `maps:from_list/1`.
+    pub fn from_slice<A>(heap: &mut A, slice: &[(Term, Term)]) -> AllocResult<Boxed<Map>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        let mut hashed_entries: Vec<(u64, Term, Term)> = slice
+            .iter()
+            .map(|(key, value)| (hash_key(*key), *key, *value))
+            .collect();
+        // Stable, so that the last occurrence of a duplicate key is still last in its run
+        hashed_entries.sort_by_key(|(hash, _, _)| *hash);
 
-        for (entry_key, entry_value) in slice {
-            value.insert(*entry_key, *entry_value);
+        let mut unique_entries: Vec<(u64, Term, Term)> = Vec::with_capacity(hashed_entries.len());
+
+        for (hash, key, value) in hashed_entries {
+            let duplicate = unique_entries
+                .iter_mut()
+                .rev()
+                .take_while(|(unique_hash, _, _)| *unique_hash == hash)
+                .find(|(_, unique_key, _)| exactly_eq(*unique_key, key));
+
+            match duplicate {
+                Some(entry) => entry.2 = value,
+                None => unique_entries.push((hash, key, value)),
+            }
         }
 
-        Self::from_hash_map(value)
+        let len = unique_entries.len();
+
+        if len == 0 {
+            Self::new(heap)
+        } else if len <= MAX_FLAT_LEN {
+            let mut entries: Vec<(Term, Term)> = Vec::with_capacity(len);
+
+            for (_, key, value) in unique_entries {
+                entries.push((key.clone_to_heap(heap)?, value.clone_to_heap(heap)?));
+            }
+
+            entries.sort_by(|(key1, _), (key2, _)| key1.cmp(key2));
+
+            let root = flat_from_sorted_entries(heap, &entries)?;
+
+            Self::from_root(heap, len, root)
+        } else {
+            let mut leaves: Vec<(u64, Term)> = Vec::with_capacity(len);
+
+            for (hash, key, value) in unique_entries {
+                leaves.push((hash, leaf(heap, key, value)?));
+            }
+
+            let root = node_from_sorted_leaves(heap, &leaves, 0)?;
+
+            Self::from_root(heap, len, root)
+        }
     }
 
-    pub fn from_list(list: Term) -> InternalResult<HashMap<Term, Term>> {
+    /// Parses a proper list of 2-tuples into the entries for a map
+    pub fn from_list(list: Term) -> InternalResult<Vec<(Term, Term)>> {
         match list.decode()? {
-            TypedTerm::Nil => Ok(HashMap::new()),
+            TypedTerm::Nil => Ok(Vec::new()),
             TypedTerm::List(cons_ptr) => {
                 let cons = cons_ptr.as_ref();
-                let mut map = HashMap::new();
+                let mut entries = Vec::new();
 
                 for result_element in cons.into_iter() {
                     match result_element {
@@ -56,7 +135,7 @@ impl Map {
                             })?;
 
                             if tuple.len() == 2 {
-                                map.insert(tuple[0], tuple[1]);
+                                entries.push((tuple[0], tuple[1]));
                             } else {
                                 return Err(anyhow!(
                                     "element ({}) of list ({}) is not a 2-arity tuple",
@@ -74,7 +153,7 @@ impl Map {
                     }
                 }
 
-                Ok(map)
+                Ok(entries)
             }
             _ => Err(TypeError)
                 .context(format!("list ({}) is not a list", list))
@@ -83,132 +162,871 @@ impl Map {
     }
 
     pub fn get(&self, key: Term) -> Option<Term> {
-        self.value.get(&key).copied()
-    }
-
-    pub fn take(&self, key: Term) -> Option<(Term, HashMap<Term, Term>)> {
-        if self.is_key(key) {
-            let mut map = self.value.clone();
-            let value = map.remove(&key).unwrap();
+        match self.root() {
+            Root::Empty => None,
+            Root::Flat(flat) => {
+                let len = self.len();
+                let elements = flat.elements();
 
-            Some((value, map))
-        } else {
-            None
+                elements[..len]
+                    .iter()
+                    .position(|flat_key| exactly_eq(*flat_key, key))
+                    .map(|index| elements[len + index])
+            }
+            Root::Hash(node) => hash_get(node, hash_key(key), key),
         }
     }
 
     pub fn is_key(&self, key: Term) -> bool {
-        self.value.contains_key(&key)
+        self.get(key).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        let len: isize = match self.len.decode().unwrap() {
+            TypedTerm::SmallInteger(small_integer) => small_integer.into(),
+            _ => unreachable!("map length ({:?}) is not a small integer", self.len),
+        };
+
+        len as usize
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.root.is_nil()
+    }
+
+    /// Returns the keys in iteration order
     pub fn keys(&self) -> Vec<Term> {
-        self.value.keys().into_iter().copied().collect()
+        self.iter().map(|(key, _)| key).collect()
     }
 
+    /// Returns the values in iteration order
     pub fn values(&self) -> Vec<Term> {
-        self.value.values().into_iter().copied().collect()
+        self.iter().map(|(_, value)| value).collect()
     }
 
-    pub fn len(&self) -> usize {
-        self.value.len()
+    /// Iterates the entries of the map.  Flat maps are iterated in key order, while hash maps are
+    /// iterated in hash order, like C-BEAM.
+    pub fn iter(&self) -> Iter<'_> {
+        let kind = match self.root() {
+            Root::Empty => IterKind::Empty,
+            Root::Flat(flat) => IterKind::Flat {
+                flat,
+                index: 0,
+                len: self.len(),
+            },
+            Root::Hash(node) => {
+                let mut stack = Vec::new();
+                stack.push((node, node_first_child_index(node)));
+
+                IterKind::Hash { stack }
+            }
+        };
+
+        Iter {
+            kind,
+            _marker: PhantomData,
+        }
     }
 
-    pub fn remove(&self, key: Term) -> Option<HashMap<Term, Term>> {
-        if self.is_key(key) {
-            let mut map = self.value.clone();
-            map.remove(&key);
-            Some(map)
-        } else {
-            None
+    /// Returns a new map with `key` associated with `value`, allocated on `heap`.  The new map
+    /// shares all unchanged entries with `self`.
+    ///
+    /// Returns `None` if `key` is already associated with `value`, in which case the caller
+    /// should reuse the original map.
+    pub fn put<A>(&self, heap: &mut A, key: Term, value: Term) -> AllocResult<Option<Boxed<Map>>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        match self.root() {
+            Root::Empty => {
+                let heap_key = key.clone_to_heap(heap)?;
+                let heap_value = value.clone_to_heap(heap)?;
+                let root = flat_from_sorted_entries(heap, &[(heap_key, heap_value)])?;
+
+                Self::from_root(heap, 1, root).map(Some)
+            }
+            Root::Flat(flat) => self.flat_put(heap, flat, key, value),
+            Root::Hash(node) => {
+                let mut grew = false;
+
+                match hash_put(heap, node.into(), hash_key(key), 0, key, value, &mut grew)? {
+                    Some(root) => {
+                        let len = if grew { self.len() + 1 } else { self.len() };
+
+                        Self::from_root(heap, len, root).map(Some)
+                    }
+                    None => Ok(None),
+                }
+            }
+        }
+    }
+
+    /// Returns a new map with the existing `key` associated with `value`, allocated on `heap`.
+    ///
+    /// Returns `None` if `key` is not in the map.
+    pub fn update<A>(&self, heap: &mut A, key: Term, value: Term) -> AllocResult<Option<Boxed<Map>>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        match self.get(key) {
+            Some(current_value) if exactly_eq(current_value, value) => Ok(Some(self.as_boxed())),
+            Some(_) => self.put(heap, key, value),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns a new map without `key`, allocated on `heap`.
+    ///
+    /// Returns `None` if `key` is not in the map.
+    pub fn remove<A>(&self, heap: &mut A, key: Term) -> AllocResult<Option<Boxed<Map>>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        match self.root() {
+            Root::Empty => Ok(None),
+            Root::Flat(flat) => self.flat_remove(heap, flat, key),
+            Root::Hash(node) => match hash_remove(heap, node.into(), hash_key(key), 0, key)? {
+                Some(removed) => {
+                    let len = self.len() - 1;
+
+                    let root = match removed {
+                        Removed::Child(child) if len > MAX_FLAT_LEN => child,
+                        Removed::Child(child) => {
+                            let mut entries = Vec::with_capacity(len);
+                            push_entries(child, &mut entries);
+                            entries.sort_by(|(key1, _), (key2, _)| key1.cmp(key2));
+
+                            flat_from_sorted_entries(heap, &entries)?
+                        }
+                        Removed::Empty => Term::NIL,
+                    };
+
+                    Self::from_root(heap, len, root).map(Some)
+                }
+                None => Ok(None),
+            },
         }
     }
 
-    pub fn update(&self, key: Term, value: Term) -> Option<HashMap<Term, Term>> {
-        if self.is_key(key) {
-            let mut map = self.value.clone();
-            map.insert(key, value);
-            Some(map)
+    /// Returns the value for `key` and a new map without `key`, allocated on `heap`.
+    ///
+    /// Returns `None` if `key` is not in the map.
+    pub fn take<A>(&self, heap: &mut A, key: Term) -> AllocResult<Option<(Term, Boxed<Map>)>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        match self.get(key) {
+            Some(value) => {
+                let map = self.remove(heap, key)?.unwrap();
+
+                Ok(Some((value, map)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns a new map with the entries of `other` put into `self`, allocated on `heap`.  Values
+    /// in `other` win for keys in both maps.
+    pub fn merge<A>(&self, heap: &mut A, other: &Map) -> AllocResult<Boxed<Map>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        // Put the entries of the smaller map into the larger one to share as much as possible
+        if self.len() >= other.len() {
+            let mut merged = self.as_boxed();
+
+            for (key, value) in other.iter() {
+                if let Some(put) = merged.put(heap, key, value)? {
+                    merged = put;
+                }
+            }
+
+            Ok(merged)
         } else {
-            None
+            let mut merged = other.as_boxed();
+
+            for (key, value) in self.iter() {
+                if !other.is_key(key) {
+                    merged = merged.put(heap, key, value)?.unwrap();
+                }
+            }
+
+            Ok(merged)
         }
     }
 
-    pub fn put(&self, key: Term, value: Term) -> Option<HashMap<Term, Term>> {
-        if self.get(key).map_or(false, |val| val == value) {
-            None
+    // Private
+
+    fn from_root<A>(heap: &mut A, len: usize, root: Term) -> AllocResult<Boxed<Map>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        let map = Self {
+            header: Default::default(),
+            len: fixnum!(len),
+            root,
+        };
+
+        unsafe {
+            let ptr = heap.alloc_layout(Layout::new::<Self>())?.as_ptr() as *mut Self;
+            ptr.write(map);
+
+            Ok(Boxed::new_unchecked(ptr))
+        }
+    }
+
+    fn as_boxed(&self) -> Boxed<Map> {
+        unsafe { Boxed::new_unchecked(self as *const Self as *mut Self) }
+    }
+
+    fn root(&self) -> Root {
+        if self.root.is_nil() {
+            Root::Empty
+        } else if self.len() <= MAX_FLAT_LEN {
+            Root::Flat(node(self.root))
         } else {
-            let mut map = self.value.clone();
-            map.insert(key, value);
-            Some(map)
+            Root::Hash(node(self.root))
         }
     }
 
-    pub fn iter(&self) -> hashbrown::hash_map::Iter<Term, Term> {
-        self.value.iter()
+    fn flat_put<A>(
+        &self,
+        heap: &mut A,
+        flat: Boxed<Tuple>,
+        key: Term,
+        value: Term,
+    ) -> AllocResult<Option<Boxed<Map>>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        let len = self.len();
+        let elements = flat.elements();
+
+        match elements[..len]
+            .iter()
+            .position(|flat_key| exactly_eq(*flat_key, key))
+        {
+            Some(index) => {
+                if exactly_eq(elements[len + index], value) {
+                    Ok(None)
+                } else {
+                    let heap_value = value.clone_to_heap(heap)?;
+                    let root = heap
+                        .tuple_from_slices(&[
+                            &elements[..(len + index)],
+                            &[heap_value],
+                            &elements[(len + index + 1)..],
+                        ])?
+                        .into();
+
+                    Self::from_root(heap, len, root).map(Some)
+                }
+            }
+            None if len < MAX_FLAT_LEN => {
+                let index = elements[..len]
+                    .iter()
+                    .position(|flat_key| key < *flat_key)
+                    .unwrap_or(len);
+                let heap_key = key.clone_to_heap(heap)?;
+                let heap_value = value.clone_to_heap(heap)?;
+
+                let root = heap
+                    .tuple_from_slices(&[
+                        &elements[..index],
+                        &[heap_key],
+                        &elements[index..(len + index)],
+                        &[heap_value],
+                        &elements[(len + index)..],
+                    ])?
+                    .into();
+
+                Self::from_root(heap, len + 1, root).map(Some)
+            }
+            // Growing past `MAX_FLAT_LEN` converts the flat map to a hash map
+            None => {
+                let mut leaves: Vec<(u64, Term)> = Vec::with_capacity(len + 1);
+
+                for (flat_key, flat_value) in elements[..len].iter().zip(&elements[len..]) {
+                    // The entries are already on `heap`, so only the leaves need to be allocated
+                    let flat_leaf: Term = heap.cons(*flat_key, *flat_value)?.into();
+                    leaves.push((hash_key(*flat_key), flat_leaf));
+                }
+
+                leaves.push((hash_key(key), leaf(heap, key, value)?));
+                leaves.sort_by_key(|(hash, _)| *hash);
+
+                let root = node_from_sorted_leaves(heap, &leaves, 0)?;
+
+                Self::from_root(heap, len + 1, root).map(Some)
+            }
+        }
     }
 
-    pub fn iter_mut(&mut self) -> hashbrown::hash_map::IterMut<Term, Term> {
-        self.value.iter_mut()
+    fn flat_remove<A>(
+        &self,
+        heap: &mut A,
+        flat: Boxed<Tuple>,
+        key: Term,
+    ) -> AllocResult<Option<Boxed<Map>>>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        let len = self.len();
+        let elements = flat.elements();
+
+        match elements[..len]
+            .iter()
+            .position(|flat_key| exactly_eq(*flat_key, key))
+        {
+            Some(_) if len == 1 => Self::new(heap).map(Some),
+            Some(index) => {
+                let root = heap
+                    .tuple_from_slices(&[
+                        &elements[..index],
+                        &elements[(index + 1)..(len + index)],
+                        &elements[(len + index + 1)..],
+                    ])?
+                    .into();
+
+                Self::from_root(heap, len - 1, root).map(Some)
+            }
+            None => Ok(None),
+        }
     }
 
-    // Private
+    fn sorted_entries(&self) -> Vec<(Term, Term)> {
+        let mut entries: Vec<(Term, Term)> = self.iter().collect();
 
-    fn sorted_keys(&self) -> Vec<Term> {
-        let mut key_vec: Vec<Term> = Vec::new();
-        key_vec.extend(self.value.keys());
-        key_vec.sort_unstable_by(|key1, key2| key1.cmp(&key2));
+        if let Root::Hash(_) = self.root() {
+            entries.sort_by(|(key1, _), (key2, _)| key1.cmp(key2));
+        }
 
-        key_vec
+        entries
     }
 }
 
-impl AsRef<HashMap<Term, Term>> for Boxed<Map> {
-    fn as_ref(&self) -> &HashMap<Term, Term> {
-        &self.as_ref().value
+enum Root {
+    Empty,
+    Flat(Boxed<Tuple>),
+    Hash(Boxed<Tuple>),
+}
+
+enum Removed {
+    /// The node that contained the key is now empty and should be removed from its parent
+    Empty,
+    /// The replacement for the node that contained the key, which may be a single leaf
+    Child(Term),
+}
+
+/// Keys match with `=:=`, not `==`, so that `1` and `1.0` are different keys, and replacing a
+/// value with one that is only `==` to it still updates the map.
+fn exactly_eq(left: Term, right: Term) -> bool {
+    left.decode().unwrap().exact_eq(&right.decode().unwrap())
+}
+
+#[allow(deprecated)]
+fn hash_key(key: Term) -> u64 {
+    // `SipHasher` with fixed keys, so that a key hashes the same no matter which process or
+    // scheduler built the map
+    let mut hasher = core::hash::SipHasher::new();
+    key.hash(&mut hasher);
+
+    hasher.finish()
+}
+
+/// The slot for `hash` in a node at `shift`.  Slots are taken from the most significant bits
+/// first, so that leaves sorted by hash are also grouped by slot at every level.
+fn slot(hash: u64, shift: u32) -> u32 {
+    ((hash >> (HASH_BITS - BITS_PER_LEVEL - shift)) & LEVEL_MASK) as u32
+}
+
+fn node(term: Term) -> Boxed<Tuple> {
+    let ptr: *mut Term = term.dyn_cast();
+
+    unsafe { Tuple::from_raw_term(ptr) }
+}
+
+fn is_leaf(term: Term) -> bool {
+    term.is_non_empty_list()
+}
+
+fn is_collision_node(node: Boxed<Tuple>) -> bool {
+    node.elements()[0].is_non_empty_list()
+}
+
+fn leaf_entry(term: Term) -> (Term, Term) {
+    let cons: Boxed<Cons> = term.dyn_cast();
+
+    (cons.head, cons.tail)
+}
+
+fn leaf<A>(heap: &mut A, key: Term, value: Term) -> AllocResult<Term>
+where
+    A: ?Sized + TermAlloc,
+{
+    let heap_key = key.clone_to_heap(heap)?;
+    let heap_value = value.clone_to_heap(heap)?;
+
+    heap.cons(heap_key, heap_value).map(|cons| cons.into())
+}
+
+fn bitmap(node: Boxed<Tuple>) -> u32 {
+    let bitmap: isize = match node.elements()[0].decode().unwrap() {
+        TypedTerm::SmallInteger(small_integer) => small_integer.into(),
+        _ => unreachable!("HAMT node ({:?}) does not start with a bitmap", node),
+    };
+
+    bitmap as u32
+}
+
+fn node_first_child_index(node: Boxed<Tuple>) -> usize {
+    if is_collision_node(node) {
+        0
+    } else {
+        1
     }
 }
 
-impl AsRef<HashMap<Term, Term>> for Map {
-    fn as_ref(&self) -> &HashMap<Term, Term> {
-        &self.value
+fn bitmap_node<A>(heap: &mut A, bitmap: u32, children: &[Term]) -> AllocResult<Term>
+where
+    A: ?Sized + TermAlloc,
+{
+    heap.tuple_from_slices(&[&[fixnum!(bitmap)], children])
+        .map(|tuple| tuple.into())
+}
+
+/// The keys and values of `entries` must already be on `heap`, as they are not copied.
+fn flat_from_sorted_entries<A>(heap: &mut A, entries: &[(Term, Term)]) -> AllocResult<Term>
+where
+    A: ?Sized + TermAlloc,
+{
+    let keys = entries.iter().map(|(key, _)| *key);
+    let values = entries.iter().map(|(_, value)| *value);
+
+    heap.tuple_from_iter(keys.chain(values), 2 * entries.len())
+        .map(|tuple| tuple.into())
+}
+
+/// Builds the node at `shift` for `leaves`, which must be sorted by hash.  A single leaf is
+/// returned as-is, so that it can be stored directly in its parent.
+fn node_from_sorted_leaves<A>(heap: &mut A, leaves: &[(u64, Term)], shift: u32) -> AllocResult<Term>
+where
+    A: ?Sized + TermAlloc,
+{
+    if leaves.len() == 1 && shift > 0 {
+        return Ok(leaves[0].1);
+    }
+
+    if shift >= HASH_BITS {
+        let collisions: Vec<Term> = leaves.iter().map(|(_, leaf)| *leaf).collect();
+
+        return heap
+            .tuple_from_slices(&[&collisions])
+            .map(|tuple| tuple.into());
+    }
+
+    let mut bitmap = 0;
+    let mut children = Vec::new();
+    let mut start = 0;
+
+    while start < leaves.len() {
+        let start_slot = slot(leaves[start].0, shift);
+        let end = leaves[start..]
+            .iter()
+            .position(|(hash, _)| slot(*hash, shift) != start_slot)
+            .map_or(leaves.len(), |offset| start + offset);
+
+        bitmap |= 1 << start_slot;
+        children.push(node_from_sorted_leaves(
+            heap,
+            &leaves[start..end],
+            shift + BITS_PER_LEVEL,
+        )?);
+
+        start = end;
     }
+
+    bitmap_node(heap, bitmap, &children)
 }
 
-impl crate::borrow::CloneToProcess for Map {
-    fn clone_to_heap<A>(&self, heap: &mut A) -> AllocResult<Term>
-    where
-        A: ?Sized + TermAlloc,
-    {
-        let layout = Layout::for_value(self);
-        let ptr = unsafe { heap.alloc_layout(layout)?.as_ptr() };
+/// Builds the node at `shift` that holds two leaves whose hashes are equal for all levels above
+/// `shift`.
+fn pair_node<A>(
+    heap: &mut A,
+    leaf1: Term,
+    hash1: u64,
+    leaf2: Term,
+    hash2: u64,
+    shift: u32,
+) -> AllocResult<Term>
+where
+    A: ?Sized + TermAlloc,
+{
+    if shift >= HASH_BITS {
+        return heap
+            .tuple_from_slices(&[&[leaf1, leaf2]])
+            .map(|tuple| tuple.into());
+    }
+
+    let slot1 = slot(hash1, shift);
+    let slot2 = slot(hash2, shift);
+
+    if slot1 == slot2 {
+        let child = pair_node(heap, leaf1, hash1, leaf2, hash2, shift + BITS_PER_LEVEL)?;
+
+        bitmap_node(heap, 1 << slot1, &[child])
+    } else if slot1 < slot2 {
+        bitmap_node(heap, (1 << slot1) | (1 << slot2), &[leaf1, leaf2])
+    } else {
+        bitmap_node(heap, (1 << slot1) | (1 << slot2), &[leaf2, leaf1])
+    }
+}
 
-        let self_value = &self.value;
-        let mut heap_value = HashMap::with_capacity(self_value.len());
+fn hash_get(mut node: Boxed<Tuple>, hash: u64, key: Term) -> Option<Term> {
+    let mut shift = 0;
 
-        for (entry_key, entry_value) in self_value {
-            let heap_entry_key = entry_key.clone_to_heap(heap)?;
-            let heap_entry_value = entry_value.clone_to_heap(heap)?;
-            heap_value.insert(heap_entry_key, heap_entry_value);
+    loop {
+        if is_collision_node(node) {
+            return node
+                .iter()
+                .map(|leaf| leaf_entry(*leaf))
+                .find(|(leaf_key, _)| exactly_eq(*leaf_key, key))
+                .map(|(_, leaf_value)| leaf_value);
         }
 
-        // Clone to ensure `value` remains valid if caller is dropped
-        let heap_self = Self {
-            header: self.header.clone(),
-            value: heap_value,
+        let bitmap = bitmap(node);
+        let bit = 1 << slot(hash, shift);
+
+        if bitmap & bit == 0 {
+            return None;
+        }
+
+        let child = node.elements()[1 + (bitmap & (bit - 1)).count_ones() as usize];
+
+        if is_leaf(child) {
+            let (leaf_key, leaf_value) = leaf_entry(child);
+
+            return if exactly_eq(leaf_key, key) {
+                Some(leaf_value)
+            } else {
+                None
+            };
+        }
+
+        node = self::node(child);
+        shift += BITS_PER_LEVEL;
+    }
+}
+
+/// Returns the replacement for `node_term` with `key` associated with `value`, or `None` if
+/// `key` is already associated with `value`.  Sets `grew` if `key` was not already in the map.
+fn hash_put<A>(
+    heap: &mut A,
+    node_term: Term,
+    hash: u64,
+    shift: u32,
+    key: Term,
+    value: Term,
+    grew: &mut bool,
+) -> AllocResult<Option<Term>>
+where
+    A: ?Sized + TermAlloc,
+{
+    let node = self::node(node_term);
+    let elements = node.elements();
+
+    if is_collision_node(node) {
+        let mut updated_elements = elements.to_vec();
+
+        match elements
+            .iter()
+            .position(|leaf| exactly_eq(leaf_entry(*leaf).0, key))
+        {
+            Some(index) => {
+                if exactly_eq(leaf_entry(elements[index]).1, value) {
+                    return Ok(None);
+                }
+
+                updated_elements[index] = leaf(heap, key, value)?;
+            }
+            None => {
+                updated_elements.push(leaf(heap, key, value)?);
+                *grew = true;
+            }
+        }
+
+        return heap
+            .tuple_from_slices(&[&updated_elements])
+            .map(|tuple| Some(tuple.into()));
+    }
+
+    let bitmap = bitmap(node);
+    let bit = 1 << slot(hash, shift);
+    let index = 1 + (bitmap & (bit - 1)).count_ones() as usize;
+
+    if bitmap & bit == 0 {
+        let mut children = Vec::with_capacity(elements.len());
+        children.extend_from_slice(&elements[1..index]);
+        children.push(leaf(heap, key, value)?);
+        children.extend_from_slice(&elements[index..]);
+        *grew = true;
+
+        return bitmap_node(heap, bitmap | bit, &children).map(Some);
+    }
+
+    let child = elements[index];
+
+    let updated_child = if is_leaf(child) {
+        let (leaf_key, leaf_value) = leaf_entry(child);
+
+        if exactly_eq(leaf_key, key) {
+            if exactly_eq(leaf_value, value) {
+                return Ok(None);
+            }
+
+            leaf(heap, key, value)?
+        } else {
+            let new_leaf = leaf(heap, key, value)?;
+            *grew = true;
+
+            pair_node(
+                heap,
+                child,
+                hash_key(leaf_key),
+                new_leaf,
+                hash,
+                shift + BITS_PER_LEVEL,
+            )?
+        }
+    } else {
+        match hash_put(heap, child, hash, shift + BITS_PER_LEVEL, key, value, grew)? {
+            Some(updated_child) => updated_child,
+            None => return Ok(None),
+        }
+    };
+
+    let mut children = elements[1..].to_vec();
+    children[index - 1] = updated_child;
+
+    bitmap_node(heap, bitmap, &children).map(Some)
+}
+
+/// Returns what should replace `node_term` once `key` is removed, or `None` if `key` is not in
+/// the map.
+fn hash_remove<A>(
+    heap: &mut A,
+    node_term: Term,
+    hash: u64,
+    shift: u32,
+    key: Term,
+) -> AllocResult<Option<Removed>>
+where
+    A: ?Sized + TermAlloc,
+{
+    let node = self::node(node_term);
+    let elements = node.elements();
+
+    if is_collision_node(node) {
+        return match elements
+            .iter()
+            .position(|leaf| exactly_eq(leaf_entry(*leaf).0, key))
+        {
+            Some(index) => {
+                let mut remaining = elements.to_vec();
+                remaining.remove(index);
+
+                let removed = if remaining.len() == 1 {
+                    Removed::Child(remaining[0])
+                } else {
+                    Removed::Child(heap.tuple_from_slices(&[&remaining])?.into())
+                };
+
+                Ok(Some(removed))
+            }
+            None => Ok(None),
         };
+    }
 
-        let size = mem::size_of_val(self);
-        unsafe {
-            ptr::copy_nonoverlapping(&heap_self as *const _ as *const u8, ptr as *mut u8, size);
+    let bitmap = bitmap(node);
+    let bit = 1 << slot(hash, shift);
+
+    if bitmap & bit == 0 {
+        return Ok(None);
+    }
+
+    let index = 1 + (bitmap & (bit - 1)).count_ones() as usize;
+    let child = elements[index];
+
+    let removed_child = if is_leaf(child) {
+        if exactly_eq(leaf_entry(child).0, key) {
+            Removed::Empty
+        } else {
+            return Ok(None);
+        }
+    } else {
+        match hash_remove(heap, child, hash, shift + BITS_PER_LEVEL, key)? {
+            Some(removed_child) => removed_child,
+            None => return Ok(None),
+        }
+    };
+
+    let mut children = elements[1..].to_vec();
+
+    let updated_bitmap = match removed_child {
+        Removed::Empty => {
+            children.remove(index - 1);
+
+            bitmap & !bit
+        }
+        Removed::Child(updated_child) => {
+            children[index - 1] = updated_child;
+
+            bitmap
+        }
+    };
+
+    let removed = match children.len() {
+        0 => Removed::Empty,
+        // Collapse nodes that only hold a single leaf into that leaf, so that the trie stays as
+        // shallow as it would be if the map had been built without the removed key.
+        1 if shift > 0 && is_leaf(children[0]) => Removed::Child(children[0]),
+        _ => Removed::Child(bitmap_node(heap, updated_bitmap, &children)?),
+    };
+
+    Ok(Some(removed))
+}
+
+fn push_entries(term: Term, entries: &mut Vec<(Term, Term)>) {
+    if is_leaf(term) {
+        entries.push(leaf_entry(term));
+    } else {
+        let node = self::node(term);
+
+        for child in &node.elements()[node_first_child_index(node)..] {
+            push_entries(*child, entries);
+        }
+    }
+}
+
+fn clone_hash_child_to_heap<A>(heap: &mut A, term: Term) -> AllocResult<Term>
+where
+    A: ?Sized + TermAlloc,
+{
+    if is_leaf(term) {
+        let (key, value) = leaf_entry(term);
+
+        leaf(heap, key, value)
+    } else {
+        let node = self::node(term);
+        let mut elements = node.elements().to_vec();
+
+        for element in elements[node_first_child_index(node)..].iter_mut() {
+            *element = clone_hash_child_to_heap(heap, *element)?;
+        }
+
+        // The children were cloned above, and the bitmap is immediate
+        heap.tuple_from_slices(&[&elements])
+            .map(|tuple| tuple.into())
+    }
+}
+
+fn hash_child_size_in_words(term: Term) -> usize {
+    if is_leaf(term) {
+        let (key, value) = leaf_entry(term);
+
+        Cons::need_in_words_from_len(1) + key.size_in_words() + value.size_in_words()
+    } else {
+        let node = self::node(term);
+        let mut words = erts::to_word_size(Layout::for_value(node.as_ref()).size());
+
+        for child in &node.elements()[node_first_child_index(node)..] {
+            words += hash_child_size_in_words(*child);
+        }
+
+        words
+    }
+}
+
+pub struct Iter<'a> {
+    kind: IterKind,
+    _marker: PhantomData<&'a Map>,
+}
+
+enum IterKind {
+    Empty,
+    Flat {
+        flat: Boxed<Tuple>,
+        index: usize,
+        len: usize,
+    },
+    Hash {
+        stack: Vec<(Boxed<Tuple>, usize)>,
+    },
+}
+
+impl Iterator for Iter<'_> {
+    type Item = (Term, Term);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.kind {
+            IterKind::Empty => None,
+            IterKind::Flat { flat, index, len } => {
+                if *index < *len {
+                    let elements = flat.elements();
+                    let entry = (elements[*index], elements[*len + *index]);
+                    *index += 1;
+
+                    Some(entry)
+                } else {
+                    None
+                }
+            }
+            IterKind::Hash { stack } => loop {
+                let (node, index) = stack.last_mut()?;
+                let elements = node.elements();
+
+                if *index < elements.len() {
+                    let child = elements[*index];
+                    *index += 1;
+
+                    if is_leaf(child) {
+                        return Some(leaf_entry(child));
+                    } else {
+                        let child_node = self::node(child);
+                        stack.push((child_node, node_first_child_index(child_node)));
+                    }
+                } else {
+                    stack.pop();
+                }
+            },
         }
+    }
+}
 
-        mem::forget(heap_self);
+impl CloneToProcess for Map {
+    fn clone_to_heap<A>(&self, heap: &mut A) -> AllocResult<Term>
+    where
+        A: ?Sized + TermAlloc,
+    {
+        let root = match self.root() {
+            Root::Empty => Term::NIL,
+            Root::Flat(flat) => flat.as_ref().clone_to_heap(heap)?,
+            Root::Hash(_) => clone_hash_child_to_heap(heap, self.root)?,
+        };
 
-        Ok((ptr as *mut Self).into())
+        Self::from_root(heap, self.len(), root).map(|map| map.into())
     }
 
     fn size_in_words(&self) -> usize {
-        crate::erts::to_word_size(Layout::for_value(self).size())
+        let words = erts::to_word_size(Layout::for_value(self).size());
+
+        let root_words = match self.root() {
+            Root::Empty => 0,
+            Root::Flat(flat) => flat.as_ref().size_in_words(),
+            Root::Hash(_) => hash_child_size_in_words(self.root),
+        };
+
+        words + root_words
     }
 }
 
@@ -216,7 +1034,8 @@ impl Debug for Map {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Map")
             .field("header", &self.header)
-            .field("value", &self.value)
+            .field("len", &self.len)
+            .field("root", &self.root)
             .finish()
     }
 }
@@ -225,7 +1044,7 @@ impl Display for Map {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "%{{")?;
 
-        let mut iter = self.iter();
+        let mut iter = self.sorted_entries().into_iter();
 
         if let Some((first_key, first_value)) = iter.next() {
             write!(f, "{} => {}", first_key, first_value)?;
@@ -243,9 +1062,7 @@ impl Eq for Map {}
 
 impl Hash for Map {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        for key in self.sorted_keys() {
-            let value = self.value[&key];
-
+        for (key, value) in self.sorted_entries() {
             key.hash(state);
             value.hash(state);
         }
@@ -254,7 +1071,12 @@ impl Hash for Map {
 
 impl PartialEq for Map {
     fn eq(&self, other: &Map) -> bool {
-        self.value.eq(&other.value)
+        (self.len() == other.len())
+            && self.iter().all(|(key, value)| {
+                other
+                    .get(key)
+                    .map_or(false, |other_value| other_value == value)
+            })
     }
 }
 impl<T> PartialEq<Boxed<T>> for Map
@@ -289,31 +1111,18 @@ impl Ord for Map {
     fn cmp(&self, other: &Self) -> cmp::Ordering {
         match self.len().cmp(&other.len()) {
             cmp::Ordering::Equal => {
-                let self_key_vec = self.sorted_keys();
-                let other_key_vec = other.sorted_keys();
+                let self_entries = self.sorted_entries();
+                let other_entries = other.sorted_entries();
+
+                let self_keys = self_entries.iter().map(|(key, _)| key);
+                let other_keys = other_entries.iter().map(|(key, _)| key);
 
-                match self_key_vec.cmp(&other_key_vec) {
+                match self_keys.cmp(other_keys) {
                     cmp::Ordering::Equal => {
-                        let self_value = &self.value;
-                        let other_value = &other.value;
-                        let mut final_ordering = cmp::Ordering::Equal;
-
-                        for key in self_key_vec {
-                            match self_value
-                                .get(&key)
-                                .unwrap()
-                                .cmp(other_value.get(&key).unwrap())
-                            {
-                                cmp::Ordering::Equal => continue,
-                                ordering => {
-                                    final_ordering = ordering;
-
-                                    break;
-                                }
-                            }
-                        }
+                        let self_values = self_entries.iter().map(|(_, value)| value);
+                        let other_values = other_entries.iter().map(|(_, value)| value);
 
-                        final_ordering
+                        self_values.cmp(other_values)
                     }
                     ordering => ordering,
                 }
@@ -333,3 +1142,247 @@ impl TryFrom<TypedTerm> for Boxed<Map> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::erts::process::alloc::Heap;
+    use crate::erts::testing::RegionHeap;
+
+    fn entries(len: usize) -> Vec<(Term, Term)> {
+        (0..len).map(|i| (fixnum!(i), fixnum!(i * 2))).collect()
+    }
+
+    mod from_slice {
+        use super::*;
+
+        #[test]
+        fn last_duplicate_key_wins() {
+            let mut heap = RegionHeap::default();
+            let map = Map::from_slice(
+                &mut heap,
+                &[(atom!("key"), fixnum!(1)), (atom!("key"), fixnum!(2))],
+            )
+            .unwrap();
+
+            assert_eq!(map.len(), 1);
+            assert_eq!(map.get(atom!("key")), Some(fixnum!(2)));
+        }
+
+        #[test]
+        fn flat_map_iterates_in_key_order() {
+            let mut heap = RegionHeap::default();
+            let map = Map::from_slice(
+                &mut heap,
+                &[(fixnum!(2), atom!("b")), (fixnum!(1), atom!("a"))],
+            )
+            .unwrap();
+
+            assert_eq!(map.keys(), vec![fixnum!(1), fixnum!(2)]);
+            assert_eq!(map.values(), vec![atom!("a"), atom!("b")]);
+        }
+
+        #[test]
+        fn hash_map_contains_all_entries() {
+            let mut heap = RegionHeap::default();
+            let entries = entries(1000);
+            let map = Map::from_slice(&mut heap, &entries).unwrap();
+
+            assert_eq!(map.len(), 1000);
+            assert_eq!(map.iter().count(), 1000);
+
+            for (key, value) in entries {
+                assert_eq!(map.get(key), Some(value));
+            }
+
+            assert_eq!(map.get(fixnum!(1000)), None);
+        }
+
+        #[test]
+        fn integer_and_float_are_different_keys() {
+            let mut heap = RegionHeap::default();
+            let float_key: Term = heap.float(1.0).map(|f| f.into()).unwrap();
+            let map = Map::from_slice(
+                &mut heap,
+                &[(fixnum!(1), atom!("a")), (float_key, atom!("b"))],
+            )
+            .unwrap();
+
+            assert_eq!(map.len(), 2);
+            assert_eq!(map.get(fixnum!(1)), Some(atom!("a")));
+            assert_eq!(map.get(float_key), Some(atom!("b")));
+        }
+    }
+
+    mod put {
+        use super::*;
+
+        #[test]
+        fn same_value_returns_none() {
+            let mut heap = RegionHeap::default();
+            let map = Map::from_slice(&mut heap, &entries(2)).unwrap();
+
+            assert!(map
+                .put(&mut heap, fixnum!(1), fixnum!(2))
+                .unwrap()
+                .is_none());
+        }
+
+        #[test]
+        fn float_equal_to_integer_key_is_new_key() {
+            let mut heap = RegionHeap::default();
+            let float_key: Term = heap.float(1.0).map(|f| f.into()).unwrap();
+
+            for len in &[2, MAX_FLAT_LEN * 2] {
+                let map = Map::from_slice(&mut heap, &entries(*len)).unwrap();
+                let updated = map.put(&mut heap, float_key, atom!("b")).unwrap().unwrap();
+
+                assert_eq!(updated.len(), len + 1);
+                assert_eq!(updated.get(fixnum!(1)), Some(fixnum!(2)));
+                assert_eq!(updated.get(float_key), Some(atom!("b")));
+            }
+        }
+
+        #[test]
+        fn float_equal_to_integer_value_replaces_value() {
+            let mut heap = RegionHeap::default();
+            let float_value: Term = heap.float(2.0).map(|f| f.into()).unwrap();
+            let map = Map::from_slice(&mut heap, &entries(2)).unwrap();
+            let updated = map
+                .put(&mut heap, fixnum!(1), float_value)
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(updated.get(fixnum!(1)), Some(float_value));
+            assert!(updated.get(fixnum!(1)).unwrap().is_float());
+        }
+
+        #[test]
+        fn does_not_modify_original() {
+            let mut heap = RegionHeap::default();
+            let original = Map::from_slice(&mut heap, &entries(100)).unwrap();
+            let updated = original
+                .put(&mut heap, fixnum!(1), atom!("updated"))
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(original.get(fixnum!(1)), Some(fixnum!(2)));
+            assert_eq!(updated.get(fixnum!(1)), Some(atom!("updated")));
+            assert_eq!(original.len(), updated.len());
+        }
+
+        #[test]
+        fn shares_unchanged_entries() {
+            let mut heap = RegionHeap::default();
+            let float_value: Term = heap.float(2.0).map(|f| f.into()).unwrap();
+
+            for len in &[2, MAX_FLAT_LEN, MAX_FLAT_LEN * 2] {
+                let mut entries = entries(*len);
+                entries[0].1 = float_value;
+                let map = Map::from_slice(&mut heap, &entries).unwrap();
+                let updated = map
+                    .put(&mut heap, atom!("new"), atom!("value"))
+                    .unwrap()
+                    .unwrap();
+
+                let original_ptr: *mut Term = map.get(fixnum!(0)).unwrap().dyn_cast();
+                let updated_ptr: *mut Term = updated.get(fixnum!(0)).unwrap().dyn_cast();
+
+                assert_eq!(original_ptr, updated_ptr);
+            }
+        }
+
+        #[test]
+        fn growing_past_max_flat_len_matches_from_slice() {
+            let mut heap = RegionHeap::default();
+            let entries = entries(MAX_FLAT_LEN * 4);
+            let mut map = Map::new(&mut heap).unwrap();
+
+            for (key, value) in entries.iter() {
+                map = map.put(&mut heap, *key, *value).unwrap().unwrap();
+            }
+
+            let from_slice = Map::from_slice(&mut heap, &entries).unwrap();
+
+            assert_eq!(map.len(), entries.len());
+            assert_eq!(map, from_slice);
+            assert_eq!(map.keys(), from_slice.keys());
+        }
+    }
+
+    mod remove {
+        use super::*;
+
+        #[test]
+        fn missing_key_returns_none() {
+            let mut heap = RegionHeap::default();
+            let map = Map::from_slice(&mut heap, &entries(100)).unwrap();
+
+            assert!(map.remove(&mut heap, atom!("missing")).unwrap().is_none());
+        }
+
+        #[test]
+        fn float_equal_to_integer_key_returns_none() {
+            let mut heap = RegionHeap::default();
+            let float_key: Term = heap.float(1.0).map(|f| f.into()).unwrap();
+
+            for len in &[2, MAX_FLAT_LEN * 2] {
+                let map = Map::from_slice(&mut heap, &entries(*len)).unwrap();
+
+                assert!(map.remove(&mut heap, float_key).unwrap().is_none());
+            }
+        }
+
+        #[test]
+        fn shrinking_to_max_flat_len_converts_to_flat_map() {
+            let mut heap = RegionHeap::default();
+            let entries = entries(MAX_FLAT_LEN + 1);
+            let map = Map::from_slice(&mut heap, &entries).unwrap();
+            let removed = map.remove(&mut heap, fixnum!(0)).unwrap().unwrap();
+
+            assert_eq!(removed.len(), MAX_FLAT_LEN);
+            assert_eq!(removed.get(fixnum!(0)), None);
+
+            let from_slice = Map::from_slice(&mut heap, &entries[1..]).unwrap();
+
+            assert_eq!(removed, from_slice);
+            assert_eq!(removed.keys(), from_slice.keys());
+        }
+
+        #[test]
+        fn every_key_from_hash_map() {
+            let mut heap = RegionHeap::default();
+            let entries = entries(200);
+            let mut map = Map::from_slice(&mut heap, &entries).unwrap();
+
+            for (i, (key, _)) in entries.iter().enumerate() {
+                map = map.remove(&mut heap, *key).unwrap().unwrap();
+
+                assert_eq!(map.len(), entries.len() - i - 1);
+                assert_eq!(map.get(*key), None);
+            }
+
+            assert!(map.is_empty());
+        }
+    }
+
+    mod clone_to_heap {
+        use super::*;
+
+        #[test]
+        fn hash_map_is_deep_copied() {
+            let mut heap = RegionHeap::default();
+            let map = Map::from_slice(&mut heap, &entries(100)).unwrap();
+
+            let mut other_heap = RegionHeap::default();
+            let cloned_term = map.clone_to_heap(&mut other_heap).unwrap();
+            let cloned: Boxed<Map> = cloned_term.try_into().unwrap();
+
+            let cloned_root_ptr: *mut Term = cloned.root.dyn_cast();
+
+            assert_eq!(map, cloned);
+            assert!(other_heap.contains(cloned_root_ptr));
+        }
+    }
+}
@@ -4,6 +4,12 @@ use core::convert::TryFrom;
 use core::fmt::{self, Debug, Display};
 use core::hash::{Hash, Hasher};
 
+use alloc::sync::Arc;
+
+use std::backtrace::Backtrace;
+
+use thiserror::Error;
+
 use crate::borrow::CloneToProcess;
 use crate::erts::exception::AllocResult;
 use crate::erts::node::Node;
@@ -15,12 +21,35 @@ use super::prelude::*;
 #[repr(transparent)]
 pub struct Port(usize);
 impl Port {
+    // The number bit count is limited to 28 bits because the number must be able to fit in the
+    // `PORT_EXT` and `NEW_PORT_EXT` external term formats.
+    const NUMBER_BIT_COUNT: u8 = 28;
+
+    pub const NUMBER_MAX: usize = (1 << (Self::NUMBER_BIT_COUNT as usize)) - 1;
+
     /// Given a the raw pid value (as a usize), reifies it into a `Port`
     #[inline]
     pub unsafe fn from_raw(port: usize) -> Self {
         Self(port)
     }
 
+    pub fn new(number: usize) -> Result<Port, InvalidPortError> {
+        if number <= Self::NUMBER_MAX {
+            Ok(Self(number))
+        } else {
+            Err(InvalidPortError::Number {
+                number,
+                backtrace: Backtrace::capture(),
+            })
+        }
+    }
+
+    /// Never exceeds 28 significant bits to remain compatible with `PORT_EXT` and `NEW_PORT_EXT`
+    /// external term formats.
+    pub fn number(&self) -> u32 {
+        self.0 as u32
+    }
+
     #[inline(always)]
     pub fn as_usize(self) -> usize {
         self.0
@@ -28,8 +57,8 @@ impl Port {
 }
 
 impl Display for Port {
-    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
-        unimplemented!()
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#Port<0.{}>", self.number())
     }
 }
 
@@ -80,21 +109,46 @@ impl TryFrom<TypedTerm> for Port {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[repr(C)]
 pub struct ExternalPort {
     header: Header<ExternalPort>,
-    node: Node,
-    next: *mut u8,
+    arc_node: Arc<Node>,
     port: Port,
 }
 impl_static_header!(ExternalPort, Term::HEADER_EXTERN_PORT);
+impl ExternalPort {
+    pub fn new(arc_node: Arc<Node>, number: usize) -> Result<Self, InvalidPortError> {
+        let port = Port::new(number)?;
+
+        Ok(Self {
+            header: Default::default(),
+            arc_node,
+            port,
+        })
+    }
+
+    pub fn arc_node(&self) -> Arc<Node> {
+        self.arc_node.clone()
+    }
+
+    pub fn number(&self) -> u32 {
+        self.port.number()
+    }
+}
+
 impl CloneToProcess for ExternalPort {
-    fn clone_to_heap<A>(&self, _heap: &mut A) -> AllocResult<Term>
+    fn clone_to_heap<A>(&self, heap: &mut A) -> AllocResult<Term>
     where
         A: ?Sized + TermAlloc,
     {
-        unimplemented!()
+        unsafe {
+            let layout = Layout::new::<Self>();
+            let ptr = heap.alloc_layout(layout)?.as_ptr() as *mut Self;
+            ptr.write(self.clone());
+
+            Ok(ptr.into())
+        }
     }
 
     fn size_in_words(&self) -> usize {
@@ -103,22 +157,23 @@ impl CloneToProcess for ExternalPort {
 }
 
 impl Display for ExternalPort {
-    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
-        unimplemented!()
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#Port<{}.{}>", self.arc_node.id(), self.port.number())
     }
 }
 
 impl Hash for ExternalPort {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.node.hash(state);
+        self.arc_node.hash(state);
         self.port.hash(state);
     }
 }
 
+impl Eq for ExternalPort {}
 impl PartialEq for ExternalPort {
     #[inline]
     fn eq(&self, other: &ExternalPort) -> bool {
-        self.node == other.node && self.port == other.port
+        self.arc_node == other.arc_node && self.port == other.port
     }
 }
 impl<T> PartialEq<Boxed<T>> for ExternalPort
@@ -131,14 +186,17 @@ where
     }
 }
 
+impl Ord for ExternalPort {
+    fn cmp(&self, other: &ExternalPort) -> cmp::Ordering {
+        self.arc_node
+            .cmp(&other.arc_node)
+            .then_with(|| self.port.cmp(&other.port))
+    }
+}
 impl PartialOrd for ExternalPort {
     #[inline]
     fn partial_cmp(&self, other: &ExternalPort) -> Option<cmp::Ordering> {
-        use cmp::Ordering;
-        match self.node.partial_cmp(&other.node) {
-            Some(Ordering::Equal) => self.port.partial_cmp(&other.port),
-            result => result,
-        }
+        Some(self.cmp(other))
     }
 }
 impl<T> PartialOrd<Boxed<T>> for ExternalPort
@@ -161,3 +219,9 @@ impl TryFrom<TypedTerm> for Boxed<ExternalPort> {
         }
     }
 }
+
+#[derive(Error, Debug)]
+pub enum InvalidPortError {
+    #[error("invalid port: number ({}) out of range (0-{})", .number, Port::NUMBER_MAX)]
+    Number { number: usize, backtrace: Backtrace },
+}
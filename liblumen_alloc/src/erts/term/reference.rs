@@ -155,13 +155,41 @@ pub struct ExternalReference {
     reference: Reference,
 }
 impl_static_header!(ExternalReference, Term::HEADER_EXTERN_REF);
+impl ExternalReference {
+    pub fn new(arc_node: Arc<Node>, scheduler_id: scheduler::ID, number: ReferenceNumber) -> Self {
+        Self {
+            header: Default::default(),
+            arc_node,
+            reference: Reference::new(scheduler_id, number),
+        }
+    }
+
+    pub fn arc_node(&self) -> Arc<Node> {
+        self.arc_node.clone()
+    }
+
+    pub fn scheduler_id(&self) -> scheduler::ID {
+        self.reference.scheduler_id()
+    }
+
+    pub fn number(&self) -> ReferenceNumber {
+        self.reference.number()
+    }
+}
+
 impl CloneToProcess for ExternalReference {
     #[inline]
-    fn clone_to_heap<A>(&self, _heap: &mut A) -> AllocResult<Term>
+    fn clone_to_heap<A>(&self, heap: &mut A) -> AllocResult<Term>
     where
         A: ?Sized + TermAlloc,
     {
-        unimplemented!()
+        unsafe {
+            let layout = Layout::new::<Self>();
+            let ptr = heap.alloc_layout(layout)?.as_ptr() as *mut Self;
+            ptr.write(self.clone());
+
+            Ok(ptr.into())
+        }
     }
 
     fn size_in_words(&self) -> usize {
@@ -170,8 +198,14 @@ impl CloneToProcess for ExternalReference {
 }
 
 impl Display for ExternalReference {
-    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
-        unimplemented!()
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "#Reference<{}.{}.{}>",
+            self.arc_node.id(),
+            self.reference.scheduler_id(),
+            self.reference.number()
+        )
     }
 }
 
@@ -182,6 +216,7 @@ impl Hash for ExternalReference {
     }
 }
 
+impl Eq for ExternalReference {}
 impl PartialEq for ExternalReference {
     fn eq(&self, other: &ExternalReference) -> bool {
         self.arc_node == other.arc_node && self.reference == other.reference
@@ -208,12 +243,16 @@ impl PartialEq<Boxed<Reference>> for ExternalReference {
     }
 }
 
+impl Ord for ExternalReference {
+    fn cmp(&self, other: &ExternalReference) -> Ordering {
+        self.arc_node
+            .cmp(&other.arc_node)
+            .then_with(|| self.reference.cmp(&other.reference))
+    }
+}
 impl PartialOrd for ExternalReference {
     fn partial_cmp(&self, other: &ExternalReference) -> Option<Ordering> {
-        match self.arc_node.partial_cmp(&other.arc_node) {
-            Some(Ordering::Equal) => self.reference.partial_cmp(&other.reference),
-            result => result,
-        }
+        Some(self.cmp(other))
     }
 }
 impl PartialOrd<Reference> for ExternalReference {
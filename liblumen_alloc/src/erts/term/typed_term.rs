@@ -565,10 +565,28 @@ impl Ord for TypedTerm {
                 TypedTerm::Atom(rhs) => lhs.cmp(rhs),
                 _ => Less,
             },
-            TypedTerm::Port(lhs) => unimplemented!("Port {:?} cmp {:?}", lhs, other),
-            TypedTerm::ExternalPort(lhs) => {
-                unimplemented!("ExternalPort {:?} cmp {:?}", lhs, other)
-            }
+            TypedTerm::Port(lhs) => match other {
+                TypedTerm::SmallInteger(_) => Greater,
+                TypedTerm::Float(_)
+                | TypedTerm::BigInteger(_)
+                | TypedTerm::Reference(_)
+                | TypedTerm::ExternalReference(_)
+                | TypedTerm::Closure(_) => Greater,
+                TypedTerm::Atom(_) => Greater,
+                TypedTerm::Port(rhs) => lhs.cmp(rhs),
+                _ => Less,
+            },
+            TypedTerm::ExternalPort(lhs) => match other {
+                TypedTerm::SmallInteger(_) => Greater,
+                TypedTerm::Float(_)
+                | TypedTerm::BigInteger(_)
+                | TypedTerm::Reference(_)
+                | TypedTerm::ExternalReference(_)
+                | TypedTerm::Closure(_) => Greater,
+                TypedTerm::Atom(_) | TypedTerm::Port(_) => Greater,
+                TypedTerm::ExternalPort(rhs) => lhs.cmp(rhs),
+                _ => Less,
+            },
             TypedTerm::Pid(lhs) => match other {
                 TypedTerm::SmallInteger(_) => Greater,
                 TypedTerm::Float(_)
@@ -0,0 +1,505 @@
+//! Disassembles the `"Code"` chunk of a BEAM file into the generic BEAM instructions.
+//!
+//! The operands of the instructions are resolved with the other chunks in the BEAM file, so that
+//! the [Disassembly](Disassembly) can be printed in the same BEAM assembly (`.S`) format as
+//! `erlc -S`.
+//!
+//! # Examples
+//!
+//!     use liblumen_beam::beam::disassembler::Disassembly;
+//!     use liblumen_beam::beam::reader::StandardBeamFile;
+//!
+//!     let beam = StandardBeamFile::from_file("tests/testdata/reader/test.beam").unwrap();
+//!     let disassembly = Disassembly::new(&beam).unwrap();
+//!
+//!     assert!(disassembly.to_string().starts_with("{module, test}."));
+//!
+//! ## References
+//!
+//! - [BEAM Wisdoms - BEAM File Format - Code](http://beam-wisdoms.clau.se/en/latest/indepth-beam-file.html#code-chunk)
+//! - [`beam_disasm`](https://github.com/erlang/otp/blob/master/lib/compiler/src/beam_disasm.erl)
+//!
+//! ## Alternative Implementations
+//!
+//! - [`org.elixir_lang.beam.chunk.code.Operation#assembly` in IntelliJ Elixir](https://github.com/KronicDeth/intellij-elixir/blob/
+//!   2f5c826040681e258e98c3e2f02b25985cd0766b/src/org/elixir_lang/beam/chunk/code/Operation.kt#
+//!   L23-L164) in Kotlin
+pub mod instruction;
+pub mod opcode;
+
+#[cfg(test)]
+mod test;
+
+use std::collections::HashMap;
+use std::fmt::{self, Display, Write};
+use std::io::Cursor;
+
+use crate::beam::reader::chunk::{CodeChunk, LineChunk, StandardChunk};
+use crate::beam::reader::compact::{Allocation, Term};
+use crate::beam::reader::parts;
+use crate::beam::reader::{ReadError, Result, StandardBeamFile};
+use crate::serialization::etf;
+
+pub use self::instruction::Instruction;
+pub use self::opcode::Opcode;
+
+/// A function in a [Disassembly](Disassembly).
+#[derive(Debug)]
+pub struct Function<'a> {
+    pub name: &'a str,
+    pub arity: u32,
+    /// The label that calls jump to, which is after the `func_info` instruction.
+    pub entry: u32,
+    /// The instructions from the label before `func_info` up to the next function.
+    pub instructions: &'a [Instruction],
+}
+
+/// The decoded instructions of a BEAM file along with the chunks needed to resolve their operands.
+pub struct Disassembly<'a> {
+    atoms: &'a [parts::Atom],
+    code: &'a CodeChunk,
+    instructions: Vec<Instruction>,
+    imports: &'a [parts::Import],
+    exports: &'a [parts::Export],
+    funs: &'a [parts::Function],
+    literals: Vec<etf::Term>,
+    strings: &'a [u8],
+    lines: Option<&'a LineChunk>,
+    /// The function name atom and arity of each function entry label.
+    entries: HashMap<u32, (u32, u32)>,
+}
+impl<'a> Disassembly<'a> {
+    /// Decodes the `"Code"` chunk in `beam`.
+    ///
+    /// The `"Atom"` or `"AtU8"` and `"Code"` chunks are required.  Operands that refer to any other
+    /// missing chunks are printed unresolved.
+    pub fn new(beam: &'a StandardBeamFile) -> Result<Self> {
+        let atoms = match beam.atoms() {
+            Some(StandardChunk::Atom(chunk)) => &chunk.atoms[..],
+            _ => return Err(ReadError::MissingChunk(*b"Atom")),
+        };
+        let code = match beam.get_chunk(b"Code") {
+            Some(StandardChunk::Code(chunk)) => chunk,
+            _ => return Err(ReadError::MissingChunk(*b"Code")),
+        };
+        let imports = match beam.get_chunk(b"ImpT") {
+            Some(StandardChunk::ImpT(chunk)) => &chunk.imports[..],
+            _ => &[],
+        };
+        let exports = match beam.get_chunk(b"ExpT") {
+            Some(StandardChunk::ExpT(chunk)) => &chunk.exports[..],
+            _ => &[],
+        };
+        let locals = match beam.get_chunk(b"LocT") {
+            Some(StandardChunk::LocT(chunk)) => &chunk.locals[..],
+            _ => &[],
+        };
+        let funs = match beam.get_chunk(b"FunT") {
+            Some(StandardChunk::FunT(chunk)) => &chunk.functions[..],
+            _ => &[],
+        };
+        let literals = match beam.get_chunk(b"LitT") {
+            Some(StandardChunk::LitT(chunk)) => chunk
+                .literals
+                .iter()
+                .map(|literal| etf::Term::decode(Cursor::new(literal)))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(ReadError::InvalidLiteral)?,
+            _ => Vec::new(),
+        };
+        let strings = match beam.get_chunk(b"StrT") {
+            Some(StandardChunk::StrT(chunk)) => &chunk.strings[..],
+            _ => &[],
+        };
+        let lines = match beam.get_chunk(b"Line") {
+            Some(StandardChunk::Line(chunk)) => Some(chunk),
+            _ => None,
+        };
+
+        let instructions = Instruction::decode_all(code)?;
+
+        let mut entries = HashMap::new();
+        for export in exports {
+            entries.insert(export.label, (export.function, export.arity));
+        }
+        for local in locals {
+            entries.insert(local.label, (local.function, local.arity));
+        }
+
+        let mut disassembly = Disassembly {
+            atoms,
+            code,
+            instructions,
+            imports,
+            exports,
+            funs,
+            literals,
+            strings,
+            lines,
+            entries,
+        };
+
+        // Functions that are neither exported nor called locally are only in `func_info`
+        let mut func_info_entries = Vec::new();
+        for function in disassembly.functions() {
+            if let Some((function_atom, arity)) = func_info(function.instructions) {
+                func_info_entries.push((function.entry, (function_atom, arity)));
+            }
+        }
+        for (entry, name_arity) in func_info_entries {
+            disassembly.entries.entry(entry).or_insert(name_arity);
+        }
+
+        Ok(disassembly)
+    }
+
+    /// The name of the module.
+    pub fn module(&self) -> &'a str {
+        &self.atoms[0].name
+    }
+
+    /// All the decoded instructions, not including `int_code_end`.
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// The instructions split into functions.
+    ///
+    /// A function starts with the `label` before its `func_info` instruction, so any `line`
+    /// instructions between them are included in the function.
+    pub fn functions(&self) -> Vec<Function> {
+        let starts: Vec<usize> = self
+            .instructions
+            .iter()
+            .enumerate()
+            .filter(|(_, instruction)| instruction.opcode.name == "func_info")
+            .map(|(index, _)| {
+                self.instructions[..index]
+                    .iter()
+                    .rposition(|instruction| instruction.opcode.name == "label")
+                    .unwrap_or(index)
+            })
+            .collect();
+
+        starts
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| {
+                let end = starts
+                    .get(i + 1)
+                    .cloned()
+                    .unwrap_or(self.instructions.len());
+                let instructions = &self.instructions[start..end];
+                let (function_atom, arity) = func_info(instructions).unwrap_or((0, 0));
+                let entry = instructions
+                    .iter()
+                    .skip_while(|instruction| instruction.opcode.name != "func_info")
+                    .find(|instruction| instruction.opcode.name == "label")
+                    .and_then(|instruction| match instruction.operands[0] {
+                        Term::Literal(label) => Some(label as u32),
+                        _ => None,
+                    })
+                    .unwrap_or(0);
+
+                Function {
+                    name: self.atom_name(function_atom).unwrap_or(""),
+                    arity,
+                    entry,
+                    instructions,
+                }
+            })
+            .collect()
+    }
+
+    /// Formats `instruction` as a BEAM assembly term without the trailing `.`
+    pub fn format_instruction(&self, instruction: &Instruction) -> String {
+        let name = instruction.opcode.name;
+        let operands = &instruction.operands;
+
+        if operands.is_empty() {
+            return name.to_string();
+        }
+
+        let formatted: Vec<String> = operands
+            .iter()
+            .enumerate()
+            .map(|(index, operand)| self.format_operand(instruction, index, operand))
+            .collect();
+
+        format!("{{{},{}}}", name, formatted.join(","))
+    }
+
+    fn format_operand(&self, instruction: &Instruction, index: usize, operand: &Term) -> String {
+        let name = instruction.opcode.name;
+
+        match (name, index, operand) {
+            ("label", 0, Term::Literal(label)) => label.to_string(),
+            ("line", 0, Term::Literal(line)) => self.format_line(*line as u32),
+            ("call", 1, Term::Label(label))
+            | ("call_last", 1, Term::Label(label))
+            | ("call_only", 1, Term::Label(label)) => self.format_local(*label),
+            ("call_ext", 1, Term::Literal(import))
+            | ("call_ext_last", 1, Term::Literal(import))
+            | ("call_ext_only", 1, Term::Literal(import))
+            | ("bif0", 0, Term::Literal(import))
+            | ("bif1", 1, Term::Literal(import))
+            | ("bif2", 1, Term::Literal(import))
+            | ("gc_bif1", 2, Term::Literal(import))
+            | ("gc_bif2", 2, Term::Literal(import))
+            | ("gc_bif3", 2, Term::Literal(import)) => self.format_import(*import as u32),
+            ("make_fun2", 0, Term::Literal(fun)) => self.format_fun(*fun as u32, true),
+            ("make_fun3", 0, Term::Literal(fun)) => self.format_fun(*fun as u32, false),
+            ("bs_put_string", 1, Term::Literal(offset)) => match operands_literal(instruction, 0) {
+                Some(len) => self.format_string(*offset, len),
+                None => offset.to_string(),
+            },
+            ("bs_match_string", 3, Term::Literal(offset)) => {
+                match operands_literal(instruction, 2) {
+                    Some(bits) => self.format_string(*offset, (bits + 7) / 8),
+                    None => offset.to_string(),
+                }
+            }
+            _ => self.format_term(operand),
+        }
+    }
+
+    fn format_term(&self, term: &Term) -> String {
+        match term {
+            Term::Literal(value) => value.to_string(),
+            Term::Integer(value) => format!("{{integer,{}}}", value),
+            Term::Atom(0) => "nil".to_string(),
+            Term::Atom(id) => match self.atom_name(*id) {
+                Some(name) => format!("{{atom,{}}}", format_atom(name)),
+                None => format!("{{atom,{}}}", id),
+            },
+            Term::X(register) => format!("{{x,{}}}", register),
+            Term::Y(register) => format!("{{y,{}}}", register),
+            Term::Label(label) => format!("{{f,{}}}", label),
+            Term::Character(character) => format!("{{char,{}}}", character),
+            Term::Float(float) => format!("{{float,{:?}}}", float),
+            Term::List(elements) => {
+                let elements: Vec<String> =
+                    elements.iter().map(|term| self.format_term(term)).collect();
+
+                format!("{{list,[{}]}}", elements.join(","))
+            }
+            Term::FloatRegister(register) => format!("{{fr,{}}}", register),
+            Term::AllocationList(allocations) => {
+                let allocations: Vec<String> = allocations
+                    .iter()
+                    .map(|allocation| match allocation {
+                        Allocation::Words(count) => format!("{{words,{}}}", count),
+                        Allocation::Floats(count) => format!("{{floats,{}}}", count),
+                        Allocation::Funs(count) => format!("{{funs,{}}}", count),
+                    })
+                    .collect();
+
+                format!("{{alloc,[{}]}}", allocations.join(","))
+            }
+            Term::ExtendedLiteral(index) => match self.literals.get(*index as usize) {
+                Some(literal) => format!("{{literal,{}}}", literal),
+                None => format!("{{literal,{}}}", index),
+            },
+            Term::TypedRegister {
+                register,
+                type_index,
+            } => format!("{{tr,{},{}}}", self.format_term(register), type_index),
+        }
+    }
+
+    fn format_line(&self, operand: u32) -> String {
+        match self.lines.and_then(|lines| lines.location(operand)) {
+            Some(location) => {
+                let file_name = match self
+                    .lines
+                    .and_then(|lines| lines.file_name(location.file_name_index))
+                {
+                    Some(file_name) => file_name.to_string(),
+                    None => format!("{}.erl", self.module()),
+                };
+
+                format!(
+                    "[{{location,{},{}}}]",
+                    format_string(file_name.as_bytes()),
+                    location.line
+                )
+            }
+            None => "[]".to_string(),
+        }
+    }
+
+    fn format_local(&self, label: u32) -> String {
+        match self.entries.get(&label) {
+            Some(&(function, arity)) => match self.atom_name(function) {
+                Some(name) => format!(
+                    "{{{},{},{}}}",
+                    format_atom(self.module()),
+                    format_atom(name),
+                    arity
+                ),
+                None => format!("{{f,{}}}", label),
+            },
+            None => format!("{{f,{}}}", label),
+        }
+    }
+
+    fn format_import(&self, index: u32) -> String {
+        let resolved = self.imports.get(index as usize).and_then(|import| {
+            let module = self.atom_name(import.module)?;
+            let function = self.atom_name(import.function)?;
+
+            Some(format!(
+                "{{extfunc,{},{},{}}}",
+                format_atom(module),
+                format_atom(function),
+                import.arity
+            ))
+        });
+
+        resolved.unwrap_or_else(|| index.to_string())
+    }
+
+    fn format_fun(&self, index: u32, with_num_free: bool) -> String {
+        match self.funs.get(index as usize) {
+            Some(fun) if with_num_free => format!(
+                "{{f,{}}},{},{},{}",
+                fun.label, fun.index, fun.old_uniq, fun.num_free
+            ),
+            Some(fun) => format!("{{f,{}}},{},{}", fun.label, fun.index, fun.old_uniq),
+            None => index.to_string(),
+        }
+    }
+
+    fn format_string(&self, offset: u64, len: u64) -> String {
+        let start = offset as usize;
+        let end = start + len as usize;
+
+        match self.strings.get(start..end) {
+            Some(bytes) => format!("{{string,{}}}", format_string(bytes)),
+            None => offset.to_string(),
+        }
+    }
+
+    fn atom_name(&self, id: parts::AtomId) -> Option<&'a str> {
+        if id == 0 {
+            None
+        } else {
+            self.atoms
+                .get(id as usize - 1)
+                .map(|atom| atom.name.as_str())
+        }
+    }
+}
+impl<'a> Display for Disassembly<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut exports: Vec<(&str, u32)> = self
+            .exports
+            .iter()
+            .filter_map(|export| Some((self.atom_name(export.function)?, export.arity)))
+            .collect();
+        exports.sort();
+        let exports: Vec<String> = exports
+            .iter()
+            .map(|(name, arity)| format!("{{{},{}}}", format_atom(name), arity))
+            .collect();
+
+        writeln!(
+            f,
+            "{{module, {}}}.  %% version = {}",
+            format_atom(self.module()),
+            self.code.version
+        )?;
+        writeln!(f)?;
+        writeln!(f, "{{exports, [{}]}}.", exports.join(","))?;
+        writeln!(f)?;
+        writeln!(f, "{{labels, {}}}.", self.code.label_count)?;
+
+        for function in self.functions() {
+            writeln!(f)?;
+            writeln!(f)?;
+            writeln!(
+                f,
+                "{{function, {}, {}, {}}}.",
+                format_atom(function.name),
+                function.arity,
+                function.entry
+            )?;
+
+            for instruction in function.instructions {
+                let indent = if instruction.opcode.name == "label" {
+                    "  "
+                } else {
+                    "    "
+                };
+
+                writeln!(f, "{}{}.", indent, self.format_instruction(instruction))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The function name atom and arity in the `func_info` instruction in `instructions`.
+fn func_info(instructions: &[Instruction]) -> Option<(parts::AtomId, u32)> {
+    let instruction = instructions
+        .iter()
+        .find(|instruction| instruction.opcode.name == "func_info")?;
+
+    match (&instruction.operands[1], &instruction.operands[2]) {
+        (Term::Atom(function), Term::Literal(arity)) => Some((*function, *arity as u32)),
+        _ => None,
+    }
+}
+
+fn operands_literal(instruction: &Instruction, index: usize) -> Option<u64> {
+    match instruction.operands.get(index) {
+        Some(Term::Literal(value)) => Some(*value),
+        _ => None,
+    }
+}
+
+/// Formats `name` as an Erlang atom, only quoting it when needed.
+fn format_atom(name: &str) -> String {
+    const RESERVED: &[&str] = &[
+        "after", "and", "andalso", "band", "begin", "bnot", "bor", "bsl", "bsr", "bxor", "case",
+        "catch", "cond", "div", "end", "fun", "if", "let", "not", "of", "or", "orelse", "receive",
+        "rem", "try", "when", "xor",
+    ];
+
+    let mut chars = name.chars();
+    let unquoted = match chars.next() {
+        Some(first) => {
+            first.is_ascii_lowercase()
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '@')
+                && !RESERVED.contains(&name)
+        }
+        None => false,
+    };
+
+    if unquoted {
+        name.to_string()
+    } else {
+        format!("'{}'", name.replace("\\", "\\\\").replace("'", "\\'"))
+    }
+}
+
+/// Formats `bytes` as an Erlang string, escaping anything that isn't printable ASCII.
+fn format_string(bytes: &[u8]) -> String {
+    let mut formatted = String::with_capacity(bytes.len() + 2);
+    formatted.push('"');
+
+    for &byte in bytes {
+        match byte {
+            b'"' => formatted.push_str("\\\""),
+            b'\\' => formatted.push_str("\\\\"),
+            b'\n' => formatted.push_str("\\n"),
+            b'\t' => formatted.push_str("\\t"),
+            0x20..=0x7E => formatted.push(byte as char),
+            _ => write!(formatted, "\\{:03o}", byte).unwrap(),
+        }
+    }
+
+    formatted.push('"');
+    formatted
+}
@@ -0,0 +1,58 @@
+use std::io::{Cursor, Read};
+
+use byteorder::ReadBytesExt;
+
+use crate::beam::reader::chunk::CodeChunk;
+use crate::beam::reader::compact;
+use crate::beam::reader::{ReadError, Result};
+
+use super::opcode::Opcode;
+
+/// The size of the fields after `info_size` in the `"Code"` chunk that are already decoded into
+/// [CodeChunk](CodeChunk).
+const KNOWN_INFO_SIZE: u32 = 16;
+
+/// A generic BEAM operation and its operands.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Instruction {
+    pub opcode: &'static Opcode,
+    pub operands: Vec<compact::Term>,
+}
+impl Instruction {
+    /// Decodes all the instructions in `code` up to, but not including, `int_code_end`.
+    pub fn decode_all(code: &CodeChunk) -> Result<Vec<Self>> {
+        let mut reader = Cursor::new(&code.bytecode);
+
+        // Newer compilers may add fields to the header that this reader does not know about
+        for _ in KNOWN_INFO_SIZE..code.info_size {
+            reader.read_u8()?;
+        }
+
+        let mut instructions = Vec::new();
+
+        while (reader.position() as usize) < code.bytecode.len() {
+            let instruction = Self::decode(&mut reader)?;
+
+            if instruction.opcode.name == "int_code_end" {
+                break;
+            }
+
+            instructions.push(instruction);
+        }
+
+        Ok(instructions)
+    }
+
+    /// Decodes a single instruction from `reader`.
+    pub fn decode<R: Read>(reader: &mut R) -> Result<Self> {
+        let number = reader.read_u8()?;
+        let opcode = Opcode::from_number(number).ok_or(ReadError::UnknownOpcode(number))?;
+        let mut operands = Vec::with_capacity(opcode.arity);
+
+        for _ in 0..opcode.arity {
+            operands.push(compact::Term::decode(reader)?);
+        }
+
+        Ok(Instruction { opcode, operands })
+    }
+}
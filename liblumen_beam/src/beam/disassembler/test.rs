@@ -0,0 +1,111 @@
+use std::path::PathBuf;
+
+use crate::beam::disassembler::{Disassembly, Opcode};
+use crate::beam::reader::compact::Term;
+use crate::beam::reader::StandardBeamFile;
+
+#[test]
+fn opcodes() {
+    let opcode = Opcode::from_number(64).unwrap();
+    assert_eq!("move", opcode.name);
+    assert_eq!(2, opcode.arity);
+
+    assert_eq!(Some(153), Opcode::from_name("line").map(|o| o.number));
+    assert_eq!(None, Opcode::from_number(0));
+    assert_eq!(None, Opcode::from_number(Opcode::MAX + 1));
+}
+
+#[test]
+fn instructions() {
+    let beam = StandardBeamFile::from_file(test_file("test.beam")).unwrap();
+    let disassembly = Disassembly::new(&beam).unwrap();
+    let instructions = disassembly.instructions();
+
+    assert_eq!(35, instructions.len());
+    assert_eq!("label", instructions[0].opcode.name);
+    assert_eq!(vec![Term::Literal(1)], instructions[0].operands);
+    assert_eq!("func_info", instructions[2].opcode.name);
+    assert_eq!(
+        vec![Term::Atom(1), Term::Atom(2), Term::Literal(1)],
+        instructions[2].operands
+    );
+    assert_eq!(
+        "return",
+        instructions
+            .iter()
+            .find(|i| i.opcode.arity == 0)
+            .unwrap()
+            .opcode
+            .name
+    );
+}
+
+#[test]
+fn functions() {
+    let beam = StandardBeamFile::from_file(test_file("test.beam")).unwrap();
+    let disassembly = Disassembly::new(&beam).unwrap();
+
+    assert_eq!("test", disassembly.module());
+    assert_eq!(
+        vec![
+            "hello/1@2",
+            "module_info/0@4",
+            "module_info/1@6",
+            "-hello/1-fun-0-/1@8"
+        ],
+        disassembly
+            .functions()
+            .iter()
+            .map(|f| format!("{}/{}@{}", f.name, f.arity, f.entry))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn listing() {
+    let beam = StandardBeamFile::from_file(test_file("test.beam")).unwrap();
+    let listing = Disassembly::new(&beam).unwrap().to_string();
+
+    assert!(listing.starts_with(
+        "{module, test}.  %% version = 0\n\
+         \n\
+         {exports, [{hello,1},{module_info,0},{module_info,1}]}.\n\
+         \n\
+         {labels, 9}.\n\
+         \n\
+         \n\
+         {function, hello, 1, 2}.\n  \
+           {label,1}.\n    \
+             {line,[{location,\"test.erl\",7}]}.\n    \
+             {func_info,{atom,test},{atom,hello},1}.\n  \
+           {label,2}.\n    \
+             {allocate,0,1}.\n    \
+             {make_fun2,{f,8},0,38182595,1}.\n    \
+             {line,[{location,\"test.erl\",9}]}.\n    \
+             {call_fun,0}.\n    \
+             {move,{atom,ok},{x,0}}.\n    \
+             {deallocate,0}.\n    \
+             return.\n"
+    ));
+    assert!(listing.contains("    {line,[]}.\n"));
+    assert!(listing.contains("    {call_ext_only,1,{extfunc,erlang,get_module_info,1}}.\n"));
+    assert!(listing.contains("{function, '-hello/1-fun-0-', 1, 8}.\n"));
+    assert!(listing.contains("    {put_list,{x,0},nil,{x,1}}.\n"));
+    assert!(listing.contains("    {move,{literal,[72,101,108,108,111,32,126,112,33]},{x,0}}.\n"));
+    assert!(listing.contains("    {call_ext_only,2,{extfunc,io,format,2}}.\n"));
+}
+
+#[test]
+fn elixir_module() {
+    let beam = StandardBeamFile::from_file(test_file("Elixir.Unicode.beam")).unwrap();
+    let listing = Disassembly::new(&beam).unwrap().to_string();
+
+    assert!(listing.starts_with("{module, 'Elixir.Unicode'}."));
+    assert!(listing.contains("{location,\"unicode.ex\","));
+}
+
+fn test_file(name: &str) -> PathBuf {
+    let mut path = PathBuf::from("tests/testdata/reader");
+    path.push(name);
+    path
+}
@@ -29,6 +29,7 @@
 //!     beam.to_file("my.beam").unwrap();
 //!
 pub mod chunk;
+pub mod compact;
 pub mod parts;
 
 mod beam_file;
@@ -49,6 +50,10 @@ pub enum ReadError {
     UnexpectedMagicNumber([u8; 4]),
     UnexpectedFormType([u8; 4]),
     UnexpectedChunk { id: chunk::Id, expected: chunk::Id },
+    UnknownOpcode(u8),
+    InvalidCompactTerm(String),
+    InvalidLiteral(crate::serialization::etf::DecodeError),
+    MissingChunk(chunk::Id),
 }
 
 impl std::fmt::Display for ReadError {
@@ -76,6 +81,10 @@ impl std::fmt::Display for ReadError {
                 bytes_to_str(id),
                 bytes_to_str(expected)
             ),
+            UnknownOpcode(ref opcode) => write!(f, "Unknown opcode {}", opcode),
+            InvalidCompactTerm(ref message) => write!(f, "Invalid compact term: {}", message),
+            InvalidLiteral(ref x) => write!(f, "Invalid literal: {}", x),
+            MissingChunk(ref id) => write!(f, "Missing chunk {}", bytes_to_str(id)),
         }
     }
 }
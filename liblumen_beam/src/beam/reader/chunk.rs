@@ -20,9 +20,11 @@ use byteorder::BigEndian;
 use byteorder::ReadBytesExt;
 use byteorder::WriteBytesExt;
 use libflate::zlib;
+use num::traits::ToPrimitive;
 
+use super::compact;
 use super::parts;
-use super::Result;
+use super::{ReadError, Result};
 
 /// The identifier which indicates the type of a chunk.
 pub type Id = [u8; 4];
@@ -205,7 +207,7 @@ impl Chunk for AtomChunk {
 ///
 /// - [AtomChunk](AtomChunk) for the module name and direct atom usage.
 /// - [ImpTChunk](ImpTChunk) to convert the import index to MFA for external calls.
-/// - "Line" chunk for `file:line` information for stacktraces that are set with the `line`
+/// - [LineChunk](LineChunk) for `file:line` information for stacktraces that are set with the `line`
 ///   operation.
 /// - [LitTChunk](LitTChunk) for literal (constant) references used as arguments to operations.
 /// - [StrTChunk](StrTChunk) for strings from the string pool used in `bs_*` operations.
@@ -791,6 +793,157 @@ impl Chunk for DocsChunk {
     }
 }
 
+/// The `"Line"` chunk maps the operand of each `line` operation in [CodeChunk](CodeChunk) to a
+/// file name and line number, so that stacktraces can include locations.
+///
+/// The items are in the compact term encoding: an integer item is the line number of the next
+/// location and an atom item switches the file of the locations that follow to the file name at
+/// that index, where `0` is the module's own source file.  The `line` operand `0` is reserved for
+/// an unknown location, so the location for operand `N` is the `N`th integer item.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LineChunk {
+    /// Line table format version.
+    pub version: u32,
+
+    /// Reserved flags.
+    pub flags: u32,
+
+    /// The number of `line` operations in [CodeChunk](CodeChunk).
+    pub instruction_count: u32,
+
+    /// The line number and file name items in the order they were encoded.
+    pub items: Vec<parts::LineItem>,
+
+    /// The names of the files other than the module's own source file.
+    pub file_names: Vec<String>,
+}
+impl LineChunk {
+    /// The locations in the order of the `line` operands that refer to them, starting with operand
+    /// `1`.
+    pub fn locations(&self) -> Vec<parts::Location> {
+        let mut file_name_index = 0;
+        let mut locations = Vec::new();
+
+        for item in &self.items {
+            match *item {
+                parts::LineItem::Line(line) => locations.push(parts::Location {
+                    file_name_index,
+                    line,
+                }),
+                parts::LineItem::FileName(index) => file_name_index = index,
+            }
+        }
+
+        locations
+    }
+
+    /// The location for the operand of a `line` operation, or `None` for operand `0`, which is an
+    /// unknown location.
+    pub fn location(&self, operand: u32) -> Option<parts::Location> {
+        if operand == 0 {
+            None
+        } else {
+            self.locations().get(operand as usize - 1).cloned()
+        }
+    }
+
+    /// The name of the file at `index` in [Location](parts::Location), or `None` for `0`, which
+    /// is the module's own source file.
+    pub fn file_name(&self, index: u32) -> Option<&str> {
+        if index == 0 {
+            None
+        } else {
+            self.file_names
+                .get(index as usize - 1)
+                .map(|file_name| file_name.as_str())
+        }
+    }
+}
+impl Chunk for LineChunk {
+    fn id(&self) -> &Id {
+        b"Line"
+    }
+
+    fn decode_data<R: Read>(id: &Id, mut reader: R) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        auxiliary::check_chunk_id(id, b"Line")?;
+        let version = reader.read_u32::<BigEndian>()?;
+        let flags = reader.read_u32::<BigEndian>()?;
+        let instruction_count = reader.read_u32::<BigEndian>()?;
+        let line_count = reader.read_u32::<BigEndian>()?;
+        let file_name_count = reader.read_u32::<BigEndian>()?;
+
+        let mut items = Vec::with_capacity(line_count as usize);
+        let mut lines = 0;
+        while lines < line_count {
+            let item = match compact::Term::decode(&mut reader)? {
+                compact::Term::Integer(line) => {
+                    lines += 1;
+                    parts::LineItem::Line(line.to_u32().ok_or_else(|| {
+                        ReadError::InvalidCompactTerm(format!("invalid line {}", line))
+                    })?)
+                }
+                compact::Term::Atom(index) => parts::LineItem::FileName(index),
+                term => {
+                    return Err(ReadError::InvalidCompactTerm(format!(
+                        "expected line or file name item, got {:?}",
+                        term
+                    )))
+                }
+            };
+            items.push(item);
+        }
+
+        let mut file_names = Vec::with_capacity(file_name_count as usize);
+        for _ in 0..file_name_count {
+            let len = reader.read_u16::<BigEndian>()? as usize;
+            let mut buf = vec![0; len];
+            reader.read_exact(&mut buf)?;
+            file_names.push(str::from_utf8(&buf)?.to_string());
+        }
+
+        Ok(LineChunk {
+            version,
+            flags,
+            instruction_count,
+            items,
+            file_names,
+        })
+    }
+
+    fn encode_data<W: Write>(&self, mut writer: W) -> Result<()> {
+        let line_count = self
+            .items
+            .iter()
+            .filter(|item| match item {
+                parts::LineItem::Line(_) => true,
+                parts::LineItem::FileName(_) => false,
+            })
+            .count();
+
+        writer.write_u32::<BigEndian>(self.version)?;
+        writer.write_u32::<BigEndian>(self.flags)?;
+        writer.write_u32::<BigEndian>(self.instruction_count)?;
+        writer.write_u32::<BigEndian>(line_count as u32)?;
+        writer.write_u32::<BigEndian>(self.file_names.len() as u32)?;
+        for item in &self.items {
+            let term = match *item {
+                parts::LineItem::Line(line) => compact::Term::Integer(line.into()),
+                parts::LineItem::FileName(index) => compact::Term::Atom(index),
+            };
+            term.encode(&mut writer)?;
+        }
+        for file_name in &self.file_names {
+            assert!(file_name.len() < 0x10000);
+            writer.write_u16::<BigEndian>(file_name.len() as u16)?;
+            writer.write_all(file_name.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
 /// A representation of commonly used chunk.
 ///
 /// ```
@@ -818,6 +971,7 @@ pub enum StandardChunk {
     Abst(AbstChunk),
     Dbgi(DbgiChunk),
     Docs(DocsChunk),
+    Line(LineChunk),
     Unknown(RawChunk),
 }
 impl Chunk for StandardChunk {
@@ -837,6 +991,7 @@ impl Chunk for StandardChunk {
             Abst(ref c) => c.id(),
             Dbgi(ref c) => c.id(),
             Docs(ref c) => c.id(),
+            Line(ref c) => c.id(),
             Unknown(ref c) => c.id(),
         }
     }
@@ -860,6 +1015,7 @@ impl Chunk for StandardChunk {
             b"Abst" => Ok(Abst(AbstChunk::decode_data(id, reader)?)),
             b"Dbgi" => Ok(Dbgi(DbgiChunk::decode_data(id, reader)?)),
             b"Docs" => Ok(Docs(DocsChunk::decode_data(id, reader)?)),
+            b"Line" => Ok(Line(LineChunk::decode_data(id, reader)?)),
             _ => Ok(Unknown(RawChunk::decode_data(id, reader)?)),
         }
     }
@@ -879,6 +1035,7 @@ impl Chunk for StandardChunk {
             Abst(ref c) => c.encode_data(writer),
             Dbgi(ref c) => c.encode_data(writer),
             Docs(ref c) => c.encode_data(writer),
+            Line(ref c) => c.encode_data(writer),
             Unknown(ref c) => c.encode_data(writer),
         }
     }
@@ -0,0 +1,281 @@
+//! The compact term encoding used for the operands in the `"Code"` chunk and for the items in the
+//! `"Line"` chunk.
+//!
+//! The low 3 bits of the first byte are the tag.  For all tags except the extended tag, the value
+//! follows in one of three forms:
+//!
+//! - `vvvv0ttt` - values less than 16 are stored in the high 4 bits.
+//! - `vvv01ttt vvvvvvvv` - values less than 2048 are stored in 11 bits across 2 bytes.
+//! - `nnn11ttt` - larger values are stored big-endian in the `nnn + 2` bytes that follow, or when
+//!   `nnn` is `7`, in the number of bytes given by a following `Literal` plus `9`.
+//!
+//! The extended tag uses the high 4 bits of the first byte to select lists, floating point
+//! registers, allocation lists and indices into the `"LitT"` chunk.
+//!
+//! ## References
+//!
+//! - [BEAM Wisdoms - BEAM File Format - Compact Term Encoding](http://beam-wisdoms.clau.se/en/latest/indepth-beam-file.html#beam-compact-term-encoding)
+//! - [`beam_asm:encode_arg/2`](https://github.com/erlang/otp/blob/OTP-23.0/lib/compiler/src/beam_asm.erl)
+//!
+//! ## Alternative Implementations
+//!
+//! - [`org.elixir_lang.beam.term.Term` in IntelliJ Elixir](https://github.com/KronicDeth/intellij-elixir/blob/
+//!   2f5c826040681e258e98c3e2f02b25985cd0766b/src/org/elixir_lang/beam/term/Term.kt) in Kotlin
+use std::io::{Read, Write};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use num::bigint::BigInt;
+use num::traits::{Signed, ToPrimitive};
+
+use super::{ReadError, Result};
+
+const LITERAL: u8 = 0;
+const INTEGER: u8 = 1;
+const ATOM: u8 = 2;
+const X_REGISTER: u8 = 3;
+const Y_REGISTER: u8 = 4;
+const LABEL: u8 = 5;
+const CHARACTER: u8 = 6;
+const EXTENDED: u8 = 7;
+
+const EXTENDED_FLOAT: u8 = 0;
+const EXTENDED_LIST: u8 = 1;
+const EXTENDED_FLOAT_REGISTER: u8 = 2;
+const EXTENDED_ALLOCATION_LIST: u8 = 3;
+const EXTENDED_LITERAL: u8 = 4;
+const EXTENDED_TYPED_REGISTER: u8 = 5;
+
+/// A compactly encoded term.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Term {
+    /// An unsigned value whose meaning depends on the operation, such as an arity or an index
+    /// into the `"ImpT"` chunk.
+    Literal(u64),
+    Integer(BigInt),
+    /// A one-based index into the `"Atom"` chunk.  `0` is `[]`.
+    Atom(u32),
+    X(u32),
+    Y(u32),
+    Label(u32),
+    Character(u32),
+    /// Only produced by very old compilers.
+    Float(f64),
+    List(Vec<Term>),
+    FloatRegister(u32),
+    AllocationList(Vec<Allocation>),
+    /// A zero-based index into the `"LitT"` chunk.
+    ExtendedLiteral(u32),
+    /// A register annotated with a zero-based index into the `"Type"` chunk.
+    TypedRegister {
+        register: Box<Term>,
+        type_index: u32,
+    },
+}
+impl Term {
+    /// Reads a term from `reader`.
+    pub fn decode<R: Read>(reader: &mut R) -> Result<Self> {
+        let first = reader.read_u8()?;
+        let tag = first & 0b111;
+
+        if tag == EXTENDED {
+            return Self::decode_extended(first >> 4, reader);
+        }
+
+        let value = decode_value(tag, first, reader)?;
+
+        if tag == INTEGER {
+            return Ok(Term::Integer(value));
+        }
+
+        let value = value
+            .to_u64()
+            .ok_or_else(|| ReadError::InvalidCompactTerm(format!("{} is too large", value)))?;
+
+        match tag {
+            LITERAL => Ok(Term::Literal(value)),
+            _ => {
+                let value = to_u32(value)?;
+
+                Ok(match tag {
+                    ATOM => Term::Atom(value),
+                    X_REGISTER => Term::X(value),
+                    Y_REGISTER => Term::Y(value),
+                    LABEL => Term::Label(value),
+                    CHARACTER => Term::Character(value),
+                    _ => unreachable!(),
+                })
+            }
+        }
+    }
+
+    /// Writes the term to `writer` in the smallest form, the same as `beam_asm`.
+    ///
+    /// Only the terms that do not use the extended tag can be written.
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let (tag, value) = match self {
+            Term::Literal(value) => (LITERAL, BigInt::from(*value)),
+            Term::Integer(value) => (INTEGER, value.clone()),
+            Term::Atom(value) => (ATOM, BigInt::from(*value)),
+            Term::X(value) => (X_REGISTER, BigInt::from(*value)),
+            Term::Y(value) => (Y_REGISTER, BigInt::from(*value)),
+            Term::Label(value) => (LABEL, BigInt::from(*value)),
+            Term::Character(value) => (CHARACTER, BigInt::from(*value)),
+            _ => {
+                return Err(ReadError::InvalidCompactTerm(format!(
+                    "{:?} cannot be encoded",
+                    self
+                )))
+            }
+        };
+
+        encode_value(tag, &value, writer)
+    }
+
+    /// The value of a `Literal` that is used as a count, such as the length of a list.
+    fn decode_count<R: Read>(reader: &mut R) -> Result<u32> {
+        match Self::decode(reader)? {
+            Term::Literal(count) => to_u32(count),
+            term => Err(ReadError::InvalidCompactTerm(format!(
+                "expected literal count, got {:?}",
+                term
+            ))),
+        }
+    }
+
+    fn decode_extended<R: Read>(extended_tag: u8, reader: &mut R) -> Result<Self> {
+        match extended_tag {
+            EXTENDED_FLOAT => Ok(Term::Float(reader.read_f64::<BigEndian>()?)),
+            EXTENDED_LIST => {
+                let len = Self::decode_count(reader)?;
+                let mut elements = Vec::with_capacity(len as usize);
+
+                for _ in 0..len {
+                    elements.push(Self::decode(reader)?);
+                }
+
+                Ok(Term::List(elements))
+            }
+            EXTENDED_FLOAT_REGISTER => Ok(Term::FloatRegister(Self::decode_count(reader)?)),
+            EXTENDED_ALLOCATION_LIST => {
+                let len = Self::decode_count(reader)?;
+                let mut allocations = Vec::with_capacity(len as usize);
+
+                for _ in 0..len {
+                    let kind = Self::decode_count(reader)?;
+                    let count = Self::decode_count(reader)?;
+
+                    allocations.push(Allocation::new(kind, count)?);
+                }
+
+                Ok(Term::AllocationList(allocations))
+            }
+            EXTENDED_LITERAL => Ok(Term::ExtendedLiteral(Self::decode_count(reader)?)),
+            EXTENDED_TYPED_REGISTER => {
+                let register = Self::decode(reader)?;
+                let type_index = Self::decode_count(reader)?;
+
+                Ok(Term::TypedRegister {
+                    register: Box::new(register),
+                    type_index,
+                })
+            }
+            _ => Err(ReadError::InvalidCompactTerm(format!(
+                "unknown extended tag {}",
+                extended_tag
+            ))),
+        }
+    }
+}
+
+/// An entry in an allocation list, which is used by `allocate*` and `test_heap` operations when
+/// more than words need to be allocated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Allocation {
+    Words(u32),
+    Floats(u32),
+    Funs(u32),
+}
+impl Allocation {
+    fn new(kind: u32, count: u32) -> Result<Self> {
+        match kind {
+            0 => Ok(Allocation::Words(count)),
+            1 => Ok(Allocation::Floats(count)),
+            2 => Ok(Allocation::Funs(count)),
+            _ => Err(ReadError::InvalidCompactTerm(format!(
+                "unknown allocation kind {}",
+                kind
+            ))),
+        }
+    }
+}
+
+fn decode_value<R: Read>(tag: u8, first: u8, reader: &mut R) -> Result<BigInt> {
+    if first & 0b1000 == 0 {
+        Ok(BigInt::from(first >> 4))
+    } else if first & 0b1_0000 == 0 {
+        let high = ((first & 0b1110_0000) as u32) << 3;
+        let low = reader.read_u8()? as u32;
+
+        Ok(BigInt::from(high | low))
+    } else {
+        let len = match first >> 5 {
+            7 => Term::decode_count(reader)? as usize + 9,
+            n => n as usize + 2,
+        };
+        let mut bytes = vec![0; len];
+        reader.read_exact(&mut bytes)?;
+
+        // Only integers are signed, but `beam_asm` adds a leading zero byte to any other value
+        // that would otherwise look negative.
+        if tag == INTEGER {
+            Ok(BigInt::from_signed_bytes_be(&bytes))
+        } else {
+            Ok(BigInt::from_bytes_be(num::bigint::Sign::Plus, &bytes))
+        }
+    }
+}
+
+fn encode_value<W: Write>(tag: u8, value: &BigInt, writer: &mut W) -> Result<()> {
+    match value.to_i64() {
+        Some(small) if 0 <= small && small < 16 => {
+            writer.write_u8(((small as u8) << 4) | tag)?;
+        }
+        Some(medium) if 0 <= medium && medium < 2048 => {
+            writer.write_u8((((medium >> 3) as u8) & 0b1110_0000) | 0b1000 | tag)?;
+            writer.write_u8(medium as u8)?;
+        }
+        _ => {
+            let mut bytes = value.to_signed_bytes_be();
+
+            // Small negative integers still take the 2 byte minimum of this form
+            if bytes.len() < 2 {
+                let sign = if value.is_negative() { 0xFF } else { 0 };
+                bytes.insert(0, sign);
+            }
+
+            let len = bytes.len();
+
+            if len <= 8 {
+                writer.write_u8((((len - 2) as u8) << 5) | 0b1_1000 | tag)?;
+            } else {
+                writer.write_u8(0b1111_1000 | tag)?;
+                encode_value(LITERAL, &BigInt::from(len - 9), writer)?;
+            }
+
+            writer.write_all(&bytes)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn to_u32(value: u64) -> Result<u32> {
+    if value <= u32::max_value() as u64 {
+        Ok(value as u32)
+    } else {
+        Err(ReadError::InvalidCompactTerm(format!(
+            "{} is too large",
+            value
+        )))
+    }
+}
@@ -59,3 +59,20 @@ pub struct Function {
     pub num_free: u32,
     pub old_uniq: u32,
 }
+
+/// An item in the "Line" chunk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineItem {
+    /// The line number of the next location.
+    Line(u32),
+    /// The index of the file name for the following locations.
+    FileName(u32),
+}
+
+/// A location referenced by a `line` operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Location {
+    /// The index of the file name in the "Line" chunk, where `0` is the module's own source file.
+    pub file_name_index: u32,
+    pub line: u32,
+}
@@ -179,6 +179,57 @@ fn standard_chunks() {
 
     // Abst Chunk
     assert_eq!(307, find_chunk!(beam, Abst).term.len());
+
+    // Line Chunk
+    let line = find_chunk!(beam, Line);
+    assert_eq!(8, line.instruction_count);
+    assert_eq!(
+        vec![7, 9, 8],
+        line.locations().iter().map(|l| l.line).collect::<Vec<_>>()
+    );
+    assert_eq!(None, line.location(0));
+    assert_eq!(
+        Some(parts::Location {
+            file_name_index: 0,
+            line: 9
+        }),
+        line.location(2)
+    );
+
+    let beam = StandardBeamFile::from_file(test_file("Elixir.Unicode.beam")).unwrap();
+    let line = find_chunk!(beam, Line);
+    assert_eq!(vec!["unicode.ex"], line.file_names);
+    assert_eq!(Some("unicode.ex"), line.file_name(1));
+    assert!(line.locations().iter().all(|l| l.file_name_index == 1));
+}
+
+#[test]
+fn compact_terms() {
+    use crate::beam::reader::compact::Term;
+
+    let cases = vec![
+        (Term::Literal(0), vec![0x00]),
+        (Term::X(1), vec![0x13]),
+        (Term::Label(15), vec![0xF5]),
+        (Term::Atom(16), vec![0x0A, 0x10]),
+        (Term::Literal(2047), vec![0xE8, 0xFF]),
+        (Term::Literal(2048), vec![0x18, 0x08, 0x00]),
+        (Term::Integer((-1).into()), vec![0x19, 0xFF, 0xFF]),
+        (Term::Integer(40_000.into()), vec![0x39, 0x00, 0x9C, 0x40]),
+        (
+            Term::Integer(num::BigInt::from(1) << 80),
+            vec![0xF9, 0x20, 0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+        ),
+    ];
+
+    for (term, bytes) in cases {
+        let mut encoded = Vec::new();
+        term.encode(&mut encoded).unwrap();
+        assert_eq!(bytes, encoded, "{:?}", term);
+
+        let decoded = Term::decode(&mut std::io::Cursor::new(&bytes)).unwrap();
+        assert_eq!(term, decoded);
+    }
 }
 
 enum EncodeTestChunk {
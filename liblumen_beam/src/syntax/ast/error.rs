@@ -41,6 +41,12 @@ pub enum FromBeamError {
     #[fail(display = "debug info is required but not present")]
     NoDebugInfo,
 
+    #[fail(
+        display = "debug info from the {} backend is not supported, only erl_abstract_code",
+        _0
+    )]
+    UnsupportedDebugInfo(String),
+
     #[fail(display = "missing module attribute")]
     NoModuleAttribute,
 
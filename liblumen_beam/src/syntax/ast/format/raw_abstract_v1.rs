@@ -40,22 +40,156 @@ pub struct AbstractCode {
     pub code: etf::Term,
 }
 impl AbstractCode {
+    /// Reads the abstract code from the `"Abst"` chunk or, for newer compilers, the `"Dbgi"` chunk.
     pub fn from_beam_file<P: AsRef<Path>>(path: P) -> FromBeamResult<Self> {
         let beam = crate::beam::reader::RawBeamFile::from_file(path)?;
-        let chunk = beam
-            .chunks()
-            .into_iter()
-            .find(|c| c.id() == b"Abst")
+        let chunks = beam.chunks();
+
+        // Modules compiled without `debug_info` still have an empty `"Abst"` chunk
+        if let Some(chunk) = chunks
+            .iter()
+            .find(|c| c.id() == b"Abst" && !c.data.is_empty())
+        {
+            let code = etf::Term::decode(std::io::Cursor::new(&chunk.data))?;
+            return Ok(AbstractCode { code });
+        }
+
+        let chunk = chunks
+            .iter()
+            .find(|c| c.id() == b"Dbgi")
             .ok_or(FromBeamError::NoDebugInfo)?;
-        let code = etf::Term::decode(std::io::Cursor::new(&chunk.data))?;
+        let debug_info = etf::Term::decode(std::io::Cursor::new(&chunk.data))?;
+        let code = Self::from_debug_info(debug_info)?;
+
         Ok(AbstractCode { code })
     }
+
+    /// Extracts the forms from `{debug_info_v1, Backend, Data}`, which are only available without
+    /// calling `Backend:debug_info/4` when `Backend` is `erl_abstract_code`.
+    fn from_debug_info(debug_info: etf::Term) -> FromBeamResult<etf::Term> {
+        let mut elements = match debug_info {
+            etf::Term::Tuple(tuple) if tuple.elements.len() == 3 => tuple.elements,
+            _ => return Err(FromBeamError::NoDebugInfo),
+        };
+        let data = elements.pop().unwrap();
+        let backend = elements.pop().unwrap();
+
+        match backend {
+            etf::Term::Atom(ref backend) if backend.name == "erl_abstract_code" => (),
+            etf::Term::Atom(backend) => {
+                return Err(FromBeamError::UnsupportedDebugInfo(backend.name))
+            }
+            _ => return Err(FromBeamError::NoDebugInfo),
+        }
+
+        // `Data` is `{Forms, Options}` or `{none, Options}` without `debug_info`
+        let forms = match data {
+            etf::Term::Tuple(mut tuple) if tuple.elements.len() == 2 => {
+                tuple.elements.swap_remove(0)
+            }
+            _ => return Err(FromBeamError::NoDebugInfo),
+        };
+
+        match forms {
+            forms @ etf::Term::List(_) => Ok(etf::Term::from(etf::Tuple::from(vec![
+                etf::Term::from(etf::Atom::from("raw_abstract_v1")),
+                forms,
+            ]))),
+            _ => Err(FromBeamError::NoDebugInfo),
+        }
+    }
+
     pub fn to_forms(&self) -> FromBeamResult<Vec<form::Form>> {
         let (_, forms) = self
             .code
             .as_match(("raw_abstract_v1", VarList(to!(form::Form))))?;
         Ok(forms)
     }
+
+    /// Writes the forms as Erlang terms, each followed by `.`, which is the format of `.abstr`
+    /// files.
+    ///
+    /// The forms are checked with [to_forms](AbstractCode::to_forms) first, so that anything that
+    /// isn't valid abstract code is reported instead of being written.
+    pub fn to_listing(&self) -> FromBeamResult<String> {
+        self.to_forms()?;
+
+        let forms = match self.code {
+            etf::Term::Tuple(ref tuple) => match tuple.elements.get(1) {
+                Some(etf::Term::List(ref list)) => &list.elements,
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+
+        let mut listing = String::new();
+        for form in forms {
+            write_term(&mut listing, form);
+            listing.push_str(".\n");
+        }
+
+        Ok(listing)
+    }
+}
+
+/// Writes `term` with Erlang syntax.  Unlike `Display` for [etf::Term](etf::Term), floats always
+/// have a fraction, so that they're read back as floats.
+fn write_term(out: &mut String, term: &etf::Term) {
+    use std::fmt::Write;
+
+    fn write_elements(out: &mut String, elements: &[etf::Term]) {
+        for (i, element) in elements.iter().enumerate() {
+            if i != 0 {
+                out.push(',');
+            }
+            write_term(out, element);
+        }
+    }
+
+    match term {
+        etf::Term::Float(float) => {
+            let formatted = format!("{:?}", float.value);
+
+            match formatted.find('e') {
+                Some(exponent) if !formatted[..exponent].contains('.') => {
+                    out.push_str(&formatted[..exponent]);
+                    out.push_str(".0");
+                    out.push_str(&formatted[exponent..]);
+                }
+                _ => out.push_str(&formatted),
+            }
+        }
+        etf::Term::List(list) => {
+            out.push('[');
+            write_elements(out, &list.elements);
+            out.push(']');
+        }
+        etf::Term::ImproperList(list) => {
+            out.push('[');
+            write_elements(out, &list.elements);
+            out.push('|');
+            write_term(out, &list.last);
+            out.push(']');
+        }
+        etf::Term::Tuple(tuple) => {
+            out.push('{');
+            write_elements(out, &tuple.elements);
+            out.push('}');
+        }
+        etf::Term::Map(map) => {
+            out.push_str("#{");
+            for (i, (key, value)) in map.entries.iter().enumerate() {
+                if i != 0 {
+                    out.push(',');
+                }
+                write_term(out, key);
+                out.push_str("=>");
+                write_term(out, value);
+            }
+            out.push('}');
+        }
+        _ => write!(out, "{}", term).unwrap(),
+    }
 }
 
 trait FromTerm<'a> {
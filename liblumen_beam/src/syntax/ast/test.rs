@@ -1,3 +1,4 @@
+use crate::syntax::ast::error::FromBeamError;
 use crate::syntax::ast::*;
 
 #[test]
@@ -9,3 +10,31 @@ fn it_works() {
         })
         .unwrap();
 }
+
+#[test]
+fn listing() {
+    use crate::syntax::ast::format::raw_abstract_v1::AbstractCode;
+
+    let code = AbstractCode::from_beam_file("tests/testdata/reader/test.beam").unwrap();
+    let listing = code.to_listing().unwrap();
+
+    assert!(listing.starts_with("{'attribute',1,'file',{[116,101,115,116,46,101,114,108],1}}.\n"));
+    assert!(listing.contains("{'attribute',2,'module','test'}.\n"));
+    assert!(listing.ends_with("{'eof',11}.\n"));
+}
+
+#[test]
+fn no_debug_info() {
+    match AST::from_beam_file("tests/testdata/simple.beam") {
+        Err(FromBeamError::NoDebugInfo) => (),
+        other => panic!("expected NoDebugInfo, got {:?}", other),
+    }
+}
+
+#[test]
+fn unsupported_debug_info() {
+    match AST::from_beam_file("tests/testdata/reader/Elixir.Unicode.beam") {
+        Err(FromBeamError::UnsupportedDebugInfo(ref backend)) if backend == "elixir_erl" => (),
+        other => panic!("expected UnsupportedDebugInfo, got {:?}", other),
+    }
+}
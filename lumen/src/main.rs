@@ -69,6 +69,9 @@ fn handle_help(err: &HelpRequested) -> ! {
     match err.primary() {
         "compile" => argparser::print_compile_help(),
         "print" => argparser::print_print_help(),
+        "shell" => argparser::print_shell_help(),
+        "lsp" => argparser::print_lsp_help(),
+        "xref" => argparser::print_xref_help(),
         _ => unimplemented!(),
     }
     process::exit(1);
@@ -0,0 +1,21 @@
+-module(init).
+
+-export([start/0, double/1]).
+
+-import(erlang, [print/1]).
+
+-spec start() -> ok | error.
+start() ->
+  Local = fun double/1,
+  Remote = fun ?MODULE:double/1,
+  Result = case {Local(1), Remote(2), Local =:= fun double/1, Remote =:= fun init:double/1, Local =:= Remote} of
+             {2, 4, true, true, false} ->
+               <<"Local and remote captures differ.">>;
+             _ ->
+               <<"Local and remote captures are confused.">>
+           end,
+  print(Result).
+
+-spec double(integer()) -> integer().
+double(X) ->
+  X * 2.
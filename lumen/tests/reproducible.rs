@@ -0,0 +1,78 @@
+mod reproducible {
+    use std::fs;
+    use std::path::Path;
+    use std::process::{Command, Stdio};
+
+    #[test]
+    fn building_in_different_directories_produces_identical_binaries() {
+        let first = build("first");
+        let second = build("second");
+
+        assert_eq!(
+            first.len(),
+            second.len(),
+            "binaries built from the same sources in different directories differ in size"
+        );
+        if let Some(offset) = first.iter().zip(second.iter()).position(|(a, b)| a != b) {
+            panic!(
+                "binaries built from the same sources in different directories differ at {:#x}",
+                offset
+            );
+        }
+    }
+
+    /// Copies the test sources into a fresh directory named `name`, and builds them from there
+    /// with that directory remapped to the same prefix, returning the contents of the binary
+    fn build(name: &str) -> Vec<u8> {
+        let cwd = std::env::current_dir().unwrap();
+        let project_dir = cwd.join("_build/reproducible").join(name);
+        if project_dir.exists() {
+            fs::remove_dir_all(&project_dir).unwrap();
+        }
+        let src_dir = project_dir.join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        for source in fs::read_dir(cwd.join("tests/reproducible")).unwrap() {
+            let source = source.unwrap().path();
+            fs::copy(&source, src_dir.join(source.file_name().unwrap())).unwrap();
+        }
+
+        let output_dir = Path::new("_build");
+        let mut command = Command::new(cwd.join("../bin/lumen"));
+
+        command
+            .current_dir(&project_dir)
+            .arg("compile")
+            .arg("--output-dir")
+            .arg(output_dir)
+            .arg("-o")
+            .arg(output_dir.join("reproducible"))
+            .arg("--remap-path-prefix")
+            .arg(format!("{}=/lumen", project_dir.display()))
+            .arg("-lc");
+
+        add_link_args(&mut command);
+
+        let compile_output = command.arg(&src_dir).stdin(Stdio::null()).output().unwrap();
+
+        assert!(
+            compile_output.status.success(),
+            "stdout = {}\nstderr = {}",
+            String::from_utf8_lossy(&compile_output.stdout),
+            String::from_utf8_lossy(&compile_output.stderr)
+        );
+
+        fs::read(project_dir.join(output_dir).join("reproducible")).unwrap()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn add_link_args(_command: &mut Command) {}
+
+    #[cfg(target_os = "linux")]
+    fn add_link_args(command: &mut Command) {
+        command
+            .arg("-lunwind")
+            .arg("-lpthread")
+            .arg("-ldl")
+            .arg("-lm");
+    }
+}
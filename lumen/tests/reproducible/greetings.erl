@@ -0,0 +1,16 @@
+-module(greetings).
+-export([all/0]).
+-spec all() -> [binary()].
+all() ->
+  Greet = fun (Name) -> greet(Name, hello) end,
+  map(Greet, [alpha, beta, gamma, delta, epsilon]).
+-spec map(fun((atom()) -> binary()), [atom()]) -> [binary()].
+map(_Fun, []) ->
+  [];
+map(Fun, [Name | Rest]) ->
+  [Fun(Name) | map(Fun, Rest)].
+-spec greet(atom(), hello | goodbye) -> binary().
+greet(Name, hello) ->
+  <<"Hello, ", (atom_to_binary(Name, utf8))/binary, "!">>;
+greet(Name, goodbye) ->
+  <<"Goodbye, ", (atom_to_binary(Name, utf8))/binary, "!">>.
@@ -0,0 +1,13 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [print/1]).
+-spec start() -> ok | error.
+start() ->
+  Print = fun (Greeting) -> print(Greeting) end,
+  each(Print, greetings:all()).
+-spec each(fun((binary()) -> term()), [binary()]) -> ok.
+each(_Fun, []) ->
+  ok;
+each(Fun, [Greeting | Rest]) ->
+  Fun(Greeting),
+  each(Fun, Rest).
@@ -0,0 +1,40 @@
+//! Mirrors [atomics](http://erlang.org/doc/man/atomics.html) module
+
+pub mod add_3;
+pub mod add_get_3;
+pub mod compare_exchange_4;
+pub mod exchange_3;
+pub mod get_2;
+pub mod info_1;
+pub mod new_2;
+pub mod put_3;
+pub mod sub_3;
+pub mod sub_get_3;
+
+use std::convert::TryInto;
+use std::sync::Arc;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use lumen_rt_core::atomics::Atomics;
+
+fn module() -> Atom {
+    Atom::try_from_str("atomics").unwrap()
+}
+
+fn atomics_from_term(term: Term) -> Result<Arc<Atomics>, exception::Exception> {
+    let boxed: Boxed<Resource> = term
+        .try_into()
+        .with_context(|| format!("atomics_ref ({}) is not an atomics reference", term))?;
+    let atomics_reference: Resource = boxed.into();
+
+    match atomics_reference.downcast_ref::<Arc<Atomics>>() {
+        Some(arc_atomics) => Ok(arc_atomics.clone()),
+        None => Err(TypeError)
+            .with_context(|| format!("atomics_ref ({}) is a resource, but not atomics", term))
+            .map_err(From::from),
+    }
+}
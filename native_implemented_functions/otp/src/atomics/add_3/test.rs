@@ -0,0 +1,36 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::atomics::add_3::native;
+use crate::atomics::{get_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn adds_to_atomic() {
+    with_process(|process| {
+        let atomics_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(1).unwrap();
+
+        assert_eq!(
+            native(atomics_ref, index, process.integer(4).unwrap()),
+            Ok(atom!("ok"))
+        );
+        assert_eq!(
+            get_2::native(process, atomics_ref, index),
+            Ok(process.integer(4).unwrap())
+        );
+    });
+}
+
+#[test]
+fn without_integer_incr_errors_badarg() {
+    with_process(|process| {
+        let atomics_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(1).unwrap();
+
+        assert_badarg!(
+            native(atomics_ref, index, atom!("one")),
+            "incr (one) is not an integer that fits in 64 bits"
+        );
+    });
+}
@@ -0,0 +1,40 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::atomics::add_get_3::native;
+use crate::atomics::new_2;
+use crate::test::with_process;
+
+#[test]
+fn returns_sum() {
+    with_process(|process| {
+        let atomics_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(1).unwrap();
+
+        assert_eq!(
+            native(process, atomics_ref, index, process.integer(2).unwrap()),
+            Ok(process.integer(2).unwrap())
+        );
+        assert_eq!(
+            native(process, atomics_ref, index, process.integer(3).unwrap()),
+            Ok(process.integer(5).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_unsigned_wraps_around_below_zero() {
+    with_process(|process| {
+        let option = process
+            .tuple_from_slice(&[atom!("signed"), false.into()])
+            .unwrap();
+        let options = process.list_from_slice(&[option]).unwrap();
+        let atomics_ref = new_2::native(process, process.integer(1).unwrap(), options).unwrap();
+        let index = process.integer(1).unwrap();
+
+        assert_eq!(
+            native(process, atomics_ref, index, process.integer(-1).unwrap()),
+            Ok(process.integer(u64::max_value()).unwrap())
+        );
+    });
+}
@@ -0,0 +1,33 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::atomics::atomics_from_term;
+
+/// Returns `ok` if the atomic was `expected` and is now `desired`, otherwise the actual value of
+/// the atomic, which is left unchanged.
+#[native_implemented_function(compare_exchange/4)]
+pub fn native(
+    process: &Process,
+    atomics_ref: Term,
+    index: Term,
+    expected: Term,
+    desired: Term,
+) -> exception::Result<Term> {
+    let atomics = atomics_from_term(atomics_ref)?;
+
+    match atomics.compare_exchange(index, expected, desired)? {
+        None => Ok(atom!("ok")),
+        Some(actual) => process.integer(actual).map_err(From::from),
+    }
+}
@@ -0,0 +1,54 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::atomics::compare_exchange_4::native;
+use crate::atomics::{get_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn with_expected_sets_desired_and_returns_ok() {
+    with_process(|process| {
+        let atomics_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(1).unwrap();
+        let expected = process.integer(0).unwrap();
+        let desired = process.integer(1).unwrap();
+
+        assert_eq!(
+            native(process, atomics_ref, index, expected, desired),
+            Ok(atom!("ok"))
+        );
+        assert_eq!(get_2::native(process, atomics_ref, index), Ok(desired));
+    });
+}
+
+#[test]
+fn without_expected_returns_actual_value_and_does_not_set_desired() {
+    with_process(|process| {
+        let atomics_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(1).unwrap();
+        let expected = process.integer(1).unwrap();
+        let desired = process.integer(2).unwrap();
+        let actual = process.integer(0).unwrap();
+
+        assert_eq!(
+            native(process, atomics_ref, index, expected, desired),
+            Ok(actual)
+        );
+        assert_eq!(get_2::native(process, atomics_ref, index), Ok(actual));
+    });
+}
+
+#[test]
+fn with_desired_out_of_range_errors_badarg() {
+    with_process(|process| {
+        let atomics_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(1).unwrap();
+        let expected = process.integer(0).unwrap();
+        let desired = process.integer(u64::max_value()).unwrap();
+
+        assert_badarg!(
+            native(process, atomics_ref, index, expected, desired),
+            "is not a signed 64-bit integer"
+        );
+    });
+}
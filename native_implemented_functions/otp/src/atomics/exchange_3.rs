@@ -0,0 +1,27 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::atomics::atomics_from_term;
+
+#[native_implemented_function(exchange/3)]
+pub fn native(
+    process: &Process,
+    atomics_ref: Term,
+    index: Term,
+    desired: Term,
+) -> exception::Result<Term> {
+    let atomics = atomics_from_term(atomics_ref)?;
+    let value = atomics.exchange(index, desired)?;
+
+    process.integer(value).map_err(From::from)
+}
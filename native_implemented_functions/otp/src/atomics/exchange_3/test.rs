@@ -0,0 +1,20 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::atomics::exchange_3::native;
+use crate::atomics::{get_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn returns_previous_value_and_sets_desired() {
+    with_process(|process| {
+        let atomics_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(1).unwrap();
+        let desired = process.integer(9).unwrap();
+
+        assert_eq!(
+            native(process, atomics_ref, index, desired),
+            Ok(process.integer(0).unwrap())
+        );
+        assert_eq!(get_2::native(process, atomics_ref, index), Ok(desired));
+    });
+}
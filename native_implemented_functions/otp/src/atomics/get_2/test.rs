@@ -0,0 +1,35 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::atomics::get_2::native;
+use crate::atomics::{new_2, put_3};
+use crate::test::with_process;
+
+#[test]
+fn without_index_in_range_errors_badarg() {
+    with_process(|process| {
+        let atomics_ref = new_2::native(process, process.integer(2).unwrap(), Term::NIL).unwrap();
+
+        assert_badarg!(
+            native(process, atomics_ref, process.integer(3).unwrap()),
+            "index (3) is not a 1-based integer between 1-2"
+        );
+    });
+}
+
+#[test]
+fn with_unsigned_returns_unsigned_value() {
+    with_process(|process| {
+        let option = process
+            .tuple_from_slice(&[atom!("signed"), false.into()])
+            .unwrap();
+        let options = process.list_from_slice(&[option]).unwrap();
+        let atomics_ref = new_2::native(process, process.integer(1).unwrap(), options).unwrap();
+        let index = process.integer(1).unwrap();
+        let value = process.integer(u64::max_value()).unwrap();
+
+        put_3::native(atomics_ref, index, value).unwrap();
+
+        assert_eq!(native(process, atomics_ref, index), Ok(value));
+    });
+}
@@ -0,0 +1,58 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::atomics::info_1::native;
+use crate::atomics::new_2;
+use crate::test::with_process;
+
+#[test]
+fn with_signed_returns_map_with_size_bounds_and_memory() {
+    with_process(|process| {
+        let atomics_ref = new_2::native(process, process.integer(2).unwrap(), Term::NIL).unwrap();
+
+        let info = native(process, atomics_ref).unwrap();
+        let info_map: Boxed<Map> = info.try_into().unwrap();
+
+        assert_eq!(
+            info_map.get(atom!("size")),
+            Some(process.integer(2).unwrap())
+        );
+        assert_eq!(
+            info_map.get(atom!("max")),
+            Some(process.integer(i64::max_value()).unwrap())
+        );
+        assert_eq!(
+            info_map.get(atom!("min")),
+            Some(process.integer(i64::min_value()).unwrap())
+        );
+
+        let memory: usize = info_map.get(atom!("memory")).unwrap().try_into().unwrap();
+
+        assert!(2 * 8 <= memory);
+    });
+}
+
+#[test]
+fn with_unsigned_returns_unsigned_bounds() {
+    with_process(|process| {
+        let option = process
+            .tuple_from_slice(&[atom!("signed"), false.into()])
+            .unwrap();
+        let options = process.list_from_slice(&[option]).unwrap();
+        let atomics_ref = new_2::native(process, process.integer(1).unwrap(), options).unwrap();
+
+        let info = native(process, atomics_ref).unwrap();
+        let info_map: Boxed<Map> = info.try_into().unwrap();
+
+        assert_eq!(
+            info_map.get(atom!("max")),
+            Some(process.integer(u64::max_value()).unwrap())
+        );
+        assert_eq!(
+            info_map.get(atom!("min")),
+            Some(process.integer(0).unwrap())
+        );
+    });
+}
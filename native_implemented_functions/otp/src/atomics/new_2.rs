@@ -0,0 +1,32 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+use std::sync::Arc;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use lumen_rt_core::atomics::{term_try_into_len, Atomics, Options};
+
+#[native_implemented_function(new/2)]
+pub fn native(process: &Process, arity: Term, options: Term) -> exception::Result<Term> {
+    let arity_usize = term_try_into_len("arity", arity)?;
+    let options_options: Options = options
+        .try_into()
+        .with_context(|| format!("options ({}) is not a list of atomics options", options))?;
+    let atomics = Atomics::new(arity_usize, options_options);
+
+    process
+        .resource(Box::new(Arc::new(atomics)))
+        .map_err(From::from)
+}
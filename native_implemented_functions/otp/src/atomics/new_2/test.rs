@@ -0,0 +1,52 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::atomics::get_2;
+use crate::atomics::new_2::native;
+use crate::test::with_process;
+
+#[test]
+fn without_positive_arity_errors_badarg() {
+    with_process(|process| {
+        let arity = process.integer(-1).unwrap();
+
+        assert_badarg!(
+            native(process, arity, Term::NIL),
+            "arity (-1) is not a positive integer"
+        );
+    });
+}
+
+#[test]
+fn with_unsupported_option_errors_badarg() {
+    with_process(|process| {
+        let arity = process.integer(1).unwrap();
+        let option = process
+            .tuple_from_slice(&[atom!("unsupported"), true.into()])
+            .unwrap();
+        let options = process.list_from_slice(&[option]).unwrap();
+
+        assert_badarg!(
+            native(process, arity, options),
+            "supported options are {signed, bool}"
+        );
+    });
+}
+
+#[test]
+fn returns_atomics_set_to_zero() {
+    with_process(|process| {
+        let arity = process.integer(3).unwrap();
+
+        let atomics_ref = native(process, arity, Term::NIL).unwrap();
+
+        assert!(atomics_ref.is_boxed_resource_reference());
+
+        for index in 1..=3 {
+            assert_eq!(
+                get_2::native(process, atomics_ref, process.integer(index).unwrap()),
+                Ok(process.integer(0).unwrap())
+            );
+        }
+    });
+}
@@ -0,0 +1,50 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::atomics::put_3::native;
+use crate::atomics::{get_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn with_signed_value_in_range_sets_atomic() {
+    with_process(|process| {
+        let atomics_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(1).unwrap();
+        let value = process.integer(i64::min_value()).unwrap();
+
+        assert_eq!(native(atomics_ref, index, value), Ok(atom!("ok")));
+        assert_eq!(get_2::native(process, atomics_ref, index), Ok(value));
+    });
+}
+
+#[test]
+fn with_signed_value_out_of_range_errors_badarg() {
+    with_process(|process| {
+        let atomics_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(1).unwrap();
+        let value = process.integer(u64::max_value()).unwrap();
+
+        assert_badarg!(
+            native(atomics_ref, index, value),
+            "is not a signed 64-bit integer"
+        );
+    });
+}
+
+#[test]
+fn with_unsigned_negative_value_errors_badarg() {
+    with_process(|process| {
+        let option = process
+            .tuple_from_slice(&[atom!("signed"), false.into()])
+            .unwrap();
+        let options = process.list_from_slice(&[option]).unwrap();
+        let atomics_ref = new_2::native(process, process.integer(1).unwrap(), options).unwrap();
+        let index = process.integer(1).unwrap();
+        let value = process.integer(-1).unwrap();
+
+        assert_badarg!(
+            native(atomics_ref, index, value),
+            "value (-1) is not an unsigned 64-bit integer"
+        );
+    });
+}
@@ -0,0 +1,27 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::atomics::new_2;
+use crate::atomics::sub_get_3::native;
+use crate::test::with_process;
+
+#[test]
+fn with_signed_wraps_around_below_min() {
+    with_process(|process| {
+        let atomics_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(1).unwrap();
+
+        assert_eq!(
+            native(
+                process,
+                atomics_ref,
+                index,
+                process.integer(i64::max_value()).unwrap()
+            ),
+            Ok(process.integer(-i64::max_value()).unwrap())
+        );
+        assert_eq!(
+            native(process, atomics_ref, index, process.integer(2).unwrap()),
+            Ok(process.integer(i64::max_value()).unwrap())
+        );
+    });
+}
@@ -0,0 +1,36 @@
+//! Mirrors [counters](http://erlang.org/doc/man/counters.html) module
+
+pub mod add_3;
+pub mod get_2;
+pub mod info_1;
+pub mod new_2;
+pub mod put_3;
+pub mod sub_3;
+
+use std::convert::TryInto;
+use std::sync::Arc;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use lumen_rt_core::counters::Counters;
+
+fn module() -> Atom {
+    Atom::try_from_str("counters").unwrap()
+}
+
+fn counters_from_term(term: Term) -> Result<Arc<Counters>, exception::Exception> {
+    let boxed: Boxed<Resource> = term
+        .try_into()
+        .with_context(|| format!("counter_ref ({}) is not a counters reference", term))?;
+    let counters_reference: Resource = boxed.into();
+
+    match counters_reference.downcast_ref::<Arc<Counters>>() {
+        Some(arc_counters) => Ok(arc_counters.clone()),
+        None => Err(TypeError)
+            .with_context(|| format!("counter_ref ({}) is a resource, but not counters", term))
+            .map_err(From::from),
+    }
+}
@@ -0,0 +1,84 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::counters::add_3::native;
+use crate::counters::{get_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn without_counters_reference_errors_badarg() {
+    with_process(|process| {
+        let counter_ref = process.next_reference().unwrap();
+        let index = process.integer(1).unwrap();
+        let incr = process.integer(1).unwrap();
+
+        assert_badarg!(
+            native(process, counter_ref, index, incr),
+            "is not a counters reference"
+        );
+    });
+}
+
+#[test]
+fn without_64_bit_incr_errors_badarg() {
+    with_process(|process| {
+        let counter_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(1).unwrap();
+        let incr = process.integer(u64::max_value()).unwrap();
+
+        assert_badarg!(
+            native(process, counter_ref, index, incr),
+            "is not a signed 64-bit integer"
+        );
+    });
+}
+
+#[test]
+fn with_write_concurrency_adds_to_counter() {
+    with_process(|process| {
+        let options = process
+            .list_from_slice(&[atom!("write_concurrency")])
+            .unwrap();
+        let counter_ref = new_2::native(process, process.integer(2).unwrap(), options).unwrap();
+        let index = process.integer(2).unwrap();
+
+        assert_eq!(
+            native(process, counter_ref, index, process.integer(3).unwrap()),
+            Ok(atom!("ok"))
+        );
+        assert_eq!(
+            native(process, counter_ref, index, process.integer(-1).unwrap()),
+            Ok(atom!("ok"))
+        );
+        assert_eq!(
+            get_2::native(process, counter_ref, index),
+            Ok(process.integer(2).unwrap())
+        );
+        assert_eq!(
+            get_2::native(process, counter_ref, process.integer(1).unwrap()),
+            Ok(process.integer(0).unwrap())
+        );
+    });
+}
+
+#[test]
+fn wraps_around_on_overflow() {
+    with_process(|process| {
+        let counter_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(1).unwrap();
+
+        native(
+            process,
+            counter_ref,
+            index,
+            process.integer(i64::max_value()).unwrap(),
+        )
+        .unwrap();
+        native(process, counter_ref, index, process.integer(1).unwrap()).unwrap();
+
+        assert_eq!(
+            get_2::native(process, counter_ref, index),
+            Ok(process.integer(i64::min_value()).unwrap())
+        );
+    });
+}
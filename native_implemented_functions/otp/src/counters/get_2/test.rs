@@ -0,0 +1,38 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::counters::get_2::native;
+use crate::counters::{add_3, new_2};
+use crate::test::with_process;
+
+#[test]
+fn without_integer_index_errors_badarg() {
+    with_process(|process| {
+        let counter_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+
+        assert_badarg!(
+            native(process, counter_ref, atom!("first")),
+            "index (first) is not a 1-based integer between 1-1"
+        );
+    });
+}
+
+#[test]
+fn with_write_concurrency_returns_sum_of_stripes() {
+    with_process(|process| {
+        let options = process
+            .list_from_slice(&[atom!("write_concurrency")])
+            .unwrap();
+        let counter_ref = new_2::native(process, process.integer(1).unwrap(), options).unwrap();
+        let index = process.integer(1).unwrap();
+
+        for _ in 0..3 {
+            add_3::native(process, counter_ref, index, process.integer(2).unwrap()).unwrap();
+        }
+
+        assert_eq!(
+            native(process, counter_ref, index),
+            Ok(process.integer(6).unwrap())
+        );
+    });
+}
@@ -0,0 +1,40 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::counters::info_1::native;
+use crate::counters::new_2;
+use crate::test::with_process;
+
+#[test]
+fn returns_map_with_size_and_memory() {
+    with_process(|process| {
+        let counter_ref = new_2::native(process, process.integer(3).unwrap(), Term::NIL).unwrap();
+
+        let info = native(process, counter_ref).unwrap();
+        let info_map: Boxed<Map> = info.try_into().unwrap();
+
+        assert_eq!(
+            info_map.get(atom!("size")),
+            Some(process.integer(3).unwrap())
+        );
+
+        let memory: usize = info_map.get(atom!("memory")).unwrap().try_into().unwrap();
+
+        assert!(3 * 8 <= memory);
+    });
+}
+
+#[test]
+fn with_atomics_reference_errors_badarg() {
+    with_process(|process| {
+        let atomics_ref =
+            crate::atomics::new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+
+        assert_badarg!(
+            native(process, atomics_ref),
+            "is a resource, but not counters"
+        );
+    });
+}
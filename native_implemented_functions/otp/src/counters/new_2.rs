@@ -0,0 +1,35 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+use std::sync::Arc;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use lumen_rt_core::atomics::term_try_into_len;
+use lumen_rt_core::counters::{Counters, Options};
+use lumen_rt_full::system::host::cpus;
+
+#[native_implemented_function(new/2)]
+pub fn native(process: &Process, size: Term, options: Term) -> exception::Result<Term> {
+    let size_usize = term_try_into_len("size", size)?;
+    let options_options: Options = options
+        .try_into()
+        .with_context(|| format!("options ({}) is not a list of counters options", options))?;
+    // Like BEAM, there is a stripe for each scheduler that could run on a logical CPU
+    let counters = Counters::new(size_usize, options_options, cpus::num_logical());
+
+    process
+        .resource(Box::new(Arc::new(counters)))
+        .map_err(From::from)
+}
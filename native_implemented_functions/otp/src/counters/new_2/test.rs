@@ -0,0 +1,80 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::counters::new_2::native;
+use crate::counters::{get_2, info_1};
+use crate::test::with_process;
+
+#[test]
+fn without_positive_size_errors_badarg() {
+    with_process(|process| {
+        let size = process.integer(0).unwrap();
+
+        assert_badarg!(
+            native(process, size, Term::NIL),
+            "size (0) is not a positive integer"
+        );
+    });
+}
+
+#[test]
+fn with_unsupported_option_errors_badarg() {
+    with_process(|process| {
+        let size = process.integer(1).unwrap();
+        let options = process.list_from_slice(&[atom!("unsupported")]).unwrap();
+
+        assert_badarg!(
+            native(process, size, options),
+            "supported options are atomics or write_concurrency"
+        );
+    });
+}
+
+#[test]
+fn with_atomics_returns_counters_of_size_set_to_zero() {
+    with_process(|process| {
+        let size = process.integer(2).unwrap();
+        let options = process.list_from_slice(&[atom!("atomics")]).unwrap();
+
+        let counter_ref = native(process, size, options).unwrap();
+
+        assert!(counter_ref.is_boxed_resource_reference());
+        assert_eq!(
+            get_2::native(process, counter_ref, process.integer(2).unwrap()),
+            Ok(process.integer(0).unwrap())
+        );
+        assert_badarg!(
+            get_2::native(process, counter_ref, process.integer(3).unwrap()),
+            "index (3) is not a 1-based integer between 1-2"
+        );
+    });
+}
+
+#[test]
+fn with_write_concurrency_uses_more_memory_than_atomics() {
+    with_process(|process| {
+        let size = process.integer(1).unwrap();
+        let atomics_options = process.list_from_slice(&[atom!("atomics")]).unwrap();
+        let write_concurrency_options = process
+            .list_from_slice(&[atom!("write_concurrency")])
+            .unwrap();
+
+        let atomics_info =
+            info_1::native(process, native(process, size, atomics_options).unwrap()).unwrap();
+        let write_concurrency_info = info_1::native(
+            process,
+            native(process, size, write_concurrency_options).unwrap(),
+        )
+        .unwrap();
+
+        assert!(memory(atomics_info) < memory(write_concurrency_info));
+    });
+}
+
+fn memory(info: Term) -> usize {
+    let info_map: Boxed<Map> = info.try_into().unwrap();
+
+    info_map.get(atom!("memory")).unwrap().try_into().unwrap()
+}
@@ -0,0 +1,41 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::counters::put_3::native;
+use crate::counters::{add_3, get_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn with_write_concurrency_replaces_sum_of_stripes() {
+    with_process(|process| {
+        let options = process
+            .list_from_slice(&[atom!("write_concurrency")])
+            .unwrap();
+        let counter_ref = new_2::native(process, process.integer(1).unwrap(), options).unwrap();
+        let index = process.integer(1).unwrap();
+
+        add_3::native(process, counter_ref, index, process.integer(7).unwrap()).unwrap();
+
+        assert_eq!(
+            native(counter_ref, index, process.integer(-3).unwrap()),
+            Ok(atom!("ok"))
+        );
+        assert_eq!(
+            get_2::native(process, counter_ref, index),
+            Ok(process.integer(-3).unwrap())
+        );
+    });
+}
+
+#[test]
+fn without_integer_value_errors_badarg() {
+    with_process(|process| {
+        let counter_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(1).unwrap();
+
+        assert_badarg!(
+            native(counter_ref, index, atom!("one")),
+            "value (one) is not a signed 64-bit integer"
+        );
+    });
+}
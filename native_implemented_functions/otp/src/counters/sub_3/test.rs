@@ -0,0 +1,37 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::counters::sub_3::native;
+use crate::counters::{get_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn subtracts_from_counter() {
+    with_process(|process| {
+        let counter_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(1).unwrap();
+
+        assert_eq!(
+            native(process, counter_ref, index, process.integer(5).unwrap()),
+            Ok(atom!("ok"))
+        );
+        assert_eq!(
+            get_2::native(process, counter_ref, index),
+            Ok(process.integer(-5).unwrap())
+        );
+    });
+}
+
+#[test]
+fn without_index_in_range_errors_badarg() {
+    with_process(|process| {
+        let counter_ref = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+        let index = process.integer(0).unwrap();
+        let decr = process.integer(1).unwrap();
+
+        assert_badarg!(
+            native(process, counter_ref, index, decr),
+            "index (0) is not a 1-based integer between 1-1"
+        );
+    });
+}
@@ -0,0 +1,178 @@
+//! Mirrors [crypto](http://erlang.org/doc/man/crypto.html) module
+
+pub mod hash_2;
+pub mod hash_final_1;
+pub mod hash_init_1;
+pub mod hash_update_2;
+pub mod mac_4;
+
+use std::convert::{TryFrom, TryInto};
+use std::sync::Arc;
+
+use anyhow::*;
+use hmac::digest::generic_array::ArrayLength;
+use hmac::digest::{BlockInput, DynDigest, FixedOutput, Input, Reset};
+use hmac::{Hmac, Mac};
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Sha224, Sha256, Sha384, Sha512};
+use sha3::{Sha3_224, Sha3_256, Sha3_384, Sha3_512};
+
+use liblumen_alloc::erts::exception::{self, AllocResult};
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+fn module() -> Atom {
+    Atom::try_from_str("crypto").unwrap()
+}
+
+/// The digest types of `hash/2`, `hash_init/1` and the `SubType` of `mac/4`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashType {
+    Md5,
+    Sha,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+}
+
+impl HashType {
+    pub fn hash(self, data: &[u8]) -> Vec<u8> {
+        let mut digest = self.digest();
+        digest.input(data);
+
+        digest.result().into_vec()
+    }
+
+    pub fn hmac(self, key: &[u8], data: &[u8]) -> Vec<u8> {
+        match self {
+            HashType::Md5 => hmac::<Md5>(key, data),
+            HashType::Sha => hmac::<Sha1>(key, data),
+            HashType::Sha224 => hmac::<Sha224>(key, data),
+            HashType::Sha256 => hmac::<Sha256>(key, data),
+            HashType::Sha384 => hmac::<Sha384>(key, data),
+            HashType::Sha512 => hmac::<Sha512>(key, data),
+            HashType::Sha3_224 => hmac::<Sha3_224>(key, data),
+            HashType::Sha3_256 => hmac::<Sha3_256>(key, data),
+            HashType::Sha3_384 => hmac::<Sha3_384>(key, data),
+            HashType::Sha3_512 => hmac::<Sha3_512>(key, data),
+        }
+    }
+
+    fn digest(self) -> Box<dyn DynDigest> {
+        match self {
+            HashType::Md5 => Box::new(Md5::default()),
+            HashType::Sha => Box::new(Sha1::default()),
+            HashType::Sha224 => Box::new(Sha224::default()),
+            HashType::Sha256 => Box::new(Sha256::default()),
+            HashType::Sha384 => Box::new(Sha384::default()),
+            HashType::Sha512 => Box::new(Sha512::default()),
+            HashType::Sha3_224 => Box::new(Sha3_224::default()),
+            HashType::Sha3_256 => Box::new(Sha3_256::default()),
+            HashType::Sha3_384 => Box::new(Sha3_384::default()),
+            HashType::Sha3_512 => Box::new(Sha3_512::default()),
+        }
+    }
+}
+
+const SUPPORTED_HASH_TYPES_CONTEXT: &str = "supported hash types are md5, sha, sha224, sha256, \
+                                            sha384, sha512, sha3_224, sha3_256, sha3_384, and \
+                                            sha3_512";
+
+impl TryFrom<Term> for HashType {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let atom: Atom = term.try_into().context(SUPPORTED_HASH_TYPES_CONTEXT)?;
+
+        match atom.name() {
+            "md5" => Ok(HashType::Md5),
+            "sha" => Ok(HashType::Sha),
+            "sha224" => Ok(HashType::Sha224),
+            "sha256" => Ok(HashType::Sha256),
+            "sha384" => Ok(HashType::Sha384),
+            "sha512" => Ok(HashType::Sha512),
+            "sha3_224" => Ok(HashType::Sha3_224),
+            "sha3_256" => Ok(HashType::Sha3_256),
+            "sha3_384" => Ok(HashType::Sha3_384),
+            "sha3_512" => Ok(HashType::Sha3_512),
+            name => Err(anyhow!("hash type ({}) is not supported", name))
+                .context(SUPPORTED_HASH_TYPES_CONTEXT),
+        }
+    }
+}
+
+/// The state of an incremental hash, as returned by `hash_init/1` and `hash_update/2`.
+///
+/// Like in BEAM, updating a state returns a new state, so that the old state can still be
+/// updated or finalized.
+pub struct HashState {
+    r#type: HashType,
+    digest: Box<dyn DynDigest>,
+}
+
+impl HashState {
+    pub fn new(r#type: HashType) -> Self {
+        Self {
+            r#type,
+            digest: r#type.digest(),
+        }
+    }
+
+    pub fn r#type(&self) -> HashType {
+        self.r#type
+    }
+
+    pub fn update(&self, data: &[u8]) -> Self {
+        let mut digest = self.digest.box_clone();
+        digest.input(data);
+
+        Self {
+            r#type: self.r#type,
+            digest,
+        }
+    }
+
+    pub fn finalize(&self) -> Vec<u8> {
+        self.digest.box_clone().result().into_vec()
+    }
+
+    pub fn to_term(self, process: &Process) -> AllocResult<Term> {
+        process.resource(Box::new(Arc::new(self)))
+    }
+}
+
+/// `name` is the name of the argument in the calling function
+pub fn hash_state_from_term(name: &str, term: Term) -> exception::Result<Arc<HashState>> {
+    let boxed: Boxed<Resource> = term
+        .try_into()
+        .with_context(|| format!("{} ({}) is not a hash state", name, term))?;
+    let hash_state_reference: Resource = boxed.into();
+
+    match hash_state_reference.downcast_ref::<Arc<HashState>>() {
+        Some(arc_hash_state) => Ok(arc_hash_state.clone()),
+        None => Err(TypeError)
+            .with_context(|| format!("{} ({}) is a resource, but not a hash state", name, term))
+            .map_err(From::from),
+    }
+}
+
+// Private
+
+fn hmac<D>(key: &[u8], data: &[u8]) -> Vec<u8>
+where
+    D: Input + BlockInput + FixedOutput + Reset + Default + Clone,
+    D::BlockSize: ArrayLength<u8>,
+    D::OutputSize: ArrayLength<u8>,
+{
+    // HMAC hashes keys that are longer than the block size, so keys of any length are valid
+    let mut mac = Hmac::<D>::new_varkey(key).unwrap();
+    mac.input(data);
+
+    mac.result().code().to_vec()
+}
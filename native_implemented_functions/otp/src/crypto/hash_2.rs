@@ -0,0 +1,28 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::crypto::HashType;
+use crate::erlang::iolist_or_binary;
+
+/// Computes a message digest of `data` with the hash algorithm `type`
+#[native_implemented_function(hash/2)]
+pub fn native(process: &Process, r#type: Term, data: Term) -> exception::Result<Term> {
+    let hash_type: HashType = r#type.try_into()?;
+    let bytes = iolist_or_binary::to_bytes("data", data)?;
+
+    process
+        .binary_from_bytes(&hash_type.hash(&bytes))
+        .map_err(From::from)
+}
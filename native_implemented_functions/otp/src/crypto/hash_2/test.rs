@@ -0,0 +1,92 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::crypto::hash_2::native;
+use crate::test::with_process;
+
+#[test]
+fn without_supported_type_errors_badarg() {
+    with_process(|process| {
+        let data = process.binary_from_bytes(b"abc").unwrap();
+
+        assert_badarg!(
+            native(process, Atom::str_to_term("md4"), data),
+            "supported hash types are md5, sha, sha224, sha256, sha384, sha512, sha3_224, sha3_256, sha3_384, and sha3_512"
+        );
+    });
+}
+
+#[test]
+fn without_iolist_or_binary_data_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            native(
+                process,
+                Atom::str_to_term("sha256"),
+                Atom::str_to_term("abc")
+            ),
+            "data (abc) is not an iolist"
+        );
+    });
+}
+
+#[test]
+fn with_binary_returns_digest() {
+    with_process(|process| {
+        let data = process.binary_from_bytes(b"abc").unwrap();
+
+        assert_eq!(
+            native(process, Atom::str_to_term("md5"), data),
+            Ok(process
+                .binary_from_bytes(&[
+                    144, 1, 80, 152, 60, 210, 79, 176, 214, 150, 63, 125, 40, 225, 127, 114
+                ])
+                .unwrap())
+        );
+        assert_eq!(
+            native(process, Atom::str_to_term("sha"), data),
+            Ok(process
+                .binary_from_bytes(&[
+                    169, 153, 62, 54, 71, 6, 129, 106, 186, 62, 37, 113, 120, 80, 194, 108, 156,
+                    208, 216, 157
+                ])
+                .unwrap())
+        );
+        assert_eq!(
+            native(process, Atom::str_to_term("sha256"), data),
+            Ok(process
+                .binary_from_bytes(&[
+                    186, 120, 22, 191, 143, 1, 207, 234, 65, 65, 64, 222, 93, 174, 34, 35, 176, 3,
+                    97, 163, 150, 23, 122, 156, 180, 16, 255, 97, 242, 0, 21, 173
+                ])
+                .unwrap())
+        );
+        assert_eq!(
+            native(process, Atom::str_to_term("sha3_256"), data),
+            Ok(process
+                .binary_from_bytes(&[
+                    58, 152, 93, 167, 79, 226, 37, 178, 4, 92, 23, 45, 107, 211, 144, 189, 133, 95,
+                    8, 110, 62, 157, 82, 91, 70, 191, 226, 69, 17, 67, 21, 50
+                ])
+                .unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_iolist_returns_same_digest_as_binary() {
+    with_process(|process| {
+        let r#type = Atom::str_to_term("sha512");
+        let binary = process.binary_from_bytes(b"abc").unwrap();
+        let iolist = process
+            .list_from_slice(&[
+                process.integer(b'a').unwrap(),
+                process.binary_from_bytes(b"bc").unwrap(),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            native(process, r#type, iolist),
+            native(process, r#type, binary)
+        );
+    });
+}
@@ -0,0 +1,25 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::crypto::hash_state_from_term;
+
+/// Finalizes the streaming hash operation represented by `state`, and returns the message
+/// digest
+#[native_implemented_function(hash_final/1)]
+pub fn native(process: &Process, state: Term) -> exception::Result<Term> {
+    let hash_state = hash_state_from_term("state", state)?;
+
+    process
+        .binary_from_bytes(&hash_state.finalize())
+        .map_err(From::from)
+}
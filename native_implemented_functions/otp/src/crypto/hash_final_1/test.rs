@@ -0,0 +1,37 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::counters::new_2;
+use crate::crypto::hash_final_1::native;
+use crate::crypto::{hash_init_1, hash_update_2};
+use crate::test::with_process;
+
+#[test]
+fn without_hash_state_errors_badarg() {
+    with_process(|process| {
+        let counters = new_2::native(process, process.integer(1).unwrap(), Term::NIL).unwrap();
+
+        assert_badarg!(
+            native(process, counters),
+            "is a resource, but not a hash state"
+        );
+    });
+}
+
+#[test]
+fn with_hash_state_returns_digest() {
+    with_process(|process| {
+        let state = hash_init_1::native(process, Atom::str_to_term("md5")).unwrap();
+        let state =
+            hash_update_2::native(process, state, process.binary_from_bytes(b"abc").unwrap())
+                .unwrap();
+
+        assert_eq!(
+            native(process, state),
+            Ok(process
+                .binary_from_bytes(&[
+                    144, 1, 80, 152, 60, 210, 79, 176, 214, 150, 63, 125, 40, 225, 127, 114
+                ])
+                .unwrap())
+        );
+    });
+}
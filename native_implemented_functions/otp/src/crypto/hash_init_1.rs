@@ -0,0 +1,26 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::crypto::{HashState, HashType};
+
+/// Initializes the state for streaming hash operations
+#[native_implemented_function(hash_init/1)]
+pub fn native(process: &Process, r#type: Term) -> exception::Result<Term> {
+    let hash_type: HashType = r#type.try_into()?;
+
+    HashState::new(hash_type)
+        .to_term(process)
+        .map_err(From::from)
+}
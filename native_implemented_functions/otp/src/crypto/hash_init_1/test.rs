@@ -0,0 +1,29 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::crypto::hash_init_1::native;
+use crate::crypto::{hash_2, hash_final_1};
+use crate::test::with_process;
+
+#[test]
+fn without_supported_type_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            native(process, Atom::str_to_term("ripemd160")),
+            "hash type (ripemd160) is not supported"
+        );
+    });
+}
+
+#[test]
+fn with_supported_type_returns_state_with_digest_of_empty_data() {
+    with_process(|process| {
+        let r#type = Atom::str_to_term("sha224");
+        let state = native(process, r#type).unwrap();
+
+        assert!(state.is_boxed_resource_reference());
+        assert_eq!(
+            hash_final_1::native(process, state),
+            hash_2::native(process, r#type, Term::NIL)
+        );
+    });
+}
@@ -0,0 +1,27 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::crypto::hash_state_from_term;
+use crate::erlang::iolist_or_binary;
+
+/// Updates the digest represented by `state` with `data`, and returns a new state
+#[native_implemented_function(hash_update/2)]
+pub fn native(process: &Process, state: Term, data: Term) -> exception::Result<Term> {
+    let hash_state = hash_state_from_term("state", state)?;
+    let bytes = iolist_or_binary::to_bytes("data", data)?;
+
+    hash_state
+        .update(&bytes)
+        .to_term(process)
+        .map_err(From::from)
+}
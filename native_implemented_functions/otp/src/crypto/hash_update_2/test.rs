@@ -0,0 +1,47 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::crypto::hash_update_2::native;
+use crate::crypto::{hash_2, hash_final_1, hash_init_1};
+use crate::test::with_process;
+
+#[test]
+fn without_hash_state_errors_badarg() {
+    with_process(|process| {
+        let data = process.binary_from_bytes(b"abc").unwrap();
+
+        assert_badarg!(
+            native(process, Atom::str_to_term("state"), data),
+            "state (state) is not a hash state"
+        );
+    });
+}
+
+#[test]
+fn with_hash_state_returns_new_state_and_leaves_old_state_unchanged() {
+    with_process(|process| {
+        let r#type = Atom::str_to_term("sha384");
+        let initial_state = hash_init_1::native(process, r#type).unwrap();
+
+        let ab_state = native(
+            process,
+            initial_state,
+            process.binary_from_bytes(b"ab").unwrap(),
+        )
+        .unwrap();
+        let abc_state =
+            native(process, ab_state, process.binary_from_bytes(b"c").unwrap()).unwrap();
+
+        assert_eq!(
+            hash_final_1::native(process, abc_state),
+            hash_2::native(process, r#type, process.binary_from_bytes(b"abc").unwrap())
+        );
+        assert_eq!(
+            hash_final_1::native(process, ab_state),
+            hash_2::native(process, r#type, process.binary_from_bytes(b"ab").unwrap())
+        );
+        assert_eq!(
+            hash_final_1::native(process, initial_state),
+            hash_2::native(process, r#type, Term::NIL)
+        );
+    });
+}
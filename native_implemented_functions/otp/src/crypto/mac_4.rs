@@ -0,0 +1,48 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::crypto::HashType;
+use crate::erlang::iolist_or_binary;
+
+/// Computes a MAC (Message Authentication Code) of `data` with `key`.  Only the `hmac` `type` is
+/// supported, with any of the hash algorithms of `hash/2` as the `sub_type`.
+#[native_implemented_function(mac/4)]
+pub fn native(
+    process: &Process,
+    r#type: Term,
+    sub_type: Term,
+    key: Term,
+    data: Term,
+) -> exception::Result<Term> {
+    let type_atom: Atom = r#type
+        .try_into()
+        .with_context(|| format!("type ({}) is not an atom", r#type))?;
+
+    if type_atom.name() != "hmac" {
+        return Err(anyhow!("type ({}) is not supported", r#type))
+            .context("supported type is hmac")
+            .map_err(From::from);
+    }
+
+    let hash_type: HashType = sub_type.try_into()?;
+    let key_bytes = iolist_or_binary::to_bytes("key", key)?;
+    let data_bytes = iolist_or_binary::to_bytes("data", data)?;
+
+    process
+        .binary_from_bytes(&hash_type.hmac(&key_bytes, &data_bytes))
+        .map_err(From::from)
+}
@@ -0,0 +1,82 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::crypto::mac_4::native;
+use crate::test::with_process;
+
+#[test]
+fn without_hmac_type_errors_badarg() {
+    with_process(|process| {
+        let key = process.binary_from_bytes(b"key").unwrap();
+        let data = process.binary_from_bytes(b"abc").unwrap();
+
+        assert_badarg!(
+            native(
+                process,
+                Atom::str_to_term("cmac"),
+                Atom::str_to_term("sha256"),
+                key,
+                data
+            ),
+            "supported type is hmac"
+        );
+    });
+}
+
+#[test]
+fn without_supported_sub_type_errors_badarg() {
+    with_process(|process| {
+        let key = process.binary_from_bytes(b"key").unwrap();
+        let data = process.binary_from_bytes(b"abc").unwrap();
+
+        assert_badarg!(
+            native(
+                process,
+                Atom::str_to_term("hmac"),
+                Atom::str_to_term("blake2b"),
+                key,
+                data
+            ),
+            "hash type (blake2b) is not supported"
+        );
+    });
+}
+
+#[test]
+fn with_hmac_returns_mac() {
+    with_process(|process| {
+        let hmac = Atom::str_to_term("hmac");
+        let key = process.binary_from_bytes(b"key").unwrap();
+
+        assert_eq!(
+            native(
+                process,
+                hmac,
+                Atom::str_to_term("md5"),
+                key,
+                process.binary_from_bytes(b"abc").unwrap()
+            ),
+            Ok(process
+                .binary_from_bytes(&[
+                    210, 254, 152, 6, 63, 135, 107, 3, 25, 58, 251, 73, 180, 151, 149, 145
+                ])
+                .unwrap())
+        );
+        assert_eq!(
+            native(
+                process,
+                hmac,
+                Atom::str_to_term("sha256"),
+                key,
+                process
+                    .binary_from_bytes(b"The quick brown fox jumps over the lazy dog")
+                    .unwrap()
+            ),
+            Ok(process
+                .binary_from_bytes(&[
+                    247, 188, 131, 244, 48, 83, 132, 36, 177, 50, 152, 230, 170, 111, 177, 67, 239,
+                    77, 89, 161, 73, 70, 23, 89, 151, 71, 157, 188, 45, 26, 60, 216
+                ])
+                .unwrap())
+        );
+    });
+}
@@ -2,6 +2,8 @@
 
 pub mod abs_1;
 pub mod add_2;
+pub mod adler32_1;
+pub mod adler32_2;
 pub mod and_2;
 pub mod andalso_2;
 pub mod append_element_2;
@@ -40,6 +42,8 @@ pub mod ceil_1;
 mod charlist_to_string;
 pub mod concatenate_2;
 pub mod convert_time_unit_3;
+pub mod crc32_1;
+pub mod crc32_2;
 pub mod date_0;
 pub mod delete_element_2;
 pub mod demonitor_1;
@@ -52,6 +56,8 @@ pub mod erase_1;
 pub mod error_1;
 pub mod error_2;
 pub mod exit_1;
+pub mod exit_2;
+pub mod external_size_1;
 pub mod float_1;
 pub mod float_to_binary_1;
 pub mod float_to_binary_2;
@@ -67,6 +73,9 @@ pub mod get_keys_1;
 pub mod get_stacktrace_0;
 pub mod group_leader_0;
 pub mod group_leader_2;
+pub mod halt_0;
+pub mod halt_1;
+pub mod halt_2;
 pub mod hd_1;
 pub mod insert_element_3;
 pub mod integer_to_binary_1;
@@ -74,7 +83,7 @@ pub mod integer_to_binary_2;
 pub mod integer_to_list_1;
 pub mod integer_to_list_2;
 mod integer_to_string;
-mod iolist_or_binary;
+pub(crate) mod iolist_or_binary;
 pub mod iolist_size_1;
 pub mod iolist_to_binary_1;
 pub mod iolist_to_iovec_1;
@@ -120,6 +129,11 @@ pub mod make_tuple_3;
 pub mod map_get_2;
 pub mod map_size_1;
 pub mod max_2;
+pub mod md5_1;
+mod md5_context;
+pub mod md5_final_1;
+pub mod md5_init_0;
+pub mod md5_update_2;
 pub mod min_2;
 pub mod monitor_2;
 pub mod monotonic_time_0;
@@ -133,7 +147,13 @@ pub mod number_or_badarith_1;
 mod number_to_integer;
 pub mod or_2;
 pub mod orelse_2;
+mod phash;
+mod phash2;
+pub mod phash2_1;
+pub mod phash2_2;
+pub mod phash_2;
 pub mod process_flag_2;
+pub mod process_info_1;
 pub mod process_info_2;
 pub mod put_2;
 pub mod raise_3;
@@ -163,6 +183,7 @@ pub mod spawn_opt_4;
 pub mod split_binary_2;
 pub mod start_timer_3;
 pub mod start_timer_4;
+pub mod statistics_1;
 mod string_to_float;
 mod string_to_integer;
 pub mod subtract_2;
@@ -171,6 +192,7 @@ pub mod system_time_0;
 pub mod system_time_1;
 mod term_to_binary;
 pub mod term_to_binary_1;
+pub mod term_to_binary_2;
 pub mod throw_1;
 pub mod time_0;
 pub mod time_offset_0;
@@ -203,6 +225,7 @@ use lumen_rt_core::context::*;
 use lumen_rt_core::time::{monotonic, Milliseconds};
 
 use lumen_rt_core::registry::pid_to_self_or_process;
+use lumen_rt_core::system;
 use lumen_rt_full::process::SchedulerDependentAlloc;
 use lumen_rt_full::timer::start::ReferenceFrame;
 use lumen_rt_full::timer::{self, Timeout};
@@ -254,6 +277,15 @@ fn cancel_timer(
     Ok(term)
 }
 
+fn halt(process: &Process, status: system::Status, options: system::Options) -> Term {
+    system::halt(status, options);
+
+    // `halt` never returns to Erlang code, so wait until the scheduler stops for the shutdown
+    process.wait();
+
+    true.into()
+}
+
 fn is_record(term: Term, record_tag: Term, size: Option<Term>) -> exception::Result<Term> {
     match term.decode()? {
         TypedTerm::Tuple(tuple) => {
@@ -0,0 +1,26 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use adler32::RollingAdler32;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::erlang::iolist_or_binary;
+
+/// Computes and returns the adler32 checksum for `data`
+#[native_implemented_function(adler32/1)]
+pub fn native(process: &Process, data: Term) -> exception::Result<Term> {
+    let bytes = iolist_or_binary::to_bytes("data", data)?;
+
+    process
+        .integer(RollingAdler32::from_buffer(&bytes).hash())
+        .map_err(From::from)
+}
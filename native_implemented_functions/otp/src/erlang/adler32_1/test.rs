@@ -0,0 +1,34 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::adler32_1::native;
+use crate::test::with_process;
+
+#[test]
+fn without_iolist_or_binary_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            native(process, process.integer(1).unwrap()),
+            "data (1) is not an iolist"
+        );
+    });
+}
+
+#[test]
+fn with_empty_binary_returns_one() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, process.binary_from_bytes(&[]).unwrap()),
+            Ok(process.integer(1).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_binary_returns_checksum() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, process.binary_from_bytes(b"Wikipedia").unwrap()),
+            Ok(process.integer(0x11E6_0398_u32).unwrap())
+        );
+    });
+}
@@ -0,0 +1,34 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use adler32::RollingAdler32;
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::erlang::iolist_or_binary;
+
+/// Continues computing the adler32 checksum by combining the previous checksum, `old_adler`,
+/// with the checksum of `data`
+#[native_implemented_function(adler32/2)]
+pub fn native(process: &Process, old_adler: Term, data: Term) -> exception::Result<Term> {
+    let old_adler_u32: u32 = old_adler
+        .try_into()
+        .with_context(|| format!("old_adler ({}) is not a 32-bit unsigned integer", old_adler))?;
+    let bytes = iolist_or_binary::to_bytes("data", data)?;
+
+    let mut rolling_adler32 = RollingAdler32::from_value(old_adler_u32);
+    rolling_adler32.update_buffer(&bytes);
+
+    process.integer(rolling_adler32.hash()).map_err(From::from)
+}
@@ -0,0 +1,34 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::adler32_1;
+use crate::erlang::adler32_2::native;
+use crate::test::with_process;
+
+#[test]
+fn without_32_bit_unsigned_integer_old_adler_errors_badarg() {
+    with_process(|process| {
+        let data = process.binary_from_bytes(b"pedia").unwrap();
+
+        assert_badarg!(
+            native(process, Atom::str_to_term("adler"), data),
+            "old_adler (adler) is not a 32-bit unsigned integer"
+        );
+    });
+}
+
+#[test]
+fn with_old_adler_continues_checksum() {
+    with_process(|process| {
+        let old_adler =
+            adler32_1::native(process, process.binary_from_bytes(b"Wiki").unwrap()).unwrap();
+
+        assert_eq!(
+            native(
+                process,
+                old_adler,
+                process.binary_from_bytes(b"pedia").unwrap()
+            ),
+            Ok(process.integer(0x11E6_0398_u32).unwrap())
+        );
+    });
+}
@@ -2,10 +2,10 @@ use proptest::prop_assert_eq;
 use proptest::strategy::Just;
 
 use liblumen_alloc::erts::process::Process;
-use liblumen_alloc::erts::term::prelude::{Atom, Term};
+use liblumen_alloc::erts::term::prelude::{Atom, Encoded, Port, Term};
 
 use crate::erlang::binary_to_term_1::native;
-use crate::test::strategy;
+use crate::test::{strategy, with_process};
 
 #[test]
 fn without_binary_errors_badarg() {
@@ -169,6 +169,171 @@ fn with_binary_encoding_small_atom_utf8_returns_atom() {
     );
 }
 
+#[test]
+fn with_binary_encoding_compressed_string_returns_list() {
+    with_binary_returns_term(
+        // :erlang.term_to_binary(List.duplicate(?a, 100), [:compressed])
+        vec![
+            131, 80, 0, 0, 0, 103, 120, 156, 203, 102, 72, 73, 164, 3, 0, 0, 204, 203, 38, 180,
+        ],
+        |process| process.charlist_from_str(&"a".repeat(100)).unwrap(),
+    );
+}
+
+#[test]
+fn with_binary_encoding_compressed_with_larger_uncompressed_size_errors_badarg() {
+    with_process(|process| {
+        // :erlang.term_to_binary(List.duplicate(?a, 100), [:compressed]) with the uncompressed
+        // size replaced by the largest size
+        let binary = process
+            .binary_from_bytes(&[
+                131, 80, 255, 255, 255, 255, 120, 156, 203, 102, 72, 73, 164, 3, 0, 0, 204, 203,
+                38, 180,
+            ])
+            .unwrap();
+
+        assert_badarg!(
+            native(process, binary),
+            "expected 4294967295 uncompressed bytes, but got 103"
+        );
+    });
+}
+
+#[test]
+fn with_binary_encoding_compressed_with_smaller_uncompressed_size_errors_badarg() {
+    with_process(|process| {
+        // :erlang.term_to_binary(List.duplicate(?a, 100), [:compressed]) with the uncompressed
+        // size replaced by a smaller size
+        let binary = process
+            .binary_from_bytes(&[
+                131, 80, 0, 0, 0, 50, 120, 156, 203, 102, 72, 73, 164, 3, 0, 0, 204, 203, 38, 180,
+            ])
+            .unwrap();
+
+        assert_badarg!(
+            native(process, binary),
+            "expected 50 uncompressed bytes, but got 51"
+        );
+    });
+}
+
+#[test]
+fn with_binary_encoding_compressed_tuple_returns_tuple() {
+    with_binary_returns_term(
+        // :erlang.term_to_binary({:ok, <<0::800>>}, [:compressed])
+        vec![
+            131, 80, 0, 0, 0, 112, 120, 156, 203, 96, 74, 97, 96, 202, 207, 206, 101, 96, 96, 72,
+            97, 160, 3, 0, 0, 9, 137, 2, 124,
+        ],
+        |process| {
+            process
+                .tuple_from_slice(&[
+                    Atom::str_to_term("ok"),
+                    process.binary_from_bytes(&[0; 100]).unwrap(),
+                ])
+                .unwrap()
+        },
+    );
+}
+
+#[test]
+fn with_binary_encoding_float_returns_float() {
+    with_binary_returns_term(
+        // :erlang.term_to_binary(1.5, minor_version: 0)
+        vec![
+            131, 99, 49, 46, 53, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48,
+            48, 48, 48, 101, 43, 48, 48, 0, 0, 0, 0, 0,
+        ],
+        |process| process.float(1.5).unwrap(),
+    );
+}
+
+#[test]
+fn with_binary_encoding_invalid_float_errors_badarg() {
+    with_process(|process| {
+        let mut byte_vec = vec![131, 99];
+        byte_vec.extend_from_slice(b"not a float");
+        byte_vec.resize(2 + 31, 0);
+        let binary = process.binary_from_bytes(&byte_vec).unwrap();
+
+        assert_badarg!(native(process, binary), "is not a float");
+    });
+}
+
+#[test]
+fn with_binary_encoding_port_returns_port() {
+    with_binary_returns_term(
+        // :erlang.term_to_binary(:erlang.list_to_port('#Port<0.5>'))
+        vec![
+            131, 102, 100, 0, 13, 110, 111, 110, 111, 100, 101, 64, 110, 111, 104, 111, 115, 116,
+            0, 0, 0, 5, 0,
+        ],
+        |_| Port::new(5).unwrap().encode().unwrap(),
+    );
+}
+
+#[test]
+fn with_binary_encoding_new_port_returns_port() {
+    with_binary_returns_term(
+        vec![
+            131, 89, 100, 0, 13, 110, 111, 110, 111, 100, 101, 64, 110, 111, 104, 111, 115, 116, 0,
+            0, 0, 5, 0, 0, 0, 0,
+        ],
+        |_| Port::new(5).unwrap().encode().unwrap(),
+    );
+}
+
+#[test]
+fn with_binary_encoding_reference_returns_reference() {
+    with_binary_returns_term(
+        vec![
+            131, 101, 100, 0, 13, 110, 111, 110, 111, 100, 101, 64, 110, 111, 104, 111, 115, 116,
+            0, 0, 0, 1, 0,
+        ],
+        |process| process.reference_from_scheduler(1.into(), 0).unwrap(),
+    );
+}
+
+#[test]
+fn with_binary_encoding_new_reference_returns_reference() {
+    with_binary_returns_term(
+        vec![
+            131, 114, 0, 3, 100, 0, 13, 110, 111, 110, 111, 100, 101, 64, 110, 111, 104, 111, 115,
+            116, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2,
+        ],
+        |process| process.reference_from_scheduler(1.into(), 2).unwrap(),
+    );
+}
+
+#[test]
+fn with_binary_encoding_new_reference_with_too_many_ids_errors_badarg() {
+    with_process(|process| {
+        let binary = process
+            .binary_from_bytes(&[
+                131, 114, 0, 4, 100, 0, 13, 110, 111, 110, 111, 100, 101, 64, 110, 111, 104, 111,
+                115, 116, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 3,
+            ])
+            .unwrap();
+
+        assert_badarg!(
+            native(process, binary),
+            "reference ID length (4) must be 1-3"
+        );
+    });
+}
+
+#[test]
+fn with_binary_encoding_atom_cache_reference_errors_badarg() {
+    with_process(|process| {
+        let binary = process.binary_from_bytes(&[131, 82, 0]).unwrap();
+
+        assert_badarg!(
+            native(process, binary),
+            "atom cache reference (0) is only valid in a distribution message"
+        );
+    });
+}
+
 fn with_binary_returns_term<T>(byte_vec: Vec<u8>, term: T)
 where
     T: Fn(&Process) -> Term,
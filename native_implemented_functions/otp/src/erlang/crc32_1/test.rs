@@ -0,0 +1,34 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::crc32_1::native;
+use crate::test::with_process;
+
+#[test]
+fn without_iolist_or_binary_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            native(process, Atom::str_to_term("data")),
+            "data (data) is not an iolist"
+        );
+    });
+}
+
+#[test]
+fn with_empty_binary_returns_zero() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, process.binary_from_bytes(&[]).unwrap()),
+            Ok(process.integer(0).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_binary_returns_checksum() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, process.binary_from_bytes(b"123456789").unwrap()),
+            Ok(process.integer(0xCBF4_3926_u32).unwrap())
+        );
+    });
+}
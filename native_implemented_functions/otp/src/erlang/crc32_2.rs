@@ -0,0 +1,33 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::erlang::iolist_or_binary;
+
+/// Continues computing the crc32 checksum by combining the previous checksum, `old_crc`, with
+/// the checksum of `data`
+#[native_implemented_function(crc32/2)]
+pub fn native(process: &Process, old_crc: Term, data: Term) -> exception::Result<Term> {
+    let old_crc_u32: u32 = old_crc
+        .try_into()
+        .with_context(|| format!("old_crc ({}) is not a 32-bit unsigned integer", old_crc))?;
+    let bytes = iolist_or_binary::to_bytes("data", data)?;
+
+    let mut hasher = crc32fast::Hasher::new_with_initial(old_crc_u32);
+    hasher.update(&bytes);
+
+    process.integer(hasher.finalize()).map_err(From::from)
+}
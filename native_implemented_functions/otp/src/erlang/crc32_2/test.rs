@@ -0,0 +1,41 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::crc32_1;
+use crate::erlang::crc32_2::native;
+use crate::test::with_process;
+
+#[test]
+fn without_32_bit_unsigned_integer_old_crc_errors_badarg() {
+    with_process(|process| {
+        let data = process.binary_from_bytes(b"56789").unwrap();
+
+        assert_badarg!(
+            native(process, process.integer(-1).unwrap(), data),
+            "old_crc (-1) is not a 32-bit unsigned integer"
+        );
+        assert_badarg!(
+            native(process, process.integer(1_u64 << 32).unwrap(), data),
+            "is not a 32-bit unsigned integer"
+        );
+    });
+}
+
+#[test]
+fn with_old_crc_continues_checksum() {
+    with_process(|process| {
+        let old_crc =
+            crc32_1::native(process, process.binary_from_bytes(b"1234").unwrap()).unwrap();
+        let data = process
+            .list_from_slice(&[
+                process.binary_from_bytes(b"567").unwrap(),
+                process.integer(b'8').unwrap(),
+                process.integer(b'9').unwrap(),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            native(process, old_crc, data),
+            Ok(process.integer(0xCBF4_3926_u32).unwrap())
+        );
+    });
+}
@@ -0,0 +1,92 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::{atom, exit};
+
+use native_implemented_function::native_implemented_function;
+
+use lumen_rt_core::registry::pid_to_process;
+use lumen_rt_full::process::send_exit_signal;
+
+#[native_implemented_function(exit/2)]
+pub fn native(process: &Process, pid_or_port: Term, reason: Term) -> exception::Result<Term> {
+    match pid_or_port.decode()? {
+        TypedTerm::Pid(pid) => {
+            if pid == process.pid() {
+                exit_self(process, reason)
+            } else {
+                // Signals to processes that no longer exist are silently dropped
+                if let Some(pid_arc_process) = pid_to_process(&pid) {
+                    exit_other(process, &pid_arc_process, reason);
+                }
+
+                Ok(true.into())
+            }
+        }
+        TypedTerm::Port(_) => unimplemented!(),
+        TypedTerm::ExternalPid(_) => unimplemented!(),
+        TypedTerm::ExternalPort(_) => unimplemented!(),
+        _ => Err(TypeError)
+            .context(format!(
+                "pid_or_port ({}) is neither a pid nor a port",
+                pid_or_port
+            ))
+            .map_err(From::from),
+    }
+}
+
+// Private
+
+fn is_kill(reason: Term) -> bool {
+    reason == atom!("kill")
+}
+
+fn is_normal(reason: Term) -> bool {
+    reason == atom!("normal")
+}
+
+/// Unlike signals to other processes, even a `normal` signal to the calling process makes it exit
+/// when it does not trap exits.
+fn exit_self(process: &Process, reason: Term) -> exception::Result<Term> {
+    if is_kill(reason) {
+        Err(exit!(
+            atom!("killed"),
+            anyhow!("killed by exit/2 from self").into()
+        )
+        .into())
+    } else if process.traps_exit() {
+        send_exit_signal(
+            process.pid_term(),
+            process,
+            reason,
+            anyhow!("exit/2 from self").into(),
+        );
+
+        Ok(true.into())
+    } else {
+        Err(exit!(reason, anyhow!("exit/2 from self").into()).into())
+    }
+}
+
+fn exit_other(process: &Process, pid_arc_process: &Process, reason: Term) {
+    let source = anyhow!("exit/2 from {}", process).into();
+
+    if is_kill(reason) {
+        // `kill` cannot be trapped, but the process exits with `killed`, so that the signals to
+        // its links are trappable.
+        lumen_rt_full::process::exit(pid_arc_process, atom!("killed"), source);
+    } else if is_normal(reason) && !pid_arc_process.traps_exit() {
+        // A `normal` exit signal is ignored unless it is converted to a message
+    } else {
+        send_exit_signal(process.pid_term(), pid_arc_process, reason, source);
+    }
+}
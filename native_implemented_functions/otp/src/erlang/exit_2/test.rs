@@ -0,0 +1,156 @@
+use anyhow::*;
+
+use proptest::strategy::{Just, Strategy};
+
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::{atom, exit};
+
+use crate::erlang::exit_2::native;
+use crate::test;
+use crate::test::{assert_exits, has_message, strategy, with_process};
+
+#[test]
+fn without_pid_or_port_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone())
+                    .prop_filter("Cannot be pid or port", |pid_or_port| {
+                        !(pid_or_port.is_pid() || pid_or_port.is_port())
+                    }),
+                strategy::term(arc_process.clone()),
+            )
+        },
+        |(arc_process, pid_or_port, reason)| {
+            prop_assert_badarg!(
+                native(&arc_process, pid_or_port, reason),
+                format!("pid_or_port ({}) is neither a pid nor a port", pid_or_port)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_non_existent_pid_returns_true() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, Pid::next_term(), atom!("kill")),
+            Ok(true.into())
+        );
+    });
+}
+
+#[test]
+fn with_self_and_normal_reason_exits_normal() {
+    with_process(|process| {
+        let reason = atom!("normal");
+
+        assert_eq!(
+            native(process, process.pid_term(), reason),
+            Err(exit!(reason, anyhow!("exit/2 from self").into()).into())
+        );
+    });
+}
+
+#[test]
+fn with_self_and_kill_reason_exits_killed_even_if_trapping_exits() {
+    with_process(|process| {
+        process.trap_exit(true);
+
+        assert_eq!(
+            native(process, process.pid_term(), atom!("kill")),
+            Err(exit!(
+                atom!("killed"),
+                anyhow!("killed by exit/2 from self").into()
+            )
+            .into())
+        );
+    });
+}
+
+#[test]
+fn with_self_trapping_exits_sends_exit_message() {
+    with_process(|process| {
+        process.trap_exit(true);
+        let reason = atom!("normal");
+
+        assert_eq!(native(process, process.pid_term(), reason), Ok(true.into()));
+
+        assert_has_message!(
+            process,
+            process
+                .tuple_from_slice(&[atom!("EXIT"), process.pid_term(), reason])
+                .unwrap()
+        );
+    });
+}
+
+#[test]
+fn with_other_and_normal_reason_does_not_exit_other() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term(), atom!("normal")),
+            Ok(true.into())
+        );
+
+        assert!(!other_arc_process.is_exiting());
+    });
+}
+
+#[test]
+fn with_other_exits_other_with_reason() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+        let reason = atom!("shutdown");
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term(), reason),
+            Ok(true.into())
+        );
+
+        assert_exits(&other_arc_process, reason, |_| {}, "exit/2 from");
+        assert!(!process.is_exiting());
+    });
+}
+
+#[test]
+fn with_other_trapping_exits_sends_exit_message() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+        other_arc_process.trap_exit(true);
+        let reason = atom!("shutdown");
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term(), reason),
+            Ok(true.into())
+        );
+
+        assert!(!other_arc_process.is_exiting());
+        assert_has_message!(
+            &other_arc_process,
+            process
+                .tuple_from_slice(&[atom!("EXIT"), process.pid_term(), reason])
+                .unwrap()
+        );
+    });
+}
+
+#[test]
+fn with_other_trapping_exits_and_kill_reason_exits_other_killed() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+        other_arc_process.trap_exit(true);
+
+        assert_eq!(
+            native(process, other_arc_process.pid_term(), atom!("kill")),
+            Ok(true.into())
+        );
+
+        assert_exits(&other_arc_process, atom!("killed"), |_| {}, "exit/2 from");
+    });
+}
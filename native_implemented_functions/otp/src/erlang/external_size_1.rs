@@ -0,0 +1,23 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::erlang::term_to_binary::external_size;
+
+/// Calculates, without doing the encoding, the maximum byte size for a term encoded in the
+/// Erlang external term format
+#[native_implemented_function(external_size/1)]
+pub fn native(process: &Process, term: Term) -> exception::Result<Term> {
+    process
+        .integer(external_size(process, term))
+        .map_err(From::from)
+}
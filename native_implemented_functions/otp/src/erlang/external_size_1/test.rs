@@ -0,0 +1,35 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::external_size_1::native;
+use crate::erlang::{byte_size_1, term_to_binary_1};
+use crate::test::with_process;
+
+// > external_size(1).
+// 3
+#[test]
+fn with_small_integer_returns_version_tag_and_byte() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, process.integer(1).unwrap()),
+            Ok(process.integer(3).unwrap())
+        );
+    });
+}
+
+#[test]
+fn returns_byte_size_of_term_to_binary() {
+    with_process(|process| {
+        let term = process
+            .tuple_from_slice(&[
+                Atom::str_to_term("ok"),
+                process.binary_from_bytes(&[1, 2, 3]).unwrap(),
+                process
+                    .list_from_slice(&[process.integer(256).unwrap(), process.float(1.0).unwrap()])
+                    .unwrap(),
+            ])
+            .unwrap();
+        let binary = term_to_binary_1::native(process, term).unwrap();
+
+        assert_eq!(native(process, term), byte_size_1::native(process, binary));
+    });
+}
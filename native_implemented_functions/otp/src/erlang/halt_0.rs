@@ -0,0 +1,14 @@
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use lumen_rt_core::system::Status;
+
+use crate::erlang::halt;
+
+#[native_implemented_function(halt/0)]
+pub fn native(process: &Process) -> exception::Result<Term> {
+    Ok(halt(process, Status::Code(0), Default::default()))
+}
@@ -0,0 +1,29 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use lumen_rt_core::system::Status;
+
+use crate::erlang::halt;
+
+#[native_implemented_function(halt/1)]
+pub fn native(process: &Process, status: Term) -> exception::Result<Term> {
+    let status_status: Status = status
+        .try_into()
+        .with_context(|| format!("status ({}) is not a valid halt status", status))?;
+
+    Ok(halt(process, status_status, Default::default()))
+}
@@ -0,0 +1,31 @@
+use proptest::prop_oneof;
+use proptest::strategy::Just;
+
+use crate::erlang::halt_1::native;
+use crate::test::strategy;
+
+#[test]
+fn without_non_negative_integer_abort_or_string_status_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                prop_oneof![
+                    strategy::term::integer::negative(arc_process.clone()),
+                    strategy::term::float(arc_process.clone()),
+                    strategy::term::local_reference(arc_process.clone()),
+                    strategy::term::tuple(arc_process.clone()),
+                    strategy::term::map(arc_process.clone()),
+                ],
+            )
+        },
+        |(arc_process, status)| {
+            prop_assert_badarg!(
+                native(&arc_process, status),
+                format!("status ({}) is not a valid halt status", status)
+            );
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,49 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::halt_2::native;
+use crate::test::strategy;
+
+#[test]
+fn with_invalid_status_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::integer::negative(arc_process.clone()),
+            )
+        },
+        |(arc_process, status)| {
+            prop_assert_badarg!(
+                native(&arc_process, status, Term::NIL),
+                format!("status ({}) is not a valid halt status", status)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_invalid_option_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_tuple(arc_process.clone()),
+            )
+        },
+        |(arc_process, option)| {
+            let status = arc_process.integer(0).unwrap();
+            let options = arc_process.list_from_slice(&[option]).unwrap();
+
+            prop_assert_badarg!(
+                native(&arc_process, status, options),
+                "supported options are {flush, bool}"
+            );
+
+            Ok(())
+        },
+    );
+}
@@ -48,6 +48,33 @@ pub fn native(
 }
 
 pub fn to_binary(process: &Process, name: &'static str, value: Term) -> exception::Result<Term> {
+    let byte_vec = to_byte_vec(name, value)?;
+
+    Ok(process.binary_from_bytes(byte_vec.as_slice()).unwrap())
+}
+
+/// The bytes of `iolist_or_binary`, such as the `Data` of `erlang:md5/1` or `crypto:hash/2`,
+/// without allocating a binary for them
+pub fn to_bytes(name: &'static str, iolist_or_binary: Term) -> exception::Result<Vec<u8>> {
+    match iolist_or_binary.decode()? {
+        TypedTerm::Nil
+        | TypedTerm::List(_)
+        | TypedTerm::BinaryLiteral(_)
+        | TypedTerm::HeapBinary(_)
+        | TypedTerm::MatchContext(_)
+        | TypedTerm::ProcBin(_)
+        | TypedTerm::SubBinary(_) => to_byte_vec(name, iolist_or_binary),
+        _ => Err(TypeError)
+            .context(term_is_not_type(
+                name,
+                iolist_or_binary,
+                &format!("an iolist ({}) or binary", r#type::IOLIST),
+            ))
+            .map_err(From::from),
+    }
+}
+
+fn to_byte_vec(name: &'static str, value: Term) -> exception::Result<Vec<u8>> {
     let mut byte_vec: Vec<u8> = Vec::new();
     let mut stack: Vec<Term> = vec![value];
 
@@ -83,6 +110,9 @@ pub fn to_binary(process: &Process, name: &'static str, value: Term) -> exceptio
 
                 stack.push(boxed_cons.head);
             }
+            TypedTerm::BinaryLiteral(binary_literal) => {
+                byte_vec.extend_from_slice(binary_literal.as_bytes());
+            }
             TypedTerm::HeapBinary(heap_binary) => {
                 byte_vec.extend_from_slice(heap_binary.as_bytes());
             }
@@ -110,7 +140,7 @@ pub fn to_binary(process: &Process, name: &'static str, value: Term) -> exceptio
         }
     }
 
-    Ok(process.binary_from_bytes(byte_vec.as_slice()).unwrap())
+    Ok(byte_vec)
 }
 
 fn element_context(name: &'static str, value: Term, element: Term) -> String {
@@ -0,0 +1,53 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::md5_1::native;
+use crate::test::with_process;
+
+#[test]
+fn without_iolist_or_binary_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            native(process, Atom::str_to_term("abc")),
+            "data (abc) is not an iolist"
+        );
+    });
+}
+
+#[test]
+fn with_empty_binary_returns_digest() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, process.binary_from_bytes(&[]).unwrap()),
+            Ok(process
+                .binary_from_bytes(&[
+                    0xd4, 0x1d, 0x8c, 0xd9, 0x8f, 0x00, 0xb2, 0x04, 0xe9, 0x80, 0x09, 0x98, 0xec,
+                    0xf8, 0x42, 0x7e
+                ])
+                .unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_iolist_returns_digest() {
+    with_process(|process| {
+        let data = process
+            .list_from_slice(&[
+                process.integer(b'a').unwrap(),
+                process
+                    .list_from_slice(&[process.binary_from_bytes(b"bc").unwrap()])
+                    .unwrap(),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            native(process, data),
+            Ok(process
+                .binary_from_bytes(&[
+                    0x90, 0x01, 0x50, 0x98, 0x3c, 0xd2, 0x4f, 0xb0, 0xd6, 0x96, 0x3f, 0x7d, 0x28,
+                    0xe1, 0x7f, 0x72
+                ])
+                .unwrap())
+        );
+    });
+}
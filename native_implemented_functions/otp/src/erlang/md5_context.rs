@@ -0,0 +1,186 @@
+//! The context of `md5_init/0`, `md5_update/2` and `md5_final/1`.
+//!
+//! As in BEAM, the context is a binary holding an OpenSSL `MD5_CTX`, so a context can be matched,
+//! stored and sent like one from BEAM.  The words of the `MD5_CTX` are little-endian, as on the
+//! platforms BEAM usually runs on.
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::AllocResult;
+
+use crate::erlang::iolist_or_binary;
+
+const BLOCK_SIZE: usize = 64;
+/// The size of an `MD5_CTX`: the four state words, the bit length as two words, a block, and the
+/// number of bytes of the block that are filled
+const CONTEXT_SIZE: usize = 4 * 4 + 2 * 4 + BLOCK_SIZE + 4;
+
+const INITIAL_STATE: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+const SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+const SINES: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+pub struct Md5Context {
+    state: [u32; 4],
+    bit_length: u64,
+    /// The bytes after those filled are always zero, as in OpenSSL
+    block: [u8; BLOCK_SIZE],
+    filled: usize,
+}
+
+impl Md5Context {
+    pub fn new() -> Self {
+        Self {
+            state: INITIAL_STATE,
+            bit_length: 0,
+            block: [0; BLOCK_SIZE],
+            filled: 0,
+        }
+    }
+
+    pub fn from_term(term: Term) -> exception::Result<Self> {
+        let bytes = if term.is_binary() {
+            iolist_or_binary::to_bytes("context", term)?
+        } else {
+            Vec::new()
+        };
+
+        if bytes.len() != CONTEXT_SIZE {
+            return Err(TypeError)
+                .with_context(|| format!("context ({}) is not an md5 context", term))
+                .map_err(From::from);
+        }
+
+        let word = |index: usize| u32::from_le_bytes(bytes[index * 4..][..4].try_into().unwrap());
+        let mut block = [0; BLOCK_SIZE];
+        block.copy_from_slice(&bytes[24..][..BLOCK_SIZE]);
+        let filled = word(22) as usize;
+
+        if BLOCK_SIZE <= filled {
+            return Err(TypeError)
+                .with_context(|| format!("context ({}) is not an md5 context", term))
+                .map_err(From::from);
+        }
+
+        Ok(Self {
+            state: [word(0), word(1), word(2), word(3)],
+            bit_length: (word(5) as u64) << 32 | word(4) as u64,
+            block,
+            filled,
+        })
+    }
+
+    pub fn to_term(&self, process: &Process) -> AllocResult<Term> {
+        let mut bytes = Vec::with_capacity(CONTEXT_SIZE);
+        for word in self.state.iter() {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes.extend_from_slice(&(self.bit_length as u32).to_le_bytes());
+        bytes.extend_from_slice(&((self.bit_length >> 32) as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.block);
+        bytes.extend_from_slice(&(self.filled as u32).to_le_bytes());
+
+        process.binary_from_bytes(&bytes)
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.bit_length = self.bit_length.wrapping_add((data.len() as u64) << 3);
+
+        if 0 < self.filled {
+            let len = (BLOCK_SIZE - self.filled).min(data.len());
+            self.block[self.filled..][..len].copy_from_slice(&data[..len]);
+            self.filled += len;
+            data = &data[len..];
+
+            if self.filled < BLOCK_SIZE {
+                return;
+            }
+
+            let block = self.block;
+            compress(&mut self.state, &block);
+            self.block = [0; BLOCK_SIZE];
+            self.filled = 0;
+        }
+
+        let mut blocks = data.chunks_exact(BLOCK_SIZE);
+        for block in &mut blocks {
+            compress(&mut self.state, block);
+        }
+
+        let rest = blocks.remainder();
+        self.block[..rest.len()].copy_from_slice(rest);
+        self.filled = rest.len();
+    }
+
+    /// The MD5 message digest of the data the context was updated with
+    pub fn finalize(mut self) -> [u8; 16] {
+        let bit_length = self.bit_length;
+
+        // Padded with a 1 bit, then 0 bits up to the bit length at the end of a block
+        self.update(&[0x80]);
+        while self.filled != BLOCK_SIZE - 8 {
+            self.update(&[0]);
+        }
+        self.update(&bit_length.to_le_bytes());
+
+        let mut digest = [0; 16];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(self.state.iter()) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+
+        digest
+    }
+}
+
+// Private
+
+fn compress(state: &mut [u32; 4], block: &[u8]) {
+    let mut words = [0_u32; 16];
+    for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+
+    let [mut a, mut b, mut c, mut d] = *state;
+
+    for i in 0..64 {
+        let (f, g) = match i / 16 {
+            0 => ((b & c) | (!b & d), i),
+            1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+            2 => (b ^ c ^ d, (3 * i + 5) % 16),
+            _ => (c ^ (b | !d), (7 * i) % 16),
+        };
+        let f = f
+            .wrapping_add(a)
+            .wrapping_add(SINES[i])
+            .wrapping_add(words[g]);
+
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(f.rotate_left(SHIFTS[i]));
+    }
+
+    state[0] = state[0].wrapping_add(a);
+    state[1] = state[1].wrapping_add(b);
+    state[2] = state[2].wrapping_add(c);
+    state[3] = state[3].wrapping_add(d);
+}
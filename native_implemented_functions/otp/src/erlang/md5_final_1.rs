@@ -0,0 +1,24 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::erlang::md5_context::Md5Context;
+
+/// Finishes the update of an MD5 `context` and returns the computed MD5 message digest
+#[native_implemented_function(md5_final/1)]
+pub fn native(process: &Process, context: Term) -> exception::Result<Term> {
+    let md5_context = Md5Context::from_term(context)?;
+
+    process
+        .binary_from_bytes(&md5_context.finalize())
+        .map_err(From::from)
+}
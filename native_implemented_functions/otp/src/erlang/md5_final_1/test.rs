@@ -0,0 +1,43 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::crypto::hash_init_1;
+use crate::erlang::md5_final_1::native;
+use crate::test::with_process;
+
+#[test]
+fn without_md5_context_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            native(process, process.integer(0).unwrap()),
+            "context (0) is not an md5 context"
+        );
+
+        let context = hash_init_1::native(process, Atom::str_to_term("md5")).unwrap();
+
+        assert_badarg!(native(process, context), "is not an md5 context");
+    });
+}
+
+#[test]
+fn with_binary_context_returns_digest() {
+    with_process(|process| {
+        // BEAM's `erlang:md5_update(erlang:md5_init(), "abc")`
+        let mut bytes = vec![
+            0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54,
+            0x32, 0x10, 24, 0, 0, 0, 0, 0, 0, 0, b'a', b'b', b'c',
+        ];
+        bytes.resize(88, 0);
+        bytes.extend_from_slice(&[3, 0, 0, 0]);
+        let context = process.binary_from_bytes(&bytes).unwrap();
+
+        assert_eq!(
+            native(process, context),
+            Ok(process
+                .binary_from_bytes(&[
+                    0x90, 0x01, 0x50, 0x98, 0x3c, 0xd2, 0x4f, 0xb0, 0xd6, 0x96, 0x3f, 0x7d, 0x28,
+                    0xe1, 0x7f, 0x72
+                ])
+                .unwrap())
+        );
+    });
+}
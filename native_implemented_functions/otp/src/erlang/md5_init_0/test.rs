@@ -0,0 +1,22 @@
+use crate::erlang::md5_init_0::native;
+use crate::erlang::{md5_1, md5_final_1};
+use crate::test::with_process;
+
+#[test]
+fn returns_binary_context_with_digest_of_empty_data() {
+    with_process(|process| {
+        let context = native(process).unwrap();
+
+        let mut bytes = vec![
+            0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54,
+            0x32, 0x10,
+        ];
+        bytes.resize(92, 0);
+
+        assert_eq!(context, process.binary_from_bytes(&bytes).unwrap());
+        assert_eq!(
+            md5_final_1::native(process, context),
+            md5_1::native(process, process.binary_from_bytes(&[]).unwrap())
+        );
+    });
+}
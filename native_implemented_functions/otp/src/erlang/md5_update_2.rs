@@ -0,0 +1,25 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::erlang::iolist_or_binary;
+use crate::erlang::md5_context::Md5Context;
+
+/// Updates an MD5 `context` with `data` and returns a new context
+#[native_implemented_function(md5_update/2)]
+pub fn native(process: &Process, context: Term, data: Term) -> exception::Result<Term> {
+    let mut md5_context = Md5Context::from_term(context)?;
+    let bytes = iolist_or_binary::to_bytes("data", data)?;
+    md5_context.update(&bytes);
+
+    md5_context.to_term(process).map_err(From::from)
+}
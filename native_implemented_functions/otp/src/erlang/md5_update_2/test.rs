@@ -0,0 +1,76 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::crypto::hash_init_1;
+use crate::erlang::md5_update_2::native;
+use crate::erlang::{md5_1, md5_final_1, md5_init_0};
+use crate::test::with_process;
+
+#[test]
+fn without_context_errors_badarg() {
+    with_process(|process| {
+        let data = process.binary_from_bytes(b"abc").unwrap();
+
+        assert_badarg!(
+            native(process, Atom::str_to_term("context"), data),
+            "context (context) is not an md5 context"
+        );
+        assert_badarg!(
+            native(process, process.binary_from_bytes(&[0; 91]).unwrap(), data),
+            "is not an md5 context"
+        );
+    });
+}
+
+#[test]
+fn with_crypto_hash_state_errors_badarg() {
+    with_process(|process| {
+        let context = hash_init_1::native(process, Atom::str_to_term("md5")).unwrap();
+        let data = process.binary_from_bytes(b"abc").unwrap();
+
+        assert_badarg!(native(process, context, data), "is not an md5 context");
+    });
+}
+
+#[test]
+fn with_context_returns_new_binary_context() {
+    with_process(|process| {
+        let initial_context = md5_init_0::native(process).unwrap();
+        let context = native(
+            process,
+            initial_context,
+            process.binary_from_bytes(b"abc").unwrap(),
+        )
+        .unwrap();
+
+        // The same as BEAM's `erlang:md5_update(erlang:md5_init(), "abc")`
+        let mut bytes = vec![
+            0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54,
+            0x32, 0x10, 24, 0, 0, 0, 0, 0, 0, 0, b'a', b'b', b'c',
+        ];
+        bytes.resize(88, 0);
+        bytes.extend_from_slice(&[3, 0, 0, 0]);
+
+        assert_eq!(context, process.binary_from_bytes(&bytes).unwrap());
+        assert_eq!(
+            md5_final_1::native(process, initial_context),
+            md5_1::native(process, Term::NIL)
+        );
+    });
+}
+
+#[test]
+fn with_data_in_chunks_returns_context_with_digest_of_all_data() {
+    with_process(|process| {
+        let data: Vec<u8> = (0..200).collect();
+        let mut context = md5_init_0::native(process).unwrap();
+
+        for chunk in data.chunks(7) {
+            context = native(process, context, process.binary_from_bytes(chunk).unwrap()).unwrap();
+        }
+
+        assert_eq!(
+            md5_final_1::native(process, context),
+            md5_1::native(process, process.binary_from_bytes(&data).unwrap())
+        );
+    });
+}
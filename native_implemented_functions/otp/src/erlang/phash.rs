@@ -0,0 +1,283 @@
+//! Portable hash of terms, as `make_hash` in BEAM's `erts/emulator/beam/utils.c`.
+//!
+//! `erlang:phash/2` is deprecated in favor of `erlang:phash2/2`, but its hash is kept bit-for-bit
+//! the same as BEAM's for data that was already sharded with it.
+
+use std::convert::TryInto;
+use std::iter;
+
+use num_bigint::{BigInt, Sign};
+
+use liblumen_alloc::erts::term::closure::Definition;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::phash2::{atom_hash, phash2, resource_number};
+
+pub fn phash(term: Term) -> u32 {
+    let mut hash: u32 = 0;
+    let mut stack: Vec<Item> = vec![Item::Term(term)];
+
+    while let Some(item) = stack.pop() {
+        let term = match item {
+            Item::Term(term) => term,
+            Item::ListByte(byte) => {
+                hash = hash.wrapping_mul(FUNNY_NUMBER2).wrapping_add(byte as u32);
+
+                continue;
+            }
+            Item::TupleTail { arity } => {
+                hash = hash.wrapping_mul(FUNNY_NUMBER9).wrapping_add(arity);
+
+                continue;
+            }
+            Item::ListTail => {
+                hash = hash.wrapping_mul(FUNNY_NUMBER8);
+
+                continue;
+            }
+        };
+
+        match term.decode().unwrap() {
+            TypedTerm::Atom(atom) => {
+                hash = hash
+                    .wrapping_mul(FUNNY_NUMBER1)
+                    .wrapping_add(atom_hash(atom))
+            }
+            TypedTerm::Nil => hash = hash.wrapping_mul(FUNNY_NUMBER3).wrapping_add(1),
+            TypedTerm::List(cons) => {
+                stack.push(Item::ListTail);
+
+                let mut tail = Term::NIL;
+                let mut item_vec = Vec::new();
+
+                for result in cons.into_iter() {
+                    match result {
+                        Ok(element) => {
+                            let byte_result: Result<u8, _> = element.try_into();
+
+                            item_vec.push(match byte_result {
+                                Ok(byte) => Item::ListByte(byte),
+                                Err(_) => Item::Term(element),
+                            })
+                        }
+                        Err(ImproperList {
+                            tail: improper_list_tail,
+                        }) => tail = improper_list_tail,
+                    }
+                }
+
+                stack.push(Item::Term(tail));
+                stack.extend(item_vec.into_iter().rev());
+            }
+            TypedTerm::Tuple(tuple) => {
+                stack.push(Item::TupleTail {
+                    arity: tuple.len() as u32,
+                });
+
+                for element in tuple.iter().rev() {
+                    stack.push(Item::Term(*element));
+                }
+            }
+            TypedTerm::Map(_) => {
+                hash = hash
+                    .wrapping_mul(FUNNY_NUMBER13)
+                    .wrapping_add(FUNNY_NUMBER14)
+                    .wrapping_add(phash2(term))
+            }
+            TypedTerm::SmallInteger(small_integer) => {
+                let small_integer_isize: isize = small_integer.into();
+                let big_int: BigInt = small_integer_isize.into();
+                hash = big_int_hash(hash, &big_int);
+            }
+            TypedTerm::BigInteger(big_integer) => {
+                let big_int: &BigInt = big_integer.as_ref().into();
+                hash = big_int_hash(hash, big_int);
+            }
+            TypedTerm::Float(float) => {
+                let float_f64: f64 = float.into();
+                // ensure positive 0.0
+                let bits = if float_f64 == 0.0 {
+                    0.0_f64.to_bits()
+                } else {
+                    float_f64.to_bits()
+                };
+
+                hash = hash
+                    .wrapping_mul(FUNNY_NUMBER6)
+                    .wrapping_add(((bits >> 32) as u32) ^ (bits as u32));
+            }
+            TypedTerm::BinaryLiteral(binary_literal) => {
+                hash = binary_hash(hash, binary_literal.as_bytes(), iter::empty())
+            }
+            TypedTerm::HeapBinary(heap_binary) => {
+                hash = binary_hash(hash, heap_binary.as_bytes(), iter::empty())
+            }
+            TypedTerm::ProcBin(process_binary) => {
+                hash = binary_hash(hash, process_binary.as_bytes(), iter::empty())
+            }
+            TypedTerm::SubBinary(subbinary) => {
+                let bytes: Vec<u8> = subbinary.full_byte_iter().collect();
+                hash = binary_hash(hash, &bytes, subbinary.partial_byte_bit_iter());
+            }
+            TypedTerm::MatchContext(match_context) => {
+                let bytes: Vec<u8> = match_context.full_byte_iter().collect();
+                hash = binary_hash(hash, &bytes, match_context.partial_byte_bit_iter());
+            }
+            TypedTerm::Closure(closure) => match closure.definition() {
+                Definition::Export { function } => {
+                    hash = hash
+                        .wrapping_mul(FUNNY_NUMBER11)
+                        .wrapping_add(closure.arity() as u32)
+                        .wrapping_mul(FUNNY_NUMBER1)
+                        .wrapping_add(atom_hash(closure.module()))
+                        .wrapping_mul(FUNNY_NUMBER1)
+                        .wrapping_add(atom_hash(*function));
+                }
+                Definition::Anonymous {
+                    index, old_unique, ..
+                } => {
+                    hash = hash
+                        .wrapping_mul(FUNNY_NUMBER10)
+                        .wrapping_add(closure.env_len() as u32)
+                        .wrapping_mul(FUNNY_NUMBER1)
+                        .wrapping_add(atom_hash(closure.module()))
+                        .wrapping_mul(FUNNY_NUMBER2)
+                        .wrapping_add(*index as u32)
+                        .wrapping_mul(FUNNY_NUMBER2)
+                        .wrapping_add(*old_unique);
+
+                    for term in closure.env_slice().iter().rev() {
+                        stack.push(Item::Term(*term));
+                    }
+                }
+            },
+            TypedTerm::Pid(pid) => {
+                hash = uint32_hash(hash, pid.number() as u32, FUNNY_NUMBER5, FUNNY_NUMBER6)
+            }
+            TypedTerm::ExternalPid(external_pid) => {
+                hash = uint32_hash(
+                    hash,
+                    external_pid.number() as u32,
+                    FUNNY_NUMBER5,
+                    FUNNY_NUMBER6,
+                )
+            }
+            TypedTerm::Port(port) => {
+                hash = uint32_hash(hash, port.number(), FUNNY_NUMBER9, FUNNY_NUMBER10)
+            }
+            TypedTerm::ExternalPort(external_port) => {
+                hash = uint32_hash(hash, external_port.number(), FUNNY_NUMBER9, FUNNY_NUMBER10)
+            }
+            TypedTerm::Reference(reference) => {
+                hash = uint32_hash(
+                    hash,
+                    reference.number() as u32,
+                    FUNNY_NUMBER9,
+                    FUNNY_NUMBER10,
+                )
+            }
+            TypedTerm::ExternalReference(external_reference) => {
+                hash = uint32_hash(
+                    hash,
+                    external_reference.number() as u32,
+                    FUNNY_NUMBER9,
+                    FUNNY_NUMBER10,
+                )
+            }
+            TypedTerm::ResourceReference(resource_reference) => {
+                hash = uint32_hash(
+                    hash,
+                    resource_number(resource_reference),
+                    FUNNY_NUMBER9,
+                    FUNNY_NUMBER10,
+                )
+            }
+        }
+    }
+
+    hash
+}
+
+// Private
+
+const FUNNY_NUMBER1: u32 = 268_440_163;
+const FUNNY_NUMBER2: u32 = 268_439_161;
+const FUNNY_NUMBER3: u32 = 268_435_459;
+const FUNNY_NUMBER4: u32 = 268_436_141;
+const FUNNY_NUMBER5: u32 = 268_438_633;
+const FUNNY_NUMBER6: u32 = 268_437_017;
+const FUNNY_NUMBER8: u32 = 268_437_511;
+const FUNNY_NUMBER9: u32 = 268_439_627;
+const FUNNY_NUMBER10: u32 = 268_440_479;
+const FUNNY_NUMBER11: u32 = 268_440_577;
+const FUNNY_NUMBER12: u32 = 268_440_581;
+const FUNNY_NUMBER13: u32 = 268_440_593;
+const FUNNY_NUMBER14: u32 = 268_440_611;
+
+enum Item {
+    Term(Term),
+    /// Bytes at the beginning of a list are hashed as bytes instead of as integers
+    ListByte(u8),
+    /// All elements of a tuple have been hashed
+    TupleTail {
+        arity: u32,
+    },
+    /// All elements and the tail of a list have been hashed
+    ListTail,
+}
+
+/// Small and big integers are hashed the same: as the bytes of BEAM's 64-bit digits, except
+/// that the high 32 bits of the most significant digit are skipped when they are `0`.
+fn big_int_hash(hash: u32, big_int: &BigInt) -> u32 {
+    let (sign, mut bytes) = big_int.to_bytes_le();
+
+    let most_significant_digit_len = match bytes.len() % 8 {
+        1..=4 => 4,
+        _ => 8,
+    };
+    let len = (bytes.len() - 1) / 8 * 8 + most_significant_digit_len;
+    bytes.resize(len, 0);
+
+    let hash = bytes.into_iter().fold(hash, |hash, byte| {
+        hash.wrapping_mul(FUNNY_NUMBER2).wrapping_add(byte as u32)
+    });
+
+    hash.wrapping_mul(if sign == Sign::Minus {
+        FUNNY_NUMBER4
+    } else {
+        FUNNY_NUMBER3
+    })
+}
+
+fn binary_hash<I: Iterator<Item = u8>>(hash: u32, bytes: &[u8], partial_byte_bit_iter: I) -> u32 {
+    let hash = bytes.iter().fold(hash, |hash, byte| {
+        hash.wrapping_mul(FUNNY_NUMBER1).wrapping_add(*byte as u32)
+    });
+
+    let (partial_byte_bit_len, partial_byte_bits) = partial_byte_bit_iter
+        .fold((0_u32, 0_u32), |(bit_len, bits), bit| {
+            (bit_len + 1, (bits << 1) | (bit as u32))
+        });
+
+    let hash = if 0 < partial_byte_bit_len {
+        hash.wrapping_mul(FUNNY_NUMBER1)
+            .wrapping_add(partial_byte_bits)
+            .wrapping_mul(FUNNY_NUMBER12)
+            .wrapping_add(partial_byte_bit_len)
+    } else {
+        hash
+    };
+
+    hash.wrapping_mul(FUNNY_NUMBER4)
+        .wrapping_add(bytes.len() as u32)
+}
+
+/// Hashes the bytes of `x`, least significant first, and then mixes in `multiplier`
+fn uint32_hash(hash: u32, x: u32, prime: u32, multiplier: u32) -> u32 {
+    x.to_le_bytes()
+        .iter()
+        .fold(hash, |hash, byte| {
+            hash.wrapping_mul(prime).wrapping_add(*byte as u32)
+        })
+        .wrapping_mul(multiplier)
+}
@@ -0,0 +1,444 @@
+//! Portable hash of terms, as `make_hash2` in BEAM's `erts/emulator/beam/utils.c`.
+//!
+//! The hash is part of the public contract of `erlang:phash2/1,2` because it is used to
+//! consistently shard data between nodes, so it is bit-for-bit the same as BEAM's, including
+//! integers being hashed as 32-bit words, maps being hashed independent of the order of their
+//! pairs, and binaries being hashed with Bob Jenkins' `lookup2` block hash.
+//!
+//! Terms whose identity only exists in Lumen, such as references, resources and anonymous
+//! functions, hash the same within one runtime, but not the same as any term in BEAM.
+
+use std::any::Any;
+use std::convert::TryInto;
+
+use anyhow::*;
+use num_bigint::{BigInt, Sign};
+
+use liblumen_alloc::erts::term::closure::Definition;
+use liblumen_alloc::erts::term::prelude::*;
+
+use lumen_rt_core::context::term_is_not_type;
+
+/// `erlang:phash2/1` only returns the lower 27 bits of the hash.
+pub const PHASH2_1_MASK: u32 = (1 << 27) - 1;
+
+pub fn phash2(term: Term) -> u32 {
+    let mut hash: u32 = 0;
+    // The hash of each map pair is calculated independent of the other pairs and `xor`ed into
+    // this, so that the hash of a map does not depend on the order of its pairs.
+    let mut hash_xor_pairs: u32 = 0;
+    let mut stack: Vec<Item> = vec![Item::Term(term)];
+
+    while let Some(item) = stack.pop() {
+        let term = match item {
+            Item::Term(term) => term,
+            Item::MapPair => {
+                hash_xor_pairs ^= hash;
+                hash = 0;
+
+                continue;
+            }
+            Item::MapTail {
+                hash: map_hash,
+                hash_xor_pairs: map_hash_xor_pairs,
+            } => {
+                hash = uint32_hash(map_hash, hash_xor_pairs, HCONST_19);
+                hash_xor_pairs = map_hash_xor_pairs;
+
+                continue;
+            }
+        };
+
+        match term.decode().unwrap() {
+            TypedTerm::Atom(atom) => {
+                hash = if hash == 0 {
+                    // Fast, but the poor hash value should be mixed.
+                    atom_hash(atom)
+                } else {
+                    uint32_hash(hash, atom_hash(atom), HCONST_3)
+                }
+            }
+            TypedTerm::Nil => {
+                hash = if hash == 0 {
+                    NIL_HASH
+                } else {
+                    uint32_hash(hash, NIL_DEF, HCONST_2)
+                }
+            }
+            TypedTerm::List(cons) => hash = list_hash(hash, cons, &mut stack),
+            TypedTerm::Tuple(tuple) => {
+                hash = uint32_hash(hash, tuple.len() as u32, HCONST_9);
+
+                for element in tuple.iter().rev() {
+                    stack.push(Item::Term(*element));
+                }
+            }
+            TypedTerm::Map(map) => {
+                let len = map.len();
+                hash = uint32_hash(hash, len as u32, HCONST_16);
+
+                if 0 < len {
+                    stack.push(Item::MapTail {
+                        hash,
+                        hash_xor_pairs,
+                    });
+                    hash = 0;
+                    hash_xor_pairs = 0;
+
+                    for (key, value) in map.iter() {
+                        stack.push(Item::MapPair);
+                        stack.push(Item::Term(value));
+                        stack.push(Item::Term(key));
+                    }
+                }
+            }
+            TypedTerm::SmallInteger(small_integer) => {
+                let small_integer_isize: isize = small_integer.into();
+                hash = integer_hash(hash, small_integer_isize as i64);
+            }
+            TypedTerm::BigInteger(big_integer) => {
+                let big_int: &BigInt = big_integer.as_ref().into();
+                hash = big_int_hash(hash, big_int);
+            }
+            TypedTerm::Float(float) => {
+                let float_f64: f64 = float.into();
+                // ensure positive 0.0
+                let bits = if float_f64 == 0.0 {
+                    0.0_f64.to_bits()
+                } else {
+                    float_f64.to_bits()
+                };
+
+                hash = uint32_hash_2(hash, (bits >> 32) as u32, bits as u32, HCONST_12);
+            }
+            TypedTerm::BinaryLiteral(binary_literal) => {
+                hash = binary_hash(hash, binary_literal.as_bytes(), 0, 0)
+            }
+            TypedTerm::HeapBinary(heap_binary) => {
+                hash = binary_hash(hash, heap_binary.as_bytes(), 0, 0)
+            }
+            TypedTerm::ProcBin(process_binary) => {
+                hash = binary_hash(hash, process_binary.as_bytes(), 0, 0)
+            }
+            TypedTerm::SubBinary(subbinary) => {
+                let bytes: Vec<u8> = subbinary.full_byte_iter().collect();
+                let (bit_len, bits) = partial_byte(subbinary.partial_byte_bit_iter());
+
+                hash = binary_hash(hash, &bytes, bit_len, bits);
+            }
+            TypedTerm::MatchContext(match_context) => {
+                let bytes: Vec<u8> = match_context.full_byte_iter().collect();
+                let (bit_len, bits) = partial_byte(match_context.partial_byte_bit_iter());
+
+                hash = binary_hash(hash, &bytes, bit_len, bits);
+            }
+            TypedTerm::Closure(closure) => match closure.definition() {
+                Definition::Export { function } => {
+                    hash = uint32_hash_2(
+                        hash,
+                        closure.arity() as u32,
+                        atom_hash(closure.module()),
+                        HCONST,
+                    );
+                    hash = uint32_hash(hash, atom_hash(*function), HCONST_14);
+                }
+                Definition::Anonymous {
+                    index, old_unique, ..
+                } => {
+                    hash = uint32_hash_2(
+                        hash,
+                        closure.env_len() as u32,
+                        atom_hash(closure.module()),
+                        HCONST,
+                    );
+                    hash = uint32_hash_2(hash, *index as u32, *old_unique, HCONST);
+
+                    for term in closure.env_slice().iter().rev() {
+                        stack.push(Item::Term(*term));
+                    }
+                }
+            },
+            // Only 15 bits are hashed.
+            TypedTerm::Pid(pid) => hash = uint32_hash(hash, pid.number() as u32, HCONST_5),
+            TypedTerm::ExternalPid(external_pid) => {
+                hash = uint32_hash(hash, external_pid.number() as u32, HCONST_5)
+            }
+            TypedTerm::Port(port) => hash = uint32_hash(hash, port.number(), HCONST_6),
+            TypedTerm::ExternalPort(external_port) => {
+                hash = uint32_hash(hash, external_port.number(), HCONST_6)
+            }
+            // Only the first 32-bit word of the reference number is hashed.
+            TypedTerm::Reference(reference) => {
+                hash = uint32_hash(hash, reference.number() as u32, HCONST_7)
+            }
+            TypedTerm::ExternalReference(external_reference) => {
+                hash = uint32_hash(hash, external_reference.number() as u32, HCONST_7)
+            }
+            TypedTerm::ResourceReference(resource_reference) => {
+                hash = uint32_hash(hash, resource_number(resource_reference), HCONST_7)
+            }
+        }
+    }
+
+    hash
+}
+
+/// The hash of an atom's name, which BEAM stores in the atom table, as `atom_hash` in
+/// `erts/emulator/beam/atom.c`.
+pub fn atom_hash(atom: Atom) -> u32 {
+    let bytes = atom.name().as_bytes();
+    let mut hash: u32 = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let mut byte = bytes[index];
+        index += 1;
+
+        // Atoms were Latin-1 before OTP 20, so two-byte UTF-8 encodings of Latin-1 characters
+        // are hashed as the Latin-1 character.
+        if index < bytes.len() && (byte & 0xFE) == 0xC2 && (bytes[index] & 0xC0) == 0x80 {
+            byte = (byte << 6) | (bytes[index] & 0x3F);
+            index += 1;
+        }
+
+        // hashpjw
+        hash = (hash << 4).wrapping_add(byte as u32);
+        let high = hash & 0xF000_0000;
+
+        if high != 0 {
+            hash ^= high >> 24;
+            hash ^= high;
+        }
+    }
+
+    hash
+}
+
+/// Resources are references in BEAM, so they are identified by the value they share, like the
+/// number of a reference
+pub fn resource_number(resource_reference: Boxed<Resource>) -> u32 {
+    let resource: Resource = resource_reference.into();
+
+    resource.value() as *const dyn Any as *const () as usize as u32
+}
+
+/// Converts the `range` of `phash/2` and `phash2/2`, which must be in `1..=2^32`.  A range of
+/// `2^32` is returned as `None` because the 32-bit hash does not need to be reduced.
+pub fn term_try_into_range(range: Term) -> anyhow::Result<Option<u32>> {
+    let range_u64: Option<u64> = range.try_into().ok();
+
+    match range_u64 {
+        Some(RANGE_MAX) => Ok(None),
+        Some(range_u64) if 1 <= range_u64 && range_u64 < RANGE_MAX => Ok(Some(range_u64 as u32)),
+        _ => Err(TypeError)
+            .with_context(|| term_is_not_type("range", range, "an integer in 1..2^32")),
+    }
+}
+
+// Private
+
+/// The golden ratio, an arbitrary value
+const HCONST: u32 = 0x9E37_79B9;
+// HCONST * {2, ..., 19} mod 2^32
+const HCONST_2: u32 = 0x3C6E_F372;
+const HCONST_3: u32 = 0xDAA6_6D2B;
+const HCONST_4: u32 = 0x78DD_E6E4;
+const HCONST_5: u32 = 0x1715_609D;
+const HCONST_6: u32 = 0xB54C_DA56;
+const HCONST_7: u32 = 0x5384_540F;
+const HCONST_9: u32 = 0x8FF3_4781;
+const HCONST_10: u32 = 0x2E2A_C13A;
+const HCONST_11: u32 = 0xCC62_3AF3;
+const HCONST_12: u32 = 0x6A99_B4AC;
+const HCONST_13: u32 = 0x08D1_2E65;
+const HCONST_14: u32 = 0xA708_A81E;
+const HCONST_15: u32 = 0x4540_21D7;
+const HCONST_16: u32 = 0xE377_9B90;
+const HCONST_19: u32 = 0xBE1E_08BB;
+
+const RANGE_MAX: u64 = 1 << 32;
+
+/// BEAM's tag for `[]`
+const NIL_DEF: u32 = 2;
+/// `uint32_hash(0, NIL_DEF, HCONST_2)`
+const NIL_HASH: u32 = 3_468_870_702;
+
+enum Item {
+    Term(Term),
+    /// The key and value of a map pair have been hashed
+    MapPair,
+    /// All pairs of a map have been hashed, so the hash of the map's container can be restored
+    MapTail {
+        hash: u32,
+        hash_xor_pairs: u32,
+    },
+}
+
+fn big_int_hash(hash: u32, big_int: &BigInt) -> u32 {
+    let (sign, bytes) = big_int.to_bytes_le();
+    let constant = if sign == Sign::Minus {
+        HCONST_10
+    } else {
+        HCONST_11
+    };
+
+    // BEAM's 64-bit digits are hashed as pairs of 32-bit words
+    bytes.chunks(8).fold(hash, |hash, chunk| {
+        let mut digit_bytes = [0; 8];
+        digit_bytes[..chunk.len()].copy_from_slice(chunk);
+        let digit = u64::from_le_bytes(digit_bytes);
+
+        uint32_hash_2(hash, digit as u32, (digit >> 32) as u32, constant)
+    })
+}
+
+fn binary_hash(hash: u32, bytes: &[u8], partial_byte_bit_len: u8, partial_byte_bits: u8) -> u32 {
+    let constant = HCONST_13.wrapping_add(hash);
+
+    if bytes.is_empty() && partial_byte_bit_len == 0 {
+        constant
+    } else {
+        let hash = block_hash(bytes, constant);
+
+        if 0 < partial_byte_bit_len {
+            uint32_hash_2(
+                hash,
+                partial_byte_bit_len as u32,
+                partial_byte_bits as u32,
+                HCONST_15,
+            )
+        } else {
+            hash
+        }
+    }
+}
+
+/// Bob Jenkins' `lookup2` hash
+fn block_hash(bytes: &[u8], initial_value: u32) -> u32 {
+    let mut a = HCONST;
+    let mut b = HCONST;
+    let mut c = initial_value;
+
+    let mut chunks = bytes.chunks_exact(12);
+
+    for chunk in &mut chunks {
+        a = a.wrapping_add(u32::from_le_bytes(chunk[0..4].try_into().unwrap()));
+        b = b.wrapping_add(u32::from_le_bytes(chunk[4..8].try_into().unwrap()));
+        c = c.wrapping_add(u32::from_le_bytes(chunk[8..12].try_into().unwrap()));
+        mix(&mut a, &mut b, &mut c);
+    }
+
+    c = c.wrapping_add(bytes.len() as u32);
+
+    // the first byte of c is reserved for the length
+    for (index, byte) in chunks.remainder().iter().enumerate() {
+        let byte_u32 = *byte as u32;
+
+        match index {
+            0..=3 => a = a.wrapping_add(byte_u32 << (8 * index)),
+            4..=7 => b = b.wrapping_add(byte_u32 << (8 * (index - 4))),
+            _ => c = c.wrapping_add(byte_u32 << (8 * (index - 7))),
+        }
+    }
+
+    mix(&mut a, &mut b, &mut c);
+
+    c
+}
+
+fn integer_hash(hash: u32, integer: i64) -> u32 {
+    // BEAM's 64-bit small integers outside of 28 bits are hashed as big integers, so that the
+    // hash is the same on 32- and 64-bit architectures.
+    if -(1 << 27) <= integer && integer < (1 << 27) {
+        let integer_i32 = integer as i32;
+
+        let hash = if integer_i32 < 0 {
+            // Negative numbers are unnecessarily mixed twice.
+            uint32_hash(hash, (-integer_i32) as u32, HCONST)
+        } else {
+            hash
+        };
+
+        uint32_hash(hash, integer_i32 as u32, HCONST)
+    } else {
+        big_int_hash(hash, &integer.into())
+    }
+}
+
+/// Hashes the bytes at the beginning of list as packed 32-bit words, and pushes the rest of the
+/// list to the `stack`.
+fn list_hash(mut hash: u32, mut cons: Boxed<Cons>, stack: &mut Vec<Item>) -> u32 {
+    let mut packed_len = 0;
+    let mut packed: u32 = 0;
+
+    loop {
+        let byte_result: Result<u8, _> = cons.head.try_into();
+
+        match byte_result {
+            Ok(byte) => {
+                packed = (packed << 8) + byte as u32;
+
+                if packed_len == 3 {
+                    hash = uint32_hash(hash, packed, HCONST_4);
+                    packed_len = 0;
+                    packed = 0;
+                } else {
+                    packed_len += 1;
+                }
+
+                match cons.tail.decode().unwrap() {
+                    TypedTerm::List(tail_cons) => cons = tail_cons,
+                    _ => {
+                        stack.push(Item::Term(cons.tail));
+
+                        break;
+                    }
+                }
+            }
+            Err(_) => {
+                stack.push(Item::Term(cons.tail));
+                stack.push(Item::Term(cons.head));
+
+                break;
+            }
+        }
+    }
+
+    if 0 < packed_len {
+        hash = uint32_hash(hash, packed, HCONST_4);
+    }
+
+    hash
+}
+
+fn mix(a: &mut u32, b: &mut u32, c: &mut u32) {
+    *a = a.wrapping_sub(*b).wrapping_sub(*c) ^ (*c >> 13);
+    *b = b.wrapping_sub(*c).wrapping_sub(*a) ^ (*a << 8);
+    *c = c.wrapping_sub(*a).wrapping_sub(*b) ^ (*b >> 13);
+    *a = a.wrapping_sub(*b).wrapping_sub(*c) ^ (*c >> 12);
+    *b = b.wrapping_sub(*c).wrapping_sub(*a) ^ (*a << 16);
+    *c = c.wrapping_sub(*a).wrapping_sub(*b) ^ (*b >> 5);
+    *a = a.wrapping_sub(*b).wrapping_sub(*c) ^ (*c >> 3);
+    *b = b.wrapping_sub(*c).wrapping_sub(*a) ^ (*a << 10);
+    *c = c.wrapping_sub(*a).wrapping_sub(*b) ^ (*b >> 15);
+}
+
+/// The bits of a partial byte, as `(bit_len, bits)`, where `bits` are the low bits
+fn partial_byte(bit_iter: Box<dyn BitIterator>) -> (u8, u8) {
+    bit_iter.fold((0, 0), |(bit_len, bits), bit| {
+        (bit_len + 1, (bits << 1) | bit)
+    })
+}
+
+fn uint32_hash(hash: u32, x: u32, constant: u32) -> u32 {
+    uint32_hash_2(hash, x, 0, constant)
+}
+
+fn uint32_hash_2(hash: u32, x: u32, y: u32, constant: u32) -> u32 {
+    let mut a = constant.wrapping_add(x);
+    let mut b = constant.wrapping_add(y);
+    let mut c = hash;
+    mix(&mut a, &mut b, &mut c);
+
+    c
+}
@@ -0,0 +1,23 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::erlang::phash2::{phash2, PHASH2_1_MASK};
+
+/// Portable hash function that gives the same hash for the same Erlang term regardless of
+/// machine architecture and ERTS version.  Returns a hash in `0..2^27`.
+#[native_implemented_function(phash2/1)]
+pub fn native(process: &Process, term: Term) -> exception::Result<Term> {
+    process
+        .integer(phash2(term) & PHASH2_1_MASK)
+        .map_err(From::from)
+}
@@ -0,0 +1,358 @@
+use num_bigint::BigInt;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::phash2_1::native;
+use crate::test::with_process;
+
+#[test]
+fn with_map_returns_same_hash_independent_of_pair_order() {
+    with_process(|process| {
+        let a = Atom::str_to_term("a");
+        let b = Atom::str_to_term("b");
+        let one = process.integer(1).unwrap();
+        let two = process.integer(2).unwrap();
+
+        assert_eq!(
+            native(
+                process,
+                process.map_from_slice(&[(a, one), (b, two)]).unwrap()
+            ),
+            native(
+                process,
+                process.map_from_slice(&[(b, two), (a, one)]).unwrap()
+            )
+        );
+    });
+}
+
+// > erlang:phash2([]).
+// 113427502
+// > erlang:phash2(a).
+// 97
+// > erlang:phash2(abc).
+// 26499
+#[test]
+fn with_nil_or_atom_returns_same_hash_as_otp() {
+    with_process(|process| {
+        let term = Term::NIL;
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(113427502_u64).unwrap())
+        );
+
+        let term = Atom::str_to_term("a");
+
+        assert_eq!(native(process, term), Ok(process.integer(97_u64).unwrap()));
+
+        let term = Atom::str_to_term("abc");
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(26499_u64).unwrap())
+        );
+    });
+}
+
+// > erlang:phash2(0).
+// 88723725
+// > erlang:phash2(1).
+// 2614250
+// > erlang:phash2(-1).
+// 44071773
+#[test]
+fn with_small_integer_returns_same_hash_as_otp() {
+    with_process(|process| {
+        let term = process.integer(0).unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(88723725_u64).unwrap())
+        );
+
+        let term = process.integer(1).unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(2614250_u64).unwrap())
+        );
+
+        let term = process.integer(-1).unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(44071773_u64).unwrap())
+        );
+    });
+}
+
+// > erlang:phash2(1 bsl 27).
+// 12354923
+// > erlang:phash2(-(1 bsl 27) - 1).
+// 76739502
+// > erlang:phash2(1 bsl 64).
+// 103122609
+// > erlang:phash2(-(1 bsl 100)).
+// 124857544
+#[test]
+fn with_integer_outside_28_bits_returns_same_hash_as_otp() {
+    with_process(|process| {
+        let term = process.integer(1_i64 << 27).unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(12354923_u64).unwrap())
+        );
+
+        let term = process.integer(-(1_i64 << 27) - 1).unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(76739502_u64).unwrap())
+        );
+
+        let term = process.integer(BigInt::from(1) << 64).unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(103122609_u64).unwrap())
+        );
+
+        let term = process.integer(-(BigInt::from(1) << 100)).unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(124857544_u64).unwrap())
+        );
+    });
+}
+
+// > erlang:phash2(1.0).
+// 77147068
+// > erlang:phash2(0.0).
+// 20875736
+// > erlang:phash2(-0.0).
+// 20875736
+#[test]
+fn with_float_returns_same_hash_as_otp() {
+    with_process(|process| {
+        let term = process.float(1.0).unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(77147068_u64).unwrap())
+        );
+
+        let term = process.float(0.0).unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(20875736_u64).unwrap())
+        );
+
+        let term = process.float(-0.0).unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(20875736_u64).unwrap())
+        );
+    });
+}
+
+// > erlang:phash2("abc").
+// 117343302
+// > erlang:phash2("abcde").
+// 71169904
+// > erlang:phash2([1,2,3,4,5]).
+// 66056394
+// > erlang:phash2([256]).
+// 73608398
+// > erlang:phash2([a,b]).
+// 9075654
+// > erlang:phash2([a|b]).
+// 74710280
+#[test]
+fn with_list_returns_same_hash_as_otp() {
+    with_process(|process| {
+        let term = process.charlist_from_str("abc").unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(117343302_u64).unwrap())
+        );
+
+        let term = process.charlist_from_str("abcde").unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(71169904_u64).unwrap())
+        );
+
+        let term = process
+            .list_from_slice(&[
+                process.integer(1).unwrap(),
+                process.integer(2).unwrap(),
+                process.integer(3).unwrap(),
+                process.integer(4).unwrap(),
+                process.integer(5).unwrap(),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(66056394_u64).unwrap())
+        );
+
+        let term = process
+            .list_from_slice(&[process.integer(256).unwrap()])
+            .unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(73608398_u64).unwrap())
+        );
+
+        let term = process
+            .list_from_slice(&[Atom::str_to_term("a"), Atom::str_to_term("b")])
+            .unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(9075654_u64).unwrap())
+        );
+
+        let term = process
+            .cons(Atom::str_to_term("a"), Atom::str_to_term("b"))
+            .unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(74710280_u64).unwrap())
+        );
+    });
+}
+
+// > erlang:phash2({}).
+// 87486268
+// > erlang:phash2({a,b}).
+// 101277806
+#[test]
+fn with_tuple_returns_same_hash_as_otp() {
+    with_process(|process| {
+        let term = process.tuple_from_slice(&[]).unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(87486268_u64).unwrap())
+        );
+
+        let term = process
+            .tuple_from_slice(&[Atom::str_to_term("a"), Atom::str_to_term("b")])
+            .unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(101277806_u64).unwrap())
+        );
+    });
+}
+
+// > erlang:phash2(<<>>).
+// 13708901
+// > erlang:phash2(<<1,2,3>>).
+// 6479071
+// > erlang:phash2(<<"hello world!">>).
+// 25699699
+#[test]
+fn with_binary_returns_same_hash_as_otp() {
+    with_process(|process| {
+        let term = process.binary_from_bytes(&[]).unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(13708901_u64).unwrap())
+        );
+
+        let term = process.binary_from_bytes(&[1, 2, 3]).unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(6479071_u64).unwrap())
+        );
+
+        let term = process.binary_from_bytes(b"hello world!").unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(25699699_u64).unwrap())
+        );
+    });
+}
+
+// > erlang:phash2(<<1:1>>).
+// 102233125
+// > erlang:phash2(<<1,5:3>>).
+// 37317630
+#[test]
+fn with_bitstring_returns_same_hash_as_otp() {
+    with_process(|process| {
+        let term = process
+            .subbinary_from_original(
+                process.binary_from_bytes(&[0b1000_0000]).unwrap(),
+                0,
+                0,
+                0,
+                1,
+            )
+            .unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(102233125_u64).unwrap())
+        );
+
+        let term = process
+            .subbinary_from_original(
+                process.binary_from_bytes(&[1, 0b1010_0000]).unwrap(),
+                0,
+                0,
+                1,
+                3,
+            )
+            .unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(37317630_u64).unwrap())
+        );
+    });
+}
+
+// > erlang:phash2(#{}).
+// 39679005
+// > erlang:phash2(#{a => 1, b => 2}).
+// 103634663
+#[test]
+fn with_map_returns_same_hash_as_otp() {
+    with_process(|process| {
+        let term = process.map_from_slice(&[]).unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(39679005_u64).unwrap())
+        );
+
+        let term = process
+            .map_from_slice(&[
+                (Atom::str_to_term("a"), process.integer(1).unwrap()),
+                (Atom::str_to_term("b"), process.integer(2).unwrap()),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            native(process, term),
+            Ok(process.integer(103634663_u64).unwrap())
+        );
+    });
+}
@@ -0,0 +1,52 @@
+use crate::erlang::phash2_2::native;
+use crate::test::hash;
+
+#[test]
+fn without_range_in_1_to_2_to_the_32_errors_badarg() {
+    hash::without_range_in_1_to_2_to_the_32_errors_badarg(native);
+}
+
+// > erlang:phash2("abc", 1000).
+// 486
+#[test]
+fn with_range_returns_hash_in_0_to_range() {
+    hash::with_range_returns_hash_in_range(native, 486, 0);
+}
+
+// The hashes are those of `erlang:phash2(Term, 1 bsl 32)`
+#[test]
+fn returns_same_hash_as_otp() {
+    hash::returns_same_hash_as_otp(
+        native,
+        &[
+            ("[]", 3468870702),
+            ("a", 97),
+            ("abc", 26499),
+            ("0", 3175731469),
+            ("1", 539485162),
+            ("-1", 1117813597),
+            ("1 bsl 27", 2562491755),
+            ("-(1 bsl 27) - 1", 1418916782),
+            ("1 bsl 64", 2519041713),
+            ("-(1 bsl 100)", 3614518472),
+            ("1.0", 3029937084),
+            ("0.0", 423528920),
+            ("-0.0", 423528920),
+            ("\"abc\"", 519996486),
+            ("\"abcde\"", 2487089008),
+            ("[1,2,3,4,5]", 3824152778),
+            ("[256]", 1684221134),
+            ("[a,b]", 3632954310),
+            ("[a|b]", 2356411656),
+            ("{}", 221703996),
+            ("{a,b}", 1980325998),
+            ("<<>>", 147926629),
+            ("<<1,2,3>>", 4167228639),
+            ("<<\"hello world!\">>", 965223795),
+            ("<<1:1>>", 3994547237),
+            ("<<1,5:3>>", 3124325374),
+            ("#{}", 844985373),
+            ("#{a => 1, b => 2}", 1982682855),
+        ],
+    );
+}
@@ -0,0 +1,32 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::erlang::phash::phash;
+use crate::erlang::phash2::term_try_into_range;
+
+/// Portable hash function that gives the same hash for the same Erlang term regardless of
+/// machine architecture and ERTS version.  Returns a hash in `1..=range`.
+///
+/// Deprecated in favor of `phash2/2`, but kept for data that is already hashed with it.
+#[native_implemented_function(phash/2)]
+pub fn native(process: &Process, term: Term, range: Term) -> exception::Result<Term> {
+    let range = term_try_into_range(range)?;
+    let hash = phash(term);
+
+    let hash_in_range: u64 = match range {
+        Some(range) => (hash % range) as u64 + 1,
+        None => hash as u64 + 1,
+    };
+
+    process.integer(hash_in_range).map_err(From::from)
+}
@@ -0,0 +1,52 @@
+use crate::erlang::phash_2::native;
+use crate::test::hash;
+
+#[test]
+fn without_range_in_1_to_2_to_the_32_errors_badarg() {
+    hash::without_range_in_1_to_2_to_the_32_errors_badarg(native);
+}
+
+// > erlang:phash("abc", 1000).
+// 166
+#[test]
+fn with_range_returns_hash_in_1_to_range() {
+    hash::with_range_returns_hash_in_range(native, 166, 1);
+}
+
+// The hashes are those of `erlang:phash(Term, 1 bsl 32)`
+#[test]
+fn returns_same_hash_as_otp() {
+    hash::returns_same_hash_as_otp(
+        native,
+        &[
+            ("[]", 2),
+            ("a", 98),
+            ("abc", 26500),
+            ("0", 1),
+            ("1", 2788898428),
+            ("-1", 1680185270),
+            ("1 bsl 27", 2147483673),
+            ("-(1 bsl 27) - 1", 3827674398),
+            ("1 bsl 64", 2788898428),
+            ("-(1 bsl 100)", 1113160529),
+            ("1.0", 1072693249),
+            ("0.0", 1),
+            ("-0.0", 1),
+            ("\"abc\"", 3654580166),
+            ("\"abcde\"", 721934915),
+            ("[1,2,3,4,5]", 1630427363),
+            ("[256]", 1015129879),
+            ("[a,b]", 667932113),
+            ("[a|b]", 4159696708),
+            ("{}", 1),
+            ("{a,b}", 1904799770),
+            ("<<>>", 1),
+            ("<<1,2,3>>", 687692590),
+            ("<<\"hello world!\">>", 625310646),
+            ("<<1:1>>", 808817679),
+            ("<<1,5:3>>", 435939697),
+            ("#{}", 1113425985),
+            ("#{a => 1, b => 2}", 2251123467),
+        ],
+    );
+}
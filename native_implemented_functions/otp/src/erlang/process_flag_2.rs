@@ -5,28 +5,35 @@
 #[cfg(all(not(target_arch = "wasm32"), test))]
 mod test;
 
+use std::convert::TryInto;
+
 use anyhow::*;
 
 use liblumen_alloc::erts::exception;
-use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::process::{MaxHeapSize, MessageQueueData, Priority, Process};
 use liblumen_alloc::erts::term::prelude::*;
 
 use native_implemented_function::native_implemented_function;
 
 use lumen_rt_core::context::*;
 
+use crate::erlang::process_info_2::max_heap_size_to_term;
+
+/// The maximum for `save_calls`, the same as BEAM.
+const SAVE_CALLS_MAX: usize = 10_000;
+
 #[native_implemented_function(process_flag/2)]
 pub fn native(process: &Process, flag: Term, value: Term) -> exception::Result<Term> {
     let flag_atom = term_try_into_atom!(flag)?;
 
     match flag_atom.name() {
-        "error_handler" => unimplemented!(),
-        "max_heap_size" => unimplemented!(),
-        "message_queue_data" => unimplemented!(),
-        "min_bin_vheap_size" => unimplemented!(),
-        "min_heap_size" => unimplemented!(),
-        "priority" => unimplemented!(),
-        "save_calls" => unimplemented!(),
+        "error_handler" => error_handler(process, value),
+        "max_heap_size" => max_heap_size(process, value),
+        "message_queue_data" => message_queue_data(process, value),
+        "min_bin_vheap_size" => min_bin_vheap_size(process, value),
+        "min_heap_size" => min_heap_size(process, value),
+        "priority" => priority(process, value),
+        "save_calls" => save_calls(process, value),
         "sensitive" => unimplemented!(),
         "trap_exit" => {
             let value_bool: bool = term_try_into_bool("trap_exit value", value)?;
@@ -36,3 +43,72 @@ pub fn native(process: &Process, flag: Term, value: Term) -> exception::Result<T
         name => Err(TryAtomFromTermError(name)).context("supported flags are error_handler, max_heap_size, message_queue_data, min_bin_vheap_size, min_heap_size, priority, save_calls, sensitive, and trap_exit").map_err(From::from),
     }
 }
+
+// Private
+
+fn error_handler(process: &Process, value: Term) -> exception::Result<Term> {
+    let module_atom = term_try_into_atom("error_handler value", value)?;
+    let old_module_atom = process.set_error_handler(module_atom);
+
+    old_module_atom.encode().map_err(From::from)
+}
+
+fn max_heap_size(process: &Process, value: Term) -> exception::Result<Term> {
+    let max_heap_size: MaxHeapSize = value.try_into()?;
+    let old_max_heap_size = process.set_max_heap_size(max_heap_size);
+
+    max_heap_size_to_term(process, old_max_heap_size).map_err(From::from)
+}
+
+fn message_queue_data(process: &Process, value: Term) -> exception::Result<Term> {
+    let message_queue_data: MessageQueueData = value.try_into()?;
+    let old_message_queue_data_atom: Atom =
+        process.set_message_queue_data(message_queue_data).into();
+
+    old_message_queue_data_atom.encode().map_err(From::from)
+}
+
+fn min_bin_vheap_size(process: &Process, value: Term) -> exception::Result<Term> {
+    let min_bin_vheap_size = term_try_into_non_negative_usize("min_bin_vheap_size value", value)?;
+    let old_min_bin_vheap_size = process.set_min_vheap_size(min_bin_vheap_size);
+
+    process.integer(old_min_bin_vheap_size).map_err(From::from)
+}
+
+fn min_heap_size(process: &Process, value: Term) -> exception::Result<Term> {
+    let min_heap_size = term_try_into_non_negative_usize("min_heap_size value", value)?;
+    let old_min_heap_size = process.set_min_heap_size(min_heap_size);
+
+    process.integer(old_min_heap_size).map_err(From::from)
+}
+
+/// The new priority takes effect the next time `process` is enqueued in its scheduler's run queue,
+/// which happens when it stops running after this call.
+fn priority(process: &Process, value: Term) -> exception::Result<Term> {
+    let priority: Priority = value.try_into()?;
+    let old_priority_atom: Atom = process.set_priority(priority).into();
+
+    old_priority_atom.encode().map_err(From::from)
+}
+
+fn save_calls(process: &Process, value: Term) -> exception::Result<Term> {
+    let save_calls: usize = value
+        .try_into()
+        .ok()
+        .filter(|save_calls| *save_calls <= SAVE_CALLS_MAX)
+        .with_context(|| {
+            format!(
+                "save_calls value ({}) must be an integer in 0-{}",
+                value, SAVE_CALLS_MAX
+            )
+        })?;
+    let old_save_calls = process.set_save_calls(save_calls);
+
+    process.integer(old_save_calls).map_err(From::from)
+}
+
+fn term_try_into_non_negative_usize(name: &str, value: Term) -> anyhow::Result<usize> {
+    value
+        .try_into()
+        .with_context(|| term_is_not_non_negative_integer(name, value))
+}
@@ -0,0 +1,42 @@
+use super::*;
+
+#[test]
+fn without_atom_value_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_atom(arc_process.clone()),
+            )
+        },
+        |(arc_process, value)| {
+            prop_assert_is_not_atom!(
+                native(&arc_process, flag(), value),
+                "error_handler value",
+                value
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_atom_value_returns_old_error_handler() {
+    with_process(|process| {
+        let value = Atom::str_to_term("custom_error_handler");
+
+        assert_eq!(
+            native(process, flag(), value),
+            Ok(Atom::str_to_term("error_handler"))
+        );
+        assert_eq!(
+            native(process, flag(), Atom::str_to_term("error_handler")),
+            Ok(value)
+        );
+    });
+}
+
+fn flag() -> Term {
+    Atom::str_to_term("error_handler")
+}
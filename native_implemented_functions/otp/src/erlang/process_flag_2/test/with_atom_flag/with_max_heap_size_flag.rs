@@ -0,0 +1,57 @@
+use super::*;
+
+use liblumen_alloc::erts::process::{MaxHeapSize, Process};
+
+#[test]
+fn with_non_negative_integer_value_returns_old_max_heap_size_map() {
+    with_process(|process| {
+        let value = process.integer(1024).unwrap();
+
+        assert_eq!(
+            native(process, flag(), value),
+            Ok(max_heap_size_map(process, 0, true, true))
+        );
+        assert_eq!(
+            process.max_heap_size(),
+            MaxHeapSize {
+                size: 1024,
+                kill: true,
+                error_logger: true
+            }
+        );
+    });
+}
+
+#[test]
+fn with_map_value_sets_size_kill_and_error_logger() {
+    with_process(|process| {
+        let value = max_heap_size_map(process, 2048, false, false);
+
+        assert_eq!(
+            native(process, flag(), value),
+            Ok(max_heap_size_map(process, 0, true, true))
+        );
+        assert_eq!(
+            process.max_heap_size(),
+            MaxHeapSize {
+                size: 2048,
+                kill: false,
+                error_logger: false
+            }
+        );
+    });
+}
+
+fn flag() -> Term {
+    Atom::str_to_term("max_heap_size")
+}
+
+fn max_heap_size_map(process: &Process, size: usize, kill: bool, error_logger: bool) -> Term {
+    process
+        .map_from_slice(&[
+            (Atom::str_to_term("error_logger"), error_logger.into()),
+            (Atom::str_to_term("kill"), kill.into()),
+            (Atom::str_to_term("size"), process.integer(size).unwrap()),
+        ])
+        .unwrap()
+}
@@ -0,0 +1,45 @@
+use super::*;
+
+use liblumen_alloc::erts::message::Message;
+use liblumen_alloc::erts::process::MessageQueueData;
+
+#[test]
+fn with_off_heap_value_returns_old_on_heap_and_stores_messages_off_heap() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, flag(), Atom::str_to_term("off_heap")),
+            Ok(Atom::str_to_term("on_heap"))
+        );
+        assert_eq!(process.message_queue_data(), MessageQueueData::OffHeap);
+
+        let message = Atom::str_to_term("message");
+        process.send_from_other(message).unwrap();
+
+        assert!(process
+            .mailbox
+            .lock()
+            .borrow()
+            .iter()
+            .all(|message| match message {
+                Message::HeapFragment(_) => true,
+                Message::Process(_) => false,
+            }));
+    });
+}
+
+#[test]
+fn with_on_heap_value_returns_old_off_heap() {
+    with_process(|process| {
+        native(process, flag(), Atom::str_to_term("off_heap")).unwrap();
+
+        assert_eq!(
+            native(process, flag(), Atom::str_to_term("on_heap")),
+            Ok(Atom::str_to_term("off_heap"))
+        );
+        assert_eq!(process.message_queue_data(), MessageQueueData::OnHeap);
+    });
+}
+
+fn flag() -> Term {
+    Atom::str_to_term("message_queue_data")
+}
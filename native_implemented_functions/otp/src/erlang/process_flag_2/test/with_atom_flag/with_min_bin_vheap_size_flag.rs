@@ -0,0 +1,18 @@
+use super::*;
+
+#[test]
+fn with_non_negative_integer_value_returns_old_min_bin_vheap_size() {
+    with_process(|process| {
+        let value = process.integer(4096).unwrap();
+
+        assert_eq!(
+            native(process, flag(), value),
+            Ok(process.integer(0).unwrap())
+        );
+        assert_eq!(process.min_vheap_size(), 4096);
+    });
+}
+
+fn flag() -> Term {
+    Atom::str_to_term("min_bin_vheap_size")
+}
@@ -0,0 +1,19 @@
+use super::*;
+
+#[test]
+fn with_non_negative_integer_value_returns_old_min_heap_size() {
+    with_process(|process| {
+        let old_min_heap_size = process.min_heap_size();
+        let value = process.integer(4096).unwrap();
+
+        assert_eq!(
+            native(process, flag(), value),
+            Ok(process.integer(old_min_heap_size).unwrap())
+        );
+        assert_eq!(process.min_heap_size(), 4096);
+    });
+}
+
+fn flag() -> Term {
+    Atom::str_to_term("min_heap_size")
+}
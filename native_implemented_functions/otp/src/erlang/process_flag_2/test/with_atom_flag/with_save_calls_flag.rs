@@ -0,0 +1,30 @@
+use super::*;
+
+#[test]
+fn with_integer_above_maximum_errors_badarg() {
+    with_process(|process| {
+        let value = process.integer(10_001).unwrap();
+
+        assert_badarg!(
+            native(process, flag(), value),
+            "save_calls value (10001) must be an integer in 0-10000"
+        );
+    });
+}
+
+#[test]
+fn with_integer_value_returns_old_save_calls() {
+    with_process(|process| {
+        let value = process.integer(16).unwrap();
+
+        assert_eq!(
+            native(process, flag(), value),
+            Ok(process.integer(0).unwrap())
+        );
+        assert_eq!(process.save_calls(), 16);
+    });
+}
+
+fn flag() -> Term {
+    Atom::str_to_term("save_calls")
+}
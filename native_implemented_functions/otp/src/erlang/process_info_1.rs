@@ -0,0 +1,74 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::{self, InternalResult};
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use lumen_rt_core::registry::pid_to_process;
+
+use crate::erlang::process_info_2::process_info;
+
+/// The items returned by `process_info/1`, in order.  `registered_name` is prepended when the
+/// process is registered.
+const DEFAULT_ITEMS: &[&str] = &[
+    "current_function",
+    "initial_call",
+    "status",
+    "message_queue_len",
+    "links",
+    "dictionary",
+    "trap_exit",
+    "error_handler",
+    "priority",
+    "group_leader",
+    "total_heap_size",
+    "heap_size",
+    "stack_size",
+    "reductions",
+    "garbage_collection",
+];
+
+#[native_implemented_function(process_info/1)]
+pub fn native(process: &Process, pid: Term) -> exception::Result<Term> {
+    let pid_pid = term_try_into_local_pid!(pid)?;
+
+    if process.pid() == pid_pid {
+        default_process_info(process, process)
+    } else {
+        match pid_to_process(&pid_pid) {
+            Some(pid_arc_process) => default_process_info(process, &pid_arc_process),
+            None => Ok(atom!("undefined")),
+        }
+    }
+    .map_err(From::from)
+}
+
+// Private
+
+fn default_process_info(process: &Process, target: &Process) -> InternalResult<Term> {
+    let mut info_vec = Vec::with_capacity(DEFAULT_ITEMS.len() + 1);
+
+    if target.registered_name.read().is_some() {
+        info_vec.push(process_info(
+            process,
+            target,
+            Atom::from_str("registered_name"),
+        )?);
+    }
+
+    for item in DEFAULT_ITEMS {
+        info_vec.push(process_info(process, target, Atom::from_str(item))?);
+    }
+
+    process
+        .list_from_slice(&info_vec)
+        .map_err(|error| error.into())
+}
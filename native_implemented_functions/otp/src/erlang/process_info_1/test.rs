@@ -0,0 +1,103 @@
+use std::convert::TryInto;
+
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use lumen_rt_core::registry;
+
+use crate::erlang::process_info_1::native;
+use crate::test::{registered_name, strategy, with_process_arc};
+
+#[test]
+fn without_local_pid_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_local_pid(arc_process.clone()),
+            )
+        },
+        |(arc_process, pid)| {
+            prop_assert_is_not_local_pid!(native(&arc_process, pid), pid);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_process_returns_undefined() {
+    with_process_arc(|arc_process| {
+        let pid = Pid::next_term();
+
+        assert_eq!(
+            native(&arc_process, pid),
+            Ok(Atom::str_to_term("undefined"))
+        );
+    });
+}
+
+#[test]
+fn without_registered_name_returns_default_items() {
+    with_process_arc(|arc_process| {
+        let info = native(&arc_process, arc_process.pid_term()).unwrap();
+
+        assert_eq!(items(info), default_items());
+    });
+}
+
+#[test]
+fn with_registered_name_returns_registered_name_before_default_items() {
+    with_process_arc(|arc_process| {
+        let registered_name = registered_name();
+        let registered_name_atom: Atom = registered_name.try_into().unwrap();
+
+        assert!(registry::put_atom_to_process(
+            registered_name_atom,
+            arc_process.clone()
+        ));
+
+        let info = native(&arc_process, arc_process.pid_term()).unwrap();
+        let mut expected_items = vec![Atom::from_str("registered_name")];
+        expected_items.extend(default_items());
+
+        assert_eq!(items(info), expected_items);
+    });
+}
+
+fn default_items() -> Vec<Atom> {
+    vec![
+        "current_function",
+        "initial_call",
+        "status",
+        "message_queue_len",
+        "links",
+        "dictionary",
+        "trap_exit",
+        "error_handler",
+        "priority",
+        "group_leader",
+        "total_heap_size",
+        "heap_size",
+        "stack_size",
+        "reductions",
+        "garbage_collection",
+    ]
+    .into_iter()
+    .map(Atom::from_str)
+    .collect()
+}
+
+fn items(info: Term) -> Vec<Atom> {
+    let info_cons: Boxed<Cons> = info.try_into().unwrap();
+
+    info_cons
+        .into_iter()
+        .map(|result| {
+            let item_value: Boxed<Tuple> = result.unwrap().try_into().unwrap();
+
+            item_value.elements()[0].try_into().unwrap()
+        })
+        .collect()
+}
@@ -5,12 +5,21 @@
 #[cfg(all(not(target_arch = "wasm32"), test))]
 mod test;
 
+use std::mem;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
 use anyhow::*;
 
+use liblumen_core::locks::MutexGuard;
+
 use liblumen_alloc::atom;
+use liblumen_alloc::borrow::CloneToProcess;
 use liblumen_alloc::erts::exception::{self, InternalResult};
-use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::message::Message;
+use liblumen_alloc::erts::process::{MaxHeapSize, Process, ProcessHeap, Status};
 use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::ModuleFunctionArity;
 
 use native_implemented_function::native_implemented_function;
 
@@ -22,53 +31,70 @@ pub fn native(process: &Process, pid: Term, item: Term) -> exception::Result<Ter
     let item_atom: Atom = term_try_into_atom!(item)?;
 
     if process.pid() == pid_pid {
-        process_info(process, item_atom)
+        process_info(process, process, item_atom)
     } else {
         match pid_to_process(&pid_pid) {
-            Some(pid_arc_process) => process_info(&pid_arc_process, item_atom),
+            Some(pid_arc_process) => process_info(process, &pid_arc_process, item_atom),
             None => Ok(atom!("undefined")),
         }
     }
     .map_err(From::from)
 }
 
-// Private
+/// Returns `max_heap_size` in the map form used by `process_flag/2` and `process_info/2`.
+pub(in crate::erlang) fn max_heap_size_to_term(
+    process: &Process,
+    max_heap_size: MaxHeapSize,
+) -> InternalResult<Term> {
+    process
+        .map_from_slice(&[
+            (atom!("error_logger"), max_heap_size.error_logger.into()),
+            (atom!("kill"), max_heap_size.kill.into()),
+            (atom!("size"), process.integer(max_heap_size.size)?),
+        ])
+        .map_err(|error| error.into())
+}
 
-fn process_info(process: &Process, item: Atom) -> InternalResult<Term> {
+/// Returns the `{Item, Value}` tuple for `item` of `target`, allocated on `process`'s heap.
+pub(in crate::erlang) fn process_info(
+    process: &Process,
+    target: &Process,
+    item: Atom,
+) -> InternalResult<Term> {
     match item.name() {
-        "backtrace" => unimplemented!(),
-        "binary" => unimplemented!(),
-        "catchlevel" => unimplemented!(),
-        "current_function" => unimplemented!(),
-        "current_location" => unimplemented!(),
-        "current_stacktrace" => unimplemented!(),
-        "dictionary" => unimplemented!(),
-        "error_handler" => unimplemented!(),
-        "garbage_collection" => unimplemented!(),
-        "garbage_collection_info" => unimplemented!(),
-        "group_leader" => unimplemented!(),
-        "heap_size" => unimplemented!(),
-        "initial_call" => unimplemented!(),
-        "links" => unimplemented!(),
-        "last_calls" => unimplemented!(),
-        "memory" => unimplemented!(),
-        "message_queue_len" => unimplemented!(),
-        "messages" => unimplemented!(),
-        "min_heap_size" => unimplemented!(),
-        "min_bin_vheap_size" => unimplemented!(),
-        "monitored_by" => unimplemented!(),
-        "monitors" => unimplemented!(),
-        "message_queue_data" => unimplemented!(),
-        "priority" => unimplemented!(),
-        "reductions" => unimplemented!(),
-        "registered_name" => registered_name(process),
-        "sequential_trace_token" => unimplemented!(),
-        "stack_size" => unimplemented!(),
-        "status" => unimplemented!(),
-        "suspending" => unimplemented!(),
-        "total_heap_size" => unimplemented!(),
-        "trace" => unimplemented!(),
-        "trap_exit" => unimplemented!(),
+        "backtrace" => backtrace(process, target),
+        "binary" => binary(process),
+        "catchlevel" => catchlevel(process),
+        "current_function" => current_function(process, target),
+        "current_location" => current_location(process, target),
+        "current_stacktrace" => current_stacktrace(process, target),
+        "dictionary" => dictionary(process, target),
+        "error_handler" => error_handler(process, target),
+        "garbage_collection" => garbage_collection(process, target),
+        "garbage_collection_info" => garbage_collection_info(process, target),
+        "group_leader" => group_leader(process, target),
+        "heap_size" => heap_size(process, target),
+        "initial_call" => initial_call(process, target),
+        "links" => links(process, target),
+        "last_calls" => last_calls(process, target),
+        "memory" => memory(process, target),
+        "message_queue_len" => message_queue_len(process, target),
+        "messages" => messages(process, target),
+        "min_heap_size" => min_heap_size(process, target),
+        "min_bin_vheap_size" => min_bin_vheap_size(process, target),
+        "monitored_by" => monitored_by(process, target),
+        "monitors" => monitors(process, target),
+        "message_queue_data" => message_queue_data(process, target),
+        "priority" => priority(process, target),
+        "reductions" => reductions(process, target),
+        "registered_name" => registered_name(process, target),
+        "sequential_trace_token" => sequential_trace_token(process),
+        "stack_size" => stack_size(process, target),
+        "status" => status(process, target),
+        "suspending" => suspending(process),
+        "total_heap_size" => total_heap_size(process, target),
+        "trace" => trace(process),
+        "trap_exit" => trap_exit(process, target),
         name => Err(TryAtomFromTermError(name))
             .context(
                 "supported items are backtrace, binary, catchlevel, current_function, \
@@ -84,16 +110,395 @@ fn process_info(process: &Process, item: Atom) -> InternalResult<Term> {
     }
 }
 
-fn registered_name(process: &Process) -> InternalResult<Term> {
-    match *process.registered_name.read() {
+// Private
+
+/// Acquires the heaps of `process` and `target` in pid order, so that two processes getting info
+/// about each other can't deadlock.  `target`'s heap is only held so that it can't be collected
+/// while terms are copied from it.
+fn acquire_heaps<'a>(
+    process: &'a Process,
+    target: &'a Process,
+) -> (MutexGuard<'a, ProcessHeap>, MutexGuard<'a, ProcessHeap>) {
+    if process.pid() < target.pid() {
+        let heap = process.acquire_heap();
+        let target_heap = target.acquire_heap();
+
+        (heap, target_heap)
+    } else {
+        let target_heap = target.acquire_heap();
+        let heap = process.acquire_heap();
+
+        (heap, target_heap)
+    }
+}
+
+fn backtrace(process: &Process, target: &Process) -> InternalResult<Term> {
+    let backtrace = format!("{}", target.stacktrace());
+    let value = process.binary_from_str(&backtrace)?;
+
+    item_value(process, "backtrace", value)
+}
+
+/// Reference-counted binaries aren't tracked per process, so none are listed.
+fn binary(process: &Process) -> InternalResult<Term> {
+    item_value(process, "binary", Term::NIL)
+}
+
+/// `catch` is compiled to the exception handling of the generated code, which the process
+/// doesn't count, so no catches are active as far as the process can tell.
+fn catchlevel(process: &Process) -> InternalResult<Term> {
+    item_value(process, "catchlevel", 0.into())
+}
+
+/// Copies `term` from `target`'s heap onto `process`'s heap, unless they are the same process.
+fn copy(process: &Process, target: &Process, term: Term) -> InternalResult<Term> {
+    if process.pid() == target.pid() {
+        Ok(term)
+    } else {
+        let (mut heap, _target_heap) = acquire_heaps(process, target);
+
+        term.clone_to_heap(&mut heap).map_err(From::from)
+    }
+}
+
+fn copy_messages(
+    heap: &mut ProcessHeap,
+    target: &Process,
+    heap_is_target_heap: bool,
+) -> InternalResult<Vec<Term>> {
+    let mut message_vec = Vec::new();
+
+    for message in target.mailbox.lock().borrow().iter() {
+        let data = *message.data();
+
+        let copied = match message {
+            Message::Process(_) if heap_is_target_heap => data,
+            // Fragment data is owned by the mailbox, so it always needs to be moved to a heap
+            _ => data.clone_to_heap(heap)?,
+        };
+
+        message_vec.push(copied);
+    }
+
+    Ok(message_vec)
+}
+
+fn current_function(process: &Process, target: &Process) -> InternalResult<Term> {
+    let value = match target.current_module_function_arity() {
+        Some(module_function_arity) => {
+            module_function_arity_to_term(process, &module_function_arity, &[])?
+        }
+        None => atom!("undefined"),
+    };
+
+    item_value(process, "current_function", value)
+}
+
+fn current_location(process: &Process, target: &Process) -> InternalResult<Term> {
+    let value = match target.current_module_function_arity() {
+        Some(module_function_arity) => {
+            let location = Term::NIL;
+
+            module_function_arity_to_term(process, &module_function_arity, &[location])?
+        }
+        None => atom!("undefined"),
+    };
+
+    item_value(process, "current_location", value)
+}
+
+fn current_stacktrace(process: &Process, target: &Process) -> InternalResult<Term> {
+    let trace = target.stacktrace();
+    let mut frame_vec = Vec::with_capacity(trace.len());
+
+    for module_function_arity in trace.iter() {
+        let location = Term::NIL;
+        let frame = module_function_arity_to_term(process, module_function_arity, &[location])?;
+
+        frame_vec.push(frame);
+    }
+
+    let value = process.list_from_slice(&frame_vec)?;
+
+    item_value(process, "current_stacktrace", value)
+}
+
+fn dictionary(process: &Process, target: &Process) -> InternalResult<Term> {
+    let mut entry_vec = Vec::new();
+
+    for (key, value) in target.dictionary_entries() {
+        let key = copy(process, target, key)?;
+        let value = copy(process, target, value)?;
+
+        entry_vec.push(process.tuple_from_slice(&[key, value])?);
+    }
+
+    let value = process.list_from_slice(&entry_vec)?;
+
+    item_value(process, "dictionary", value)
+}
+
+fn error_handler(process: &Process, target: &Process) -> InternalResult<Term> {
+    let value = target.error_handler().encode()?;
+
+    item_value(process, "error_handler", value)
+}
+
+fn garbage_collection(process: &Process, target: &Process) -> InternalResult<Term> {
+    let max_heap_size = max_heap_size_to_term(process, target.max_heap_size())?;
+
+    let value = keyword_list(
+        process,
+        &[
+            ("max_heap_size", max_heap_size),
+            (
+                "min_bin_vheap_size",
+                process.integer(target.min_vheap_size())?,
+            ),
+            ("min_heap_size", process.integer(target.min_heap_size())?),
+            ("fullsweep_after", process.integer(target.max_gen_gcs())?),
+            ("minor_gcs", process.integer(target.minor_gcs())?),
+        ],
+    )?;
+
+    item_value(process, "garbage_collection", value)
+}
+
+fn garbage_collection_info(process: &Process, target: &Process) -> InternalResult<Term> {
+    let value = keyword_list(
+        process,
+        &[
+            ("heap_block_size", process.integer(target.heap_size())?),
+            ("mbuf_size", process.integer(target.off_heap_size())?),
+            ("stack_size", process.integer(target.stack_used())?),
+            (
+                "total_heap_size",
+                process.integer(target.total_heap_size())?,
+            ),
+        ],
+    )?;
+
+    item_value(process, "garbage_collection_info", value)
+}
+
+fn group_leader(process: &Process, target: &Process) -> InternalResult<Term> {
+    item_value(process, "group_leader", target.get_group_leader_pid_term())
+}
+
+fn heap_size(process: &Process, target: &Process) -> InternalResult<Term> {
+    let value = process.integer(target.heap_size())?;
+
+    item_value(process, "heap_size", value)
+}
+
+fn initial_call(process: &Process, target: &Process) -> InternalResult<Term> {
+    let value = module_function_arity_to_term(process, &target.initial_module_function_arity, &[])?;
+
+    item_value(process, "initial_call", value)
+}
+
+fn item_value(process: &Process, item: &str, value: Term) -> InternalResult<Term> {
+    let tag = Atom::str_to_term(item);
+
+    process
+        .tuple_from_slice(&[tag, value])
+        .map_err(|error| error.into())
+}
+
+fn keyword_list(process: &Process, pairs: &[(&str, Term)]) -> InternalResult<Term> {
+    let mut pair_vec = Vec::with_capacity(pairs.len());
+
+    for (key, value) in pairs {
+        pair_vec.push(process.tuple_from_slice(&[Atom::str_to_term(key), *value])?);
+    }
+
+    process
+        .list_from_slice(&pair_vec)
+        .map_err(|error| error.into())
+}
+
+fn last_calls(process: &Process, target: &Process) -> InternalResult<Term> {
+    let value = match target.saved_calls() {
+        Some(saved_calls) => {
+            let mut call_vec = Vec::with_capacity(saved_calls.len());
+
+            for module_function_arity in saved_calls.iter() {
+                call_vec.push(module_function_arity_to_term(
+                    process,
+                    module_function_arity,
+                    &[],
+                )?);
+            }
+
+            process.list_from_slice(&call_vec)?
+        }
+        None => false.into(),
+    };
+
+    item_value(process, "last_calls", value)
+}
+
+fn links(process: &Process, target: &Process) -> InternalResult<Term> {
+    let pid_vec: Vec<Term> = target
+        .linked_pid_set
+        .iter()
+        .map(|pid| pid.key().encode().unwrap())
+        .collect();
+    let value = process.list_from_slice(&pid_vec)?;
+
+    item_value(process, "links", value)
+}
+
+fn memory(process: &Process, target: &Process) -> InternalResult<Term> {
+    let bytes = mem::size_of::<Process>() + target.total_heap_size() * mem::size_of::<Term>();
+    let value = process.integer(bytes)?;
+
+    item_value(process, "memory", value)
+}
+
+fn message_queue_data(process: &Process, target: &Process) -> InternalResult<Term> {
+    let message_queue_data_atom: Atom = target.message_queue_data().into();
+    let value = message_queue_data_atom.encode()?;
+
+    item_value(process, "message_queue_data", value)
+}
+
+fn message_queue_len(process: &Process, target: &Process) -> InternalResult<Term> {
+    let len = target.mailbox.lock().borrow().len();
+    let value = process.integer(len)?;
+
+    item_value(process, "message_queue_len", value)
+}
+
+fn messages(process: &Process, target: &Process) -> InternalResult<Term> {
+    // The heaps are acquired before the mailbox, in the same order as when a message is sent
+    let message_vec = if process.pid() == target.pid() {
+        let mut heap = process.acquire_heap();
+
+        copy_messages(&mut heap, target, true)?
+    } else {
+        let (mut heap, _target_heap) = acquire_heaps(process, target);
+
+        copy_messages(&mut heap, target, false)?
+    };
+
+    let value = process.list_from_slice(&message_vec)?;
+
+    item_value(process, "messages", value)
+}
+
+fn min_bin_vheap_size(process: &Process, target: &Process) -> InternalResult<Term> {
+    let value = process.integer(target.min_vheap_size())?;
+
+    item_value(process, "min_bin_vheap_size", value)
+}
+
+fn min_heap_size(process: &Process, target: &Process) -> InternalResult<Term> {
+    let value = process.integer(target.min_heap_size())?;
+
+    item_value(process, "min_heap_size", value)
+}
+
+fn module_function_arity_to_term(
+    process: &Process,
+    module_function_arity: &Arc<ModuleFunctionArity>,
+    extra: &[Term],
+) -> InternalResult<Term> {
+    let module = module_function_arity.module.encode()?;
+    let function = module_function_arity.function.encode()?;
+    let arity = process.integer(module_function_arity.arity)?;
+
+    process
+        .tuple_from_slices(&[&[module, function, arity], extra])
+        .map_err(|error| error.into())
+}
+
+fn monitored_by(process: &Process, target: &Process) -> InternalResult<Term> {
+    let pid_vec: Vec<Term> = target
+        .monitor_by_reference
+        .iter()
+        .map(|entry| entry.value().monitoring_pid().encode().unwrap())
+        .collect();
+    let value = process.list_from_slice(&pid_vec)?;
+
+    item_value(process, "monitored_by", value)
+}
+
+fn monitors(process: &Process, target: &Process) -> InternalResult<Term> {
+    let tag = atom!("process");
+    let mut monitor_vec = Vec::with_capacity(target.monitored_pid_by_reference.len());
+
+    for entry in target.monitored_pid_by_reference.iter() {
+        let pid = entry.value().encode()?;
+
+        monitor_vec.push(process.tuple_from_slice(&[tag, pid])?);
+    }
+
+    let value = process.list_from_slice(&monitor_vec)?;
+
+    item_value(process, "monitors", value)
+}
+
+fn priority(process: &Process, target: &Process) -> InternalResult<Term> {
+    let priority_atom: Atom = target.priority().into();
+    let value = priority_atom.encode()?;
+
+    item_value(process, "priority", value)
+}
+
+fn reductions(process: &Process, target: &Process) -> InternalResult<Term> {
+    let reductions = target.total_reductions.load(Ordering::Relaxed);
+    let value = process.integer(reductions)?;
+
+    item_value(process, "reductions", value)
+}
+
+fn registered_name(process: &Process, target: &Process) -> InternalResult<Term> {
+    match *target.registered_name.read() {
         Some(registered_name) => {
-            let tag = atom!("registered_name");
             let value = registered_name.encode()?;
 
-            process
-                .tuple_from_slice(&[tag, value])
-                .map_err(|error| error.into())
+            item_value(process, "registered_name", value)
         }
         None => Ok(Term::NIL),
     }
 }
+
+fn sequential_trace_token(process: &Process) -> InternalResult<Term> {
+    item_value(process, "sequential_trace_token", Term::NIL)
+}
+
+fn stack_size(process: &Process, target: &Process) -> InternalResult<Term> {
+    let value = process.integer(target.stack_used())?;
+
+    item_value(process, "stack_size", value)
+}
+
+fn status(process: &Process, target: &Process) -> InternalResult<Term> {
+    let value = match *target.status.read() {
+        Status::Runnable => atom!("runnable"),
+        Status::Running => atom!("running"),
+        Status::Waiting => atom!("waiting"),
+        Status::Exiting(_) => atom!("exiting"),
+    };
+
+    item_value(process, "status", value)
+}
+
+fn suspending(process: &Process) -> InternalResult<Term> {
+    item_value(process, "suspending", Term::NIL)
+}
+
+fn total_heap_size(process: &Process, target: &Process) -> InternalResult<Term> {
+    let value = process.integer(target.total_heap_size())?;
+
+    item_value(process, "total_heap_size", value)
+}
+
+fn trace(process: &Process) -> InternalResult<Term> {
+    item_value(process, "trace", 0.into())
+}
+
+fn trap_exit(process: &Process, target: &Process) -> InternalResult<Term> {
+    item_value(process, "trap_exit", target.traps_exit().into())
+}
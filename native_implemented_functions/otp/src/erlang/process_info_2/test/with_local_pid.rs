@@ -1,4 +1,9 @@
+mod with_binary;
+mod with_catchlevel;
+mod with_dictionary;
+mod with_message_queue_len;
 mod with_registered_name;
+mod with_trap_exit;
 
 use super::*;
 
@@ -29,7 +34,39 @@ fn without_supported_item_errors_badarg() {
 fn unsupported_item_atom() -> BoxedStrategy<Term> {
     strategy::atom()
         .prop_filter("Item cannot be supported", |atom| match atom.name() {
-            "registered_name" => false,
+            "backtrace"
+            | "binary"
+            | "catchlevel"
+            | "current_function"
+            | "current_location"
+            | "current_stacktrace"
+            | "dictionary"
+            | "error_handler"
+            | "garbage_collection"
+            | "garbage_collection_info"
+            | "group_leader"
+            | "heap_size"
+            | "initial_call"
+            | "links"
+            | "last_calls"
+            | "memory"
+            | "message_queue_len"
+            | "messages"
+            | "min_heap_size"
+            | "min_bin_vheap_size"
+            | "monitored_by"
+            | "monitors"
+            | "message_queue_data"
+            | "priority"
+            | "reductions"
+            | "registered_name"
+            | "sequential_trace_token"
+            | "stack_size"
+            | "status"
+            | "suspending"
+            | "total_heap_size"
+            | "trace"
+            | "trap_exit" => false,
             _ => true,
         })
         .prop_map(|atom| atom.encode().unwrap())
@@ -0,0 +1,15 @@
+use super::*;
+
+#[test]
+fn returns_empty_list() {
+    with_process_arc(|arc_process| {
+        assert_eq!(
+            native(&arc_process, arc_process.pid_term(), item()),
+            Ok(arc_process.tuple_from_slice(&[item(), Term::NIL]).unwrap())
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("binary")
+}
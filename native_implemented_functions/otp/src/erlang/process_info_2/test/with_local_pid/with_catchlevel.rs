@@ -0,0 +1,17 @@
+use super::*;
+
+#[test]
+fn returns_zero() {
+    with_process_arc(|arc_process| {
+        assert_eq!(
+            native(&arc_process, arc_process.pid_term(), item()),
+            Ok(arc_process
+                .tuple_from_slice(&[item(), arc_process.integer(0).unwrap()])
+                .unwrap())
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("catchlevel")
+}
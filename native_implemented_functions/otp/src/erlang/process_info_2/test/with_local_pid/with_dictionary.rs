@@ -0,0 +1,42 @@
+use super::*;
+
+#[test]
+fn without_entries_returns_empty_list() {
+    with_process_arc(|arc_process| {
+        assert_eq!(
+            native(&arc_process, arc_process.pid_term(), item()),
+            Ok(arc_process.tuple_from_slice(&[item(), Term::NIL]).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_entries_in_other_process_returns_copied_entries() {
+    with_process_arc(|parent_process_arc| {
+        let child_process_arc = test::process::child(&parent_process_arc);
+        let key = Atom::str_to_term("key");
+        let value = child_process_arc.integer(usize::max_value()).unwrap();
+
+        child_process_arc.put(key, value).unwrap();
+
+        let parent_value = parent_process_arc.integer(usize::max_value()).unwrap();
+
+        assert_eq!(
+            native(&parent_process_arc, child_process_arc.pid_term(), item()),
+            Ok(parent_process_arc
+                .tuple_from_slice(&[
+                    item(),
+                    parent_process_arc
+                        .list_from_slice(&[parent_process_arc
+                            .tuple_from_slice(&[key, parent_value])
+                            .unwrap()])
+                        .unwrap()
+                ])
+                .unwrap())
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("dictionary")
+}
@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn returns_number_of_messages_in_mailbox() {
+    with_process_arc(|arc_process| {
+        assert_eq!(
+            native(&arc_process, arc_process.pid_term(), item()),
+            Ok(arc_process
+                .tuple_from_slice(&[item(), arc_process.integer(0).unwrap()])
+                .unwrap())
+        );
+
+        arc_process.send_from_self(Atom::str_to_term("message"));
+
+        assert_eq!(
+            native(&arc_process, arc_process.pid_term(), item()),
+            Ok(arc_process
+                .tuple_from_slice(&[item(), arc_process.integer(1).unwrap()])
+                .unwrap())
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("message_queue_len")
+}
@@ -0,0 +1,53 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use lumen_rt_core::time::{convert_milliseconds, Unit::Native};
+
+use lumen_rt_full::scheduler::Scheduler;
+
+#[native_implemented_function(statistics/1)]
+pub fn native(process: &Process, item: Term) -> exception::Result<Term> {
+    let item_atom = term_try_into_atom!(item)?;
+
+    match item_atom.name() {
+        "scheduler_wall_time" | "scheduler_wall_time_all" => scheduler_wall_time(process),
+        name => Err(TryAtomFromTermError(name))
+            .context("supported items are scheduler_wall_time and scheduler_wall_time_all")
+            .map_err(From::from),
+    }
+}
+
+// Private
+
+/// `[{SchedulerId, ActiveTime, TotalTime}]` for each scheduler, with times in native time units.
+///
+/// Unlike BEAM, the counters are always on, so `erlang:system_flag(scheduler_wall_time, true)` is
+/// not needed first.
+fn scheduler_wall_time(process: &Process) -> exception::Result<Term> {
+    let mut tuple_vec = Vec::new();
+
+    for arc_scheduler in Scheduler::all() {
+        let id: u128 = arc_scheduler.id.into();
+        let (active, total) = arc_scheduler.wall_time();
+
+        tuple_vec.push(process.tuple_from_slice(&[
+            process.integer(id)?,
+            process.integer(convert_milliseconds(active, Native))?,
+            process.integer(convert_milliseconds(total, Native))?,
+        ])?);
+    }
+
+    process.list_from_slice(&tuple_vec).map_err(From::from)
+}
@@ -0,0 +1,79 @@
+use std::convert::TryInto;
+
+use proptest::strategy::{Just, Strategy};
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use lumen_rt_core::time::monotonic;
+use lumen_rt_full::scheduler::Scheduler;
+
+use crate::erlang::statistics_1::native;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_atom_item_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_atom(arc_process.clone()),
+            )
+        },
+        |(arc_process, item)| {
+            prop_assert_is_not_atom!(native(&arc_process, item), item);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_unsupported_item_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            native(process, Atom::str_to_term("unsupported")),
+            "supported items are scheduler_wall_time and scheduler_wall_time_all"
+        );
+    });
+}
+
+#[test]
+fn with_scheduler_wall_time_counts_active_and_total_time_of_current_scheduler() {
+    with_process(|process| {
+        let start_time_in_milliseconds = monotonic::freeze_time_in_milliseconds();
+        let item = Atom::str_to_term("scheduler_wall_time");
+
+        let (first_active, first_total) =
+            current_scheduler_wall_time(native(process, item).unwrap());
+
+        monotonic::freeze_at_time_in_milliseconds(start_time_in_milliseconds + 2);
+
+        let (second_active, second_total) =
+            current_scheduler_wall_time(native(process, item).unwrap());
+
+        // The scheduler never parked, so it was active the whole time
+        assert_eq!(second_active, first_active + 2);
+        assert_eq!(second_total, first_total + 2);
+    });
+}
+
+fn current_scheduler_wall_time(list: Term) -> (u64, u64) {
+    let id: u128 = Scheduler::current().id.into();
+    let boxed_cons: Boxed<Cons> = list.try_into().unwrap();
+
+    for result in boxed_cons.into_iter() {
+        let tuple: Boxed<Tuple> = result.unwrap().try_into().unwrap();
+        let elements = tuple.elements();
+        let tuple_id: u64 = elements[0].try_into().unwrap();
+
+        if tuple_id as u128 == id {
+            return (
+                elements[1].try_into().unwrap(),
+                elements[2].try_into().unwrap(),
+            );
+        }
+    }
+
+    panic!("current scheduler is not in {}", list);
+}
@@ -1,14 +1,17 @@
-mod options;
+pub mod options;
 
 use std::collections::VecDeque;
 use std::convert::TryInto;
+use std::io::Write;
 use std::mem;
 use std::sync::Arc;
 
+use flate2::write::ZlibEncoder;
 use num_bigint::{BigInt, Sign};
 
 use liblumen_alloc::erts::exception;
 use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::scheduler;
 use liblumen_alloc::erts::term::closure::{Creator, Definition};
 use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::erts::Node;
@@ -19,8 +22,14 @@ use lumen_rt_full::distribution::external_term_format::{version, Tag};
 
 use options::*;
 
+/// The byte size of `term` encoded in the external term format without compression
+pub fn external_size(process: &Process, term: Term) -> usize {
+    term_to_byte_vec(process, &Default::default(), term).len()
+}
+
 pub fn term_to_binary(process: &Process, term: Term, options: Options) -> exception::Result<Term> {
-    let byte_vec = term_to_byte_vec(process, &options, term);
+    let uncompressed_byte_vec = term_to_byte_vec(process, &options, term);
+    let byte_vec = compress(&options.compression, uncompressed_byte_vec);
 
     process
         .binary_from_bytes(&byte_vec)
@@ -29,13 +38,6 @@ pub fn term_to_binary(process: &Process, term: Term, options: Options) -> except
 
 // Private
 
-// TODO implement creation rotation
-// > A 32-bit big endian unsigned integer. All identifiers originating from the same node
-// > incarnation must have identical Creation values. This makes it possible to separate identifiers
-// > from old (crashed) nodes from a new one. The value zero should be avoided for normal operations
-// > as it is used as a wild card for debug purpose (like a pid returned by erlang:list_to_pid/1).
-const CREATION: u8 = 0;
-
 const NEWER_REFERENCE_EXT_MAX_U32_LEN: usize = 3;
 
 const SMALL_INTEGER_EXT_MIN: isize = std::u8::MIN as isize;
@@ -114,6 +116,50 @@ fn append_pid(byte_vec: &mut Vec<u8>, arc_node: Arc<Node>, id: u32, serial: u32)
     };
 }
 
+fn append_port(byte_vec: &mut Vec<u8>, arc_node: Arc<Node>, id: u32) {
+    let creation = arc_node.creation();
+
+    let tag = if creation <= (std::u8::MAX as u32) {
+        Tag::Port
+    } else {
+        Tag::NewPort
+    };
+
+    push_tag(byte_vec, tag);
+
+    byte_vec.extend_from_slice(&atom_to_byte_vec(arc_node.name()));
+    byte_vec.extend_from_slice(&id.to_be_bytes());
+
+    if creation <= (std::u8::MAX as u32) {
+        byte_vec.push(creation as u8);
+    } else {
+        byte_vec.extend_from_slice(&creation.to_be_bytes());
+    };
+}
+
+fn append_reference(
+    byte_vec: &mut Vec<u8>,
+    arc_node: Arc<Node>,
+    scheduler_id: scheduler::ID,
+    number: ReferenceNumber,
+) {
+    let scheduler_id_u32: u32 = scheduler_id.into();
+
+    push_tag(byte_vec, Tag::NewerReference);
+
+    let u32_byte_len = mem::size_of::<u32>();
+    let len_usize = (mem::size_of::<u32>() + mem::size_of::<u64>()) / u32_byte_len;
+    // > Len - A 16-bit big endian unsigned integer not larger than 3.
+    assert!(len_usize <= NEWER_REFERENCE_EXT_MAX_U32_LEN);
+    append_usize_as_u16(byte_vec, len_usize);
+
+    byte_vec.extend_from_slice(&atom_to_byte_vec(arc_node.name()));
+    byte_vec.extend_from_slice(&arc_node.creation().to_be_bytes());
+
+    byte_vec.extend_from_slice(&scheduler_id_u32.to_be_bytes());
+    byte_vec.extend_from_slice(&number.to_be_bytes());
+}
+
 fn append_usize_as_u16(byte_vec: &mut Vec<u8>, len_usize: usize) {
     assert!(len_usize <= (std::u16::MAX as usize));
     let len_u16 = len_usize as u16;
@@ -149,6 +195,35 @@ fn atom_to_byte_vec(atom: Atom) -> Vec<u8> {
     byte_vec
 }
 
+/// > The uncompressed size is a 32-bit big-endian unsigned integer, followed by the zlib-compressed
+/// > data, which is the term encoding without the version number.
+/// -- http://erlang.org/doc/apps/erts/erl_ext_dist.html#compressed-term-format
+///
+/// Like BEAM, the uncompressed encoding is kept when compression is disabled with level `0` or
+/// the compressed encoding would not be any smaller.
+fn compress(compression: &Compression, uncompressed_byte_vec: Vec<u8>) -> Vec<u8> {
+    let Compression(level) = *compression;
+
+    if level == 0 {
+        return uncompressed_byte_vec;
+    }
+
+    let uncompressed_bytes = &uncompressed_byte_vec[1..];
+    let mut encoder = ZlibEncoder::new(
+        vec![version::NUMBER, Tag::Compressed.into()],
+        flate2::Compression::new(level as u32),
+    );
+    append_usize_as_u32(encoder.get_mut(), uncompressed_bytes.len());
+    encoder.write_all(uncompressed_bytes).unwrap();
+    let compressed_byte_vec = encoder.finish().unwrap();
+
+    if compressed_byte_vec.len() < uncompressed_byte_vec.len() {
+        compressed_byte_vec
+    } else {
+        uncompressed_byte_vec
+    }
+}
+
 // Tail is the final tail  of the list; it is NIL_EXT for a proper list, but can be any type if the
 // list is improper (for example, [a|b]).
 // -- http://erlang.org/doc/apps/erts/erl_ext_dist.html#list_ext
@@ -320,8 +395,8 @@ fn term_to_byte_vec(process: &Process, options: &Options, term: Term) -> Vec<u8>
                 append_usize_as_u32(&mut byte_vec, len_usize);
 
                 for (key, value) in map.iter() {
-                    stack.push_front(*value);
-                    stack.push_front(*key);
+                    stack.push_front(value);
+                    stack.push_front(key);
                 }
             }
             TypedTerm::HeapBinary(heap_bin) => {
@@ -354,24 +429,30 @@ fn term_to_byte_vec(process: &Process, options: &Options, term: Term) -> Vec<u8>
                 byte_vec.extend_from_slice(proc_bin.as_bytes());
             }
             TypedTerm::Reference(reference) => {
-                let scheduler_id_u32: u32 = reference.scheduler_id().into();
-                let number: u64 = reference.number().into();
-
-                push_tag(&mut byte_vec, Tag::NewerReference);
-
-                let u32_byte_len = mem::size_of::<u32>();
-                let len_usize = (mem::size_of::<u32>() + mem::size_of::<u64>()) / u32_byte_len;
-                // > Len - A 16-bit big endian unsigned integer not larger than 3.
-                assert!(len_usize <= NEWER_REFERENCE_EXT_MAX_U32_LEN);
-                append_usize_as_u16(&mut byte_vec, len_usize);
-
-                byte_vec.extend_from_slice(&atom_to_byte_vec(node::atom()));
-
-                let creation_u32 = CREATION as u32;
-                byte_vec.extend_from_slice(&creation_u32.to_be_bytes());
-
-                byte_vec.extend_from_slice(&scheduler_id_u32.to_be_bytes());
-                byte_vec.extend_from_slice(&number.to_be_bytes());
+                append_reference(
+                    &mut byte_vec,
+                    arc_node(),
+                    reference.scheduler_id(),
+                    reference.number(),
+                );
+            }
+            TypedTerm::ExternalReference(external_reference) => {
+                append_reference(
+                    &mut byte_vec,
+                    external_reference.arc_node(),
+                    external_reference.scheduler_id(),
+                    external_reference.number(),
+                );
+            }
+            TypedTerm::Port(port) => {
+                append_port(&mut byte_vec, arc_node(), port.number());
+            }
+            TypedTerm::ExternalPort(external_port) => {
+                append_port(
+                    &mut byte_vec,
+                    external_port.arc_node(),
+                    external_port.number(),
+                );
             }
             TypedTerm::SubBinary(subbinary) => {
                 if subbinary.is_binary() {
@@ -243,6 +243,47 @@ fn with_non_empty_atom_returns_atom_ext() {
     });
 }
 
+// PORT_EXT (102)
+#[test]
+fn with_port_returns_port_ext() {
+    with_process(|process| {
+        let port = Port::new(5).unwrap().encode().unwrap();
+        let binary = native(process, port).unwrap();
+
+        assert_eq!(
+            binary,
+            process
+                .binary_from_bytes(&[
+                    VERSION_NUMBER,
+                    PORT_EXT,
+                    100,
+                    0,
+                    13,
+                    110,
+                    111,
+                    110,
+                    111,
+                    100,
+                    101,
+                    64,
+                    110,
+                    111,
+                    104,
+                    111,
+                    115,
+                    116,
+                    0,
+                    0,
+                    0,
+                    5,
+                    0
+                ])
+                .unwrap()
+        );
+        assert_eq!(binary_to_term_1::native(process, binary), Ok(port));
+    });
+}
+
 // PID_EXT (103)
 #[test]
 fn with_pid_returns_pid_ext() {
@@ -666,6 +707,7 @@ const NEW_FLOAT_EXT: u8 = 70;
 const SMALL_INTEGER_EXT: u8 = 97;
 const INTEGER_EXT: u8 = 98;
 const ATOM_EXT: u8 = 100;
+const PORT_EXT: u8 = 102;
 const PID_EXT: u8 = 103;
 const SMALL_TUPLE_EXT: u8 = 104;
 const NIL_EXT: u8 = 106;
@@ -0,0 +1,31 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::Term;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::erlang::term_to_binary::options::Options;
+use crate::erlang::term_to_binary::term_to_binary;
+
+#[native_implemented_function(term_to_binary/2)]
+pub fn native(process: &Process, term: Term, options: Term) -> exception::Result<Term> {
+    let options: Options = options.try_into().ok().with_context(|| {
+        format!(
+            "options ({}) must be a proper list of compressed, {{compressed, Level}}, or {{minor_version, Version}}",
+            options
+        )
+    })?;
+
+    term_to_binary(process, term, options)
+}
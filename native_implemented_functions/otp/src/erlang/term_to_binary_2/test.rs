@@ -0,0 +1,121 @@
+use proptest::strategy::Just;
+use proptest::{prop_assert, prop_assert_eq};
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::binary_to_term_1;
+use crate::erlang::term_to_binary_2::native;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_proper_list_options_errors_badarg() {
+    with_process(|process| {
+        let term = Atom::str_to_term("term");
+        let options = Atom::str_to_term("compressed");
+
+        assert_badarg!(native(process, term, options), "must be a proper list");
+    });
+}
+
+#[test]
+fn with_compressed_level_out_of_range_errors_badarg() {
+    with_process(|process| {
+        let term = Atom::str_to_term("term");
+        let options = compressed_level_options(process, 10);
+
+        assert_badarg!(native(process, term, options), "must be a proper list");
+    });
+}
+
+#[test]
+fn with_compressed_roundtrips_through_binary_to_term() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process),
+                0_u8..=9_u8,
+            )
+        },
+        |(arc_process, term, level)| {
+            let options = compressed_level_options(&arc_process, level);
+            let result_binary = native(&arc_process, term, options);
+
+            prop_assert!(result_binary.is_ok());
+
+            let binary = result_binary.unwrap();
+
+            prop_assert!(binary.is_binary());
+            prop_assert_eq!(binary_to_term_1::native(&arc_process, binary), Ok(term));
+
+            Ok(())
+        },
+    );
+}
+
+// COMPRESSED (80)
+#[test]
+fn with_compressed_with_compressible_term_returns_compressed() {
+    with_process(|process| {
+        let term = process.charlist_from_str(&"a".repeat(100)).unwrap();
+        let options = process
+            .cons(Atom::str_to_term("compressed"), Term::NIL)
+            .unwrap();
+
+        let binary = native(process, term, options).unwrap();
+        let heap_binary = match binary.decode().unwrap() {
+            TypedTerm::HeapBinary(heap_binary) => heap_binary,
+            typed_term => panic!("{:?} is not a heap binary", typed_term),
+        };
+        let bytes = heap_binary.as_bytes();
+
+        // STRING_EXT is 3 bytes of header and 100 bytes of characters
+        assert_eq!(&bytes[0..6], &[131, 80, 0, 0, 0, 103]);
+        assert!(bytes.len() < 1 + 103);
+        assert_eq!(binary_to_term_1::native(process, binary), Ok(term));
+    });
+}
+
+#[test]
+fn with_compressed_with_incompressible_term_returns_uncompressed() {
+    with_process(|process| {
+        let term = Atom::str_to_term("a");
+        let options = process
+            .cons(Atom::str_to_term("compressed"), Term::NIL)
+            .unwrap();
+
+        assert_eq!(
+            native(process, term, options),
+            Ok(process.binary_from_bytes(&[131, 100, 0, 1, 97]).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_compressed_level_0_returns_uncompressed() {
+    with_process(|process| {
+        let term = process.charlist_from_str(&"a".repeat(100)).unwrap();
+        let options = compressed_level_options(process, 0);
+
+        let mut byte_vec = vec![131, 107, 0, 100];
+        byte_vec.extend(std::iter::repeat(b'a').take(100));
+
+        assert_eq!(
+            native(process, term, options),
+            Ok(process.binary_from_bytes(&byte_vec).unwrap())
+        );
+    });
+}
+
+fn compressed_level_options(process: &Process, level: u8) -> Term {
+    process
+        .cons(
+            process
+                .tuple_from_slice(&[Atom::str_to_term("compressed"), process.integer(level).unwrap()])
+                .unwrap(),
+            Term::NIL,
+        )
+        .unwrap()
+}
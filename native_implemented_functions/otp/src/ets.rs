@@ -0,0 +1,146 @@
+pub mod delete_1;
+pub mod delete_2;
+pub mod foldl_3;
+pub mod info_2;
+pub mod insert_2;
+pub mod insert_new_2;
+pub mod lookup_2;
+pub mod match_2;
+pub mod match_object_2;
+mod match_spec;
+pub mod new_2;
+pub mod select_2;
+pub mod tab2list_1;
+pub mod update_counter_3;
+
+use std::sync::Arc;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::{self, AllocResult};
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::CloneToProcess;
+
+use lumen_rt_core::ets::{self, Access, Table};
+
+fn module() -> Atom {
+    Atom::try_from_str("ets").unwrap()
+}
+
+// Private
+
+/// Every object in `table`, in table order, as a list on `process`'s heap
+fn objects_to_list(process: &Process, table: &Table) -> exception::Result<Term> {
+    let mut object_vec = Vec::with_capacity(table.len());
+
+    table.try_for_each(|object| -> exception::Result<()> {
+        object_vec.push(object.clone_to_process(process));
+
+        Ok(())
+    })?;
+
+    process.list_from_slice(&object_vec).map_err(From::from)
+}
+
+/// The table identifier returned by `new/2`: the name for named tables, otherwise the reference
+fn table_to_term(process: &Process, table: &Table) -> AllocResult<Term> {
+    if table.is_named() {
+        Ok(table.name().encode().unwrap())
+    } else {
+        let reference = table.reference();
+
+        process.reference_from_scheduler(reference.scheduler_id(), reference.number())
+    }
+}
+
+/// The table named by or with the reference `table` if it exists
+fn term_to_option_table(table: Term) -> exception::Result<Option<Arc<Table>>> {
+    match table.decode().unwrap() {
+        TypedTerm::Atom(name) => Ok(ets::name_to_table(&name)),
+        TypedTerm::Reference(reference) => Ok(ets::reference_to_table(&reference)),
+        _ => Err(TypeError)
+            .with_context(|| format!("table ({}) is not an atom or reference", table))
+            .map_err(From::from),
+    }
+}
+
+fn term_to_table(table: Term) -> exception::Result<Arc<Table>> {
+    term_to_option_table(table)?
+        .with_context(|| format!("table ({}) does not exist", table))
+        .map_err(From::from)
+}
+
+fn term_to_readable_table(process: &Process, table: Term) -> exception::Result<Arc<Table>> {
+    let arc_table = term_to_table(table)?;
+
+    if arc_table.can_read(process.pid()) {
+        Ok(arc_table)
+    } else {
+        Err(anyhow!(
+            "table ({}) is private to its owner ({})",
+            table,
+            arc_table.owner()
+        )
+        .into())
+    }
+}
+
+fn term_to_writable_table(process: &Process, table: Term) -> exception::Result<Arc<Table>> {
+    let arc_table = term_to_table(table)?;
+
+    if arc_table.can_write(process.pid()) {
+        Ok(arc_table)
+    } else {
+        let access = match arc_table.access() {
+            Access::Private => "private",
+            Access::Protected => "protected",
+            Access::Public => unreachable!(),
+        };
+
+        Err(anyhow!(
+            "table ({}) is {} to its owner ({})",
+            table,
+            access,
+            arc_table.owner()
+        )
+        .into())
+    }
+}
+
+/// `object_or_objects` as either a single tuple or a proper list of tuples, each with an element
+/// at the table's `keypos`.
+fn term_to_object_vec(table: &Table, object_or_objects: Term) -> exception::Result<Vec<Term>> {
+    let object_vec = match object_or_objects.decode().unwrap() {
+        TypedTerm::Tuple(_) => vec![object_or_objects],
+        TypedTerm::Nil => Vec::new(),
+        TypedTerm::List(cons) => cons
+            .into_iter()
+            .collect::<std::result::Result<_, _>>()
+            .map_err(|_| ImproperListError)
+            .with_context(|| format!("objects ({}) is improper", object_or_objects))?,
+        _ => {
+            return Err(TypeError)
+                .with_context(|| {
+                    format!(
+                        "object_or_objects ({}) is not a tuple or list of tuples",
+                        object_or_objects
+                    )
+                })
+                .map_err(From::from)
+        }
+    };
+
+    for object in &object_vec {
+        if table.key(*object).is_none() {
+            return Err(anyhow!(
+                "object ({}) is not a tuple with at least {} elements",
+                object,
+                table.keypos()
+            )
+            .into());
+        }
+    }
+
+    Ok(object_vec)
+}
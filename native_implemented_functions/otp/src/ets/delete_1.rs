@@ -0,0 +1,24 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use lumen_rt_core::ets;
+
+use crate::ets::term_to_writable_table;
+
+#[native_implemented_function(delete/1)]
+pub fn native(process: &Process, table: Term) -> exception::Result<Term> {
+    let arc_table = term_to_writable_table(process, table)?;
+    ets::delete(&arc_table);
+
+    Ok(true.into())
+}
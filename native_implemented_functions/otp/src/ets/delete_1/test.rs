@@ -0,0 +1,33 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::delete_1::native;
+use crate::ets::{info_2, new_2};
+use crate::test::{process, with_process};
+
+#[test]
+fn with_owned_table_deletes_table() {
+    with_process(|process| {
+        let name = Atom::str_to_term("delete_1_with_owned_table");
+        let options = process
+            .list_from_slice(&[Atom::str_to_term("named_table")])
+            .unwrap();
+
+        assert_eq!(new_2::native(process, name, options), Ok(name));
+        assert_eq!(native(process, name), Ok(true.into()));
+        assert_eq!(
+            info_2::native(process, name, Atom::str_to_term("size")),
+            Ok(Atom::str_to_term("undefined"))
+        );
+        assert_badarg!(native(process, name), "does not exist");
+    });
+}
+
+#[test]
+fn with_protected_table_from_other_process_errors_badarg() {
+    with_process(|owner_process| {
+        let table = new_2::native(owner_process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let other_arc_process = process::default();
+
+        assert_badarg!(native(&other_arc_process, table), "protected");
+    });
+}
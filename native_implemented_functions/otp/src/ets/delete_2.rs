@@ -0,0 +1,22 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::ets::term_to_writable_table;
+
+#[native_implemented_function(delete/2)]
+pub fn native(process: &Process, table: Term, key: Term) -> exception::Result<Term> {
+    let arc_table = term_to_writable_table(process, table)?;
+    arc_table.delete_key(key);
+
+    Ok(true.into())
+}
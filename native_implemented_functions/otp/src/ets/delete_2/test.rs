@@ -0,0 +1,42 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::delete_2::native;
+use crate::ets::{insert_2, lookup_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn with_key_deletes_all_objects_with_key() {
+    with_process(|process| {
+        let options = process
+            .list_from_slice(&[Atom::str_to_term("duplicate_bag")])
+            .unwrap();
+        let table = new_2::native(process, Atom::str_to_term("table"), options).unwrap();
+        let key = Atom::str_to_term("key");
+        let other_key = Atom::str_to_term("other_key");
+        let object = process.tuple_from_slice(&[key]).unwrap();
+        let other_object = process.tuple_from_slice(&[other_key]).unwrap();
+        let objects = process
+            .list_from_slice(&[object, object, other_object])
+            .unwrap();
+
+        assert_eq!(insert_2::native(process, table, objects), Ok(true.into()));
+        assert_eq!(native(process, table, key), Ok(true.into()));
+        assert_eq!(lookup_2::native(process, table, key), Ok(Term::NIL));
+        assert_eq!(
+            lookup_2::native(process, table, other_key),
+            Ok(process.list_from_slice(&[other_object]).unwrap())
+        );
+    });
+}
+
+#[test]
+fn without_key_returns_true() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+
+        assert_eq!(
+            native(process, table, Atom::str_to_term("key")),
+            Ok(true.into())
+        );
+    });
+}
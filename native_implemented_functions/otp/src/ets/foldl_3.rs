@@ -0,0 +1,105 @@
+mod label_1;
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+use std::sync::Arc;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::{self, Alloc};
+use liblumen_alloc::erts::process::code::stack::frame::{Frame, Placement};
+use liblumen_alloc::erts::process::{code, Process};
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::ModuleFunctionArity;
+
+use crate::ets::{objects_to_list, term_to_readable_table};
+
+pub fn place_frame_with_arguments(
+    process: &Process,
+    placement: Placement,
+    function: Term,
+    acc0: Term,
+    table: Term,
+) -> Result<(), Alloc> {
+    process.stack_push(table)?;
+    process.stack_push(acc0)?;
+    process.stack_push(function)?;
+    process.place_frame(frame(), placement);
+
+    Ok(())
+}
+
+// Private
+
+/// ```elixir
+/// def foldl(function, acc0, table) do
+///   objects = :ets.tab2list(table)
+///   :lists.foldl(function, acc0, objects)
+/// end
+/// ```
+///
+/// The objects are copied to the process heap before `function` is first called, so `function`
+/// is free to modify `table`.
+fn code(arc_process: &Arc<Process>) -> code::Result {
+    arc_process.reduce();
+
+    let function = arc_process.stack_peek(1).unwrap();
+    let acc0 = arc_process.stack_peek(2).unwrap();
+    let table = arc_process.stack_peek(3).unwrap();
+
+    const STACK_USED: usize = 3;
+
+    match objects(arc_process, function, table) {
+        Ok(objects) => {
+            arc_process.stack_popn(STACK_USED);
+
+            label_1::place_frame_with_arguments(
+                arc_process,
+                Placement::Replace,
+                function,
+                objects,
+            )?;
+            // `label_1` expects the accumulator as if it had been returned from a call
+            arc_process.stack_push(acc0)?;
+
+            Process::call_code(arc_process)
+        }
+        Err(exception) => code::result_from_exception(arc_process, STACK_USED, exception),
+    }
+}
+
+fn frame() -> Frame {
+    Frame::new(module_function_arity(), code)
+}
+
+fn function() -> Atom {
+    Atom::try_from_str("foldl").unwrap()
+}
+
+fn module_function_arity() -> Arc<ModuleFunctionArity> {
+    Arc::new(ModuleFunctionArity {
+        module: super::module(),
+        function: function(),
+        arity: 3,
+    })
+}
+
+fn objects(process: &Process, function: Term, table: Term) -> exception::Result<Term> {
+    let boxed_closure: Boxed<Closure> = function
+        .try_into()
+        .ok()
+        .with_context(|| format!("function ({}) is not a function", function))?;
+
+    if boxed_closure.arity() != 2 {
+        return Err(anyhow!("function ({}) does not have an arity of 2", function).into());
+    }
+
+    let arc_table = term_to_readable_table(process, table)?;
+
+    objects_to_list(process, &arc_table)
+}
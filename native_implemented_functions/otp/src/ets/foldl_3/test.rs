@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use liblumen_alloc::borrow::clone_to_process::CloneToProcess;
+use liblumen_alloc::erts::process::code::stack::frame::Placement;
+use liblumen_alloc::erts::process::code::Code;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use lumen_rt_full::future::Ready;
+
+use crate::ets::foldl_3::place_frame_with_arguments;
+use crate::ets::{insert_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn with_empty_table_returns_acc0() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let function = prepend(process);
+        let acc0 = Atom::str_to_term("acc0");
+
+        let Ready { result, .. } = run_until_ready(function, acc0, table);
+
+        assert_eq!(result, Ok(acc0));
+    });
+}
+
+#[test]
+fn calls_function_with_each_object_in_table_order() {
+    with_process(|process| {
+        let options = process
+            .list_from_slice(&[Atom::str_to_term("ordered_set")])
+            .unwrap();
+        let table = new_2::native(process, Atom::str_to_term("table"), options).unwrap();
+        let objects = (1..=3)
+            .map(|i| {
+                process
+                    .tuple_from_slice(&[process.integer(i).unwrap()])
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let list = process
+            .list_from_slice(&[objects[1], objects[2], objects[0]])
+            .unwrap();
+        assert_eq!(insert_2::native(process, table, list), Ok(true.into()));
+        let function = prepend(process);
+
+        let Ready { result, .. } = run_until_ready(function, Term::NIL, table);
+
+        assert_eq!(
+            result,
+            Ok(process
+                .list_from_slice(&[objects[2], objects[1], objects[0]])
+                .unwrap())
+        );
+    });
+}
+
+#[test]
+fn without_arity_2_function_errors_badarg() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let function = process
+            .export_closure(
+                Atom::try_from_str("module").unwrap(),
+                Atom::try_from_str("function").unwrap(),
+                1,
+                None,
+            )
+            .unwrap();
+
+        let Ready { result, .. } = run_until_ready(function, Term::NIL, table);
+
+        assert_badarg!(result, "does not have an arity of 2");
+    });
+}
+
+#[test]
+fn with_private_table_of_other_process_errors_badarg() {
+    with_process(|process| {
+        let options = process
+            .list_from_slice(&[Atom::str_to_term("private")])
+            .unwrap();
+        let table = new_2::native(process, Atom::str_to_term("table"), options).unwrap();
+        let function = prepend(process);
+
+        let Ready { result, .. } = run_until_ready(function, Term::NIL, table);
+
+        assert_badarg!(result, "private");
+    });
+}
+
+/// `fun (Object, Acc) -> [Object | Acc] end`
+fn prepend(process: &Process) -> Term {
+    let code: Code = |arc_process: &Arc<Process>| {
+        let object = arc_process.stack_peek(1).unwrap();
+        let acc = arc_process.stack_peek(2).unwrap();
+        let list = arc_process.cons(object, acc)?;
+        arc_process.return_from_call(2, list)?;
+
+        Process::call_code(arc_process)
+    };
+
+    process
+        .export_closure(
+            Atom::try_from_str("module").unwrap(),
+            Atom::try_from_str("prepend").unwrap(),
+            2,
+            Some(code),
+        )
+        .unwrap()
+}
+
+fn run_until_ready(function: Term, acc0: Term, table: Term) -> Ready {
+    lumen_rt_full::future::run_until_ready(
+        Default::default(),
+        |child_process| {
+            let child_function = function.clone_to_process(child_process);
+            let child_acc0 = acc0.clone_to_process(child_process);
+            let child_table = table.clone_to_process(child_process);
+
+            place_frame_with_arguments(
+                child_process,
+                Placement::Push,
+                child_function,
+                child_acc0,
+                child_table,
+            )
+            .map_err(|e| e.into())
+        },
+        5_000,
+    )
+    .unwrap()
+}
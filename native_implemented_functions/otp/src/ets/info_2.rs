@@ -0,0 +1,70 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use lumen_rt_core::ets::{Access, Type};
+
+use crate::ets::term_to_option_table;
+
+/// Returns `undefined` if `table` does not exist.
+#[native_implemented_function(info/2)]
+pub fn native(process: &Process, table: Term, item: Term) -> exception::Result<Term> {
+    let item_atom = term_try_into_atom!(item)?;
+
+    let arc_table = match term_to_option_table(table)? {
+        Some(arc_table) => arc_table,
+        None => return Ok(atom!("undefined")),
+    };
+
+    match item_atom.name() {
+        "id" => {
+            let reference = arc_table.reference();
+
+            process
+                .reference_from_scheduler(reference.scheduler_id(), reference.number())
+                .map_err(From::from)
+        }
+        "keypos" => process.integer(arc_table.keypos()).map_err(From::from),
+        "memory" => process.integer(arc_table.memory()).map_err(From::from),
+        "name" => Ok(arc_table.name().encode()?),
+        "named_table" => Ok(arc_table.is_named().into()),
+        "owner" => Ok(arc_table.owner().encode()?),
+        "protection" => {
+            let protection = match arc_table.access() {
+                Access::Public => "public",
+                Access::Protected => "protected",
+                Access::Private => "private",
+            };
+
+            Ok(Atom::str_to_term(protection))
+        }
+        "size" => process.integer(arc_table.len()).map_err(From::from),
+        "type" => {
+            let r#type = match arc_table.r#type() {
+                Type::Set => "set",
+                Type::OrderedSet => "ordered_set",
+                Type::Bag => "bag",
+                Type::DuplicateBag => "duplicate_bag",
+            };
+
+            Ok(Atom::str_to_term(r#type))
+        }
+        _ => Err(anyhow!(
+            "item ({}) is not one of id, keypos, memory, name, named_table, owner, protection, size, or type",
+            item
+        )
+        .into()),
+    }
+}
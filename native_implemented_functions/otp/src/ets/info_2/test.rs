@@ -0,0 +1,62 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::info_2::native;
+use crate::ets::{insert_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn without_table_returns_undefined() {
+    with_process(|process| {
+        assert_eq!(
+            native(
+                process,
+                Atom::str_to_term("info_2_without_table"),
+                Atom::str_to_term("size")
+            ),
+            Ok(Atom::str_to_term("undefined"))
+        );
+    });
+}
+
+#[test]
+fn with_invalid_item_errors_badarg() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+
+        assert_badarg!(
+            native(process, table, Atom::str_to_term("colour")),
+            "is not one of"
+        );
+    });
+}
+
+#[test]
+fn with_size_returns_number_of_objects() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let objects = process
+            .list_from_slice(&[
+                process.tuple_from_slice(&[Atom::str_to_term("a")]).unwrap(),
+                process.tuple_from_slice(&[Atom::str_to_term("b")]).unwrap(),
+            ])
+            .unwrap();
+
+        assert_eq!(insert_2::native(process, table, objects), Ok(true.into()));
+        assert_eq!(
+            native(process, table, Atom::str_to_term("size")),
+            Ok(process.integer(2).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_owner_returns_creating_process() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+
+        assert_eq!(
+            native(process, table, Atom::str_to_term("owner")),
+            Ok(process.pid_term())
+        );
+    });
+}
@@ -0,0 +1,23 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::ets::{term_to_object_vec, term_to_writable_table};
+
+#[native_implemented_function(insert/2)]
+pub fn native(process: &Process, table: Term, object_or_objects: Term) -> exception::Result<Term> {
+    let arc_table = term_to_writable_table(process, table)?;
+    let object_vec = term_to_object_vec(&arc_table, object_or_objects)?;
+    arc_table.insert(&object_vec)?;
+
+    Ok(true.into())
+}
@@ -0,0 +1,103 @@
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::insert_2::native;
+use crate::ets::{lookup_2, new_2};
+use crate::test::{process, with_process};
+
+#[test]
+fn without_table_errors_badarg() {
+    with_process(|process| {
+        let table = Atom::str_to_term("insert_2_without_table");
+        let object = process
+            .tuple_from_slice(&[Atom::str_to_term("key")])
+            .unwrap();
+
+        assert_badarg!(native(process, table, object), "does not exist");
+    });
+}
+
+#[test]
+fn with_object_without_key_errors_badarg() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let object = process.tuple_from_slice(&[]).unwrap();
+
+        assert_badarg!(native(process, table, object), "at least 1 elements");
+    });
+}
+
+#[test]
+fn with_protected_table_from_other_process_errors_badarg() {
+    with_process(|owner_process| {
+        let table = new_2::native(owner_process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let other_arc_process = process::default();
+        let object = other_arc_process
+            .tuple_from_slice(&[Atom::str_to_term("key")])
+            .unwrap();
+
+        assert_badarg!(native(&other_arc_process, table, object), "protected");
+    });
+}
+
+#[test]
+fn with_set_replaces_object_with_same_key() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let key = Atom::str_to_term("key");
+        let first = object(process, key, 1);
+        let second = object(process, key, 2);
+
+        assert_eq!(native(process, table, first), Ok(true.into()));
+        assert_eq!(native(process, table, second), Ok(true.into()));
+        assert_eq!(
+            lookup_2::native(process, table, key),
+            Ok(process.list_from_slice(&[second]).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_bag_keeps_distinct_objects_with_same_key() {
+    with_process(|process| {
+        let options = process
+            .list_from_slice(&[Atom::str_to_term("bag")])
+            .unwrap();
+        let table = new_2::native(process, Atom::str_to_term("table"), options).unwrap();
+        let key = Atom::str_to_term("key");
+        let first = object(process, key, 1);
+        let second = object(process, key, 2);
+        let objects = process.list_from_slice(&[first, second, first]).unwrap();
+
+        assert_eq!(native(process, table, objects), Ok(true.into()));
+        assert_eq!(
+            lookup_2::native(process, table, key),
+            Ok(process.list_from_slice(&[first, second]).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_duplicate_bag_keeps_duplicate_objects() {
+    with_process(|process| {
+        let options = process
+            .list_from_slice(&[Atom::str_to_term("duplicate_bag")])
+            .unwrap();
+        let table = new_2::native(process, Atom::str_to_term("table"), options).unwrap();
+        let key = Atom::str_to_term("key");
+        let first = object(process, key, 1);
+        let objects = process.list_from_slice(&[first, first]).unwrap();
+
+        assert_eq!(native(process, table, objects), Ok(true.into()));
+        assert_eq!(
+            lookup_2::native(process, table, key),
+            Ok(process.list_from_slice(&[first, first]).unwrap())
+        );
+    });
+}
+
+fn object(process: &Process, key: Term, value: isize) -> Term {
+    process
+        .tuple_from_slice(&[key, process.integer(value).unwrap()])
+        .unwrap()
+}
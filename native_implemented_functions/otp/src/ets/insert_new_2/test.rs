@@ -0,0 +1,48 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::insert_new_2::native;
+use crate::ets::{lookup_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn without_key_in_table_inserts_all_objects() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let first = process
+            .tuple_from_slice(&[Atom::str_to_term("first")])
+            .unwrap();
+        let second = process
+            .tuple_from_slice(&[Atom::str_to_term("second")])
+            .unwrap();
+        let objects = process.list_from_slice(&[first, second]).unwrap();
+
+        assert_eq!(native(process, table, objects), Ok(true.into()));
+        assert_eq!(
+            lookup_2::native(process, table, Atom::str_to_term("second")),
+            Ok(process.list_from_slice(&[second]).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_any_key_in_table_inserts_nothing() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let first = process
+            .tuple_from_slice(&[Atom::str_to_term("first")])
+            .unwrap();
+        let second = process
+            .tuple_from_slice(&[Atom::str_to_term("second")])
+            .unwrap();
+
+        assert_eq!(native(process, table, first), Ok(true.into()));
+
+        let objects = process.list_from_slice(&[second, first]).unwrap();
+
+        assert_eq!(native(process, table, objects), Ok(false.into()));
+        assert_eq!(
+            lookup_2::native(process, table, Atom::str_to_term("second")),
+            Ok(Term::NIL)
+        );
+    });
+}
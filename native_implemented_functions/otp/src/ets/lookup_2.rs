@@ -0,0 +1,29 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::CloneToProcess;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::ets::term_to_readable_table;
+
+#[native_implemented_function(lookup/2)]
+pub fn native(process: &Process, table: Term, key: Term) -> exception::Result<Term> {
+    let arc_table = term_to_readable_table(process, table)?;
+    let mut object_vec = Vec::new();
+
+    arc_table.lookup(key, |object| -> exception::Result<()> {
+        object_vec.push(object.clone_to_process(process));
+
+        Ok(())
+    })?;
+
+    process.list_from_slice(&object_vec).map_err(From::from)
+}
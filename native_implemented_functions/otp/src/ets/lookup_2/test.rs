@@ -0,0 +1,92 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::lookup_2::native;
+use crate::ets::{insert_2, new_2};
+use crate::test::{process, with_process};
+
+#[test]
+fn without_key_returns_empty_list() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+
+        assert_eq!(
+            native(process, table, Atom::str_to_term("key")),
+            Ok(Term::NIL)
+        );
+    });
+}
+
+#[test]
+fn with_set_compares_keys_exactly() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let object = process
+            .tuple_from_slice(&[process.integer(1).unwrap()])
+            .unwrap();
+
+        assert_eq!(insert_2::native(process, table, object), Ok(true.into()));
+        assert_eq!(
+            native(process, table, process.float(1.0).unwrap()),
+            Ok(Term::NIL)
+        );
+    });
+}
+
+#[test]
+fn with_ordered_set_compares_keys_by_value() {
+    with_process(|process| {
+        let options = process
+            .list_from_slice(&[Atom::str_to_term("ordered_set")])
+            .unwrap();
+        let table = new_2::native(process, Atom::str_to_term("table"), options).unwrap();
+        let object = process
+            .tuple_from_slice(&[process.integer(1).unwrap()])
+            .unwrap();
+
+        assert_eq!(insert_2::native(process, table, object), Ok(true.into()));
+        assert_eq!(
+            native(process, table, process.float(1.0).unwrap()),
+            Ok(process.list_from_slice(&[object]).unwrap())
+        );
+    });
+}
+
+#[test]
+fn from_other_process_with_protected_table_returns_copy() {
+    with_process(|owner_process| {
+        let table = new_2::native(owner_process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let key = owner_process.binary_from_str("key").unwrap();
+        let object = owner_process
+            .tuple_from_slice(&[key, owner_process.list_from_slice(&[key]).unwrap()])
+            .unwrap();
+
+        assert_eq!(
+            insert_2::native(owner_process, table, object),
+            Ok(true.into())
+        );
+
+        let other_arc_process = process::default();
+        let other_key = other_arc_process.binary_from_str("key").unwrap();
+
+        assert_eq!(
+            native(&other_arc_process, table, other_key),
+            Ok(owner_process.list_from_slice(&[object]).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_private_table_from_other_process_errors_badarg() {
+    with_process(|owner_process| {
+        let options = owner_process
+            .list_from_slice(&[Atom::str_to_term("private")])
+            .unwrap();
+        let table = new_2::native(owner_process, Atom::str_to_term("table"), options).unwrap();
+        let other_arc_process = process::default();
+
+        assert_badarg!(
+            native(&other_arc_process, table, Atom::str_to_term("key")),
+            "private"
+        );
+    });
+}
@@ -0,0 +1,35 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::ets::match_spec::{self, Bindings};
+use crate::ets::term_to_readable_table;
+
+/// Returns, for each object matching `pattern`, the list of values bound to the `'$N'` variables
+/// in `pattern`, ordered by `N`.
+#[native_implemented_function(match/2)]
+pub fn native(process: &Process, table: Term, pattern: Term) -> exception::Result<Term> {
+    let arc_table = term_to_readable_table(process, table)?;
+    let mut match_vec = Vec::new();
+
+    arc_table.try_for_each(|object| -> exception::Result<()> {
+        let mut bindings = Bindings::new();
+
+        if match_spec::is_match(pattern, object, &mut bindings) {
+            match_vec.push(match_spec::bindings_to_list(process, &bindings)?);
+        }
+
+        Ok(())
+    })?;
+
+    process.list_from_slice(&match_vec).map_err(From::from)
+}
@@ -0,0 +1,74 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::match_2::native;
+use crate::ets::{insert_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn returns_bindings_ordered_by_variable_number() {
+    with_process(|process| {
+        let options = process
+            .list_from_slice(&[Atom::str_to_term("ordered_set")])
+            .unwrap();
+        let table = new_2::native(process, Atom::str_to_term("table"), options).unwrap();
+        let one = process.integer(1).unwrap();
+        let two = process.integer(2).unwrap();
+        let a = Atom::str_to_term("a");
+        let b = Atom::str_to_term("b");
+        let objects = process
+            .list_from_slice(&[
+                process.tuple_from_slice(&[one, a, a]).unwrap(),
+                process.tuple_from_slice(&[two, a, b]).unwrap(),
+            ])
+            .unwrap();
+
+        assert_eq!(insert_2::native(process, table, objects), Ok(true.into()));
+
+        let pattern = process
+            .tuple_from_slice(&[
+                Atom::str_to_term("$2"),
+                Atom::str_to_term("_"),
+                Atom::str_to_term("$1"),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            native(process, table, pattern),
+            Ok(process
+                .list_from_slice(&[
+                    process.list_from_slice(&[a, one]).unwrap(),
+                    process.list_from_slice(&[b, two]).unwrap()
+                ])
+                .unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_repeated_variable_only_matches_exactly_equal_elements() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let one = process.integer(1).unwrap();
+        let two = process.integer(2).unwrap();
+        let objects = process
+            .list_from_slice(&[
+                process
+                    .tuple_from_slice(&[one, process.float(1.0).unwrap()])
+                    .unwrap(),
+                process.tuple_from_slice(&[two, two]).unwrap(),
+            ])
+            .unwrap();
+
+        assert_eq!(insert_2::native(process, table, objects), Ok(true.into()));
+
+        let variable = Atom::str_to_term("$1");
+        let pattern = process.tuple_from_slice(&[variable, variable]).unwrap();
+
+        assert_eq!(
+            native(process, table, pattern),
+            Ok(process
+                .list_from_slice(&[process.list_from_slice(&[two]).unwrap()])
+                .unwrap())
+        );
+    });
+}
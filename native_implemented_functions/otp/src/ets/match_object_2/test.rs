@@ -0,0 +1,66 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::match_object_2::native;
+use crate::ets::{insert_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn returns_matching_objects() {
+    with_process(|process| {
+        let options = process
+            .list_from_slice(&[Atom::str_to_term("ordered_set")])
+            .unwrap();
+        let table = new_2::native(process, Atom::str_to_term("table"), options).unwrap();
+        let ok = Atom::str_to_term("ok");
+        let error = Atom::str_to_term("error");
+        let first = process
+            .tuple_from_slice(&[process.integer(1).unwrap(), ok])
+            .unwrap();
+        let second = process
+            .tuple_from_slice(&[process.integer(2).unwrap(), error])
+            .unwrap();
+        let third = process
+            .tuple_from_slice(&[process.integer(3).unwrap(), ok])
+            .unwrap();
+        let objects = process.list_from_slice(&[first, second, third]).unwrap();
+
+        assert_eq!(insert_2::native(process, table, objects), Ok(true.into()));
+
+        let pattern = process
+            .tuple_from_slice(&[Atom::str_to_term("_"), ok])
+            .unwrap();
+
+        assert_eq!(
+            native(process, table, pattern),
+            Ok(process.list_from_slice(&[first, third]).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_map_pattern_matches_subset_of_keys() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+        let a = Atom::str_to_term("a");
+        let b = Atom::str_to_term("b");
+        let map = process
+            .map_from_slice(&[
+                (a, process.integer(1).unwrap()),
+                (b, process.integer(2).unwrap()),
+            ])
+            .unwrap();
+        let object = process.tuple_from_slice(&[a, map]).unwrap();
+
+        assert_eq!(insert_2::native(process, table, object), Ok(true.into()));
+
+        let map_pattern = process
+            .map_from_slice(&[(b, Atom::str_to_term("_"))])
+            .unwrap();
+        let pattern = process.tuple_from_slice(&[a, map_pattern]).unwrap();
+
+        assert_eq!(
+            native(process, table, pattern),
+            Ok(process.list_from_slice(&[object]).unwrap())
+        );
+    });
+}
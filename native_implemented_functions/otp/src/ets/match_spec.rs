@@ -0,0 +1,374 @@
+//! Match patterns, as used by `match/2` and `match_object/2`, and match specifications, as used by
+//! `select/2`.
+//!
+//! Objects are matched while they are still in the table, so any part of an object that ends up in
+//! a result is cloned to the process heap as soon as it is referenced.
+
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::CloneToProcess;
+
+use crate::erlang;
+
+/// Values bound to `'$N'` variables, ordered by `N`.
+pub type Bindings = BTreeMap<usize, Term>;
+
+pub fn bindings_to_list(process: &Process, bindings: &Bindings) -> exception::Result<Term> {
+    let value_vec: Vec<Term> = bindings
+        .values()
+        .map(|value| value.clone_to_process(process))
+        .collect();
+
+    process.list_from_slice(&value_vec).map_err(From::from)
+}
+
+/// Matches `term` against `pattern`, binding any `'$N'` variables in `bindings`.  `'_'` matches
+/// anything and a variable that is already bound only matches a term exactly equal to its value.
+pub fn is_match(pattern: Term, term: Term, bindings: &mut Bindings) -> bool {
+    match pattern.decode().unwrap() {
+        TypedTerm::Atom(atom) => match atom.name() {
+            "_" => true,
+            name => match variable(name) {
+                Some(n) => match bindings.get(&n) {
+                    Some(bound) => is_exactly_equal(*bound, term),
+                    None => {
+                        bindings.insert(n, term);
+
+                        true
+                    }
+                },
+                None => is_exactly_equal(pattern, term),
+            },
+        },
+        TypedTerm::Tuple(pattern_tuple) => match term.decode().unwrap() {
+            TypedTerm::Tuple(tuple) => {
+                (pattern_tuple.len() == tuple.len())
+                    && pattern_tuple
+                        .elements()
+                        .iter()
+                        .zip(tuple.elements().iter())
+                        .all(|(element_pattern, element)| {
+                            is_match(*element_pattern, *element, bindings)
+                        })
+            }
+            _ => false,
+        },
+        TypedTerm::List(pattern_cons) => match term.decode().unwrap() {
+            TypedTerm::List(cons) => {
+                is_match(pattern_cons.head, cons.head, bindings)
+                    && is_match(pattern_cons.tail, cons.tail, bindings)
+            }
+            _ => false,
+        },
+        TypedTerm::Map(pattern_map) => match term.decode().unwrap() {
+            TypedTerm::Map(map) => {
+                pattern_map
+                    .iter()
+                    .all(|(key, value_pattern)| match map.get(key) {
+                        Some(value) => is_match(value_pattern, value, bindings),
+                        None => false,
+                    })
+            }
+            _ => false,
+        },
+        _ => is_exactly_equal(pattern, term),
+    }
+}
+
+pub struct MatchSpec {
+    clauses: Vec<Clause>,
+}
+
+impl MatchSpec {
+    pub fn from_term(match_spec: Term) -> exception::Result<Self> {
+        let clause_terms = proper_list_to_vec(match_spec).with_context(|| {
+            format!(
+                "match_spec ({}) must be a proper list of {{Head, Guards, Body}}",
+                match_spec
+            )
+        })?;
+        let mut clauses = Vec::with_capacity(clause_terms.len());
+
+        for clause_term in clause_terms {
+            clauses.push(Clause::from_term(clause_term).with_context(|| {
+                format!(
+                    "match_spec ({}) clause ({}) must be {{Head, Guards, Body}} with Guards and Body as proper lists",
+                    match_spec, clause_term
+                )
+            })?);
+        }
+
+        Ok(Self { clauses })
+    }
+
+    /// The result of the body of the first clause whose head matches `object` and whose guards
+    /// are all `true`, cloned to `process`'s heap.
+    ///
+    /// Like a failing guard, a body that raises excludes `object`.
+    pub fn run(&self, process: &Process, object: Term) -> exception::Result<Option<Term>> {
+        for clause in &self.clauses {
+            let mut bindings = Bindings::new();
+
+            if is_match(clause.head, object, &mut bindings)
+                && clause.guards_are_true(process, object, &bindings)
+            {
+                let mut result = Term::NIL;
+
+                for expression in &clause.body {
+                    match evaluate(process, *expression, object, &bindings) {
+                        Ok(value) => result = value,
+                        Err(error @ exception::Exception::System(_)) => return Err(error),
+                        Err(_) => return Ok(None),
+                    }
+                }
+
+                return Ok(Some(result));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+// Private
+
+struct Clause {
+    head: Term,
+    guards: Vec<Term>,
+    body: Vec<Term>,
+}
+
+impl Clause {
+    fn from_term(clause: Term) -> anyhow::Result<Self> {
+        let tuple: Boxed<Tuple> = clause
+            .try_into()
+            .map_err(|_| anyhow!("clause is not a tuple"))?;
+
+        if tuple.len() != 3 {
+            return Err(anyhow!("clause does not have 3 elements"));
+        }
+
+        let elements = tuple.elements();
+
+        Ok(Self {
+            head: elements[0],
+            guards: proper_list_to_vec(elements[1])?,
+            body: proper_list_to_vec(elements[2])?,
+        })
+    }
+
+    fn guards_are_true(&self, process: &Process, object: Term, bindings: &Bindings) -> bool {
+        self.guards
+            .iter()
+            .all(|guard| match evaluate(process, *guard, object, bindings) {
+                Ok(value) => value == Atom::str_to_term("true"),
+                Err(_) => false,
+            })
+    }
+}
+
+fn call(process: &Process, name: &str, arguments: &[Term]) -> exception::Result<Term> {
+    match (name, arguments) {
+        // Type tests
+        ("is_atom", [term]) => Ok(erlang::is_atom_1::native(*term)),
+        ("is_binary", [term]) => Ok(erlang::is_binary_1::native(*term)),
+        ("is_bitstring", [term]) => Ok(erlang::is_bitstring_1::native(*term)),
+        ("is_boolean", [term]) => Ok(erlang::is_boolean_1::native(*term)),
+        ("is_float", [term]) => Ok(erlang::is_float_1::native(*term)),
+        ("is_function", [term]) => Ok(erlang::is_function_1::native(*term)),
+        ("is_integer", [term]) => Ok(erlang::is_integer_1::native(*term)),
+        ("is_list", [term]) => Ok(erlang::is_list_1::native(*term)),
+        ("is_map", [term]) => Ok(erlang::is_map_1::native(*term)),
+        ("is_number", [term]) => Ok(erlang::is_number_1::native(*term)),
+        ("is_pid", [term]) => Ok(erlang::is_pid_1::native(*term)),
+        ("is_reference", [term]) => Ok(erlang::is_reference_1::native(*term)),
+        ("is_tuple", [term]) => Ok(erlang::is_tuple_1::native(*term)),
+        ("is_record", [term, record_tag]) => erlang::is_record_2::native(*term, *record_tag),
+        // Comparisons
+        ("==", [left, right]) => Ok(erlang::are_equal_after_conversion_2::native(*left, *right)),
+        ("/=", [left, right]) => Ok(erlang::are_not_equal_after_conversion_2::native(
+            *left, *right,
+        )),
+        ("=:=", [left, right]) => Ok(erlang::are_exactly_equal_2::native(*left, *right)),
+        ("=/=", [left, right]) => Ok(erlang::are_exactly_not_equal_2::native(*left, *right)),
+        ("<", [left, right]) => Ok(erlang::is_less_than_2::native(*left, *right)),
+        ("=<", [left, right]) => Ok(erlang::is_equal_or_less_than_2::native(*left, *right)),
+        (">", [left, right]) => Ok(erlang::is_greater_than_2::native(*left, *right)),
+        (">=", [left, right]) => Ok(erlang::is_greater_than_or_equal_2::native(*left, *right)),
+        // Boolean operators
+        ("and", [left, right]) => erlang::and_2::native(*left, *right),
+        ("or", [left, right]) => erlang::or_2::native(*left, *right),
+        ("xor", [left, right]) => erlang::xor_2::native(*left, *right),
+        ("not", [boolean]) => erlang::not_1::native(*boolean),
+        // Arithmetic
+        ("+", [augend, addend]) => erlang::add_2::native(process, *augend, *addend),
+        ("-", [minuend, subtrahend]) => erlang::subtract_2::native(process, *minuend, *subtrahend),
+        ("-", [number]) => erlang::negate_1::native(process, *number),
+        ("*", [multiplier, multiplicand]) => {
+            erlang::multiply_2::native(process, *multiplier, *multiplicand)
+        }
+        ("/", [dividend, divisor]) => erlang::divide_2::native(process, *dividend, *divisor),
+        ("div", [dividend, divisor]) => erlang::div_2::native(process, *dividend, *divisor),
+        ("rem", [dividend, divisor]) => erlang::rem_2::native(process, *dividend, *divisor),
+        ("band", [left, right]) => erlang::band_2::native(process, *left, *right),
+        ("bor", [left, right]) => erlang::bor_2::native(process, *left, *right),
+        ("bxor", [left, right]) => erlang::bxor_2::native(process, *left, *right),
+        ("bnot", [integer]) => erlang::bnot_1::native(process, *integer),
+        ("bsl", [integer, shift]) => erlang::bsl_2::native(process, *integer, *shift),
+        ("bsr", [integer, shift]) => erlang::bsr_2::native(process, *integer, *shift),
+        ("abs", [number]) => erlang::abs_1::native(process, *number),
+        ("float", [number]) => erlang::float_1::native(process, *number),
+        ("max", [left, right]) => Ok(erlang::max_2::native(*left, *right)),
+        ("min", [left, right]) => Ok(erlang::min_2::native(*left, *right)),
+        // Data structures
+        ("bit_size", [bitstring]) => erlang::bit_size_1::native(process, *bitstring),
+        ("byte_size", [bitstring]) => erlang::byte_size_1::native(process, *bitstring),
+        ("element", [index, tuple]) => erlang::element_2::native(*index, *tuple),
+        ("hd", [list]) => erlang::hd_1::native(*list),
+        ("length", [list]) => erlang::length_1::native(process, *list),
+        ("map_get", [key, map]) => erlang::map_get_2::native(process, *key, *map),
+        ("map_size", [map]) => erlang::map_size_1::native(process, *map),
+        ("size", [binary_or_tuple]) => erlang::size_1::native(process, *binary_or_tuple),
+        ("tl", [list]) => erlang::tl_1::native(*list),
+        ("tuple_size", [tuple]) => erlang::tuple_size_1::native(process, *tuple),
+        // Process
+        ("node", []) => Ok(erlang::node_0::native()),
+        ("self", []) => Ok(erlang::self_0::native(process)),
+        _ => Err(anyhow!("{}/{} is not a match spec function", name, arguments.len()).into()),
+    }
+}
+
+fn evaluate(
+    process: &Process,
+    expression: Term,
+    object: Term,
+    bindings: &Bindings,
+) -> exception::Result<Term> {
+    match expression.decode().unwrap() {
+        TypedTerm::Atom(atom) => match atom.name() {
+            "$_" => Ok(object.clone_to_process(process)),
+            "$$" => bindings_to_list(process, bindings),
+            name => match variable(name) {
+                Some(n) => bindings
+                    .get(&n)
+                    .map(|value| value.clone_to_process(process))
+                    .with_context(|| format!("variable ({}) is unbound", expression))
+                    .map_err(From::from),
+                None => Ok(expression),
+            },
+        },
+        TypedTerm::Tuple(tuple) => evaluate_tuple(process, expression, &tuple, object, bindings),
+        TypedTerm::List(_) => {
+            let element_vec = proper_list_to_vec(expression)?;
+            let mut value_vec = Vec::with_capacity(element_vec.len());
+
+            for element in element_vec {
+                value_vec.push(evaluate(process, element, object, bindings)?);
+            }
+
+            process.list_from_slice(&value_vec).map_err(From::from)
+        }
+        _ => Ok(expression),
+    }
+}
+
+fn evaluate_boolean(
+    process: &Process,
+    expression: Term,
+    object: Term,
+    bindings: &Bindings,
+) -> exception::Result<bool> {
+    let value = evaluate(process, expression, object, bindings)?;
+    let boolean = term_try_into_bool!(value)?;
+
+    Ok(boolean)
+}
+
+fn evaluate_tuple(
+    process: &Process,
+    expression: Term,
+    tuple: &Tuple,
+    object: Term,
+    bindings: &Bindings,
+) -> exception::Result<Term> {
+    let elements = tuple.elements();
+
+    match elements {
+        // `{{...}}` constructs a tuple
+        [inner] if inner.is_boxed_tuple() => {
+            let inner_tuple: Boxed<Tuple> = (*inner).try_into().unwrap();
+            let mut value_vec = Vec::with_capacity(inner_tuple.len());
+
+            for element in inner_tuple.elements() {
+                value_vec.push(evaluate(process, *element, object, bindings)?);
+            }
+
+            process.tuple_from_slice(&value_vec).map_err(From::from)
+        }
+        [function, arguments @ ..] if function.is_atom() => {
+            let function_atom: Atom = (*function).try_into().unwrap();
+
+            match (function_atom.name(), arguments) {
+                ("const", [constant]) => Ok(*constant),
+                ("andalso", operands) => {
+                    for operand in operands {
+                        if !evaluate_boolean(process, *operand, object, bindings)? {
+                            return Ok(false.into());
+                        }
+                    }
+
+                    Ok(true.into())
+                }
+                ("orelse", operands) => {
+                    for operand in operands {
+                        if evaluate_boolean(process, *operand, object, bindings)? {
+                            return Ok(true.into());
+                        }
+                    }
+
+                    Ok(false.into())
+                }
+                (name, arguments) => {
+                    let mut value_vec = Vec::with_capacity(arguments.len());
+
+                    for argument in arguments {
+                        value_vec.push(evaluate(process, *argument, object, bindings)?);
+                    }
+
+                    call(process, name, &value_vec)
+                }
+            }
+        }
+        _ => Err(anyhow!("expression ({}) is not a match spec expression", expression).into()),
+    }
+}
+
+fn is_exactly_equal(left: Term, right: Term) -> bool {
+    left.decode().unwrap().exact_eq(&right.decode().unwrap())
+}
+
+fn proper_list_to_vec(list: Term) -> anyhow::Result<Vec<Term>> {
+    match list.decode().unwrap() {
+        TypedTerm::Nil => Ok(Vec::new()),
+        TypedTerm::List(cons) => cons
+            .into_iter()
+            .collect::<std::result::Result<Vec<Term>, _>>()
+            .map_err(|_| anyhow!("list ({}) is improper", list)),
+        _ => Err(anyhow!("list ({}) is not a list", list)),
+    }
+}
+
+/// `N` if `name` is a `'$N'` variable
+fn variable(name: &str) -> Option<usize> {
+    if name.starts_with('$') && (1 < name.len()) && name[1..].bytes().all(|b| b.is_ascii_digit()) {
+        name[1..].parse().ok()
+    } else {
+        None
+    }
+}
@@ -0,0 +1,53 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+mod options;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use lumen_rt_core::ets::{self, Table};
+use lumen_rt_full::process::SchedulerDependentAlloc;
+
+use crate::ets::table_to_term;
+
+use options::Options;
+
+#[native_implemented_function(new/2)]
+pub fn native(process: &Process, name: Term, options: Term) -> exception::Result<Term> {
+    let name_atom = term_try_into_atom!(name)?;
+    let options: Options = options.try_into().ok().with_context(|| {
+        format!(
+            "options ({}) must be a proper list of set, ordered_set, bag, duplicate_bag, public, protected, private, named_table, or {{keypos, Pos}}",
+            options
+        )
+    })?;
+
+    let reference_term = process.next_reference()?;
+    let reference: Boxed<Reference> = reference_term.try_into().unwrap();
+    let table = Table::new(
+        *reference,
+        name_atom,
+        options.named,
+        options.r#type,
+        options.access,
+        options.keypos,
+        process.pid(),
+    );
+
+    match ets::register(table) {
+        Some(arc_table) => table_to_term(process, &arc_table).map_err(From::from),
+        None => Err(anyhow!("name ({}) is already used by another named table", name).into()),
+    }
+}
@@ -0,0 +1,116 @@
+use std::convert::{TryFrom, TryInto};
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use lumen_rt_core::ets::{Access, Type};
+
+pub struct Options {
+    pub r#type: Type,
+    pub access: Access,
+    pub named: bool,
+    pub keypos: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            r#type: Type::Set,
+            access: Access::Protected,
+            named: false,
+            keypos: 1,
+        }
+    }
+}
+
+impl Options {
+    fn put_option_term(&mut self, option: Term) -> Result<&Self, TryFromTermError> {
+        match option.decode().unwrap() {
+            TypedTerm::Atom(atom) => {
+                match atom.name() {
+                    "set" => self.r#type = Type::Set,
+                    "ordered_set" => self.r#type = Type::OrderedSet,
+                    "bag" => self.r#type = Type::Bag,
+                    "duplicate_bag" => self.r#type = Type::DuplicateBag,
+                    "public" => self.access = Access::Public,
+                    "protected" => self.access = Access::Protected,
+                    "private" => self.access = Access::Private,
+                    "named_table" => self.named = true,
+                    // Tables are never compressed
+                    "compressed" => (),
+                    name => return Err(TryFromTermError::AtomName(name)),
+                }
+
+                Ok(self)
+            }
+            TypedTerm::Tuple(tuple) => {
+                if tuple.len() == 2 {
+                    let atom: Atom = tuple[0]
+                        .try_into()
+                        .map_err(|_| TryFromTermError::KeywordKeyType)?;
+
+                    match atom.name() {
+                        "keypos" => {
+                            let keypos: usize = tuple[1]
+                                .try_into()
+                                .map_err(|_| TryFromTermError::KeyposType)?;
+
+                            if 0 < keypos {
+                                self.keypos = keypos;
+
+                                Ok(self)
+                            } else {
+                                Err(TryFromTermError::KeyposType)
+                            }
+                        }
+                        // Concurrency hints are accepted, but tables always use a single
+                        // reader-writer lock
+                        "read_concurrency" | "write_concurrency" => {
+                            let _: bool = tuple[1]
+                                .try_into()
+                                .map_err(|_| TryFromTermError::ConcurrencyType)?;
+
+                            Ok(self)
+                        }
+                        name => Err(TryFromTermError::KeywordKeyName(name)),
+                    }
+                } else {
+                    Err(TryFromTermError::TupleLen)
+                }
+            }
+            _ => Err(TryFromTermError::ElementType),
+        }
+    }
+}
+
+impl TryFrom<Term> for Options {
+    type Error = TryFromTermError;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let mut options: Options = Default::default();
+        let mut options_term = term;
+
+        loop {
+            match options_term.decode().unwrap() {
+                TypedTerm::Nil => return Ok(options),
+                TypedTerm::List(cons) => {
+                    options.put_option_term(cons.head)?;
+                    options_term = cons.tail;
+
+                    continue;
+                }
+                _ => return Err(TryFromTermError::Type),
+            };
+        }
+    }
+}
+
+pub enum TryFromTermError {
+    AtomName(&'static str),
+    ConcurrencyType,
+    KeyposType,
+    KeywordKeyType,
+    KeywordKeyName(&'static str),
+    ElementType,
+    TupleLen,
+    Type,
+}
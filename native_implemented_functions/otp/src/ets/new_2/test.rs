@@ -0,0 +1,106 @@
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::info_2;
+use crate::ets::new_2::native;
+use crate::test::with_process;
+
+#[test]
+fn without_atom_name_errors_badarg() {
+    with_process(|process| {
+        let name = process.integer(1).unwrap();
+
+        assert_badarg!(native(process, name, Term::NIL), "name");
+    });
+}
+
+#[test]
+fn with_invalid_option_errors_badarg() {
+    with_process(|process| {
+        let name = Atom::str_to_term("new_2_with_invalid_option");
+        let options = options(process, &[Atom::str_to_term("unordered_set")]);
+
+        assert_badarg!(native(process, name, options), "must be a proper list");
+    });
+}
+
+#[test]
+fn without_named_table_returns_reference() {
+    with_process(|process| {
+        let name = Atom::str_to_term("new_2_without_named_table");
+
+        let table = native(process, name, Term::NIL).unwrap();
+
+        assert!(table.is_reference());
+        assert_eq!(
+            info_2::native(process, table, Atom::str_to_term("name")),
+            Ok(name)
+        );
+        assert_eq!(
+            info_2::native(process, table, Atom::str_to_term("type")),
+            Ok(Atom::str_to_term("set"))
+        );
+        assert_eq!(
+            info_2::native(process, table, Atom::str_to_term("protection")),
+            Ok(Atom::str_to_term("protected"))
+        );
+    });
+}
+
+#[test]
+fn with_named_table_returns_name() {
+    with_process(|process| {
+        let name = Atom::str_to_term("new_2_with_named_table");
+        let options = options(
+            process,
+            &[
+                Atom::str_to_term("ordered_set"),
+                Atom::str_to_term("public"),
+                Atom::str_to_term("named_table"),
+            ],
+        );
+
+        assert_eq!(native(process, name, options), Ok(name));
+        assert_eq!(
+            info_2::native(process, name, Atom::str_to_term("type")),
+            Ok(Atom::str_to_term("ordered_set"))
+        );
+        assert_eq!(
+            info_2::native(process, name, Atom::str_to_term("protection")),
+            Ok(Atom::str_to_term("public"))
+        );
+    });
+}
+
+#[test]
+fn with_named_table_with_name_in_use_errors_badarg() {
+    with_process(|process| {
+        let name = Atom::str_to_term("new_2_with_name_in_use");
+        let options = options(process, &[Atom::str_to_term("named_table")]);
+
+        assert_eq!(native(process, name, options), Ok(name));
+        assert_badarg!(native(process, name, options), "already used");
+    });
+}
+
+#[test]
+fn with_keypos_sets_keypos() {
+    with_process(|process| {
+        let name = Atom::str_to_term("new_2_with_keypos");
+        let keypos = process
+            .tuple_from_slice(&[Atom::str_to_term("keypos"), process.integer(2).unwrap()])
+            .unwrap();
+        let options = options(process, &[keypos]);
+
+        let table = native(process, name, options).unwrap();
+
+        assert_eq!(
+            info_2::native(process, table, Atom::str_to_term("keypos")),
+            Ok(process.integer(2).unwrap())
+        );
+    });
+}
+
+fn options(process: &Process, options: &[Term]) -> Term {
+    process.list_from_slice(options).unwrap()
+}
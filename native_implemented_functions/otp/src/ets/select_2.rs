@@ -0,0 +1,32 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::ets::match_spec::MatchSpec;
+use crate::ets::term_to_readable_table;
+
+#[native_implemented_function(select/2)]
+pub fn native(process: &Process, table: Term, match_spec: Term) -> exception::Result<Term> {
+    let arc_table = term_to_readable_table(process, table)?;
+    let match_spec = MatchSpec::from_term(match_spec)?;
+    let mut result_vec = Vec::new();
+
+    arc_table.try_for_each(|object| -> exception::Result<()> {
+        if let Some(result) = match_spec.run(process, object)? {
+            result_vec.push(result);
+        }
+
+        Ok(())
+    })?;
+
+    process.list_from_slice(&result_vec).map_err(From::from)
+}
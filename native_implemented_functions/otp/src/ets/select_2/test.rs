@@ -0,0 +1,152 @@
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::select_2::native;
+use crate::ets::{insert_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn without_proper_list_match_spec_errors_badarg() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+
+        assert_badarg!(
+            native(process, table, Atom::str_to_term("match_spec")),
+            "must be a proper list"
+        );
+    });
+}
+
+#[test]
+fn with_guard_returns_body_of_matching_objects() {
+    with_process(|process| {
+        let table = populated_table(process);
+        // [{{'$1', '$2'}, [{'>', '$2', 1}], [{{'$2', '$1'}}]}]
+        let head = process
+            .tuple_from_slice(&[Atom::str_to_term("$1"), Atom::str_to_term("$2")])
+            .unwrap();
+        let guard = process
+            .tuple_from_slice(&[
+                Atom::str_to_term(">"),
+                Atom::str_to_term("$2"),
+                process.integer(1).unwrap(),
+            ])
+            .unwrap();
+        let body = process
+            .tuple_from_slice(&[process
+                .tuple_from_slice(&[Atom::str_to_term("$2"), Atom::str_to_term("$1")])
+                .unwrap()])
+            .unwrap();
+        let match_spec = match_spec(process, head, &[guard], &[body]);
+
+        assert_eq!(
+            native(process, table, match_spec),
+            Ok(process
+                .list_from_slice(&[
+                    process
+                        .tuple_from_slice(&[process.integer(2).unwrap(), Atom::str_to_term("b")])
+                        .unwrap(),
+                    process
+                        .tuple_from_slice(&[process.integer(3).unwrap(), Atom::str_to_term("c")])
+                        .unwrap(),
+                ])
+                .unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_dollar_underscore_body_returns_objects() {
+    with_process(|process| {
+        let table = populated_table(process);
+        // [{{'_', '$1'}, [{'andalso', {is_integer, '$1'}, {'=<', '$1', 1}}], ['$_']}]
+        let head = process
+            .tuple_from_slice(&[Atom::str_to_term("_"), Atom::str_to_term("$1")])
+            .unwrap();
+        let guard = process
+            .tuple_from_slice(&[
+                Atom::str_to_term("andalso"),
+                process
+                    .tuple_from_slice(&[Atom::str_to_term("is_integer"), Atom::str_to_term("$1")])
+                    .unwrap(),
+                process
+                    .tuple_from_slice(&[
+                        Atom::str_to_term("=<"),
+                        Atom::str_to_term("$1"),
+                        process.integer(1).unwrap(),
+                    ])
+                    .unwrap(),
+            ])
+            .unwrap();
+        let match_spec = match_spec(process, head, &[guard], &[Atom::str_to_term("$_")]);
+
+        assert_eq!(
+            native(process, table, match_spec),
+            Ok(process
+                .list_from_slice(&[process
+                    .tuple_from_slice(&[Atom::str_to_term("a"), process.integer(1).unwrap()])
+                    .unwrap()])
+                .unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_raising_guard_skips_object() {
+    with_process(|process| {
+        let table = populated_table(process);
+        // [{{'$1', '_'}, [{'>', {'+', '$1', 1}, 0}], [true]}]
+        let head = process
+            .tuple_from_slice(&[Atom::str_to_term("$1"), Atom::str_to_term("_")])
+            .unwrap();
+        let guard = process
+            .tuple_from_slice(&[
+                Atom::str_to_term(">"),
+                process
+                    .tuple_from_slice(&[
+                        Atom::str_to_term("+"),
+                        Atom::str_to_term("$1"),
+                        process.integer(1).unwrap(),
+                    ])
+                    .unwrap(),
+                process.integer(0).unwrap(),
+            ])
+            .unwrap();
+        let match_spec = match_spec(process, head, &[guard], &[true.into()]);
+
+        assert_eq!(native(process, table, match_spec), Ok(Term::NIL));
+    });
+}
+
+fn match_spec(process: &Process, head: Term, guards: &[Term], body: &[Term]) -> Term {
+    let clause = process
+        .tuple_from_slice(&[
+            head,
+            process.list_from_slice(guards).unwrap(),
+            process.list_from_slice(body).unwrap(),
+        ])
+        .unwrap();
+
+    process.list_from_slice(&[clause]).unwrap()
+}
+
+fn populated_table(process: &Process) -> Term {
+    let options = process
+        .list_from_slice(&[Atom::str_to_term("ordered_set")])
+        .unwrap();
+    let table = new_2::native(process, Atom::str_to_term("table"), options).unwrap();
+    let objects: Vec<Term> = ["a", "b", "c"]
+        .iter()
+        .zip(1..)
+        .map(|(key, value)| {
+            process
+                .tuple_from_slice(&[Atom::str_to_term(key), process.integer(value).unwrap()])
+                .unwrap()
+        })
+        .collect();
+    let objects = process.list_from_slice(&objects).unwrap();
+
+    assert_eq!(insert_2::native(process, table, objects), Ok(true.into()));
+
+    table
+}
@@ -0,0 +1,21 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::ets::{objects_to_list, term_to_readable_table};
+
+#[native_implemented_function(tab2list/1)]
+pub fn native(process: &Process, table: Term) -> exception::Result<Term> {
+    let arc_table = term_to_readable_table(process, table)?;
+
+    objects_to_list(process, &arc_table)
+}
@@ -0,0 +1,38 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::tab2list_1::native;
+use crate::ets::{insert_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn with_ordered_set_returns_objects_in_key_order() {
+    with_process(|process| {
+        let options = process
+            .list_from_slice(&[Atom::str_to_term("ordered_set")])
+            .unwrap();
+        let table = new_2::native(process, Atom::str_to_term("table"), options).unwrap();
+        let one = process
+            .tuple_from_slice(&[process.integer(1).unwrap()])
+            .unwrap();
+        let two = process
+            .tuple_from_slice(&[process.integer(2).unwrap()])
+            .unwrap();
+        let atom = process.tuple_from_slice(&[Atom::str_to_term("a")]).unwrap();
+        let objects = process.list_from_slice(&[atom, two, one]).unwrap();
+
+        assert_eq!(insert_2::native(process, table, objects), Ok(true.into()));
+        assert_eq!(
+            native(process, table),
+            Ok(process.list_from_slice(&[one, two, atom]).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_empty_table_returns_empty_list() {
+    with_process(|process| {
+        let table = new_2::native(process, Atom::str_to_term("table"), Term::NIL).unwrap();
+
+        assert_eq!(native(process, table), Ok(Term::NIL));
+    });
+}
@@ -0,0 +1,201 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use lumen_rt_core::ets::{Table, Type};
+
+use crate::erlang::add_2;
+use crate::ets::term_to_writable_table;
+
+/// `update_op` is either a single operation, in which case the updated counter is returned, or a
+/// list of operations, in which case the list of updated counters is returned.  An operation is
+/// one of
+///
+/// * `Incr` - add `Incr` to the element after the key
+/// * `{Pos, Incr}` - add `Incr` to the element at `Pos`
+/// * `{Pos, Incr, Threshold, SetValue}` - add `Incr` to the element at `Pos`, but use `SetValue`
+///   instead if the sum goes above (for a non-negative `Incr`) or below (for a negative `Incr`)
+///   `Threshold`
+#[native_implemented_function(update_counter/3)]
+pub fn native(
+    process: &Process,
+    table: Term,
+    key: Term,
+    update_op: Term,
+) -> exception::Result<Term> {
+    let arc_table = term_to_writable_table(process, table)?;
+
+    match arc_table.r#type() {
+        Type::Set | Type::OrderedSet => (),
+        Type::Bag | Type::DuplicateBag => {
+            return Err(anyhow!("table ({}) is not a set or ordered_set", table).into())
+        }
+    }
+
+    let (operation_vec, is_list) = match update_op.decode().unwrap() {
+        TypedTerm::Nil => (Vec::new(), true),
+        TypedTerm::List(cons) => {
+            let mut operation_vec = Vec::new();
+
+            for result in cons.into_iter() {
+                let operation_term = result
+                    .map_err(|_| ImproperListError)
+                    .with_context(|| format!("update_op ({}) is improper", update_op))?;
+                operation_vec.push(Operation::from_term(&arc_table, operation_term)?);
+            }
+
+            (operation_vec, true)
+        }
+        _ => (vec![Operation::from_term(&arc_table, update_op)?], false),
+    };
+
+    let mut counter_vec = Vec::with_capacity(operation_vec.len());
+
+    let updated = arc_table.update(key, |object| -> exception::Result<Term> {
+        let tuple: Boxed<Tuple> = object.try_into().unwrap();
+        let mut element_vec = tuple.elements().to_vec();
+
+        for operation in &operation_vec {
+            let counter = operation.apply(process, object, &element_vec)?;
+            element_vec[operation.position - 1] = counter;
+            counter_vec.push(counter);
+        }
+
+        process.tuple_from_slice(&element_vec).map_err(From::from)
+    })?;
+
+    if !updated {
+        return Err(anyhow!("table ({}) does not have key ({})", table, key).into());
+    }
+
+    if is_list {
+        process.list_from_slice(&counter_vec).map_err(From::from)
+    } else {
+        Ok(counter_vec[0])
+    }
+}
+
+struct Operation {
+    /// 1-based
+    position: usize,
+    increment: Term,
+    threshold_set_value: Option<(Term, Term)>,
+}
+
+impl Operation {
+    fn apply(
+        &self,
+        process: &Process,
+        object: Term,
+        element_vec: &[Term],
+    ) -> exception::Result<Term> {
+        let counter = element_vec
+            .get(self.position - 1)
+            .copied()
+            .with_context(|| {
+                format!(
+                    "object ({}) does not have an element at position ({})",
+                    object, self.position
+                )
+            })?;
+
+        if !counter.is_integer() {
+            return Err(anyhow!(
+                "object ({}) element at position ({}) is not an integer",
+                object,
+                self.position
+            )
+            .into());
+        }
+
+        let sum = add_2::native(process, counter, self.increment)?;
+
+        match self.threshold_set_value {
+            Some((threshold, set_value)) => {
+                let zero = process.integer(0)?;
+                let increment_is_negative = self.increment < zero;
+
+                if (!increment_is_negative && threshold < sum)
+                    || (increment_is_negative && sum < threshold)
+                {
+                    Ok(set_value)
+                } else {
+                    Ok(sum)
+                }
+            }
+            None => Ok(sum),
+        }
+    }
+
+    fn from_term(table: &Table, operation: Term) -> exception::Result<Self> {
+        let keypos = table.keypos();
+
+        let (position, increment, threshold_set_value) = match operation.decode().unwrap() {
+            TypedTerm::Tuple(tuple) => {
+                let (position, increment, threshold_set_value) = match tuple.elements() {
+                    [position, increment] => (*position, *increment, None),
+                    [position, increment, threshold, set_value] => {
+                        (*position, *increment, Some((*threshold, *set_value)))
+                    }
+                    _ => {
+                        return Err(anyhow!(
+                            "update_op ({}) is not Incr, {{Pos, Incr}}, or {{Pos, Incr, Threshold, SetValue}}",
+                            operation
+                        )
+                        .into())
+                    }
+                };
+
+                let position: usize = position
+                    .try_into()
+                    .ok()
+                    .filter(|position| (0 < *position) && (*position != keypos))
+                    .with_context(|| {
+                        format!(
+                            "update_op ({}) position is not a positive integer other than keypos ({})",
+                            operation, keypos
+                        )
+                    })?;
+
+                (position, increment, threshold_set_value)
+            }
+            _ => (keypos + 1, operation, None),
+        };
+
+        let mut terms = vec![increment];
+
+        if let Some((threshold, set_value)) = threshold_set_value {
+            terms.push(threshold);
+            terms.push(set_value);
+        }
+
+        for term in terms {
+            if !term.is_integer() {
+                return Err(anyhow!(
+                    "update_op ({}) Incr, Threshold, and SetValue must be integers",
+                    operation
+                )
+                .into());
+            }
+        }
+
+        Ok(Self {
+            position,
+            increment,
+            threshold_set_value,
+        })
+    }
+}
@@ -0,0 +1,151 @@
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::update_counter_3::native;
+use crate::ets::{insert_2, lookup_2, new_2};
+use crate::test::with_process;
+
+#[test]
+fn with_increment_updates_element_after_key() {
+    with_process(|process| {
+        let table = counter_table(process, &[Atom::str_to_term("set")]);
+        let key = Atom::str_to_term("key");
+
+        assert_eq!(
+            native(process, table, key, process.integer(5).unwrap()),
+            Ok(process.integer(15).unwrap())
+        );
+        assert_eq!(
+            lookup_2::native(process, table, key),
+            Ok(process
+                .list_from_slice(&[process
+                    .tuple_from_slice(&[
+                        key,
+                        process.integer(15).unwrap(),
+                        process.integer(20).unwrap()
+                    ])
+                    .unwrap()])
+                .unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_list_of_operations_returns_list_of_counters() {
+    with_process(|process| {
+        let table = counter_table(process, &[Atom::str_to_term("ordered_set")]);
+        let key = Atom::str_to_term("key");
+        let operations = process
+            .list_from_slice(&[
+                operation(process, 2, 1),
+                operation(process, 3, -1),
+                operation(process, 2, 1),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            native(process, table, key, operations),
+            Ok(process
+                .list_from_slice(&[
+                    process.integer(11).unwrap(),
+                    process.integer(19).unwrap(),
+                    process.integer(12).unwrap(),
+                ])
+                .unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_threshold_exceeded_sets_value() {
+    with_process(|process| {
+        let table = counter_table(process, &[Atom::str_to_term("set")]);
+        let key = Atom::str_to_term("key");
+        let operation = process
+            .tuple_from_slice(&[
+                process.integer(2).unwrap(),
+                process.integer(5).unwrap(),
+                process.integer(12).unwrap(),
+                process.integer(0).unwrap(),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            native(process, table, key, operation),
+            Ok(process.integer(0).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_keypos_errors_badarg() {
+    with_process(|process| {
+        let table = counter_table(process, &[Atom::str_to_term("set")]);
+        let key = Atom::str_to_term("key");
+
+        assert_badarg!(
+            native(process, table, key, operation(process, 1, 1)),
+            "other than keypos"
+        );
+    });
+}
+
+#[test]
+fn without_key_errors_badarg() {
+    with_process(|process| {
+        let table = counter_table(process, &[Atom::str_to_term("set")]);
+
+        assert_badarg!(
+            native(
+                process,
+                table,
+                Atom::str_to_term("other_key"),
+                process.integer(1).unwrap()
+            ),
+            "does not have key"
+        );
+    });
+}
+
+#[test]
+fn with_bag_errors_badarg() {
+    with_process(|process| {
+        let table = counter_table(process, &[Atom::str_to_term("bag")]);
+
+        assert_badarg!(
+            native(
+                process,
+                table,
+                Atom::str_to_term("key"),
+                process.integer(1).unwrap()
+            ),
+            "is not a set or ordered_set"
+        );
+    });
+}
+
+/// Table with the object `{key, 10, 20}`
+fn counter_table(process: &Process, options: &[Term]) -> Term {
+    let options = process.list_from_slice(options).unwrap();
+    let table = new_2::native(process, Atom::str_to_term("table"), options).unwrap();
+    let object = process
+        .tuple_from_slice(&[
+            Atom::str_to_term("key"),
+            process.integer(10).unwrap(),
+            process.integer(20).unwrap(),
+        ])
+        .unwrap();
+
+    assert_eq!(insert_2::native(process, table, object), Ok(true.into()));
+
+    table
+}
+
+fn operation(process: &Process, position: isize, increment: isize) -> Term {
+    process
+        .tuple_from_slice(&[
+            process.integer(position).unwrap(),
+            process.integer(increment).unwrap(),
+        ])
+        .unwrap()
+}
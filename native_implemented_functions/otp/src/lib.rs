@@ -6,10 +6,16 @@
 #[macro_use]
 mod macros;
 
+pub mod atomics;
 pub mod binary;
+pub mod counters;
+pub mod crypto;
 pub mod erlang;
+pub mod ets;
 pub mod lists;
 pub mod maps;
+pub mod persistent_term;
+pub mod rand;
 pub mod timer;
 
 #[cfg(test)]
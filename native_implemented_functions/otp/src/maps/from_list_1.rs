@@ -13,8 +13,8 @@ use native_implemented_function::native_implemented_function;
 
 #[native_implemented_function(from_list/1)]
 pub fn native(process: &Process, list: Term) -> exception::Result<Term> {
-    let hash_map = Map::from_list(list)?;
-    let map = process.map_from_hash_map(hash_map)?;
+    let entries = Map::from_list(list)?;
+    let map = process.map_from_slice(&entries)?;
 
     Ok(map)
 }
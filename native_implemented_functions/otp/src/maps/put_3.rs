@@ -14,9 +14,10 @@ use native_implemented_function::native_implemented_function;
 #[native_implemented_function(put/3)]
 pub fn native(process: &Process, key: Term, value: Term, map: Term) -> exception::Result<Term> {
     let boxed_map = term_try_into_map_or_badmap!(process, map)?;
+    let mut heap = process.acquire_heap();
 
-    match boxed_map.put(key, value) {
-        Some(hash_map) => Ok(process.map_from_hash_map(hash_map)?),
+    match boxed_map.put(&mut heap, key, value)? {
+        Some(put_map) => Ok(put_map.into()),
         None => Ok(map),
     }
 }
@@ -11,12 +11,13 @@ use liblumen_alloc::erts::term::prelude::*;
 
 use native_implemented_function::native_implemented_function;
 
-#[native_implemented_function(+/2)]
+#[native_implemented_function(remove/2)]
 pub fn native(process: &Process, key: Term, map: Term) -> exception::Result<Term> {
     let boxed_map = term_try_into_map_or_badmap!(process, map)?;
+    let mut heap = process.acquire_heap();
 
-    match boxed_map.remove(key) {
-        Some(hash_map) => Ok(process.map_from_hash_map(hash_map)?),
+    match boxed_map.remove(&mut heap, key)? {
+        Some(removed_map) => Ok(removed_map.into()),
         None => Ok(map),
     }
 }
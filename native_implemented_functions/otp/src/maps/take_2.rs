@@ -7,6 +7,7 @@ mod test;
 
 use liblumen_alloc::atom;
 use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::alloc::TermAlloc;
 use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 
@@ -16,11 +17,10 @@ use native_implemented_function::native_implemented_function;
 pub fn native(process: &Process, key: Term, map: Term) -> exception::Result<Term> {
     let boxed_map = term_try_into_map_or_badmap!(process, map)?;
 
-    let result = match boxed_map.take(key) {
-        Some((value, hash_map)) => {
-            let map = process.map_from_hash_map(hash_map)?;
-            process.tuple_from_slice(&[value, map])?
-        }
+    let mut heap = process.acquire_heap();
+
+    let result = match boxed_map.take(&mut heap, key)? {
+        Some((value, taken_map)) => heap.tuple_from_slice(&[value, taken_map.into()])?.into(),
         None => atom!("error"),
     };
 
@@ -16,9 +16,10 @@ use native_implemented_function::native_implemented_function;
 #[native_implemented_function(update/3)]
 pub fn native(process: &Process, key: Term, value: Term, map: Term) -> exception::Result<Term> {
     let boxed_map = term_try_into_map_or_badmap!(process, map)?;
+    let result = boxed_map.update(&mut process.acquire_heap(), key, value)?;
 
-    match boxed_map.update(key, value) {
-        Some(hash_map) => Ok(process.map_from_hash_map(hash_map)?),
+    match result {
+        Some(updated_map) => Ok(updated_map.into()),
         None => Err(badkey(
             process,
             key,
@@ -0,0 +1,25 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::persistent_term::erase_1::native;
+use crate::persistent_term::{get_2, put_2};
+use crate::test::with_process;
+
+#[test]
+fn without_key_returns_false() {
+    let key = Atom::str_to_term("erase_1_without_key");
+
+    assert_eq!(native(key), Ok(false.into()));
+}
+
+#[test]
+fn with_key_returns_true_and_erases_value() {
+    with_process(|process| {
+        let key = Atom::str_to_term("erase_1_with_key");
+        let value = process.tuple_from_slice(&[key]).unwrap();
+
+        put_2::native(key, value).unwrap();
+
+        assert_eq!(native(key), Ok(true.into()));
+        assert_eq!(get_2::native(process, key, Term::NIL), Ok(Term::NIL));
+    });
+}
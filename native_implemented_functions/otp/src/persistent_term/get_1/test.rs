@@ -0,0 +1,36 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::persistent_term::get_1::native;
+use crate::persistent_term::put_2;
+use crate::test::with_process;
+
+#[test]
+fn without_key_errors_badarg() {
+    with_process(|process| {
+        let key = Atom::str_to_term("get_1_without_key");
+
+        assert_badarg!(
+            native(process, key),
+            "key (get_1_without_key) does not have a persistent term"
+        );
+    });
+}
+
+#[test]
+fn with_key_returns_value_copied_to_the_process_heap() {
+    with_process(|process| {
+        let key = Atom::str_to_term("get_1_with_key");
+        let value = process.binary_from_str("value").unwrap();
+
+        put_2::native(key, value).unwrap();
+
+        let read_value = native(process, key).unwrap();
+
+        assert_eq!(read_value, value);
+
+        let read_value_ptr: *mut Term = read_value.dyn_cast();
+        let value_ptr: *mut Term = value.dyn_cast();
+        assert!(process.is_owner(read_value_ptr));
+        assert_ne!(read_value_ptr, value_ptr);
+    });
+}
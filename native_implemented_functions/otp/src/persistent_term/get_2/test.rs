@@ -0,0 +1,28 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::persistent_term::get_2::native;
+use crate::persistent_term::put_2;
+use crate::test::with_process;
+
+#[test]
+fn without_key_returns_default() {
+    with_process(|process| {
+        let key = Atom::str_to_term("get_2_without_key");
+        let default = atom!("default");
+
+        assert_eq!(native(process, key, default), Ok(default));
+    });
+}
+
+#[test]
+fn with_key_returns_value() {
+    with_process(|process| {
+        let key = Atom::str_to_term("get_2_with_key");
+        let value = process.integer(SmallInteger::MAX_VALUE + 1).unwrap();
+
+        put_2::native(key, value).unwrap();
+
+        assert_eq!(native(process, key, atom!("default")), Ok(value));
+    });
+}
@@ -0,0 +1,25 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use lumen_rt_core::persistent_term;
+
+#[native_implemented_function(info/0)]
+pub fn native(process: &Process) -> exception::Result<Term> {
+    let count = process.integer(persistent_term::count())?;
+    let memory = process.integer(persistent_term::memory())?;
+
+    process
+        .map_from_slice(&[(atom!("count"), count), (atom!("memory"), memory)])
+        .map_err(From::from)
+}
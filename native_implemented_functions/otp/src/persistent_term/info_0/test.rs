@@ -0,0 +1,28 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::persistent_term::info_0::native;
+use crate::persistent_term::put_2;
+use crate::test::with_process;
+
+#[test]
+fn returns_map_with_count_and_memory() {
+    with_process(|process| {
+        put_2::native(
+            Atom::str_to_term("info_0_key"),
+            process.tuple_from_slice(&[atom!("value")]).unwrap(),
+        )
+        .unwrap();
+
+        let info = native(process).unwrap();
+        let info_map: Boxed<Map> = info.try_into().unwrap();
+
+        let count: usize = info_map.get(atom!("count")).unwrap().try_into().unwrap();
+        let memory: usize = info_map.get(atom!("memory")).unwrap().try_into().unwrap();
+
+        assert!(0 < count);
+        assert!(0 < memory);
+    });
+}
@@ -0,0 +1,38 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::persistent_term::get_1;
+use crate::persistent_term::put_2::native;
+use crate::test::with_process;
+
+#[test]
+fn without_key_stores_value() {
+    with_process(|process| {
+        let key = Atom::str_to_term("put_2_without_key");
+        let value = process
+            .tuple_from_slice(&[atom!("value"), process.integer(1).unwrap()])
+            .unwrap();
+
+        assert_eq!(native(key, value), Ok(atom!("ok")));
+
+        assert_eq!(get_1::native(process, key), Ok(value));
+    });
+}
+
+#[test]
+fn with_key_replaces_value() {
+    with_process(|process| {
+        let key = Atom::str_to_term("put_2_with_key");
+        let old_value = process.list_from_slice(&[atom!("old")]).unwrap();
+        let new_value = process.list_from_slice(&[atom!("new")]).unwrap();
+
+        assert_eq!(native(key, old_value), Ok(atom!("ok")));
+        let read_old_value = get_1::native(process, key).unwrap();
+
+        assert_eq!(native(key, new_value), Ok(atom!("ok")));
+
+        assert_eq!(get_1::native(process, key), Ok(new_value));
+        // The value that was read is a copy, so freeing the replaced value does not affect it
+        assert_eq!(read_old_value, old_value);
+    });
+}
@@ -0,0 +1,118 @@
+//! Mirrors [rand](http://erlang.org/doc/man/rand.html) module
+
+pub mod bytes_1;
+pub mod export_seed_0;
+pub mod jump_0;
+pub mod jump_1;
+pub mod normal_0;
+pub mod normal_2;
+pub mod seed_1;
+pub mod seed_2;
+pub mod seed_s_1;
+pub mod seed_s_2;
+pub mod uniform_0;
+pub mod uniform_1;
+pub mod uniform_real_0;
+pub mod uniform_s_1;
+pub mod uniform_s_2;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+use num_bigint::BigInt;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use lumen_rt_full::system::random::{seed_key, Algorithm, Seed, State};
+
+fn module() -> Atom {
+    Atom::try_from_str("rand").unwrap()
+}
+
+/// The state from an algorithm, which is seeded with a non-constant seed, or from a state or
+/// exported state
+fn seed_s(process: &Process, alg_or_state: Term) -> exception::Result<State> {
+    if alg_or_state.is_atom() {
+        let algorithm: Algorithm = alg_or_state.try_into()?;
+
+        Ok(State::seed(algorithm, Seed::non_constant(process)))
+    } else {
+        alg_or_state
+            .try_into()
+            .with_context(|| {
+                format!(
+                    "alg_or_state ({}) is neither an algorithm nor a state",
+                    alg_or_state
+                )
+            })
+            .map_err(From::from)
+    }
+}
+
+fn seed_s_with_seed(alg: Term, seed: Term) -> exception::Result<State> {
+    let algorithm: Algorithm = alg.try_into()?;
+    let seed_seed: Seed = seed.try_into()?;
+
+    Ok(State::seed(algorithm, seed_seed))
+}
+
+/// The implicit state, which is seeded with the default algorithm if the process has none
+fn seed_get(process: &Process) -> exception::Result<State> {
+    let state = process.get_value_from_key(seed_key());
+
+    if state == atom!("undefined") {
+        Ok(State::seed(Algorithm::DEFAULT, Seed::non_constant(process)))
+    } else {
+        state
+            .try_into()
+            .with_context(|| format!("rand_seed ({}) in process dictionary", state))
+            .map_err(From::from)
+    }
+}
+
+/// Stores `state` as the implicit state and returns it as a term
+fn seed_put(process: &Process, state: &State) -> exception::Result<Term> {
+    let state_term = state.to_term(process)?;
+    process.put(seed_key(), state_term)?;
+
+    Ok(state_term)
+}
+
+/// `{Value, NewState}`, as returned by the functions with explicit state
+fn value_and_state(process: &Process, value: Term, state: &State) -> exception::Result<Term> {
+    let state_term = state.to_term(process)?;
+
+    process
+        .tuple_from_slice(&[value, state_term])
+        .map_err(From::from)
+}
+
+fn term_try_into_state(state: Term) -> exception::Result<State> {
+    state
+        .try_into()
+        .with_context(|| format!("state ({}) is not a rand state", state))
+        .map_err(From::from)
+}
+
+fn term_try_into_n(n: Term) -> exception::Result<BigInt> {
+    let n_big_int: BigInt = match n.decode()? {
+        TypedTerm::SmallInteger(small_integer) => {
+            let n_isize: isize = small_integer.into();
+
+            n_isize.into()
+        }
+        TypedTerm::BigInteger(big_integer) => big_integer.into(),
+        _ => BigInt::from(0),
+    };
+
+    if BigInt::from(1) <= n_big_int {
+        Ok(n_big_int)
+    } else {
+        Err(TypeError)
+            .with_context(|| format!("n ({}) is not a positive integer", n))
+            .map_err(From::from)
+    }
+}
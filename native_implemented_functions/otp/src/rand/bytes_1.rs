@@ -0,0 +1,33 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use lumen_rt_core::context::*;
+
+use crate::rand::{seed_get, seed_put};
+
+#[native_implemented_function(bytes/1)]
+pub fn native(process: &Process, n: Term) -> exception::Result<Term> {
+    let n_usize: usize = n
+        .try_into()
+        .with_context(|| term_is_not_non_negative_integer("n", n))?;
+
+    let mut state = seed_get(process)?;
+    let bytes = state.bytes(n_usize);
+    seed_put(process, &state)?;
+
+    process.binary_from_bytes(&bytes).map_err(From::from)
+}
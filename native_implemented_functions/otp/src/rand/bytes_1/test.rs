@@ -0,0 +1,51 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::bytes_1::native;
+use crate::rand::{seed_1, seed_s_2};
+use crate::test::with_process;
+
+#[test]
+fn without_non_negative_integer_n_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            native(process, process.integer(-1).unwrap()),
+            "n (-1) is not a non-negative integer"
+        );
+    });
+}
+
+#[test]
+fn with_zero_returns_empty_binary() {
+    with_process(|process| {
+        assert_eq!(
+            native(process, process.integer(0).unwrap()),
+            Ok(process.binary_from_bytes(&[]).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_seed_returns_same_bytes_as_otp() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exrop"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+        seed_1::native(process, state).unwrap();
+
+        assert_eq!(
+            native(process, process.integer(10).unwrap()),
+            Ok(process
+                .binary_from_bytes(&[0xbf, 0xf4, 0xd3, 0xb3, 0x83, 0x88, 0x3d, 0x3f, 0x18, 0x69])
+                .unwrap())
+        );
+    });
+}
@@ -0,0 +1,29 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use lumen_rt_full::system::random::{seed_key, State};
+
+/// The implicit state as `{Alg, AlgState}`, or `undefined` if the process has not been seeded
+#[native_implemented_function(export_seed/0)]
+pub fn native(process: &Process) -> exception::Result<Term> {
+    let state = process.get_value_from_key(seed_key());
+    let result_state: Result<State, _> = state.try_into();
+
+    match result_state {
+        Ok(state_state) => state_state.to_export_term(process).map_err(From::from),
+        Err(_) => Ok(atom!("undefined")),
+    }
+}
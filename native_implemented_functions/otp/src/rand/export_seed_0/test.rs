@@ -0,0 +1,46 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::export_seed_0::native;
+use crate::rand::{seed_1, seed_s_2};
+use crate::test::with_process;
+
+#[test]
+fn without_seed_returns_undefined() {
+    with_process(|process| {
+        assert_eq!(native(process), Ok(atom!("undefined")));
+    });
+}
+
+#[test]
+fn with_seed_returns_algorithm_and_algorithm_state() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exsp"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+        seed_1::native(process, state).unwrap();
+
+        let alg_state = process
+            .cons(
+                process.integer(72022415603679006_u64).unwrap(),
+                process.integer(144185572652843231_u64).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            native(process),
+            Ok(process
+                .tuple_from_slice(&[atom!("exsp"), alg_state])
+                .unwrap())
+        );
+    });
+}
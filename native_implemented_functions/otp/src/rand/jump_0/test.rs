@@ -0,0 +1,32 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::jump_0::native;
+use crate::rand::{jump_1, seed_1, seed_s_2};
+use crate::test::with_process;
+
+#[test]
+fn with_seed_stores_jumped_state() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exrop"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+        seed_1::native(process, state).unwrap();
+
+        let jumped_state = native(process).unwrap();
+
+        assert_eq!(Ok(jumped_state), jump_1::native(process, state));
+        assert_eq!(
+            process.get_value_from_key(Atom::str_to_term("rand_seed")),
+            jumped_state
+        );
+    });
+}
@@ -0,0 +1,22 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::rand::term_try_into_state;
+
+#[native_implemented_function(jump/1)]
+pub fn native(process: &Process, state: Term) -> exception::Result<Term> {
+    let mut state_state = term_try_into_state(state)?;
+    state_state.jump();
+
+    state_state.to_term(process).map_err(From::from)
+}
@@ -0,0 +1,60 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::jump_1::native;
+use crate::rand::{seed_s_2, uniform_s_1};
+use crate::test::with_process;
+
+#[test]
+fn with_state_returns_different_state_for_same_algorithm() {
+    with_process(|process| {
+        for alg in &["exrop", "exs1024s", "exsp"] {
+            let state = seed_s_2::native(
+                process,
+                Atom::str_to_term(alg),
+                process
+                    .tuple_from_slice(&[
+                        process.integer(1).unwrap(),
+                        process.integer(2).unwrap(),
+                        process.integer(3).unwrap(),
+                    ])
+                    .unwrap(),
+            )
+            .unwrap();
+
+            let jumped_state = native(process, state).unwrap();
+
+            assert_ne!(jumped_state, state);
+
+            let tuple: Boxed<Tuple> = state.try_into().unwrap();
+            let jumped_tuple: Boxed<Tuple> = jumped_state.try_into().unwrap();
+
+            assert_eq!(jumped_tuple[0], tuple[0]);
+        }
+    });
+}
+
+#[test]
+fn jumped_state_is_still_valid_state() {
+    with_process(|process| {
+        for alg in &["exrop", "exs1024s", "exsp"] {
+            let state = seed_s_2::native(
+                process,
+                Atom::str_to_term(alg),
+                process
+                    .tuple_from_slice(&[
+                        process.integer(1).unwrap(),
+                        process.integer(2).unwrap(),
+                        process.integer(3).unwrap(),
+                    ])
+                    .unwrap(),
+            )
+            .unwrap();
+
+            let jumped_state = native(process, state).unwrap();
+
+            assert!(uniform_s_1::native(process, jumped_state).is_ok());
+        }
+    });
+}
@@ -0,0 +1,69 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::normal_0::native;
+use crate::rand::{seed_1, seed_s_2};
+use crate::test::with_process;
+
+#[test]
+fn with_exrop_returns_same_floats_as_otp() {
+    returns_same_floats_as_otp(
+        "exrop",
+        &[
+            -0.8274681199562238,
+            0.16516407765801933,
+            2.3252137639207056,
+            0.21027351649844472,
+            0.09807437427395752,
+        ],
+    );
+}
+
+#[test]
+fn with_exs1024s_returns_same_floats_as_otp() {
+    returns_same_floats_as_otp(
+        "exs1024s",
+        &[
+            -0.12917763020993325,
+            -1.7960182421298452,
+            0.455867300284135,
+            0.5996343918415497,
+            -0.4904556218324746,
+        ],
+    );
+}
+
+#[test]
+fn with_exsp_returns_same_floats_as_otp() {
+    returns_same_floats_as_otp(
+        "exsp",
+        &[
+            -0.6384228541460415,
+            0.8977390995501492,
+            0.03248458887526535,
+            -0.11313393452045845,
+            -1.753910038020854,
+        ],
+    );
+}
+
+fn returns_same_floats_as_otp(alg: &str, expected: &[f64]) {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term(alg),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+        seed_1::native(process, state).unwrap();
+
+        for expected_f64 in expected {
+            assert_eq!(native(process), Ok(process.float(*expected_f64).unwrap()));
+        }
+    });
+}
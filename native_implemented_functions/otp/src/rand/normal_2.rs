@@ -0,0 +1,37 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::rand::{seed_get, seed_put};
+
+/// A float from the normal distribution N(`mean`, `variance`)
+#[native_implemented_function(normal/2)]
+pub fn native(process: &Process, mean: Term, variance: Term) -> exception::Result<Term> {
+    let mean_f64: f64 = mean
+        .try_into()
+        .with_context(|| format!("mean ({}) is not a number", mean))?;
+    let variance_f64: f64 = variance
+        .try_into()
+        .ok()
+        .filter(|variance_f64| 0.0 < *variance_f64)
+        .with_context(|| format!("variance ({}) is not a positive number", variance))?;
+
+    let mut state = seed_get(process)?;
+    let x = mean_f64 + variance_f64.sqrt() * state.normal();
+    seed_put(process, &state)?;
+
+    process.float(x).map_err(From::from)
+}
@@ -0,0 +1,58 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::normal_2::native;
+use crate::rand::{seed_1, seed_s_2};
+use crate::test::with_process;
+
+#[test]
+fn without_number_mean_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            native(process, atom!("mean"), process.float(1.0).unwrap()),
+            "mean (mean) is not a number"
+        );
+    });
+}
+
+#[test]
+fn without_positive_variance_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            native(
+                process,
+                process.integer(0).unwrap(),
+                process.float(0.0).unwrap()
+            ),
+            "is not a positive number"
+        );
+    });
+}
+
+#[test]
+fn with_seed_scales_and_shifts_standard_normal() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exrop"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+        seed_1::native(process, state).unwrap();
+
+        assert_eq!(
+            native(
+                process,
+                process.integer(10).unwrap(),
+                process.integer(4).unwrap()
+            ),
+            Ok(process.float(10.0 + 2.0 * -0.8274681199562238).unwrap())
+        );
+    });
+}
@@ -0,0 +1,40 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::seed_1::native;
+use crate::rand::{seed_s_2, uniform_0};
+use crate::test::with_process;
+
+#[test]
+fn with_algorithm_seeds_differently_each_time() {
+    with_process(|process| {
+        let first_state = native(process, atom!("exrop")).unwrap();
+        let second_state = native(process, atom!("exrop")).unwrap();
+
+        assert_ne!(first_state, second_state);
+    });
+}
+
+#[test]
+fn with_state_stores_it_for_implicit_state_functions() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exsp"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(native(process, state), Ok(state));
+        assert_eq!(
+            uniform_0::native(process),
+            Ok(process.float(0.40502929729990744).unwrap())
+        );
+    });
+}
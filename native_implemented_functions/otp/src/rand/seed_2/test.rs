@@ -0,0 +1,28 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::seed_2::native;
+use crate::rand::{seed_s_2, uniform_0};
+use crate::test::with_process;
+
+#[test]
+fn returns_state_and_stores_it_for_implicit_state_functions() {
+    with_process(|process| {
+        let seed = process
+            .tuple_from_slice(&[
+                process.integer(1).unwrap(),
+                process.integer(2).unwrap(),
+                process.integer(3).unwrap(),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            native(process, atom!("exrop"), seed),
+            seed_s_2::native(process, atom!("exrop"), seed)
+        );
+        assert_eq!(
+            uniform_0::native(process),
+            Ok(process.float(0.7498295129076106).unwrap())
+        );
+    });
+}
@@ -0,0 +1,21 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::rand::seed_s;
+
+#[native_implemented_function(seed_s/1)]
+pub fn native(process: &Process, alg_or_state: Term) -> exception::Result<Term> {
+    let state = seed_s(process, alg_or_state)?;
+
+    state.to_term(process).map_err(From::from)
+}
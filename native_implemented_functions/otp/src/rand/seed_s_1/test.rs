@@ -0,0 +1,72 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::seed_s_1::native;
+use crate::rand::{export_seed_0, seed_1, seed_s_2};
+use crate::test::with_process;
+
+#[test]
+fn with_algorithm_returns_state_for_algorithm() {
+    with_process(|process| {
+        let state = native(process, atom!("exsp")).unwrap();
+        let tuple: Boxed<Tuple> = state.try_into().unwrap();
+        let handler: Boxed<Map> = tuple[0].try_into().unwrap();
+
+        assert_eq!(handler.get(atom!("type")), Some(atom!("exsp")));
+    });
+}
+
+#[test]
+fn with_state_returns_state() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exs1024s"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(native(process, state), Ok(state));
+    });
+}
+
+#[test]
+fn with_exported_state_returns_state() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exs1024s"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+
+        seed_1::native(process, state).unwrap();
+        let exported_state = export_seed_0::native(process).unwrap();
+
+        assert_eq!(native(process, exported_state), Ok(state));
+    });
+}
+
+#[test]
+fn without_algorithm_or_state_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            native(process, process.integer(1).unwrap()),
+            "alg_or_state (1) is neither an algorithm nor a state"
+        );
+    });
+}
@@ -0,0 +1,91 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::seed_s_2::native;
+use crate::test::with_process;
+
+#[test]
+fn without_supported_algorithm_errors_badarg() {
+    with_process(|process| {
+        let seed = process
+            .tuple_from_slice(&[
+                process.integer(1).unwrap(),
+                process.integer(2).unwrap(),
+                process.integer(3).unwrap(),
+            ])
+            .unwrap();
+
+        assert_badarg!(
+            native(process, Atom::str_to_term("exs64"), seed),
+            "supported algorithms are exrop, exs1024s, and exsp"
+        );
+    });
+}
+
+#[test]
+fn without_three_tuple_seed_errors_badarg() {
+    with_process(|process| {
+        let seed = process
+            .tuple_from_slice(&[process.integer(1).unwrap(), process.integer(2).unwrap()])
+            .unwrap();
+
+        assert_badarg!(
+            native(process, atom!("exrop"), seed),
+            "seed must be a tuple of 3 integers"
+        );
+    });
+}
+
+#[test]
+fn with_exrop_returns_algorithm_handler_and_improper_list() {
+    with_process(|process| {
+        let seed = process
+            .tuple_from_slice(&[
+                process.integer(1).unwrap(),
+                process.integer(2).unwrap(),
+                process.integer(3).unwrap(),
+            ])
+            .unwrap();
+
+        let handler = process
+            .map_from_slice(&[
+                (atom!("type"), atom!("exrop")),
+                (atom!("bits"), process.integer(58).unwrap()),
+                (atom!("weak_low_bits"), process.integer(1).unwrap()),
+            ])
+            .unwrap();
+        let alg_state = process
+            .cons(
+                process.integer(216142952727055094_u64).unwrap(),
+                process.integer(288211065979672063_u64).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            native(process, atom!("exrop"), seed),
+            Ok(process.tuple_from_slice(&[handler, alg_state]).unwrap())
+        );
+    });
+}
+
+#[test]
+fn with_same_seed_returns_same_state() {
+    with_process(|process| {
+        let seed = process
+            .tuple_from_slice(&[
+                process.integer(1).unwrap(),
+                process.integer(2).unwrap(),
+                process.integer(3).unwrap(),
+            ])
+            .unwrap();
+
+        for alg in &["exrop", "exs1024s", "exsp"] {
+            let alg_term = Atom::str_to_term(alg);
+
+            assert_eq!(
+                native(process, alg_term, seed),
+                native(process, alg_term, seed)
+            );
+        }
+    });
+}
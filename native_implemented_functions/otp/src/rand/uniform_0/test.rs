@@ -0,0 +1,46 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::uniform_0::native;
+use crate::rand::{seed_1, seed_s_2};
+use crate::test::with_process;
+
+#[test]
+fn without_seed_seeds_with_default_algorithm() {
+    with_process(|process| {
+        let f: f64 = native(process).unwrap().try_into().unwrap();
+
+        assert!(0.0 <= f && f < 1.0);
+
+        let state = process.get_value_from_key(Atom::str_to_term("rand_seed"));
+        let tuple: Boxed<Tuple> = state.try_into().unwrap();
+        let handler: Boxed<Map> = tuple[0].try_into().unwrap();
+
+        assert_eq!(handler.get(atom!("type")), Some(atom!("exrop")));
+    });
+}
+
+#[test]
+fn with_seed_returns_same_floats_as_otp() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exs1024s"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+        seed_1::native(process, state).unwrap();
+
+        for expected in &[0.06907625299228148, 0.9812752738326551, 0.2854748458370905] {
+            assert_eq!(native(process), Ok(process.float(*expected).unwrap()));
+        }
+    });
+}
@@ -0,0 +1,70 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::uniform_1::native;
+use crate::rand::{seed_1, seed_s_2};
+use crate::test::with_process;
+
+#[test]
+fn without_positive_integer_n_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            native(process, process.integer(-1).unwrap()),
+            "n (-1) is not a positive integer"
+        );
+    });
+}
+
+#[test]
+fn with_seed_returns_same_integers_as_otp() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exsp"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+        seed_1::native(process, state).unwrap();
+        let n = process.integer(10).unwrap();
+
+        for expected in &[4, 3, 8, 1, 6] {
+            assert_eq!(native(process, n), Ok(process.integer(*expected).unwrap()));
+        }
+    });
+}
+
+#[test]
+fn with_big_n_returns_same_integers_as_otp() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exsp"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+        seed_1::native(process, state).unwrap();
+        let n = process.integer((1_u128 << 100) + 7).unwrap();
+
+        assert_eq!(
+            native(process, n),
+            Ok(process.integer(17649965340696729706754741_u128).unwrap())
+        );
+        assert_eq!(
+            native(process, n),
+            Ok(process
+                .integer(874160759456579077774889847970_u128)
+                .unwrap())
+        );
+    });
+}
@@ -0,0 +1,23 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::rand::{seed_get, seed_put};
+
+#[native_implemented_function(uniform_real/0)]
+pub fn native(process: &Process) -> exception::Result<Term> {
+    let mut state = seed_get(process)?;
+    let x = state.uniform_real();
+    seed_put(process, &state)?;
+
+    process.float(x).map_err(From::from)
+}
@@ -0,0 +1,32 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::uniform_real_0::native;
+use crate::rand::{seed_1, seed_s_2};
+use crate::test::with_process;
+
+#[test]
+fn with_seed_returns_same_floats_as_otp() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exrop"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+        seed_1::native(process, state).unwrap();
+
+        for expected in &[
+            0.7498295129076106,
+            0.061616554892445406,
+            0.15526988640515307,
+        ] {
+            assert_eq!(native(process), Ok(process.float(*expected).unwrap()));
+        }
+    });
+}
@@ -0,0 +1,71 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::seed_s_2;
+use crate::rand::uniform_s_1::native;
+use crate::test::with_process;
+
+#[test]
+fn with_exrop_returns_same_floats_as_otp() {
+    returns_same_floats_as_otp(
+        "exrop",
+        &[0.7498295129076106, 0.06161655489244533, 0.7924073127680873],
+    );
+}
+
+#[test]
+fn with_exs1024s_returns_same_floats_as_otp() {
+    returns_same_floats_as_otp(
+        "exs1024s",
+        &[0.06907625299228148, 0.9812752738326551, 0.2854748458370905],
+    );
+}
+
+#[test]
+fn with_exsp_returns_same_floats_as_otp() {
+    returns_same_floats_as_otp(
+        "exsp",
+        &[
+            0.40502929729990744,
+            0.45336720247823115,
+            0.040945116920410474,
+        ],
+    );
+}
+
+#[test]
+fn without_state_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            native(process, Atom::str_to_term("exrop")),
+            "state (exrop) is not a rand state"
+        );
+    });
+}
+
+fn returns_same_floats_as_otp(alg: &str, expected: &[f64]) {
+    with_process(|process| {
+        let mut state = seed_s_2::native(
+            process,
+            Atom::str_to_term(alg),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+
+        for expected_f64 in expected {
+            let value_and_state = native(process, state).unwrap();
+            let tuple: Boxed<Tuple> = value_and_state.try_into().unwrap();
+
+            assert_eq!(tuple[0], process.float(*expected_f64).unwrap());
+
+            state = tuple[1];
+        }
+    });
+}
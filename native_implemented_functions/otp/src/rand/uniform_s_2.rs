@@ -0,0 +1,23 @@
+// wasm32 proptest cannot be compiled at the same time as non-wasm32 proptest, so disable tests that
+// use proptest completely for wasm32
+//
+// See https://github.com/rust-lang/cargo/issues/4866
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use native_implemented_function::native_implemented_function;
+
+use crate::rand::{term_try_into_n, term_try_into_state, value_and_state};
+
+#[native_implemented_function(uniform_s/2)]
+pub fn native(process: &Process, n: Term, state: Term) -> exception::Result<Term> {
+    let n_big_int = term_try_into_n(n)?;
+    let mut state_state = term_try_into_state(state)?;
+    let x = process.integer(state_state.uniform_n(&n_big_int))?;
+
+    value_and_state(process, x, &state_state)
+}
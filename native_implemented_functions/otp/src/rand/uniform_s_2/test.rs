@@ -0,0 +1,127 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::rand::seed_s_2;
+use crate::rand::uniform_s_2::native;
+use crate::test::with_process;
+
+#[test]
+fn without_positive_integer_n_errors_badarg() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exrop"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_badarg!(
+            native(process, process.integer(0).unwrap(), state),
+            "n (0) is not a positive integer"
+        );
+    });
+}
+
+#[test]
+fn with_small_n_returns_same_integers_as_otp() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exrop"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            integers(process, 10_u128, state, 5),
+            vec![4, 5, 3, 6, 8]
+                .into_iter()
+                .map(|integer: u128| process.integer(integer).unwrap())
+                .collect::<Vec<Term>>()
+        );
+    });
+}
+
+#[test]
+fn with_power_of_two_big_n_returns_same_integers_as_otp() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exrop"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            integers(process, 1_u128 << 80, state, 2),
+            vec![
+                process.integer(133442035006720779941815_u128).unwrap(),
+                process.integer(727082209413649712788138_u128).unwrap(),
+            ]
+        );
+    });
+}
+
+#[test]
+fn with_big_n_returns_same_integers_as_otp() {
+    with_process(|process| {
+        let state = seed_s_2::native(
+            process,
+            Atom::str_to_term("exrop"),
+            process
+                .tuple_from_slice(&[
+                    process.integer(1).unwrap(),
+                    process.integer(2).unwrap(),
+                    process.integer(3).unwrap(),
+                ])
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            integers(process, (1_u128 << 100) + 7, state, 2),
+            vec![
+                process
+                    .integer(524146847497432090223088261089_u128)
+                    .unwrap(),
+                process
+                    .integer(764171200141353817385709399215_u128)
+                    .unwrap(),
+            ]
+        );
+    });
+}
+
+fn integers(process: &Process, n: u128, mut state: Term, count: usize) -> Vec<Term> {
+    let n_term = process.integer(n).unwrap();
+
+    (0..count)
+        .map(|_| {
+            let value_and_state = native(process, n_term, state).unwrap();
+            let tuple: Boxed<Tuple> = value_and_state.try_into().unwrap();
+            state = tuple[1];
+
+            tuple[0]
+        })
+        .collect()
+}
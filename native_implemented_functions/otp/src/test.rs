@@ -7,6 +7,8 @@ pub mod process_dictionary;
 //
 // See https://github.com/rust-lang/cargo/issues/4866
 #[cfg(all(not(target_arch = "wasm32"), test))]
+pub mod hash;
+#[cfg(all(not(target_arch = "wasm32"), test))]
 pub mod proptest;
 #[cfg(all(not(target_arch = "wasm32"), test))]
 pub mod strategy;
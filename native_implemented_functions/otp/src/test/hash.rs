@@ -0,0 +1,123 @@
+//! The cases shared by the tests of `erlang:phash/2` and `erlang:phash2/2`, which differ only in
+//! the hashes OTP gives and the range the hashes are in.
+
+use num_bigint::BigInt;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::test::with_process;
+
+pub type Native = fn(&Process, Term, Term) -> exception::Result<Term>;
+
+pub fn without_range_in_1_to_2_to_the_32_errors_badarg(native: Native) {
+    with_process(|process| {
+        let term = Atom::str_to_term("a");
+
+        assert_badarg!(
+            native(process, term, process.integer(0).unwrap()),
+            "range (0) is not an integer in 1..2^32"
+        );
+        assert_badarg!(
+            native(process, term, process.integer((1_u64 << 32) + 1).unwrap()),
+            "is not an integer in 1..2^32"
+        );
+        assert_badarg!(
+            native(process, term, Atom::str_to_term("range")),
+            "range (range) is not an integer in 1..2^32"
+        );
+    });
+}
+
+/// Asserts that `native` hashes `"abc"` to `in_1000` with a range of 1000, and to `in_1` with a
+/// range of 1
+pub fn with_range_returns_hash_in_range(native: Native, in_1000: u64, in_1: u64) {
+    with_process(|process| {
+        let term = process.charlist_from_str("abc").unwrap();
+
+        assert_eq!(
+            native(process, term, process.integer(1000).unwrap()),
+            Ok(process.integer(in_1000).unwrap())
+        );
+        assert_eq!(
+            native(process, term, process.integer(1).unwrap()),
+            Ok(process.integer(in_1).unwrap())
+        );
+    });
+}
+
+/// Asserts that `native` hashes each term, given as the Erlang expression for it, to the hash OTP
+/// gives it with a range of `1 bsl 32`
+pub fn returns_same_hash_as_otp(native: Native, hashes: &[(&str, u64)]) {
+    with_process(|process| {
+        let range = process.integer(1_u64 << 32).unwrap();
+
+        for (expression, hash) in hashes.iter().copied() {
+            assert_eq!(
+                native(process, term(process, expression), range),
+                Ok(process.integer(hash).unwrap()),
+                "hash of {}",
+                expression
+            );
+        }
+    });
+}
+
+fn term(process: &Process, expression: &str) -> Term {
+    let a = Atom::str_to_term("a");
+    let b = Atom::str_to_term("b");
+    let integer = |i: i64| process.integer(i).unwrap();
+
+    match expression {
+        "[]" => Term::NIL,
+        "a" => a,
+        "abc" => Atom::str_to_term("abc"),
+        "0" => integer(0),
+        "1" => integer(1),
+        "-1" => integer(-1),
+        "1 bsl 27" => integer(1 << 27),
+        "-(1 bsl 27) - 1" => integer(-(1 << 27) - 1),
+        "1 bsl 64" => process.integer(BigInt::from(1) << 64).unwrap(),
+        "-(1 bsl 100)" => process.integer(-(BigInt::from(1) << 100)).unwrap(),
+        "1.0" => process.float(1.0).unwrap(),
+        "0.0" => process.float(0.0).unwrap(),
+        "-0.0" => process.float(-0.0).unwrap(),
+        "\"abc\"" => process.charlist_from_str("abc").unwrap(),
+        "\"abcde\"" => process.charlist_from_str("abcde").unwrap(),
+        "[1,2,3,4,5]" => process
+            .list_from_slice(&[integer(1), integer(2), integer(3), integer(4), integer(5)])
+            .unwrap(),
+        "[256]" => process.list_from_slice(&[integer(256)]).unwrap(),
+        "[a,b]" => process.list_from_slice(&[a, b]).unwrap(),
+        "[a|b]" => process.cons(a, b).unwrap(),
+        "{}" => process.tuple_from_slice(&[]).unwrap(),
+        "{a,b}" => process.tuple_from_slice(&[a, b]).unwrap(),
+        "<<>>" => process.binary_from_bytes(&[]).unwrap(),
+        "<<1,2,3>>" => process.binary_from_bytes(&[1, 2, 3]).unwrap(),
+        "<<\"hello world!\">>" => process.binary_from_bytes(b"hello world!").unwrap(),
+        "<<1:1>>" => process
+            .subbinary_from_original(
+                process.binary_from_bytes(&[0b1000_0000]).unwrap(),
+                0,
+                0,
+                0,
+                1,
+            )
+            .unwrap(),
+        "<<1,5:3>>" => process
+            .subbinary_from_original(
+                process.binary_from_bytes(&[1, 0b1010_0000]).unwrap(),
+                0,
+                0,
+                1,
+                3,
+            )
+            .unwrap(),
+        "#{}" => process.map_from_slice(&[]).unwrap(),
+        "#{a => 1, b => 2}" => process
+            .map_from_slice(&[(a, integer(1)), (b, integer(2))])
+            .unwrap(),
+        _ => panic!("no term for {}", expression),
+    }
+}
@@ -0,0 +1,288 @@
+//! Arrays of 64-bit atomic integers, as created by `atomics:new/2`
+//!
+//! The array is not on any process heap: processes reference it through a `Resource` term, so
+//! that all processes holding the reference operate on the same integers.
+use std::convert::{TryFrom, TryInto};
+use std::mem;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use anyhow::*;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::context::*;
+use crate::proplist::TryPropListFromTermError;
+
+pub struct Atomics {
+    signed: bool,
+    values: Box<[AtomicI64]>,
+}
+
+impl Atomics {
+    pub fn new(arity: usize, options: Options) -> Self {
+        Self {
+            signed: options.signed,
+            values: new_values(arity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The smallest value an atomic can hold
+    pub fn min(&self) -> Integer {
+        if self.signed {
+            i64::min_value().into()
+        } else {
+            0_u64.into()
+        }
+    }
+
+    /// The largest value an atomic can hold
+    pub fn max(&self) -> Integer {
+        if self.signed {
+            i64::max_value().into()
+        } else {
+            u64::max_value().into()
+        }
+    }
+
+    /// The memory, in bytes, used by the array
+    pub fn memory(&self) -> usize {
+        mem::size_of::<Self>() + self.len() * mem::size_of::<AtomicI64>()
+    }
+
+    pub fn get(&self, index: Term) -> anyhow::Result<Integer> {
+        let atomic = self.atomic(index)?;
+
+        Ok(self.to_integer(atomic.load(Ordering::SeqCst)))
+    }
+
+    pub fn put(&self, index: Term, value: Term) -> anyhow::Result<()> {
+        let atomic = self.atomic(index)?;
+        let value_i64 = self.value_from_term("value", value)?;
+        atomic.store(value_i64, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    /// Adds `incr` to the atomic at `index`, wrapping around on overflow, and returns the result
+    pub fn add_get(&self, index: Term, incr: Term) -> anyhow::Result<Integer> {
+        let atomic = self.atomic(index)?;
+        let incr_i64 = incr_from_term(incr)?;
+        let previous = atomic.fetch_add(incr_i64, Ordering::SeqCst);
+
+        Ok(self.to_integer(previous.wrapping_add(incr_i64)))
+    }
+
+    /// Subtracts `decr` from the atomic at `index`, wrapping around on overflow, and returns the
+    /// result
+    pub fn sub_get(&self, index: Term, decr: Term) -> anyhow::Result<Integer> {
+        let atomic = self.atomic(index)?;
+        let decr_i64 = incr_from_term(decr)?;
+        let previous = atomic.fetch_sub(decr_i64, Ordering::SeqCst);
+
+        Ok(self.to_integer(previous.wrapping_sub(decr_i64)))
+    }
+
+    /// Sets the atomic at `index` to `desired` and returns its previous value
+    pub fn exchange(&self, index: Term, desired: Term) -> anyhow::Result<Integer> {
+        let atomic = self.atomic(index)?;
+        let desired_i64 = self.value_from_term("desired", desired)?;
+        let previous = atomic.swap(desired_i64, Ordering::SeqCst);
+
+        Ok(self.to_integer(previous))
+    }
+
+    /// Sets the atomic at `index` to `desired` if it is `expected`.  Returns `None` if it was set,
+    /// otherwise the actual value of the atomic.
+    pub fn compare_exchange(
+        &self,
+        index: Term,
+        expected: Term,
+        desired: Term,
+    ) -> anyhow::Result<Option<Integer>> {
+        let atomic = self.atomic(index)?;
+        let expected_i64 = self.value_from_term("expected", expected)?;
+        let desired_i64 = self.value_from_term("desired", desired)?;
+
+        match atomic.compare_exchange(
+            expected_i64,
+            desired_i64,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        ) {
+            Ok(_) => Ok(None),
+            Err(actual) => Ok(Some(self.to_integer(actual))),
+        }
+    }
+
+    // Private
+
+    fn atomic(&self, index: Term) -> anyhow::Result<&AtomicI64> {
+        let zero_based_index = try_into_zero_based_index(index, self.len())?;
+
+        Ok(&self.values[zero_based_index])
+    }
+
+    fn to_integer(&self, value: i64) -> Integer {
+        if self.signed {
+            value.into()
+        } else {
+            (value as u64).into()
+        }
+    }
+
+    fn value_from_term(&self, name: &str, term: Term) -> anyhow::Result<i64> {
+        let r#type = if self.signed {
+            "a signed 64-bit integer"
+        } else {
+            "an unsigned 64-bit integer"
+        };
+
+        match term_to_i128(term) {
+            Some(value) if self.min_i128() <= value && value <= self.max_i128() => Ok(value as i64),
+            _ => Err(TypeError).with_context(|| term_is_not_type(name, term, r#type)),
+        }
+    }
+
+    fn min_i128(&self) -> i128 {
+        if self.signed {
+            i64::min_value() as i128
+        } else {
+            0
+        }
+    }
+
+    fn max_i128(&self) -> i128 {
+        if self.signed {
+            i64::max_value() as i128
+        } else {
+            u64::max_value() as i128
+        }
+    }
+}
+
+/// The `Opts` argument to `atomics:new/2`
+pub struct Options {
+    /// Whether the atomics are interpreted as signed two's complement integers
+    pub signed: bool,
+}
+
+const SUPPORTED_OPTIONS_CONTEXT: &str = "supported options are {signed, bool}";
+
+impl Options {
+    fn put_option_term(&mut self, option: Term) -> Result<&Options, anyhow::Error> {
+        let tuple: Boxed<Tuple> = option.try_into().context(SUPPORTED_OPTIONS_CONTEXT)?;
+
+        if tuple.len() == 2 {
+            let atom: Atom = tuple[0]
+                .try_into()
+                .map_err(|_| TryPropListFromTermError::KeywordKeyType)?;
+
+            match atom.name() {
+                "signed" => {
+                    self.signed = tuple[1].try_into().context("signed value must be a bool")?;
+
+                    Ok(self)
+                }
+                name => Err(TryPropListFromTermError::KeywordKeyName(name))
+                    .context(SUPPORTED_OPTIONS_CONTEXT),
+            }
+        } else {
+            Err(TryPropListFromTermError::TupleNotPair.into())
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options { signed: true }
+    }
+}
+
+impl TryFrom<Term> for Options {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let mut options: Options = Default::default();
+        let mut options_term = term;
+
+        loop {
+            match options_term.decode().unwrap() {
+                TypedTerm::Nil => return Ok(options),
+                TypedTerm::List(cons) => {
+                    options
+                        .put_option_term(cons.head)
+                        .with_context(|| SUPPORTED_OPTIONS_CONTEXT)?;
+                    options_term = cons.tail;
+
+                    continue;
+                }
+                _ => return Err(ImproperListError).context(SUPPORTED_OPTIONS_CONTEXT),
+            }
+        }
+    }
+}
+
+/// Converts the `arity` or `size` argument of `atomics:new/2` and `counters:new/2`, which must be
+/// a positive integer
+pub fn term_try_into_len(name: &str, term: Term) -> anyhow::Result<usize> {
+    match term.try_into() {
+        Ok(len) if 0 < len => Ok(len),
+        _ => Err(TypeError).with_context(|| term_is_not_type(name, term, "a positive integer")),
+    }
+}
+
+// Private
+
+pub(crate) fn new_values(len: usize) -> Box<[AtomicI64]> {
+    (0..len).map(|_| AtomicI64::new(0)).collect()
+}
+
+pub(crate) fn try_into_zero_based_index(index: Term, len: usize) -> anyhow::Result<usize> {
+    match index.try_into() {
+        Ok(one_based_index) if 1 <= one_based_index && one_based_index <= len => {
+            Ok(one_based_index - 1)
+        }
+        _ => Err(TypeError).with_context(|| term_is_not_in_one_based_range(index, len)),
+    }
+}
+
+/// Increments wrap around, so any integer that fits in 64 bits, whether signed or unsigned, is
+/// allowed and only its low 64 bits are used.
+pub(crate) fn incr_from_term(incr: Term) -> anyhow::Result<i64> {
+    match term_to_i128(incr) {
+        Some(incr_i128)
+            if (i64::min_value() as i128) <= incr_i128
+                && incr_i128 <= (u64::max_value() as i128) =>
+        {
+            Ok(incr_i128 as i64)
+        }
+        _ => Err(TypeError)
+            .with_context(|| term_is_not_type("incr", incr, "an integer that fits in 64 bits")),
+    }
+}
+
+pub(crate) fn term_to_i128(term: Term) -> Option<i128> {
+    match term.decode() {
+        Ok(TypedTerm::SmallInteger(small_integer)) => {
+            let small_isize: isize = small_integer.into();
+
+            Some(small_isize as i128)
+        }
+        Ok(TypedTerm::BigInteger(big_integer)) => {
+            let big_int: BigInt = big_integer.into();
+
+            big_int.to_i128()
+        }
+        _ => None,
+    }
+}
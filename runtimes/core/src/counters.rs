@@ -0,0 +1,202 @@
+//! Arrays of 64-bit signed counters, as created by `counters:new/2`
+//!
+//! With the `write_concurrency` layout, every counter has one stripe per scheduler and processes
+//! only update the stripe of the scheduler they run on, so that schedulers updating the same
+//! counter don't contend for its cache line.  Reading a counter sums its stripes, so reads are
+//! slower and may not reflect concurrent updates.
+use std::convert::{TryFrom, TryInto};
+use std::mem;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use anyhow::*;
+
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::Process;
+
+use crate::atomics::{new_values, term_to_i128, try_into_zero_based_index};
+use crate::context::*;
+
+// Stripes start on their own cache line
+const STRIPE_ALIGNMENT_IN_COUNTERS: usize = 64 / mem::size_of::<AtomicI64>();
+
+pub struct Counters {
+    len: usize,
+    /// The distance between the same counter in consecutive stripes
+    stride: usize,
+    stripes: usize,
+    values: Box<[AtomicI64]>,
+}
+
+impl Counters {
+    /// `schedulers` is the number of stripes used by the `write_concurrency` layout
+    pub fn new(size: usize, options: Options, schedulers: usize) -> Self {
+        let (stride, stripes) = match options.layout {
+            Layout::Atomics => (size, 1),
+            Layout::WriteConcurrency => {
+                let stride = (size + STRIPE_ALIGNMENT_IN_COUNTERS - 1)
+                    / STRIPE_ALIGNMENT_IN_COUNTERS
+                    * STRIPE_ALIGNMENT_IN_COUNTERS;
+
+                (stride, schedulers.max(1))
+            }
+        };
+
+        Self {
+            len: size,
+            stride,
+            stripes,
+            values: new_values(stride * stripes),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The memory, in bytes, used by the array, including all stripes
+    pub fn memory(&self) -> usize {
+        mem::size_of::<Self>() + self.values.len() * mem::size_of::<AtomicI64>()
+    }
+
+    pub fn get(&self, index: Term) -> anyhow::Result<i64> {
+        let zero_based_index = try_into_zero_based_index(index, self.len)?;
+
+        Ok((0..self.stripes).fold(0, |sum, stripe| {
+            sum.wrapping_add(
+                self.values[stripe * self.stride + zero_based_index].load(Ordering::SeqCst),
+            )
+        }))
+    }
+
+    /// Adds `incr` to the counter at `index`, wrapping around on overflow
+    pub fn add(&self, process: &Process, index: Term, incr: Term) -> anyhow::Result<()> {
+        let counter = self.counter(process, index)?;
+        let incr_i64 = term_try_into_i64("incr", incr)?;
+        counter.fetch_add(incr_i64, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    /// Subtracts `decr` from the counter at `index`, wrapping around on overflow
+    pub fn sub(&self, process: &Process, index: Term, decr: Term) -> anyhow::Result<()> {
+        let counter = self.counter(process, index)?;
+        let decr_i64 = term_try_into_i64("decr", decr)?;
+        counter.fetch_sub(decr_i64, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    /// Sets the counter at `index` to `value`.
+    ///
+    /// With the `write_concurrency` layout, the stripes are not updated atomically together, so
+    /// concurrent updates may be lost, like in BEAM.
+    pub fn put(&self, index: Term, value: Term) -> anyhow::Result<()> {
+        let zero_based_index = try_into_zero_based_index(index, self.len)?;
+        let value_i64 = term_try_into_i64("value", value)?;
+
+        self.values[zero_based_index].store(value_i64, Ordering::SeqCst);
+
+        for stripe in 1..self.stripes {
+            self.values[stripe * self.stride + zero_based_index].store(0, Ordering::SeqCst);
+        }
+
+        Ok(())
+    }
+
+    // Private
+
+    fn counter(&self, process: &Process, index: Term) -> anyhow::Result<&AtomicI64> {
+        let zero_based_index = try_into_zero_based_index(index, self.len)?;
+
+        Ok(&self.values[self.stripe(process) * self.stride + zero_based_index])
+    }
+
+    /// Processes that aren't scheduled yet, such as in tests, use the first stripe
+    fn stripe(&self, process: &Process) -> usize {
+        match process.scheduler_id() {
+            Some(scheduler_id) => {
+                let scheduler_id_u32: u32 = scheduler_id.into();
+
+                (scheduler_id_u32 as usize) % self.stripes
+            }
+            None => 0,
+        }
+    }
+}
+
+/// How the counters are laid out in memory
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// Each counter is one atomic integer
+    Atomics,
+    /// Each counter is striped across schedulers
+    WriteConcurrency,
+}
+
+/// The `Opts` argument to `counters:new/2`
+pub struct Options {
+    pub layout: Layout,
+}
+
+const SUPPORTED_OPTIONS_CONTEXT: &str = "supported options are atomics or write_concurrency";
+
+impl Options {
+    fn put_option_term(&mut self, option: Term) -> Result<&Options, anyhow::Error> {
+        let atom: Atom = option.try_into().context(SUPPORTED_OPTIONS_CONTEXT)?;
+
+        self.layout = match atom.name() {
+            "atomics" => Layout::Atomics,
+            "write_concurrency" => Layout::WriteConcurrency,
+            name => {
+                return Err(anyhow!("unsupported option ({})", name))
+                    .context(SUPPORTED_OPTIONS_CONTEXT)
+            }
+        };
+
+        Ok(self)
+    }
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            layout: Layout::Atomics,
+        }
+    }
+}
+
+impl TryFrom<Term> for Options {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let mut options: Options = Default::default();
+        let mut options_term = term;
+
+        loop {
+            match options_term.decode().unwrap() {
+                TypedTerm::Nil => return Ok(options),
+                TypedTerm::List(cons) => {
+                    options
+                        .put_option_term(cons.head)
+                        .with_context(|| SUPPORTED_OPTIONS_CONTEXT)?;
+                    options_term = cons.tail;
+
+                    continue;
+                }
+                _ => return Err(ImproperListError).context(SUPPORTED_OPTIONS_CONTEXT),
+            }
+        }
+    }
+}
+
+// Private
+
+fn term_try_into_i64(name: &str, term: Term) -> anyhow::Result<i64> {
+    term_to_i128(term)
+        .and_then(|value| i64::try_from(value).ok())
+        .with_context(|| term_is_not_type(name, term, "a signed 64-bit integer"))
+}
@@ -0,0 +1,88 @@
+//! Erlang Term Storage
+//!
+//! Tables live outside of any process heap.  Each stored object is copied into its own
+//! `HeapFragment` and copied back onto the caller's heap when read.  A table is owned by the
+//! process that created it and is deleted when that process exits.
+mod object;
+mod table;
+
+pub use object::Object;
+pub use table::{Access, Table, Type};
+
+use std::sync::Arc;
+
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+lazy_static! {
+    static ref TABLE_BY_REFERENCE: DashMap<Reference, Arc<Table>> = Default::default();
+    static ref TABLE_BY_NAME: DashMap<Atom, Arc<Table>> = Default::default();
+    static ref TABLE_REFERENCES_BY_OWNER: DashMap<Pid, Vec<Reference>> = Default::default();
+}
+
+/// Deletes `table`, releasing all the objects stored in it.
+pub fn delete(table: &Table) {
+    if table.is_named() {
+        TABLE_BY_NAME.remove(&table.name());
+    }
+
+    let reference = table.reference();
+    TABLE_BY_REFERENCE.remove(&reference);
+
+    let owner = table.owner();
+
+    if let Some(mut references) = TABLE_REFERENCES_BY_OWNER.get_mut(&owner) {
+        references.retain(|owned| *owned != reference);
+    }
+
+    TABLE_REFERENCES_BY_OWNER.remove_if(&owner, |_, references| references.is_empty());
+}
+
+/// Deletes all tables owned by `pid`.  Called when the owner exits.
+pub fn delete_owned_by(pid: Pid) {
+    if let Some((_, references)) = TABLE_REFERENCES_BY_OWNER.remove(&pid) {
+        for reference in references {
+            if let Some(table) = reference_to_table(&reference) {
+                delete(&table);
+            }
+        }
+    }
+}
+
+pub fn name_to_table(name: &Atom) -> Option<Arc<Table>> {
+    TABLE_BY_NAME.get(name).map(|entry| entry.value().clone())
+}
+
+pub fn reference_to_table(reference: &Reference) -> Option<Arc<Table>> {
+    TABLE_BY_REFERENCE
+        .get(reference)
+        .map(|entry| entry.value().clone())
+}
+
+/// Registers `table`.  Returns `None` if `table` is named and there is already a table with the
+/// same name.
+pub fn register(table: Table) -> Option<Arc<Table>> {
+    let arc_table = Arc::new(table);
+
+    if arc_table.is_named() {
+        // The name is checked and taken while holding its entry, so that only one of two
+        // processes creating tables with the same name at the same time succeeds
+        match TABLE_BY_NAME.entry(arc_table.name()) {
+            Entry::Occupied(_) => return None,
+            Entry::Vacant(entry) => {
+                entry.insert(arc_table.clone());
+            }
+        }
+    }
+
+    TABLE_BY_REFERENCE.insert(arc_table.reference(), arc_table.clone());
+    TABLE_REFERENCES_BY_OWNER
+        .entry(arc_table.owner())
+        .or_default()
+        .push(arc_table.reference());
+
+    Some(arc_table)
+}
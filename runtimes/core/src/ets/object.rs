@@ -0,0 +1,79 @@
+use std::hash::{Hash, Hasher};
+use std::ptr::{self, NonNull};
+
+use liblumen_alloc::erts::exception::AllocResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::{CloneToProcess, HeapFragment};
+
+/// A term copied off of a process heap into a `HeapFragment` owned by the object.
+pub struct Object {
+    term: Term,
+    fragment: Option<NonNull<HeapFragment>>,
+}
+
+impl Object {
+    pub fn new(term: Term) -> AllocResult<Self> {
+        if term.is_boxed() || term.is_non_empty_list() {
+            let (term, fragment) = term.clone_to_fragment()?;
+
+            Ok(Self {
+                term,
+                fragment: Some(fragment),
+            })
+        } else {
+            Ok(Self {
+                term,
+                fragment: None,
+            })
+        }
+    }
+
+    /// Wraps `term` without copying it, so that it can be used to look up stored keys and
+    /// objects while `term` is still on the caller's heap.
+    pub(crate) fn borrowed(term: Term) -> Self {
+        Self {
+            term,
+            fragment: None,
+        }
+    }
+
+    /// The copied term.  It is only valid while `self` is alive, so it must be cloned to a
+    /// process heap before the table lock protecting `self` is released.
+    pub fn term(&self) -> Term {
+        self.term
+    }
+
+    pub fn size_in_words(&self) -> usize {
+        self.term.size_in_words()
+    }
+}
+
+impl Drop for Object {
+    fn drop(&mut self) {
+        if let Some(fragment) = self.fragment.take() {
+            unsafe { ptr::drop_in_place(fragment.as_ptr()) };
+        }
+    }
+}
+
+impl Eq for Object {}
+
+impl Hash for Object {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.term.hash(state)
+    }
+}
+
+impl PartialEq for Object {
+    fn eq(&self, other: &Object) -> bool {
+        self.term
+            .decode()
+            .unwrap()
+            .exact_eq(&other.term.decode().unwrap())
+    }
+}
+
+// The fragment is only ever reachable through `self`, so it is safe to move between or share across
+// schedulers as long as it is guarded by the table lock.
+unsafe impl Send for Object {}
+unsafe impl Sync for Object {}
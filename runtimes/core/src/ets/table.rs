@@ -0,0 +1,326 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+
+use hashbrown::HashMap;
+
+use liblumen_core::locks::RwLock;
+
+use liblumen_alloc::erts::exception::{Alloc, AllocResult};
+use liblumen_alloc::erts::term::prelude::*;
+
+use super::Object;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Access {
+    /// Any process can read or write
+    Public,
+    /// Any process can read, only the owner can write
+    Protected,
+    /// Only the owner can read or write
+    Private,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Type {
+    /// One object per key, keys compared with `=:=`
+    Set,
+    /// One object per key, keys compared with `==` and iterated in term order
+    OrderedSet,
+    /// Many objects per key, but each object at most once
+    Bag,
+    /// Many objects per key, including identical objects
+    DuplicateBag,
+}
+
+pub struct Table {
+    reference: Reference,
+    name: Atom,
+    named: bool,
+    r#type: Type,
+    access: Access,
+    /// 1-based index of the key in each object tuple
+    keypos: usize,
+    owner: Pid,
+    storage: RwLock<Storage>,
+}
+
+impl Table {
+    pub fn new(
+        reference: Reference,
+        name: Atom,
+        named: bool,
+        r#type: Type,
+        access: Access,
+        keypos: usize,
+        owner: Pid,
+    ) -> Self {
+        assert!(0 < keypos);
+
+        let storage = match r#type {
+            Type::OrderedSet => Storage::Ordered(Default::default()),
+            _ => Storage::Hash(Default::default()),
+        };
+
+        Self {
+            reference,
+            name,
+            named,
+            r#type,
+            access,
+            keypos,
+            owner,
+            storage: RwLock::new(storage),
+        }
+    }
+
+    pub fn access(&self) -> Access {
+        self.access
+    }
+
+    pub fn can_read(&self, pid: Pid) -> bool {
+        self.access != Access::Private || self.owner == pid
+    }
+
+    pub fn can_write(&self, pid: Pid) -> bool {
+        self.access == Access::Public || self.owner == pid
+    }
+
+    /// Removes all objects with `key`
+    pub fn delete_key(&self, key: Term) {
+        self.storage.write().remove(key);
+    }
+
+    pub fn is_named(&self) -> bool {
+        self.named
+    }
+
+    /// Inserts `objects`, replacing any object with the same key in a `set` or `ordered_set`.
+    ///
+    /// Each object must already have been checked to be a tuple with an element at `keypos`.
+    pub fn insert(&self, objects: &[Term]) -> AllocResult<()> {
+        let mut storage = self.storage.write();
+
+        for object in objects {
+            self.insert_in(&mut storage, *object)?;
+        }
+
+        Ok(())
+    }
+
+    /// Like `insert`, but does nothing and returns `false` if any of the keys in `objects` is
+    /// already in the table.
+    pub fn insert_new(&self, objects: &[Term]) -> AllocResult<bool> {
+        let mut storage = self.storage.write();
+
+        for object in objects {
+            if storage.get(self.key(*object).unwrap()).is_some() {
+                return Ok(false);
+            }
+        }
+
+        for object in objects {
+            self.insert_in(&mut storage, *object)?;
+        }
+
+        Ok(true)
+    }
+
+    /// The key of `object` if it is a tuple large enough to have an element at `keypos`.
+    pub fn key(&self, object: Term) -> Option<Term> {
+        let tuple: Boxed<Tuple> = object.try_into().ok()?;
+
+        tuple.elements().get(self.keypos - 1).copied()
+    }
+
+    pub fn keypos(&self) -> usize {
+        self.keypos
+    }
+
+    /// Number of objects in the table
+    pub fn len(&self) -> usize {
+        self.storage
+            .read()
+            .values()
+            .map(|object_vec| object_vec.len())
+            .sum()
+    }
+
+    /// Calls `f` with each object with `key`.  The terms passed to `f` must be cloned before they
+    /// outlive the call.
+    pub fn lookup<F, E>(&self, key: Term, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(Term) -> Result<(), E>,
+    {
+        if let Some(object_vec) = self.storage.read().get(key) {
+            for object in object_vec {
+                f(object.term())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Words of off-heap memory used by the objects in the table
+    pub fn memory(&self) -> usize {
+        self.storage
+            .read()
+            .values()
+            .flat_map(|object_vec| object_vec.iter())
+            .map(|object| object.size_in_words())
+            .sum()
+    }
+
+    pub fn name(&self) -> Atom {
+        self.name
+    }
+
+    pub fn owner(&self) -> Pid {
+        self.owner
+    }
+
+    pub fn reference(&self) -> Reference {
+        self.reference
+    }
+
+    /// Calls `f` with every object in the table.  For an `ordered_set` the objects are in key
+    /// order.  The terms passed to `f` must be cloned before they outlive the call.
+    pub fn try_for_each<F, E>(&self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(Term) -> Result<(), E>,
+    {
+        for object_vec in self.storage.read().values() {
+            for object in object_vec {
+                f(object.term())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn r#type(&self) -> Type {
+        self.r#type
+    }
+
+    /// Replaces the single object with `key` with the object returned by `f`, which is passed
+    /// the current object.  Returns `Ok(false)` if there is no object with `key`.
+    ///
+    /// Only valid for `set` and `ordered_set` tables.
+    pub fn update<F, E>(&self, key: Term, f: F) -> Result<bool, E>
+    where
+        F: FnOnce(Term) -> Result<Term, E>,
+        E: From<Alloc>,
+    {
+        assert!(matches!(self.r#type, Type::Set | Type::OrderedSet));
+
+        let mut storage = self.storage.write();
+
+        match storage.get_mut(key) {
+            Some(object_vec) => {
+                let updated = f(object_vec[0].term())?;
+                object_vec[0] = Object::new(updated)?;
+
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    // Private
+
+    fn insert_in(&self, storage: &mut Storage, object: Term) -> AllocResult<()> {
+        let key = self.key(object).unwrap();
+
+        match storage.get_mut(key) {
+            Some(object_vec) => match self.r#type {
+                Type::Set | Type::OrderedSet => {
+                    object_vec[0] = Object::new(object)?;
+                }
+                Type::Bag => {
+                    let borrowed = Object::borrowed(object);
+
+                    if !object_vec.iter().any(|stored| stored == &borrowed) {
+                        object_vec.push(Object::new(object)?);
+                    }
+                }
+                Type::DuplicateBag => {
+                    object_vec.push(Object::new(object)?);
+                }
+            },
+            None => {
+                storage.insert(Object::new(key)?, vec![Object::new(object)?]);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+enum Storage {
+    Hash(HashMap<Object, Vec<Object>>),
+    Ordered(BTreeMap<OrderedKey, Vec<Object>>),
+}
+
+impl Storage {
+    fn get(&self, key: Term) -> Option<&Vec<Object>> {
+        match self {
+            Self::Hash(map) => map.get(&Object::borrowed(key)),
+            Self::Ordered(map) => map.get(&OrderedKey(Object::borrowed(key))),
+        }
+    }
+
+    fn get_mut(&mut self, key: Term) -> Option<&mut Vec<Object>> {
+        match self {
+            Self::Hash(map) => map.get_mut(&Object::borrowed(key)),
+            Self::Ordered(map) => map.get_mut(&OrderedKey(Object::borrowed(key))),
+        }
+    }
+
+    fn insert(&mut self, key: Object, object_vec: Vec<Object>) {
+        match self {
+            Self::Hash(map) => {
+                map.insert(key, object_vec);
+            }
+            Self::Ordered(map) => {
+                map.insert(OrderedKey(key), object_vec);
+            }
+        }
+    }
+
+    fn remove(&mut self, key: Term) -> Option<Vec<Object>> {
+        match self {
+            Self::Hash(map) => map.remove(&Object::borrowed(key)),
+            Self::Ordered(map) => map.remove(&OrderedKey(Object::borrowed(key))),
+        }
+    }
+
+    fn values<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Vec<Object>> + 'a> {
+        match self {
+            Self::Hash(map) => Box::new(map.values()),
+            Self::Ordered(map) => Box::new(map.values()),
+        }
+    }
+}
+
+/// Orders keys of `ordered_set` tables by term order, so that `1` and `1.0` are the same key
+struct OrderedKey(Object);
+
+impl Eq for OrderedKey {}
+
+impl Ord for OrderedKey {
+    fn cmp(&self, other: &OrderedKey) -> Ordering {
+        self.0.term().cmp(&other.0.term())
+    }
+}
+
+impl PartialEq for OrderedKey {
+    fn eq(&self, other: &OrderedKey) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for OrderedKey {
+    fn partial_cmp(&self, other: &OrderedKey) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
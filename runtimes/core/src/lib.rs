@@ -1,13 +1,20 @@
 // Layout helpers
 #![feature(alloc_layout_extra)]
+// For `crate::system::ExitStatus`
+#![feature(termination_trait_lib)]
 
+pub mod atomics;
 pub mod builtins;
 pub mod context;
+pub mod counters;
 pub mod distribution;
+pub mod ets;
+pub mod persistent_term;
 pub mod process;
 pub mod proplist;
 pub mod registry;
 pub mod scheduler;
+pub mod system;
 pub mod time;
 pub mod timer;
 
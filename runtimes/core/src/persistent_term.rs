@@ -0,0 +1,72 @@
+//! Persistent term storage
+//!
+//! Each value is copied once into its own `HeapFragment` outside of any process heap, and reading
+//! it copies it onto the reader's heap, the same as an ETS lookup.  No process references a
+//! fragment, so replacing or erasing a value frees its fragment immediately.
+use std::mem;
+
+use hashbrown::HashMap;
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::RwLock;
+
+use liblumen_alloc::borrow::CloneToProcess;
+use liblumen_alloc::erts::exception::AllocResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::Process;
+
+use crate::ets::Object;
+
+lazy_static! {
+    static ref VALUE_BY_KEY: RwLock<HashMap<Object, Object>> = Default::default();
+}
+
+/// Stores `value` under `key`, replacing any previous value.
+///
+/// Storing a value exactly equal (`=:=`) to the current one does nothing.
+pub fn put(key: Term, value: Term) -> AllocResult<()> {
+    let mut value_by_key = VALUE_BY_KEY.write();
+
+    if let Some(stored_value) = value_by_key.get(&Object::borrowed(key)) {
+        if *stored_value == Object::borrowed(value) {
+            return Ok(());
+        }
+    }
+
+    // Dropping the replaced value frees its fragment
+    value_by_key.insert(Object::new(key)?, Object::new(value)?);
+
+    Ok(())
+}
+
+/// The value stored under `key`, copied onto `process`'s heap
+pub fn get(process: &Process, key: Term) -> Option<Term> {
+    VALUE_BY_KEY
+        .read()
+        .get(&Object::borrowed(key))
+        .map(|value| value.term().clone_to_process(process))
+}
+
+/// Erases the value stored under `key`.  Returns `false` if there was no value.
+pub fn erase(key: Term) -> bool {
+    VALUE_BY_KEY
+        .write()
+        .remove(&Object::borrowed(key))
+        .is_some()
+}
+
+/// The number of stored keys
+pub fn count() -> usize {
+    VALUE_BY_KEY.read().len()
+}
+
+/// The memory, in bytes, used by the stored keys and values
+pub fn memory() -> usize {
+    let words: usize = VALUE_BY_KEY
+        .read()
+        .iter()
+        .map(|(key, value)| key.size_in_words() + value.size_in_words())
+        .sum();
+
+    words * mem::size_of::<Term>()
+}
@@ -45,7 +45,7 @@ impl Queues {
     }
 
     pub fn enqueue(&mut self, arc_process: Arc<Process>) {
-        match arc_process.priority {
+        match arc_process.priority() {
             Priority::Low | Priority::Normal => self.normal_low.enqueue(arc_process),
             Priority::High => self.high.enqueue(arc_process),
             Priority::Max => self.max.enqueue(arc_process),
@@ -56,6 +56,11 @@ impl Queues {
         self.waiting.len() + self.normal_low.len() + self.high.len() + self.max.len()
     }
 
+    /// Number of processes that can be run, i.e. excluding waiting processes
+    pub fn runnable_len(&self) -> usize {
+        self.normal_low.len() + self.high.len() + self.max.len()
+    }
+
     /// Returns the process is not pushed back because it is exiting
     #[must_use]
     pub fn requeue(&mut self, arc_process: Arc<Process>) -> Option<Arc<Process>> {
@@ -75,6 +80,20 @@ impl Queues {
         }
     }
 
+    /// Removes a runnable process for which `stealable` returns `true`, so that another
+    /// scheduler can run it.  Processes are taken from the back of the highest priority queue, so
+    /// that the processes this scheduler is about to run stay put.  Waiting processes are never
+    /// stolen, as their wake up is routed to this scheduler.
+    pub fn steal<F>(&mut self, stealable: F) -> Option<Arc<Process>>
+    where
+        F: Fn(&Arc<Process>) -> bool,
+    {
+        self.max
+            .steal(&stealable)
+            .or_else(|| self.high.steal(&stealable))
+            .or_else(|| self.normal_low.steal(&stealable))
+    }
+
     pub fn stop_waiting(&mut self, process: &Process) {
         match self.waiting.get(process) {
             Some(arc_process) => {
@@ -174,6 +193,15 @@ impl Immediate {
     pub fn enqueue(&mut self, process: Arc<Process>) {
         self.0.push_back(process);
     }
+
+    pub fn steal<F>(&mut self, stealable: F) -> Option<Arc<Process>>
+    where
+        F: Fn(&Arc<Process>) -> bool,
+    {
+        let index = self.0.iter().rposition(stealable)?;
+
+        self.0.remove(index)
+    }
 }
 
 /// A run queue where the `Arc<Process` is run only when its delay is `0`.  This allows
@@ -213,6 +241,20 @@ impl Delayed {
         let delayed_process = DelayedProcess::new(arc_process);
         self.0.push_back(delayed_process);
     }
+
+    pub fn steal<F>(&mut self, stealable: F) -> Option<Arc<Process>>
+    where
+        F: Fn(&Arc<Process>) -> bool,
+    {
+        let index = self
+            .0
+            .iter()
+            .rposition(|delayed_process| stealable(&delayed_process.arc_process))?;
+
+        self.0
+            .remove(index)
+            .map(|delayed_process| delayed_process.arc_process)
+    }
 }
 
 type Delay = u8;
@@ -226,7 +268,7 @@ struct DelayedProcess {
 impl DelayedProcess {
     fn new(arc_process: Arc<Process>) -> DelayedProcess {
         DelayedProcess {
-            delay: Self::priority_to_delay(arc_process.priority),
+            delay: Self::priority_to_delay(arc_process.priority()),
             arc_process,
         }
     }
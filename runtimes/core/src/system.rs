@@ -0,0 +1,225 @@
+//! System-wide shutdown, as requested by `erlang:halt/0,1,2`
+//!
+//! A halt does not stop the system directly: it records the requested exit status, which each
+//! scheduler checks between processes.  Once the schedulers have stopped, the runtime's `main`
+//! runs the exit hooks and exits with the requested status.
+use std::convert::{TryFrom, TryInto};
+use std::io::{self, Write};
+use std::process::Termination;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use anyhow::*;
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::Mutex;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::proplist::TryPropListFromTermError;
+
+/// The exit status used when the system halts with a slogan
+pub const SLOGAN_STATUS: i32 = 1;
+
+// `i64::MIN` can't be the status of a halt, as statuses are `i32`
+const NOT_HALTING: i64 = i64::MIN;
+
+static HALT_STATUS: AtomicI64 = AtomicI64::new(NOT_HALTING);
+
+lazy_static! {
+    static ref EXIT_HOOKS: Mutex<Vec<Box<dyn FnOnce() + Send>>> = Mutex::new(Vec::new());
+}
+
+/// The `Status` argument to `erlang:halt/1,2`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Status {
+    /// Exit the OS process with this status code
+    Code(i32),
+    /// Abort the OS process, producing a core dump if enabled
+    Abort,
+    /// Report the slogan as the reason the system crashed, then exit with `SLOGAN_STATUS`
+    Slogan(String),
+}
+
+const SUPPORTED_STATUS_CONTEXT: &str =
+    "status must be a non-negative integer, the atom abort, or a string";
+
+impl TryFrom<Term> for Status {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        match term.decode()? {
+            TypedTerm::SmallInteger(small_integer) => {
+                let code: isize = small_integer.into();
+
+                if 0 <= code {
+                    // Like BEAM, only the low bits survive the trip through the OS
+                    Ok(Status::Code(code as i32))
+                } else {
+                    Err(anyhow!("status ({}) is negative", term)).context(SUPPORTED_STATUS_CONTEXT)
+                }
+            }
+            TypedTerm::Atom(atom) if atom.name() == "abort" => Ok(Status::Abort),
+            TypedTerm::Nil => Ok(Status::Slogan(String::new())),
+            TypedTerm::List(cons) => {
+                let slogan: String = cons.try_into().context(SUPPORTED_STATUS_CONTEXT)?;
+
+                Ok(Status::Slogan(slogan))
+            }
+            _ => Err(TypeError).context(SUPPORTED_STATUS_CONTEXT),
+        }
+    }
+}
+
+/// The `Options` argument to `erlang:halt/2`
+pub struct Options {
+    /// Whether the system is shut down in an orderly way, running exit hooks and flushing output,
+    /// or the OS process exits immediately
+    pub flush: bool,
+}
+
+const SUPPORTED_OPTIONS_CONTEXT: &str = "supported options are {flush, bool}";
+
+impl Options {
+    fn put_option_term(&mut self, option: Term) -> Result<&Options, anyhow::Error> {
+        let tuple: Boxed<Tuple> = option.try_into().context(SUPPORTED_OPTIONS_CONTEXT)?;
+
+        if tuple.len() == 2 {
+            let atom: Atom = tuple[0]
+                .try_into()
+                .map_err(|_| TryPropListFromTermError::KeywordKeyType)?;
+
+            match atom.name() {
+                "flush" => {
+                    self.flush = tuple[1].try_into().context("flush value must be a bool")?;
+
+                    Ok(self)
+                }
+                name => Err(TryPropListFromTermError::KeywordKeyName(name))
+                    .context(SUPPORTED_OPTIONS_CONTEXT),
+            }
+        } else {
+            Err(TryPropListFromTermError::TupleNotPair.into())
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options { flush: true }
+    }
+}
+
+impl TryFrom<Term> for Options {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let mut options: Options = Default::default();
+        let mut options_term = term;
+
+        loop {
+            match options_term.decode().unwrap() {
+                TypedTerm::Nil => return Ok(options),
+                TypedTerm::List(cons) => {
+                    options
+                        .put_option_term(cons.head)
+                        .with_context(|| SUPPORTED_OPTIONS_CONTEXT)?;
+                    options_term = cons.tail;
+
+                    continue;
+                }
+                _ => return Err(ImproperListError).context(SUPPORTED_OPTIONS_CONTEXT),
+            }
+        }
+    }
+}
+
+/// The status the runtime's `main` exits the OS process with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExitStatus(pub i32);
+
+impl ExitStatus {
+    pub const SUCCESS: ExitStatus = ExitStatus(0);
+    pub const FAILURE: ExitStatus = ExitStatus(1);
+
+    /// The status of the requested halt, or `SUCCESS` if the system stopped without one
+    pub fn from_halt() -> ExitStatus {
+        halt_status().map(ExitStatus).unwrap_or(ExitStatus::SUCCESS)
+    }
+}
+
+impl Termination for ExitStatus {
+    fn report(self) -> i32 {
+        self.0
+    }
+}
+
+/// Halts the system with `status`.
+///
+/// `Status::Abort` and halts without `flush` end the OS process before returning.  Otherwise,
+/// an orderly shutdown is requested and the caller should stop running Erlang code, so that its
+/// scheduler can notice the request.
+pub fn halt(status: Status, options: Options) {
+    let code = match status {
+        Status::Code(code) => code,
+        Status::Abort => std::process::abort(),
+        Status::Slogan(slogan) => {
+            eprintln!("Runtime terminating: {}", slogan);
+
+            SLOGAN_STATUS
+        }
+    };
+
+    if options.flush {
+        request_halt(code);
+    } else {
+        std::process::exit(code);
+    }
+}
+
+/// Requests an orderly shutdown of the system that ends with the OS process exiting with
+/// `status`.
+///
+/// Only the first request sets the status, so that a process halting while the system is already
+/// shutting down cannot change it.
+pub fn request_halt(status: i32) {
+    let _ = HALT_STATUS.compare_exchange(
+        NOT_HALTING,
+        status as i64,
+        Ordering::SeqCst,
+        Ordering::SeqCst,
+    );
+}
+
+/// The exit status of the requested halt, or `None` if the system is not halting
+pub fn halt_status() -> Option<i32> {
+    match HALT_STATUS.load(Ordering::SeqCst) {
+        NOT_HALTING => None,
+        status => Some(status as i32),
+    }
+}
+
+/// Registers `hook` to run when the system shuts down in an orderly way.
+///
+/// Hooks run in the reverse order they are registered, like `atexit`.
+pub fn at_exit<F>(hook: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    EXIT_HOOKS.lock().push(Box::new(hook));
+}
+
+/// Runs and removes the registered exit hooks, then flushes standard output and error
+pub fn run_exit_hooks() {
+    loop {
+        // Don't hold the lock while running the hook, so that it may register others
+        let hook = EXIT_HOOKS.lock().pop();
+
+        match hook {
+            Some(hook) => hook(),
+            None => break,
+        }
+    }
+
+    let _ = io::stdout().flush();
+    let _ = io::stderr().flush();
+}
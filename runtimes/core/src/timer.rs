@@ -94,6 +94,16 @@ impl Hierarchy {
         }
     }
 
+    /// Milliseconds until the earliest running timer times out, so that an idle scheduler knows
+    /// how long it can park.  `None` if there are no running timers.
+    pub fn milliseconds_until_next_timeout(&self) -> Option<Milliseconds> {
+        self.timer_by_reference_number
+            .values()
+            .filter_map(|weak_timer| weak_timer.upgrade())
+            .map(|arc_timer| arc_timer.milliseconds_remaining())
+            .min()
+    }
+
     pub fn read(&self, timer_reference_number: ReferenceNumber) -> Option<Milliseconds> {
         self.timer_by_reference_number
             .get(&timer_reference_number)
@@ -34,6 +34,36 @@ pub fn get(
         })
 }
 
+/// Old funs (`FUN_EXT`) only record the `module`, `index` and `old_unique`, so the `unique` and
+/// `arity` have to come from the code that was inserted for the same function.
+pub fn get_by_old_unique(
+    module: &Atom,
+    index: &Index,
+    old_unique: &OldUnique,
+) -> Option<(Unique, Arity, Code)> {
+    RW_LOCK_CODE_BY_ARITY_BY_UNIQUE_BY_OLD_UNIQUE_BY_INDEX_BY_MODULE
+        .read()
+        .get(module)
+        .and_then(|code_by_arity_by_unique_by_old_unique_by_index| {
+            code_by_arity_by_unique_by_old_unique_by_index
+                .get(index)
+                .and_then(|code_by_arity_by_unique_by_old_unique| {
+                    code_by_arity_by_unique_by_old_unique
+                        .get(old_unique)
+                        .and_then(|code_by_arity_by_unique| {
+                            code_by_arity_by_unique
+                                .iter()
+                                .flat_map(|(unique, code_by_arity)| {
+                                    code_by_arity
+                                        .iter()
+                                        .map(move |(arity, code)| (*unique, *arity, *code))
+                                })
+                                .next()
+                        })
+                })
+        })
+}
+
 pub fn insert(
     module: Atom,
     index: Index,
@@ -1,11 +1,15 @@
 mod arc_node;
 mod atom;
+mod atom_cache_reference;
 mod atom_utf8;
 mod big;
 mod binary;
 mod bit_binary;
+mod compressed;
 mod export;
 mod f64;
+mod float;
+mod function;
 mod i32;
 mod integer;
 mod isize;
@@ -14,8 +18,12 @@ mod map;
 mod new_float;
 mod new_function;
 mod new_pid;
+mod new_port;
+mod new_reference;
 mod newer_reference;
 mod pid;
+mod port;
+mod reference;
 mod sign;
 mod small_atom;
 mod small_atom_utf8;
@@ -75,6 +83,21 @@ pub enum DecodeError {
     UnexpectedVersion { version: u8, backtrace: Backtrace },
     #[error("unexpected tag ({tag})")]
     UnexpectedTag { tag: Tag, backtrace: Backtrace },
+    #[error("atom cache reference ({index}) is only valid in a distribution message")]
+    AtomCacheReference { index: u8, backtrace: Backtrace },
+    #[error("reference ID length ({len}) must be 1-3")]
+    ReferenceIDLen { len: usize, backtrace: Backtrace },
+    #[error("could not decompress")]
+    Decompress {
+        source: std::io::Error,
+        backtrace: Backtrace,
+    },
+    #[error("expected {expected} uncompressed bytes, but got {actual}")]
+    UncompressedSize {
+        expected: usize,
+        actual: usize,
+        backtrace: Backtrace,
+    },
 }
 
 impl From<DecodeError> for InternalException {
@@ -88,6 +111,7 @@ impl From<DecodeError> for InternalException {
 pub enum Tag {
     NewFloat = 70,
     BitBinary = 77,
+    Compressed = 80,
     AtomCacheReference = 82,
     NewPID = 88,
     NewPort = 89,
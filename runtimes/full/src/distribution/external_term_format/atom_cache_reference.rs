@@ -0,0 +1,26 @@
+use std::backtrace::Backtrace;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+
+use super::atom::atom_bytes_to_term_bytes;
+use super::{u8, DecodeError};
+
+/// > Refers to the atom with AtomCacheReferenceIndex in the distribution header.
+/// -- http://erlang.org/doc/apps/erts/erl_ext_dist.html#atom_cache_ref
+///
+/// Only distribution messages have a distribution header, so, like BEAM, an atom cache reference
+/// in the external term format passed to `binary_to_term` is an error.
+pub fn decode_atom(bytes: &[u8]) -> InternalResult<(Atom, &[u8])> {
+    let (index, _) = u8::decode(bytes)?;
+
+    Err(DecodeError::AtomCacheReference {
+        index,
+        backtrace: Backtrace::capture(),
+    }
+    .into())
+}
+
+pub fn decode_term(bytes: &[u8]) -> InternalResult<(Term, &[u8])> {
+    decode_atom(bytes).map(atom_bytes_to_term_bytes)
+}
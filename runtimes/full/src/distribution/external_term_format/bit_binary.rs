@@ -1,3 +1,5 @@
+use anyhow::*;
+
 use liblumen_alloc::erts::exception::InternalResult;
 use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::erts::Process;
@@ -9,7 +11,9 @@ pub fn decode<'a>(process: &Process, bytes: &'a [u8]) -> InternalResult<(Term, &
     let len_usize = len_u32 as usize;
 
     let (partial_byte_bit_len, after_partial_byte_bit_len_bytes) = u8::decode(after_len_bytes)?;
-    assert!(0 < partial_byte_bit_len);
+    if !(0 < partial_byte_bit_len && partial_byte_bit_len <= 8) {
+        return Err(anyhow!("bits in last byte ({}) must be 1-8", partial_byte_bit_len).into());
+    }
 
     try_split_at(after_partial_byte_bit_len_bytes, len_usize).and_then(
         |(data_bytes, after_data_bytes)| {
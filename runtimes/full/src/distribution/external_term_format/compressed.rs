@@ -0,0 +1,64 @@
+use std::backtrace::Backtrace;
+use std::io::Read;
+
+use flate2::bufread::ZlibDecoder;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Process;
+
+use super::{term, u32, DecodeError};
+
+/// The uncompressed size comes from the untrusted encoding, so no more than this is allocated
+/// before any data is inflated; the buffer grows as needed past it.
+const MAX_PREALLOCATED_BYTES: usize = 64 * 1024;
+
+/// > The uncompressed size is a 32-bit big-endian unsigned integer, followed by the zlib-compressed
+/// > data, which is the term encoding without the version number.
+/// -- http://erlang.org/doc/apps/erts/erl_ext_dist.html#compressed-term-format
+pub fn decode<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    let (uncompressed_size_u32, after_uncompressed_size_bytes) = u32::decode(bytes)?;
+    let uncompressed_size = uncompressed_size_u32 as usize;
+
+    let mut decoder = ZlibDecoder::new(after_uncompressed_size_bytes);
+    let mut uncompressed_byte_vec =
+        Vec::with_capacity(uncompressed_size.min(MAX_PREALLOCATED_BYTES));
+    // One more byte than expected is read so that data past the uncompressed size is caught
+    decoder
+        .by_ref()
+        .take(uncompressed_size as u64 + 1)
+        .read_to_end(&mut uncompressed_byte_vec)
+        .map_err(|error| DecodeError::Decompress {
+            source: error,
+            backtrace: Backtrace::capture(),
+        })?;
+
+    if uncompressed_byte_vec.len() != uncompressed_size {
+        return Err(DecodeError::UncompressedSize {
+            expected: uncompressed_size,
+            actual: uncompressed_byte_vec.len(),
+            backtrace: Backtrace::capture(),
+        }
+        .into());
+    }
+
+    let compressed_len = decoder.total_in() as usize;
+    let after_compressed_bytes = &after_uncompressed_size_bytes[compressed_len..];
+
+    let (term, after_term_bytes) = term::decode_tagged(process, safe, &uncompressed_byte_vec)?;
+
+    if after_term_bytes.is_empty() {
+        Ok((term, after_compressed_bytes))
+    } else {
+        Err(DecodeError::UncompressedSize {
+            expected: uncompressed_size,
+            actual: uncompressed_size - after_term_bytes.len(),
+            backtrace: Backtrace::capture(),
+        }
+        .into())
+    }
+}
@@ -0,0 +1,42 @@
+use std::str;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Process;
+
+use super::try_split_at;
+
+/// > A finite float (i.e. not inf, -inf or NaN) is stored in string format. The format used in
+/// > sprintf to format the float is "%.20e" (there are more bytes allocated than necessary). To
+/// > unpack the float, use sscanf with format "%lf".
+/// -- http://erlang.org/doc/apps/erts/erl_ext_dist.html#float_ext
+const LEN: usize = 31;
+
+pub fn decode<'a>(process: &Process, bytes: &'a [u8]) -> InternalResult<(Term, &'a [u8])> {
+    let (float_bytes, after_float_bytes) = try_split_at(bytes, LEN)?;
+    let f = bytes_try_into_f64(float_bytes)?;
+    let float = process.float(f)?;
+
+    Ok((float, after_float_bytes))
+}
+
+fn bytes_try_into_f64(bytes: &[u8]) -> anyhow::Result<f64> {
+    // The string is NUL-padded to fill the allocated bytes
+    let len = bytes
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or_else(|| bytes.len());
+    let string = str::from_utf8(&bytes[..len]).context("float bytes are not UTF-8")?;
+    let f: f64 = string
+        .trim()
+        .parse()
+        .with_context(|| format!("float string ({:?}) is not a float", string))?;
+
+    if f.is_finite() {
+        Ok(f)
+    } else {
+        Err(anyhow!("float string ({:?}) is not finite", string))
+    }
+}
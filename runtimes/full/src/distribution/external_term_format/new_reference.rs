@@ -0,0 +1,21 @@
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Process;
+
+use super::{arc_node, reference, u16, u8};
+
+pub fn decode<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    let (len_u16, after_len_bytes) = u16::decode(bytes)?;
+    let (arc_node, after_node_bytes) = arc_node::decode(safe, after_len_bytes)?;
+    // TODO use creation to differentiate respawned nodes
+    let (_creation, after_creation_bytes) = u8::decode(after_node_bytes)?;
+    let (id_vec, after_id_bytes) = reference::decode_ids(after_creation_bytes, len_u16 as usize)?;
+
+    let reference = reference::to_term(process, arc_node, &id_vec)?;
+
+    Ok((reference, after_id_bytes))
+}
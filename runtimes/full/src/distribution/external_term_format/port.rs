@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::{Node, Process};
+use liblumen_alloc::CloneToProcess;
+
+use lumen_rt_core::distribution::nodes::node;
+
+use super::{arc_node, u32, u8};
+
+pub fn decode<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    let (arc_node, after_node_bytes) = arc_node::decode(safe, bytes)?;
+    let (id, after_id_bytes) = u32::decode(after_node_bytes)?;
+    // TODO use creation to differentiate respawned nodes
+    let (_creation, after_creation_bytes) = u8::decode(after_id_bytes)?;
+
+    let port = to_term(process, arc_node, id)?;
+
+    Ok((port, after_creation_bytes))
+}
+
+pub fn to_term(process: &Process, arc_node: Arc<Node>, id: u32) -> InternalResult<Term> {
+    let port = if arc_node == node::arc_node() {
+        Port::new(id as usize)?.encode()?
+    } else {
+        ExternalPort::new(arc_node, id as usize)?.clone_to_process(process)
+    };
+
+    Ok(port)
+}
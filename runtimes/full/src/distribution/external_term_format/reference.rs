@@ -0,0 +1,81 @@
+use std::backtrace::Backtrace;
+use std::mem;
+use std::sync::Arc;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::{Node, Process};
+use liblumen_alloc::CloneToProcess;
+
+use lumen_rt_core::distribution::nodes::node;
+
+use super::{arc_node, u32, u8, DecodeError};
+use crate::distribution::external_term_format::try_split_at;
+
+/// > Len - A 16-bit big endian unsigned integer not larger than 3.
+/// -- http://erlang.org/doc/apps/erts/erl_ext_dist.html#new_reference_ext
+const ID_MAX_LEN: usize = 3;
+
+pub fn decode<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    let (arc_node, after_node_bytes) = arc_node::decode(safe, bytes)?;
+    let (id, after_id_bytes) = u32::decode(after_node_bytes)?;
+    // TODO use creation to differentiate respawned nodes
+    let (_creation, after_creation_bytes) = u8::decode(after_id_bytes)?;
+
+    let reference = to_term(process, arc_node, &[id])?;
+
+    Ok((reference, after_creation_bytes))
+}
+
+pub fn decode_ids(bytes: &[u8], len: usize) -> InternalResult<(Vec<u32>, &[u8])> {
+    if ID_MAX_LEN < len {
+        return Err(DecodeError::ReferenceIDLen {
+            len,
+            backtrace: Backtrace::capture(),
+        }
+        .into());
+    }
+
+    let (id_bytes, after_id_bytes) = try_split_at(bytes, len * mem::size_of::<u32>())?;
+    let mut id_vec = Vec::with_capacity(len);
+    let mut remaining_bytes = id_bytes;
+
+    for _ in 0..len {
+        let (id, after_id_bytes) = u32::decode(remaining_bytes)?;
+        id_vec.push(id);
+        remaining_bytes = after_id_bytes;
+    }
+
+    Ok((id_vec, after_id_bytes))
+}
+
+/// The first ID is the scheduler ID and the remaining IDs are the big endian words of the
+/// reference number, which is how `term_to_binary` encodes references.
+pub fn to_term(process: &Process, arc_node: Arc<Node>, ids: &[u32]) -> InternalResult<Term> {
+    let (scheduler_id_u32, number_ids) = match ids.split_first() {
+        Some(split) => split,
+        None => {
+            return Err(DecodeError::ReferenceIDLen {
+                len: 0,
+                backtrace: Backtrace::capture(),
+            }
+            .into())
+        }
+    };
+    let number = number_ids
+        .iter()
+        .fold(0_u64, |acc, id| (acc << 32) | (*id as u64));
+
+    let reference = if arc_node == node::arc_node() {
+        process.reference_from_scheduler((*scheduler_id_u32).into(), number)?
+    } else {
+        ExternalReference::new(arc_node, (*scheduler_id_u32).into(), number)
+            .clone_to_process(process)
+    };
+
+    Ok(reference)
+}
@@ -60,6 +60,9 @@ pub mod timer;
 #[cfg(test)]
 pub mod test;
 
+#[cfg(not(any(test, target_arch = "wasm32")))]
+use lumen_rt_core::system::ExitStatus;
+
 /// The main entry point for the runtime
 ///
 /// NOTE: This is currently conditionally compiled, since `lumen_web` depends on
@@ -75,21 +78,25 @@ fn main() -> impl ::std::process::Termination + 'static {
 }
 
 #[cfg(not(any(test, target_arch = "wasm32")))]
-fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()> {
+fn main_internal(name: &str, version: &str, argv: Vec<String>) -> ExitStatus {
     use self::config::Config;
     use self::logging::Logger;
     use self::scheduler::Scheduler;
     use self::system::break_handler::{self, Signal};
     use bus::Bus;
     use log::Level;
-    use std::thread;
+    use lumen_rt_core::system;
+    use lumen_rt_core::time::Milliseconds;
+
+    // How long an idle scheduler parks before checking for system signals again
+    const SIGNAL_CHECK_MILLISECONDS: Milliseconds = 100;
 
     // Load system configuration
     let _config = match Config::from_argv(name.to_string(), version.to_string(), argv) {
         Ok(config) => config,
         Err(err) => {
             eprintln!("Config error: {}", err);
-            return Err(());
+            return ExitStatus::FAILURE;
         }
     };
 
@@ -111,45 +118,44 @@ fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()>
         if let Ok(sig) = rx1.try_recv() {
             match sig {
                 // For now, SIGINT initiates a controlled shutdown
-                Signal::INT => {
-                    // If an error occurs, report it before shutdown
-                    if let Err(err) = scheduler.shutdown() {
-                        eprintln!("System error: {}", err);
-                        return Err(());
-                    } else {
-                        break;
-                    }
-                }
+                Signal::INT => break,
                 // Technically, we may never see these signals directly,
                 // we may just be terminated out of hand; but just in case,
                 // we handle them explicitly by immediately terminating, so
                 // that we are good citizens of the operating system
                 sig if sig.should_terminate() => {
-                    return Err(());
+                    return ExitStatus::FAILURE;
                 }
                 // All other signals can be surfaced to other parts of the
                 // system for custom use, e.g. SIGCHLD, SIGALRM, SIGUSR1/2
                 _ => (),
             }
         }
+        // A process called `erlang:halt`, so stop running processes
+        if system::halt_status().is_some() {
+            break;
+        }
         // If the scheduler scheduled a process this cycle, then we're busy
         // and should keep working until we have an idle period
         if scheduled {
             continue;
         }
-        // Otherwise,
-        // In some configurations, it makes more sense for us to spin and use
-        // spin_loop_hint here instead; namely when we're supposed to be the primary
-        // software on a system, and threads are pinned to cores, it makes no sense
-        // to yield to the system scheduler. However on a system with contention for
-        // system resources, or where threads aren't pinned to cores, we're better off
-        // explicitly yielding to the scheduler, rather than waiting to be preempted at
-        // a potentially inopportune time.
-        //
-        // In any case, for now, we always explicitly yield until we've got proper support
-        // for configuring the system
-        thread::yield_now()
+        // Otherwise, take work from a busy scheduler if there is any
+        if scheduler.steal() {
+            continue;
+        }
+        // Only when there is nothing to steal do we give up the CPU by parking until a process
+        // becomes runnable or a timer is due.  The park is bounded so that system signals are
+        // still checked while idle.
+        scheduler.park(Some(SIGNAL_CHECK_MILLISECONDS));
     }
 
-    Ok(())
+    // If an error occurs, report it before exiting
+    match scheduler.shutdown() {
+        Ok(()) => ExitStatus::from_halt(),
+        Err(err) => {
+            eprintln!("System error: {}", err);
+            ExitStatus::FAILURE
+        }
+    }
 }
@@ -12,6 +12,7 @@ use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::erts::ModuleFunctionArity;
 use liblumen_alloc::{atom, CloneToProcess, HeapFragment, Monitor};
 
+use lumen_rt_core::ets;
 use lumen_rt_core::process::monitor;
 use lumen_rt_core::registry::*;
 
@@ -88,69 +89,80 @@ pub fn monitor(process: &Process, monitored_process: &Process) -> AllocResult<Te
 pub fn propagate_exit(process: &Process, exception: &RuntimeException) {
     monitor::propagate_exit(process, exception);
     propagate_exit_to_links(process, exception);
+    ets::delete_owned_by(process.pid());
 }
 
 pub fn propagate_exit_to_links(process: &Process, exception: &RuntimeException) {
     if !is_expected_exception(exception) {
-        let tag = atom!("EXIT");
         let from = process.pid_term();
         let reason = exception.reason().unwrap_or_else(|| atom!("system_error"));
-        let reason_word_size = reason.size_in_words();
-        let exit_message_elements: &[Term] = &[tag, from, reason];
-        let exit_message_word_size = Tuple::need_in_words_from_elements(exit_message_elements);
         let source: ArcError = exception
             .source()
             .context(format!("propagating exit from {}", process));
 
         for linked_pid in process.linked_pid_set.iter() {
             if let Some(linked_pid_arc_process) = pid_to_process(linked_pid.key()) {
-                if linked_pid_arc_process.traps_exit() {
-                    match linked_pid_arc_process.try_acquire_heap() {
-                        Some(ref mut linked_pid_heap) => {
-                            if exit_message_word_size <= linked_pid_heap.heap_available() {
-                                send_self_exit_message(
-                                    &linked_pid_arc_process,
-                                    linked_pid_heap,
-                                    exit_message_elements,
-                                );
-                            } else {
-                                send_heap_exit_message(
-                                    &linked_pid_arc_process,
-                                    exit_message_elements,
-                                );
-                            }
-                        }
-                        None => {
-                            send_heap_exit_message(&linked_pid_arc_process, exit_message_elements);
-                        }
-                    }
+                send_exit_signal(from, &linked_pid_arc_process, reason, source.clone());
+            }
+        }
+    }
+}
+
+/// Sends an exit signal with `reason` from the process or port `from` to `process`.
+///
+/// If `process` traps exits, the signal is converted to an `{'EXIT', From, Reason}` message;
+/// otherwise, `process` exits with `reason`.
+pub fn send_exit_signal(from: Term, process: &Process, reason: Term, source: ArcError) {
+    if process.traps_exit() {
+        let exit_message_elements: &[Term] = &[atom!("EXIT"), from, reason];
+        let exit_message_word_size = Tuple::need_in_words_from_elements(exit_message_elements);
+
+        match process.try_acquire_heap() {
+            Some(ref mut heap) => {
+                if exit_message_word_size <= heap.heap_available() {
+                    send_self_exit_message(process, heap, exit_message_elements);
                 } else {
-                    // only tell the linked process to exit.  When it is run by its scheduler, it
-                    // will go through propagating its own exit.
-                    match linked_pid_arc_process.try_acquire_heap() {
-                        Some(ref mut linked_pid_heap) => {
-                            if reason_word_size <= linked_pid_heap.heap_available() {
-                                exit_in_heap(
-                                    &linked_pid_arc_process,
-                                    linked_pid_heap,
-                                    reason,
-                                    source.clone(),
-                                );
-                            } else {
-                                exit_in_heap_fragment(
-                                    &linked_pid_arc_process,
-                                    reason,
-                                    source.clone(),
-                                );
-                            }
-                        }
-                        None => {
-                            exit_in_heap_fragment(&linked_pid_arc_process, reason, source.clone());
-                        }
-                    }
+                    send_heap_exit_message(process, exit_message_elements);
                 }
             }
+            None => {
+                send_heap_exit_message(process, exit_message_elements);
+            }
+        }
+
+        stop_waiting(process);
+    } else {
+        exit(process, reason, source);
+    }
+}
+
+/// Tells `process` to exit with `reason`, whether or not it traps exits.
+///
+/// `process` only exits when it is next run by its scheduler, which then propagates its exit, so
+/// it is woken up if it is waiting.
+pub fn exit(process: &Process, reason: Term, source: ArcError) {
+    match process.try_acquire_heap() {
+        Some(ref mut heap) => {
+            if reason.size_in_words() <= heap.heap_available() {
+                exit_in_heap(process, heap, reason, source);
+            } else {
+                exit_in_heap_fragment(process, reason, source);
+            }
         }
+        None => {
+            exit_in_heap_fragment(process, reason, source);
+        }
+    }
+
+    stop_waiting(process);
+}
+
+fn stop_waiting(process: &Process) {
+    if let Some(arc_scheduler) = process
+        .scheduler_id()
+        .and_then(|scheduler_id| Scheduler::from_id(&scheduler_id))
+    {
+        arc_scheduler.stop_waiting(process);
     }
 }
 
@@ -1,4 +1,3 @@
-mod message_queue_data;
 mod out_of_code;
 
 use std::convert::{TryFrom, TryInto};
@@ -10,7 +9,7 @@ use liblumen_alloc::erts::exception::Alloc;
 use liblumen_alloc::erts::process::alloc::{default_heap_size, heap, next_heap_size};
 use liblumen_alloc::erts::process::code::stack::frame::Placement;
 use liblumen_alloc::erts::process::priority::Priority;
-use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::process::{MaxHeapSize, MessageQueueData, Process};
 use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::ModuleFunctionArity;
 
@@ -18,8 +17,6 @@ use lumen_rt_core::proplist::TryPropListFromTermError;
 
 use crate::process;
 
-use message_queue_data::*;
-
 #[must_use]
 pub struct Connection {
     pub linked: bool,
@@ -27,13 +24,6 @@ pub struct Connection {
     pub monitor_reference: Option<Term>,
 }
 
-#[derive(Clone, Copy, Debug)]
-pub struct MaxHeapSize {
-    size: Option<usize>,
-    kill: Option<bool>,
-    error_logger: Option<bool>,
-}
-
 #[derive(Clone, Copy, Debug)]
 pub struct Options {
     pub link: bool,
@@ -102,6 +92,7 @@ impl Options {
             heap,
             heap_size,
         );
+        self.set_flags(&process);
         out_of_code::place_frame_with_arguments(&process, Placement::Push)?;
 
         Ok(process)
@@ -113,7 +104,7 @@ impl Options {
         match self.priority {
             Some(priority) => priority,
             None => match parent_process {
-                Some(process) => process.priority,
+                Some(process) => process.priority(),
                 None => Default::default(),
             },
         }
@@ -164,7 +155,12 @@ impl Options {
 
                     Ok(self)
                 }
-                "max_heap_size" => unimplemented!(),
+                "max_heap_size" => {
+                    let max_heap_size = tuple[1].try_into().context("max_heap_size")?;
+                    self.max_heap_size = Some(max_heap_size);
+
+                    Ok(self)
+                }
                 "message_queue_data" => {
                     let message_queue_data = tuple[1].try_into().context("message_queue_data")?;
                     self.message_queue_data = message_queue_data;
@@ -196,6 +192,20 @@ impl Options {
         }
     }
 
+    /// Applies the process flags that are not needed to create the process.  `min_heap_size` is
+    /// already applied by sizing the heap.
+    fn set_flags(&self, process: &Process) {
+        if let Some(min_bin_vheap_size) = self.min_bin_vheap_size {
+            process.set_min_vheap_size(min_bin_vheap_size);
+        }
+
+        if let Some(max_heap_size) = self.max_heap_size {
+            process.set_max_heap_size(max_heap_size);
+        }
+
+        process.set_message_queue_data(self.message_queue_data);
+    }
+
     fn sized_heap(&self) -> Result<(*mut Term, usize), Alloc> {
         let heap_size = self.heap_size();
         let heap = heap(self.heap_size())?;
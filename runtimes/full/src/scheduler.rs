@@ -6,24 +6,36 @@ use core::sync::atomic::{AtomicU64, Ordering};
 
 use alloc::sync::{Arc, Weak};
 
+use std::thread::{self, Thread};
+use std::time::Duration;
+
+use anyhow::anyhow;
 use hashbrown::HashMap;
 
 use liblumen_core::locks::{Mutex, RwLock};
 
+use liblumen_alloc::atom;
 use liblumen_alloc::erts::exception::{Result, SystemException};
 use liblumen_alloc::erts::process::code::Code;
-use liblumen_alloc::erts::process::{Priority, Process, Status};
+use liblumen_alloc::erts::process::gc::GcError;
+use liblumen_alloc::erts::process::{Priority, Process, ProcessFlags, Status};
 pub use liblumen_alloc::erts::scheduler::{id, ID};
 use liblumen_alloc::erts::term::prelude::*;
 
 use lumen_rt_core::registry::put_pid_to_process;
 use lumen_rt_core::scheduler::{run_queue, Run};
+use lumen_rt_core::system;
+use lumen_rt_core::time::{monotonic, Milliseconds};
 use lumen_rt_core::timer::Hierarchy;
 
 use crate::process;
 use crate::process::spawn;
 use crate::process::spawn::options::{Connection, Options};
 
+// How long an idle scheduler parks before trying to steal again, as a process enqueued on another
+// scheduler doesn't unpark it
+const STEAL_CHECK_MILLISECONDS: Milliseconds = 100;
+
 pub trait Scheduled {
     fn scheduler(&self) -> Option<Arc<Scheduler>>;
 }
@@ -50,9 +62,26 @@ pub struct Scheduler {
     // Non-monotonic unique integers are scoped to the scheduler ID and then use this per-scheduler
     // `u64`.
     unique_integer: AtomicU64,
+    // The thread running this scheduler, so that other threads can wake it when it is parked.  Only
+    // set the first time the scheduler parks, as schedulers that are only driven by `run_once`
+    // never need waking.
+    thread: Mutex<Option<Thread>>,
+    wall_time: Mutex<WallTime>,
 }
 
 impl Scheduler {
+    /// All schedulers that are still registered, in `id` order
+    pub fn all() -> Vec<Arc<Scheduler>> {
+        let mut arc_scheduler_vec: Vec<Arc<Scheduler>> = SCHEDULER_BY_ID
+            .lock()
+            .values()
+            .filter_map(|weak_scheduler| weak_scheduler.upgrade())
+            .collect();
+        arc_scheduler_vec.sort_by_key(|arc_scheduler| arc_scheduler.id);
+
+        arc_scheduler_vec
+    }
+
     pub fn current() -> Arc<Scheduler> {
         SCHEDULER.with(|thread_local_scheduler| thread_local_scheduler.clone())
     }
@@ -84,15 +113,27 @@ impl Scheduler {
         self.unique_integer.fetch_add(1, Ordering::SeqCst)
     }
 
-    // TODO: Request application master termination for controlled shutdown
-    // This request will always come from the thread which spawned the application
-    // master, i.e. the "main" scheduler thread
-    //
-    // Returns `Ok(())` if shutdown was successful, `Err(anyhow::Error)` if something
-    // went wrong during shutdown, and it was not able to complete normally
+    /// Shuts down the system in an orderly way: all schedulers stop running processes, then the
+    /// exit hooks are run.
+    ///
+    /// This is called from the main scheduler thread, either after a process called
+    /// `erlang:halt/0,1,2`, or when the system is interrupted.
+    ///
+    /// Returns `Ok(())` if shutdown was successful, `Err(anyhow::Error)` if something
+    /// went wrong during shutdown, and it was not able to complete normally
     pub fn shutdown(&self) -> anyhow::Result<()> {
-        // For now just Ok(()), but this needs to be addressed when proper
-        // system startup/shutdown is in place
+        // If nothing called `halt`, the system still halts, but successfully
+        system::request_halt(0);
+
+        // Parked schedulers only notice the halt when they wake up
+        for arc_scheduler in Self::all() {
+            if arc_scheduler.id != self.id {
+                arc_scheduler.unpark();
+            }
+        }
+
+        system::run_exit_hooks();
+
         Ok(())
     }
 
@@ -106,10 +147,68 @@ impl Scheduler {
     /// > 8. Pick a process to execute
     /// > -- [The Scheduler Loop](https://blog.stenmans.org/theBeamBook/#_the_scheduler_loop)
     pub fn run(&self) {
-        loop {
-            // TODO sleep or steal if nothing run
-            let _ = self.run_once();
+        while system::halt_status().is_none() {
+            if !self.run_once() && !self.steal() {
+                self.park(Some(STEAL_CHECK_MILLISECONDS));
+            }
+        }
+    }
+
+    /// Parks the scheduler's thread until a process in its run queues becomes runnable, the next
+    /// timer in its `hierarchy` is due to time out, or `max_milliseconds` pass, whichever is first.
+    /// Returns immediately if there is already a runnable process.
+    ///
+    /// While parked, the scheduler is not counted as active in its `wall_time`.
+    pub fn park(&self, max_milliseconds: Option<Milliseconds>) {
+        // Register the thread before checking the run queues, so that a process made runnable
+        // after the check unparks the thread instead of being missed.
+        self.thread.lock().get_or_insert_with(thread::current);
+
+        if self.has_runnable() {
+            return;
         }
+
+        let timeout_milliseconds = match (
+            self.hierarchy.read().milliseconds_until_next_timeout(),
+            max_milliseconds,
+        ) {
+            (Some(timeout_milliseconds), Some(max_milliseconds)) => {
+                Some(timeout_milliseconds.min(max_milliseconds))
+            }
+            (timeout_milliseconds, None) => timeout_milliseconds,
+            (None, max_milliseconds) => max_milliseconds,
+        };
+
+        if timeout_milliseconds == Some(0) {
+            return;
+        }
+
+        self.wall_time.lock().inactive();
+
+        match timeout_milliseconds {
+            Some(timeout_milliseconds) => {
+                thread::park_timeout(Duration::from_millis(timeout_milliseconds))
+            }
+            None => thread::park(),
+        }
+
+        self.wall_time.lock().active();
+    }
+
+    /// Steals a runnable process from another scheduler's run queues into this scheduler's run
+    /// queues.  Returns `true` if a process was stolen.
+    pub fn steal(&self) -> bool {
+        Self::all()
+            .iter()
+            .filter(|victim| victim.id != self.id)
+            .any(|victim| self.steal_from(victim))
+    }
+
+    /// Milliseconds this scheduler has been active, i.e. not parked, and milliseconds since it was
+    /// created, like a `{SchedulerId, ActiveTime, TotalTime}` of
+    /// `erlang:statistics(scheduler_wall_time)`.
+    pub fn wall_time(&self) -> (Milliseconds, Milliseconds) {
+        self.wall_time.lock().get()
     }
 
     /// > 1. Update reduction counters
@@ -143,14 +242,7 @@ impl Scheduler {
                         match Process::run(&arc_process) {
                             Ok(()) => (),
                             Err(exception) => match exception {
-                                SystemException::Alloc(_) => {
-                                    match arc_process.garbage_collect(0, &mut []) {
-                                        Ok(_freed) => (),
-                                        Err(gc_err) => {
-                                            panic!("fatal garbage collection error: {:?}", gc_err)
-                                        }
-                                    }
-                                }
+                                SystemException::Alloc(_) => Self::garbage_collect(&arc_process),
                                 err => panic!("system error: {}", err),
                             },
                         }
@@ -172,12 +264,34 @@ impl Scheduler {
                     break true;
                 }
                 Run::Delayed => continue,
-                // TODO steal processes or sleep if nothing to steal
+                // The caller decides whether to `steal` or `park`
                 Run::None => break false,
             }
         }
     }
 
+    /// Collects `arc_process` after it ran out of heap, following up with a full sweep if a minor
+    /// collection is not enough.  If the process exceeds its `max_heap_size` and is configured to be
+    /// killed, it is exited with `killed`.
+    fn garbage_collect(arc_process: &Process) {
+        let result = match arc_process.garbage_collect(0, &mut []) {
+            Err(GcError::FullsweepRequired) => {
+                arc_process.set_flags(ProcessFlags::NeedFullSweep);
+
+                arc_process.garbage_collect(0, &mut [])
+            }
+            result => result,
+        };
+
+        match result {
+            Ok(_freed) => (),
+            Err(GcError::MaxHeapSizeExceeded) => {
+                arc_process.exit(atom!("killed"), anyhow!("maximum heap size exceeded").into())
+            }
+            Err(gc_err) => panic!("fatal garbage collection error: {:?}", gc_err),
+        }
+    }
+
     pub fn run_queues_len(&self) -> usize {
         self.run_queues.read().len()
     }
@@ -218,6 +332,9 @@ impl Scheduler {
         let arc_process = Arc::new(process);
 
         writable_run_queues.enqueue(Arc::clone(&arc_process));
+        drop(writable_run_queues);
+
+        self.unpark();
 
         arc_process
     }
@@ -291,6 +408,9 @@ impl Scheduler {
         let mut writable_run_queues = self.run_queues.write();
 
         writable_run_queues.enqueue(Arc::clone(&arc_process));
+        drop(writable_run_queues);
+
+        self.unpark();
 
         put_pid_to_process(&arc_process);
 
@@ -299,10 +419,15 @@ impl Scheduler {
 
     pub fn stop_waiting(&self, process: &Process) {
         self.run_queues.write().stop_waiting(process);
+        self.unpark();
     }
 
     // Private
 
+    fn has_runnable(&self) -> bool {
+        0 < self.run_queues.read().runnable_len()
+    }
+
     fn new() -> Scheduler {
         Scheduler {
             id: id::next(),
@@ -310,6 +435,8 @@ impl Scheduler {
             reference_count: AtomicU64::new(0),
             run_queues: Default::default(),
             unique_integer: AtomicU64::new(0),
+            thread: Mutex::new(None),
+            wall_time: Mutex::new(WallTime::new()),
         }
     }
 
@@ -331,6 +458,30 @@ impl Scheduler {
 
         arc_scheduler
     }
+
+    /// Moves one runnable process from `victim`'s run queues to this scheduler's run queues.
+    ///
+    /// Only one run queue lock is held at a time, so that two schedulers stealing from each other
+    /// cannot deadlock.
+    fn steal_from(&self, victim: &Scheduler) -> bool {
+        let option_arc_process = victim.run_queues.write().steal(|_| true);
+
+        match option_arc_process {
+            Some(arc_process) => {
+                arc_process.schedule_with(self.id);
+                self.run_queues.write().enqueue(arc_process);
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn unpark(&self) {
+        if let Some(thread) = self.thread.lock().as_ref() {
+            thread.unpark();
+        }
+    }
 }
 
 impl Debug for Scheduler {
@@ -379,6 +530,49 @@ impl Spawned {
     }
 }
 
+/// Active and total wall time of a scheduler in monotonic milliseconds
+struct WallTime {
+    created: Milliseconds,
+    active: Milliseconds,
+    // `None` while parked
+    active_since: Option<Milliseconds>,
+}
+
+impl WallTime {
+    fn new() -> Self {
+        let now = monotonic::time_in_milliseconds();
+
+        Self {
+            created: now,
+            active: 0,
+            active_since: Some(now),
+        }
+    }
+
+    fn active(&mut self) {
+        if self.active_since.is_none() {
+            self.active_since = Some(monotonic::time_in_milliseconds());
+        }
+    }
+
+    fn get(&self) -> (Milliseconds, Milliseconds) {
+        let now = monotonic::time_in_milliseconds();
+        let active = match self.active_since {
+            Some(active_since) => self.active + now.saturating_sub(active_since),
+            None => self.active,
+        };
+        let total = now.saturating_sub(self.created);
+
+        (active, total)
+    }
+
+    fn inactive(&mut self) {
+        if let Some(active_since) = self.active_since.take() {
+            self.active += monotonic::time_in_milliseconds().saturating_sub(active_since);
+        }
+    }
+}
+
 thread_local! {
   static SCHEDULER: Arc<Scheduler> = Scheduler::registered();
 }
@@ -1,6 +1,7 @@
 mod spawn_apply_3;
 
 use std::sync::Arc;
+use std::thread;
 
 use anyhow::*;
 
@@ -51,6 +52,41 @@ fn scheduler_does_run_exiting_process() {
     assert!(!scheduler.is_run_queued(&arc_process));
 }
 
+#[test]
+fn scheduler_parks_only_when_there_is_no_runnable_process() {
+    let arc_process = test::process::default();
+    let scheduler = Scheduler::current();
+
+    assert!(scheduler.is_run_queued(&arc_process));
+
+    // Would block forever if it parked
+    scheduler.park(None);
+}
+
+#[test]
+fn scheduler_steals_runnable_process_from_other_scheduler() {
+    let arc_process = test::process::default();
+    let victim = Scheduler::current();
+
+    assert!(victim.is_run_queued(&arc_process));
+
+    let stolen_arc_process = arc_process.clone();
+    let thief_id = thread::spawn(move || {
+        let thief = Scheduler::current();
+
+        // The child was enqueued after its init parent, so it is at the back of the run queue
+        assert!(thief.steal_from(&victim));
+        assert!(thief.is_run_queued(&stolen_arc_process));
+        assert!(!victim.is_run_queued(&stolen_arc_process));
+
+        thief.id
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(arc_process.scheduler_id(), Some(thief_id));
+}
+
 fn exit_1_place_frame_with_arguments(
     process: &Process,
     placement: Placement,
@@ -1,29 +1,518 @@
-#![allow(dead_code)]
-#![allow(unused_imports)]
-/// ## Algorithms
-///
-/// * 'exrop' - Xoroshiro116+, 58 bits precision and period of 2^116-1 (jump equivalent to 2^64
-///   calls)
-/// * 'exs1024s' - Xorshift1024*, 64 bits precision and period of 2^1024-1 (jump equivalent to
-///   2^512)
-/// * 'exsp' - Xorshift116+, 58 bits precision and period of 2^116-1 (jump equivalent to 2^64)
-///
-/// Default is 'exrop'
-///
-/// ## Implementation Overview
-///
-/// Every time a random number is requested, a state is used to calculate it and a new state is
-/// produced. The state can either be implicit or be an explicit argument and return value.
-///
-/// The functions with implicit state use the process dictionary variable rand_seed to remember
-/// the current state.
-///
-/// If a process calls uniform/0, uniform/1 or uniform_real/0 without setting a seed first,
-/// seed/1 is called automatically with the default algorithm and creates a non-constant seed.
-///
-/// The functions with explicit state never use the process dictionary.
-use xorshift::*;
-
-pub extern "C" fn uniform() {
-    unimplemented!()
+//! Pseudo random number generation for the `rand` module
+//!
+//! ## Algorithms
+//!
+//! * 'exrop' - Xoroshiro116+, 58 bits precision and period of 2^116-1 (jump equivalent to 2^64
+//!   calls)
+//! * 'exs1024s' - Xorshift1024*, 64 bits precision and period of 2^1024-1 (jump equivalent to
+//!   2^512)
+//! * 'exsp' - Xorshift116+, 58 bits precision and period of 2^116-1 (jump equivalent to 2^64)
+//!
+//! Default is 'exrop'
+//!
+//! ## Implementation Overview
+//!
+//! Every time a random number is requested, a state is used to calculate it and a new state is
+//! produced. The state can either be implicit or be an explicit argument and return value.
+//!
+//! The functions with implicit state use the process dictionary variable rand_seed to remember
+//! the current state.
+//!
+//! If a process calls uniform/0, uniform/1 or uniform_real/0 without setting a seed first,
+//! seed/1 is called automatically with the default algorithm and creates a non-constant seed.
+//!
+//! The functions with explicit state never use the process dictionary.
+//!
+//! ## Compatibility
+//!
+//! For the same seed, every function produces the same sequence as the `rand` module in OTP, so
+//! states can be exported from one runtime and imported into the other.
+mod exrop;
+mod exs1024s;
+mod exsp;
+mod normal;
+
+use std::collections::hash_map::DefaultHasher;
+use std::convert::{TryFrom, TryInto};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::*;
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, ToPrimitive, Zero};
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::AllocResult;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::system::time::system_time;
+
+/// 2^-53, which turns 53 random bits into a float in `[0.0, 1.0)`
+const TWO_POW_MINUS53: f64 = 1.0 / ((1_u64 << 53) as f64);
+
+/// The name of the process dictionary key holding the implicit state
+pub fn seed_key() -> Term {
+    atom!("rand_seed")
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    Exrop,
+    Exs1024s,
+    Exsp,
+}
+
+impl Algorithm {
+    pub const DEFAULT: Algorithm = Algorithm::Exrop;
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Exrop => "exrop",
+            Algorithm::Exs1024s => "exs1024s",
+            Algorithm::Exsp => "exsp",
+        }
+    }
+
+    /// The number of bits in each generated integer
+    fn bits(self) -> u32 {
+        match self {
+            Algorithm::Exrop | Algorithm::Exsp => 58,
+            Algorithm::Exs1024s => 64,
+        }
+    }
+
+    /// The number of low bits in each generated integer that are not as random as the others
+    fn weak_low_bits(self) -> u32 {
+        match self {
+            Algorithm::Exrop | Algorithm::Exsp => 1,
+            Algorithm::Exs1024s => 3,
+        }
+    }
+}
+
+const SUPPORTED_ALGORITHMS_CONTEXT: &str = "supported algorithms are exrop, exs1024s, and exsp";
+
+impl TryFrom<Term> for Algorithm {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let atom: Atom = term.try_into().context(SUPPORTED_ALGORITHMS_CONTEXT)?;
+
+        match atom.name() {
+            "exrop" => Ok(Algorithm::Exrop),
+            "exs1024s" => Ok(Algorithm::Exs1024s),
+            "exsp" => Ok(Algorithm::Exsp),
+            name => Err(anyhow!("algorithm ({}) is not supported", name))
+                .context(SUPPORTED_ALGORITHMS_CONTEXT),
+        }
+    }
+}
+
+/// The traditional `{A1, A2, A3}` integer triplet seed.  Only the low bits of each integer are
+/// used, so they are kept as their low 64 bits in two's complement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Seed(pub [u64; 3]);
+
+impl Seed {
+    /// A seed that differs between processes and between calls, like OTP's
+    /// `{erlang:phash2([{node(),self()}]), erlang:system_time(), erlang:unique_integer()}`
+    pub fn non_constant(process: &Process) -> Self {
+        static UNIQUE_INTEGER: AtomicU64 = AtomicU64::new(0);
+
+        let mut hasher = DefaultHasher::new();
+        process.pid().hash(&mut hasher);
+
+        Seed([
+            hasher.finish(),
+            system_time().as_nanos() as u64,
+            UNIQUE_INTEGER.fetch_add(1, Ordering::SeqCst),
+        ])
+    }
+}
+
+const SEED_CONTEXT: &str = "seed must be a tuple of 3 integers";
+
+impl TryFrom<Term> for Seed {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let tuple: Boxed<Tuple> = term.try_into().context(SEED_CONTEXT)?;
+
+        if tuple.len() == 3 {
+            let mut seed = [0; 3];
+
+            for (integer, element) in seed.iter_mut().zip(tuple.iter()) {
+                let big_int = term_to_big_int(*element)
+                    .with_context(|| format!("seed element ({}) is not an integer", element))
+                    .context(SEED_CONTEXT)?;
+                *integer = low_u64(&big_int);
+            }
+
+            Ok(Seed(seed))
+        } else {
+            Err(anyhow!("seed ({}) does not have 3 elements", term)).context(SEED_CONTEXT)
+        }
+    }
+}
+
+/// The state of a generator.  As a term, it is OTP's `{AlgHandler, AlgState}`, where
+/// `AlgHandler` is a map of the algorithm's properties, or its exported form `{Alg, AlgState}`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum State {
+    Exrop(exrop::State),
+    Exs1024s(exs1024s::State),
+    Exsp(exsp::State),
+}
+
+impl State {
+    pub fn seed(algorithm: Algorithm, seed: Seed) -> Self {
+        match algorithm {
+            Algorithm::Exrop => State::Exrop(exrop::State::seed(seed)),
+            Algorithm::Exs1024s => State::Exs1024s(exs1024s::State::seed(seed)),
+            Algorithm::Exsp => State::Exsp(exsp::State::seed(seed)),
+        }
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            State::Exrop(_) => Algorithm::Exrop,
+            State::Exs1024s(_) => Algorithm::Exs1024s,
+            State::Exsp(_) => Algorithm::Exsp,
+        }
+    }
+
+    /// A float uniformly distributed in `[0.0, 1.0)`
+    pub fn uniform(&mut self) -> f64 {
+        let bits = self.algorithm().bits();
+        let v = self.next();
+
+        (v >> (bits - 53)) as f64 * TWO_POW_MINUS53
+    }
+
+    /// An integer uniformly distributed in `1..=n`.  `n` must be positive.
+    pub fn uniform_n(&mut self, n: &BigInt) -> BigInt {
+        let max = 1_u128 << self.algorithm().bits();
+
+        match n.to_u128() {
+            Some(n_u128) if n_u128 <= max => {
+                let max_minus_n = max - n_u128;
+
+                loop {
+                    let v = self.next() as u128;
+
+                    // Really work saving in odd cases; large ranges in particular
+                    if v < n_u128 {
+                        break (v + 1).into();
+                    }
+
+                    let i = v % n_u128;
+
+                    // Otherwise `v` is in the truncated top range, which would make low numbers
+                    // more likely
+                    if v - i <= max_minus_n {
+                        break (i + 1).into();
+                    }
+                }
+            }
+            _ => {
+                let v = self.next();
+
+                self.uniform_range(n, v)
+            }
+        }
+    }
+
+    /// A float uniformly distributed in `(0.0, 1.0)` where, unlike `uniform`, small numbers have
+    /// their full 53 bits of precision.
+    pub fn uniform_real(&mut self) -> f64 {
+        let bits = self.algorithm().bits();
+        let m1 = self.next() >> (bits - 56);
+
+        if let Some(float) = uniform_real_from_56_bits(m1, 0) {
+            float
+        } else {
+            // Need more bits
+            let v2 = self.next();
+            self.uniform_real_fill(m1, -56, v2)
+        }
+    }
+
+    /// A float from the standard normal distribution, N(0, 1)
+    pub fn normal(&mut self) -> f64 {
+        normal::normal(self)
+    }
+
+    pub fn bytes(&mut self, n: usize) -> Vec<u8> {
+        let algorithm = self.algorithm();
+        let bits = algorithm.bits();
+        // Whole bytes are used from each generated number, but not the weak low bits
+        let good_bytes = ((bits - algorithm.weak_low_bits()) >> 3) as usize;
+        let good_bits = (good_bytes << 3) as u32;
+        let shift = bits - good_bits;
+
+        let mut bytes = Vec::with_capacity(n);
+        let mut remaining = n;
+
+        while good_bytes < remaining {
+            let v = self.next() >> shift;
+            bytes.extend_from_slice(&v.to_be_bytes()[8 - good_bytes..]);
+            remaining -= good_bytes;
+        }
+
+        // As in OTP's last `bytes_r` clause, the last bytes are the ones just below bit
+        // `good_bits`, not the high bytes of the number with the weak low bits shifted out
+        let v = self
+            .next()
+            .checked_shr(good_bits - ((remaining as u32) << 3))
+            .unwrap_or(0);
+        bytes.extend_from_slice(&v.to_be_bytes()[8 - remaining..]);
+
+        bytes
+    }
+
+    /// Advances the state as if `next` was called 2^64 times, or 2^512 times for `exs1024s`, so
+    /// that the jumped states of one seed can be used for non-overlapping sequences.
+    pub fn jump(&mut self) {
+        match self {
+            State::Exrop(state) => state.jump(),
+            State::Exs1024s(state) => state.jump(),
+            State::Exsp(state) => state.jump(),
+        }
+    }
+
+    /// `{AlgHandler, AlgState}`
+    pub fn to_term(&self, process: &Process) -> AllocResult<Term> {
+        let algorithm = self.algorithm();
+        let bits = process.integer(algorithm.bits() as usize)?;
+        let weak_low_bits = process.integer(algorithm.weak_low_bits() as usize)?;
+        let alg_handler = process.map_from_slice(&[
+            (atom!("type"), Atom::str_to_term(algorithm.name())),
+            (atom!("bits"), bits),
+            (atom!("weak_low_bits"), weak_low_bits),
+        ])?;
+        let alg_state = self.alg_state_to_term(process)?;
+
+        process.tuple_from_slice(&[alg_handler, alg_state])
+    }
+
+    /// `{Alg, AlgState}`, as returned by `export_seed`
+    pub fn to_export_term(&self, process: &Process) -> AllocResult<Term> {
+        let alg_state = self.alg_state_to_term(process)?;
+
+        process.tuple_from_slice(&[Atom::str_to_term(self.algorithm().name()), alg_state])
+    }
+
+    // Private
+
+    fn next(&mut self) -> u64 {
+        match self {
+            State::Exrop(state) => state.next(),
+            State::Exs1024s(state) => state.next(),
+            State::Exsp(state) => state.next(),
+        }
+    }
+
+    fn alg_state_to_term(&self, process: &Process) -> AllocResult<Term> {
+        match self {
+            State::Exrop(state) => state.to_term(process),
+            State::Exs1024s(state) => state.to_term(process),
+            State::Exsp(state) => state.to_term(process),
+        }
+    }
+
+    /// For ranges larger than the algorithm's bits, more bits are shifted in from further numbers
+    fn uniform_range(&mut self, range: &BigInt, mut v: u64) -> BigInt {
+        let algorithm = self.algorithm();
+        let bits = algorithm.bits();
+        let weak_low_bits = algorithm.weak_low_bits();
+        let range_minus_1: BigInt = range - 1;
+
+        loop {
+            if (range & &range_minus_1).is_zero() {
+                // Power of 2, so generate at least the number of bits for the range
+                let (v1, _) = self.uniform_range_bits(range >> bits as usize, v, weak_low_bits);
+
+                return (v1 & range_minus_1) + 1;
+            } else {
+                // Generate a value with two bits more than the range, so the probability of
+                // drawing a value in the truncated top range is under 0.25
+                let (v1, b) =
+                    self.uniform_range_bits(range >> (bits - 2) as usize, v, weak_low_bits);
+                let i = &v1 % range;
+
+                if v1 - &i <= (BigInt::one() << b) - range {
+                    return i + 1;
+                } else {
+                    v = self.next();
+                }
+            }
+        }
+    }
+
+    fn uniform_range_bits(
+        &mut self,
+        mut range: BigInt,
+        v: u64,
+        weak_low_bits: u32,
+    ) -> (BigInt, usize) {
+        let bits = self.algorithm().bits() as usize;
+        // Waste the weak low bits when shifting in new bits
+        let shift = bits - weak_low_bits as usize;
+        let shift_mask = !((BigInt::one() << weak_low_bits as usize) - 1);
+        let mut v: BigInt = v.into();
+        let mut b = bits;
+
+        while BigInt::one() < range {
+            let v1 = self.next();
+            range = range >> shift;
+            v = ((v & &shift_mask) << shift) | BigInt::from(v1);
+            b += shift;
+        }
+
+        (v, b)
+    }
+
+    /// `m0` has fewer than 53 bits, so fill it up from `v1`, generating more numbers as long as
+    /// they are all zeros
+    fn uniform_real_fill(&mut self, mut m0: u64, mut bit_no: i32, mut v1: u64) -> f64 {
+        let bits = self.algorithm().bits();
+
+        loop {
+            // `-1064` is a very theoretical bottom case, only reached by a malfunctioning
+            // generator producing 18 56-bit zeros in a row
+            if m0 != 0 || bit_no == -1064 {
+                // Fill up to 53 bits
+                let b0 = 53 - significant_bits(m0);
+                let m = (m0 << b0) | (v1 >> (bits - b0));
+
+                break m as f64 * pow2(bit_no - b0 as i32);
+            }
+
+            let m1 = v1 >> (bits - 56);
+
+            if let Some(float) = uniform_real_from_56_bits(m1, bit_no) {
+                break float;
+            }
+
+            // For the last round there must be at least 43 bits in `m1`, or the float would
+            // underflow
+            if bit_no == -1008 && (1 << 42) <= m1 {
+                break m1 as f64 * pow2(bit_no - 56);
+            }
+
+            m0 = m1;
+            bit_no -= 56;
+            v1 = self.next();
+        }
+    }
+}
+
+impl TryFrom<Term> for State {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let tuple: Boxed<Tuple> = term
+            .try_into()
+            .with_context(|| format!("state ({}) is not a tuple", term))?;
+
+        if tuple.len() != 2 {
+            return Err(anyhow!("state ({}) does not have 2 elements", term));
+        }
+
+        let algorithm_term = match tuple[0].decode()? {
+            TypedTerm::Map(map) => map
+                .get(atom!("type"))
+                .with_context(|| format!("state ({}) algorithm handler has no type", term))?,
+            _ => tuple[0],
+        };
+        let algorithm: Algorithm = algorithm_term.try_into()?;
+        let alg_state = tuple[1];
+
+        let result = match algorithm {
+            Algorithm::Exrop => exrop::State::try_from(alg_state).map(State::Exrop),
+            Algorithm::Exs1024s => exs1024s::State::try_from(alg_state).map(State::Exs1024s),
+            Algorithm::Exsp => exsp::State::try_from(alg_state).map(State::Exsp),
+        };
+
+        result
+            .with_context(|| format!("state ({}) is not a valid {} state", term, algorithm.name()))
+    }
+}
+
+// Private
+
+/// Uses 53 bits of a 56-bit `m1` for a float, if it has at least that many
+fn uniform_real_from_56_bits(m1: u64, bit_no: i32) -> Option<f64> {
+    if (1 << 55) <= m1 {
+        // We have 56 bits - waste 3
+        Some((m1 >> 3) as f64 * pow2(bit_no - 53))
+    } else if (1 << 54) <= m1 {
+        // We have 55 bits - waste 2
+        Some((m1 >> 2) as f64 * pow2(bit_no - 54))
+    } else if (1 << 53) <= m1 {
+        // We have 54 bits - waste 1
+        Some((m1 >> 1) as f64 * pow2(bit_no - 55))
+    } else if (1 << 52) <= m1 {
+        // We have 53 bits - use all
+        Some(m1 as f64 * pow2(bit_no - 56))
+    } else {
+        None
+    }
+}
+
+/// 2^`exponent`, exactly, down to the smallest subnormal
+fn pow2(exponent: i32) -> f64 {
+    if -1022 <= exponent {
+        f64::from_bits(((exponent + 1023) as u64) << 52)
+    } else if -1074 <= exponent {
+        f64::from_bits(1 << (exponent + 1074))
+    } else {
+        0.0
+    }
+}
+
+fn significant_bits(v: u64) -> u32 {
+    64 - v.leading_zeros()
+}
+
+fn term_to_big_int(term: Term) -> Option<BigInt> {
+    match term.decode() {
+        Ok(TypedTerm::SmallInteger(small_integer)) => {
+            let small_isize: isize = small_integer.into();
+
+            Some(small_isize.into())
+        }
+        Ok(TypedTerm::BigInteger(big_integer)) => Some(big_integer.into()),
+        _ => None,
+    }
+}
+
+/// The low 64 bits of `big_int` in two's complement
+fn low_u64(big_int: &BigInt) -> u64 {
+    let bytes = big_int.to_signed_bytes_le();
+    let sign_extension = if big_int.sign() == Sign::Minus {
+        0xFF
+    } else {
+        0
+    };
+    let mut low_bytes = [sign_extension; 8];
+
+    for (low_byte, byte) in low_bytes.iter_mut().zip(bytes.iter()) {
+        *low_byte = *byte;
+    }
+
+    u64::from_le_bytes(low_bytes)
+}
+
+/// Converts an element of an algorithm state, which must be a non-negative integer with at most
+/// `bits` bits
+fn term_try_into_state_integer(term: Term, bits: u32) -> anyhow::Result<u64> {
+    match term_to_big_int(term) {
+        Some(big_int) if big_int.sign() != Sign::Minus && big_int.bits() <= bits as usize => {
+            Ok(low_u64(&big_int))
+        }
+        _ => Err(TypeError)
+            .with_context(|| format!("{} is not a {}-bit non-negative integer", term, bits)),
+    }
 }
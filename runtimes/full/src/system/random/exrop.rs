@@ -0,0 +1,103 @@
+//! Xoroshiro116+ with the state as `[S0|S1]`
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::AllocResult;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use super::{term_try_into_state_integer, Seed};
+
+const MASK_58: u64 = (1 << 58) - 1;
+
+/// x^(2^64) modulo the characteristic polynomial of the generator
+const JUMP: u128 = 0x9863200f83fcd4a11293241fcb12a;
+const JUMP_BITS: u32 = 116;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct State {
+    s0: u64,
+    s1: u64,
+}
+
+impl State {
+    pub fn seed(Seed([a1, a2, a3]): Seed) -> Self {
+        let State { s1, .. } = State {
+            s0: a1.wrapping_mul(4294967197).wrapping_add(1) & MASK_58,
+            s1: a2.wrapping_mul(4294967231).wrapping_add(1) & MASK_58,
+        }
+        .next_state();
+
+        State {
+            s0: a3.wrapping_mul(4294967279).wrapping_add(1) & MASK_58,
+            s1,
+        }
+        .next_state()
+    }
+
+    pub fn next(&mut self) -> u64 {
+        let v = self.s0.wrapping_add(self.s1) & MASK_58;
+        *self = self.next_state();
+
+        v
+    }
+
+    pub fn jump(&mut self) {
+        let mut jumped = State { s0: 0, s1: 0 };
+        let mut state = self.clone();
+
+        for bit in 0..JUMP_BITS {
+            if (JUMP >> bit) & 1 == 1 {
+                jumped.s0 ^= state.s0;
+                jumped.s1 ^= state.s1;
+            }
+
+            state = state.next_state();
+        }
+
+        *self = jumped;
+    }
+
+    pub fn to_term(&self, process: &Process) -> AllocResult<Term> {
+        let s0 = process.integer(self.s0)?;
+        let s1 = process.integer(self.s1)?;
+
+        process.cons(s0, s1)
+    }
+
+    // Private
+
+    fn next_state(&self) -> State {
+        let s1_a = self.s1 ^ self.s0;
+
+        State {
+            s0: rotl58(self.s0, 24) ^ s1_a ^ bsl58(s1_a, 2),
+            s1: rotl58(s1_a, 35),
+        }
+    }
+}
+
+impl TryFrom<Term> for State {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let cons: Boxed<Cons> = term
+            .try_into()
+            .context("exrop state must be an improper list [S0|S1]")?;
+
+        Ok(State {
+            s0: term_try_into_state_integer(cons.head, 58)?,
+            s1: term_try_into_state_integer(cons.tail, 58)?,
+        })
+    }
+}
+
+/// Shifts `x` left by `n`, dropping the bits shifted past 58 bits
+pub(super) fn bsl58(x: u64, n: u32) -> u64 {
+    (x << n) & MASK_58
+}
+
+fn rotl58(x: u64, n: u32) -> u64 {
+    bsl58(x, n) | (x >> (58 - n))
+}
@@ -0,0 +1,193 @@
+//! Xorshift1024* with the state as `{L, RL}`, a ring buffer of 16 integers split into the list
+//! `L`, whose head is the current integer, and the reversed list `RL` of the integers before it
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::AllocResult;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use super::{term_try_into_state_integer, Seed};
+
+const LEN: usize = 16;
+
+/// x^(2^512) modulo the characteristic polynomial of the generator, least significant word first
+const JUMP: [u64; LEN] = [
+    0x84242f96eca9c41d,
+    0xa3c65b8776f96855,
+    0x5b34a39f070b5837,
+    0x4489affce4f31a1e,
+    0x2ffeeb0a48316f40,
+    0xdc2d9891fe68c022,
+    0x3659132bb12fea70,
+    0xaac17d8efa43cab8,
+    0xc4cb815590989b13,
+    0x5ee975283d71c93b,
+    0x691548c86c1bd540,
+    0x7910c41d10a1e6a5,
+    0x0b5fc64563b3e2a8,
+    0x047f7684e9fc949d,
+    0xb99181f2d8f685ca,
+    0x284600e3f30e38c3,
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct State {
+    ring: [u64; LEN],
+    /// The index of the current integer in `ring`
+    p: usize,
+    /// The length of `RL`, which is not `p`, as `{L, RL}` is only rotated when `L` runs out
+    rl_len: usize,
+}
+
+impl State {
+    pub fn seed(Seed([a1, a2, a3]): Seed) -> Self {
+        let b1 = (((a1 & MASK_21) + 1) * 2097131) & MASK_21;
+        let b2 = (((a2 & MASK_21) + 1) * 2097133) & MASK_21;
+        let b3 = (((a3 & MASK_21) + 1) * 2097143) & MASK_21;
+        let mut r = (b1 << 43) | (b2 << 22) | (b3 << 1) | 1;
+
+        // Xorshift64* fills the list in reverse
+        let mut ring = [0; LEN];
+
+        for integer in ring.iter_mut().rev() {
+            let (x, next_r) = exs64_next(r);
+            *integer = x;
+            r = next_r;
+        }
+
+        State {
+            ring,
+            p: 0,
+            rl_len: 0,
+        }
+    }
+
+    pub fn next(&mut self) -> u64 {
+        let s0 = self.ring[self.p];
+        let p1 = (self.p + 1) % LEN;
+        let s1 = self.ring[p1];
+
+        let s1_1 = s1 ^ (s1 << 31);
+        let s1_2 = s1_1 ^ (s1_1 >> 11);
+        let s0_1 = s0 ^ (s0 >> 30);
+        let ns1 = s0_1 ^ s1_2;
+
+        self.ring[p1] = ns1;
+        self.p = p1;
+        self.rl_len = (self.rl_len % (LEN - 1)) + 1;
+
+        ns1.wrapping_mul(1181783497276652981)
+    }
+
+    pub fn jump(&mut self) {
+        let mut jumped = [0; LEN];
+        let mut state = self.clone();
+
+        for word in JUMP.iter() {
+            for bit in 0..64 {
+                if (word >> bit) & 1 == 1 {
+                    for (jumped_integer, integer) in jumped.iter_mut().zip(state.current_ring()) {
+                        *jumped_integer ^= integer;
+                    }
+                }
+
+                state.next();
+            }
+        }
+
+        // Jumping a multiple of `LEN` keeps the split of `{L, RL}`
+        self.ring = jumped;
+        self.p = 0;
+    }
+
+    pub fn to_term(&self, process: &Process) -> AllocResult<Term> {
+        let mut integers = Vec::with_capacity(LEN);
+
+        for integer in self.current_ring() {
+            integers.push(process.integer(integer)?);
+        }
+
+        let (l, rl) = integers.split_at_mut(LEN - self.rl_len);
+        rl.reverse();
+
+        let l_term = process.list_from_slice(l)?;
+        let rl_term = process.list_from_slice(rl)?;
+
+        process.tuple_from_slice(&[l_term, rl_term])
+    }
+
+    // Private
+
+    /// The integers in the ring, starting with the current one
+    fn current_ring(&self) -> impl Iterator<Item = u64> + '_ {
+        self.ring[self.p..]
+            .iter()
+            .chain(self.ring[..self.p].iter())
+            .copied()
+    }
+}
+
+const STATE_CONTEXT: &str = "exs1024s state must be {L, RL} of 16 integers, where L is not empty";
+
+impl TryFrom<Term> for State {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let tuple: Boxed<Tuple> = term.try_into().context(STATE_CONTEXT)?;
+
+        if tuple.len() != 2 {
+            return Err(TypeError).context(STATE_CONTEXT);
+        }
+
+        let l = term_try_into_integers(tuple[0])?;
+        let mut rl = term_try_into_integers(tuple[1])?;
+
+        if l.is_empty() || l.len() + rl.len() != LEN {
+            return Err(TypeError).context(STATE_CONTEXT);
+        }
+
+        let rl_len = rl.len();
+        rl.reverse();
+
+        let mut ring = [0; LEN];
+
+        for (integer, element) in ring.iter_mut().zip(l.into_iter().chain(rl.into_iter())) {
+            *integer = element;
+        }
+
+        Ok(State { ring, p: 0, rl_len })
+    }
+}
+
+// Private
+
+const MASK_21: u64 = (1 << 21) - 1;
+
+fn exs64_next(r: u64) -> (u64, u64) {
+    let r1 = r ^ (r >> 12);
+    let r2 = r1 ^ (r1 << 25);
+    let r3 = r2 ^ (r2 >> 27);
+
+    (r3.wrapping_mul(2685821657736338717), r3)
+}
+
+fn term_try_into_integers(term: Term) -> anyhow::Result<Vec<u64>> {
+    let mut integers = Vec::with_capacity(LEN);
+
+    match term.decode()? {
+        TypedTerm::Nil => (),
+        TypedTerm::List(cons) => {
+            for result in cons.into_iter() {
+                let element = result
+                    .map_err(|_| ImproperListError)
+                    .context(STATE_CONTEXT)?;
+                integers.push(term_try_into_state_integer(element, 64)?);
+            }
+        }
+        _ => return Err(TypeError).context(STATE_CONTEXT),
+    }
+
+    Ok(integers)
+}
@@ -0,0 +1,98 @@
+//! Xorshift116+ with the state as `[S1|S0]`
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::AllocResult;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use super::exrop::bsl58;
+use super::{term_try_into_state_integer, Seed};
+
+const MASK_58: u64 = (1 << 58) - 1;
+
+/// x^(2^64) modulo the characteristic polynomial of the generator
+const JUMP: u128 = 0xd174a83e17de2302f8ea6bc32c797;
+const JUMP_BITS: u32 = 116;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct State {
+    s1: u64,
+    s0: u64,
+}
+
+impl State {
+    pub fn seed(Seed([a1, a2, a3]): Seed) -> Self {
+        let State { s0, .. } = State {
+            s1: a1.wrapping_mul(4294967197).wrapping_add(1) & MASK_58,
+            s0: a2.wrapping_mul(4294967231).wrapping_add(1) & MASK_58,
+        }
+        .next_state();
+
+        State {
+            s1: a3.wrapping_mul(4294967279).wrapping_add(1) & MASK_58,
+            s0,
+        }
+        .next_state()
+    }
+
+    pub fn next(&mut self) -> u64 {
+        let next_state = self.next_state();
+        let v = self.s0.wrapping_add(next_state.s0) & MASK_58;
+        *self = next_state;
+
+        v
+    }
+
+    pub fn jump(&mut self) {
+        let mut jumped = State { s1: 0, s0: 0 };
+        let mut state = self.clone();
+
+        for bit in 0..JUMP_BITS {
+            if (JUMP >> bit) & 1 == 1 {
+                jumped.s1 ^= state.s1;
+                jumped.s0 ^= state.s0;
+            }
+
+            state = state.next_state();
+        }
+
+        *self = jumped;
+    }
+
+    pub fn to_term(&self, process: &Process) -> AllocResult<Term> {
+        let s1 = process.integer(self.s1)?;
+        let s0 = process.integer(self.s0)?;
+
+        process.cons(s1, s0)
+    }
+
+    // Private
+
+    /// Members `s0` and `s1` are swapped compared to the reference implementation
+    fn next_state(&self) -> State {
+        let s1_1 = self.s1 ^ bsl58(self.s1, 24);
+        let s1_2 = s1_1 ^ self.s0 ^ (s1_1 >> 11) ^ (self.s0 >> 41);
+
+        State {
+            s1: self.s0,
+            s0: s1_2,
+        }
+    }
+}
+
+impl TryFrom<Term> for State {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let cons: Boxed<Cons> = term
+            .try_into()
+            .context("exsp state must be an improper list [S1|S0]")?;
+
+        Ok(State {
+            s1: term_try_into_state_integer(cons.head, 58)?,
+            s0: term_try_into_state_integer(cons.tail, 58)?,
+        })
+    }
+}
@@ -2,9 +2,15 @@ pub mod receive;
 
 use std::convert::TryInto;
 use std::panic;
+use std::slice;
 
+use liblumen_core::sys::dynamic_call::DynamicCallee;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::apply;
+use liblumen_alloc::erts::exception::AllocResult;
 use liblumen_alloc::erts::term::prelude::*;
-use liblumen_alloc::erts::Process;
+use liblumen_alloc::erts::{ModuleFunctionArity, Process};
 
 use lumen_rt_core::process::current_process;
 use lumen_rt_core::registry;
@@ -14,6 +20,11 @@ extern "Rust" {
     fn stop_waiting(proc: &Process);
 }
 
+extern "C" {
+    #[link_name = "__lumen_start_panic"]
+    fn start_panic(payload: Term) -> !;
+}
+
 #[export_name = "__lumen_builtin_send"]
 pub extern "C" fn builtin_send(to_term: Term, msg: Term) -> Term {
     let result = panic::catch_unwind(|| {
@@ -46,3 +57,126 @@ pub extern "C" fn builtin_send(to_term: Term, msg: Term) -> Term {
         Term::NONE
     }
 }
+
+/// Resolves the callee of a dynamic call to `module:function/arity` in the dispatch table
+///
+/// This is called by the dispatch functions generated with the symbol table. If `module`
+/// or `function` are not atoms, or the function doesn't exist, `None` is returned, and the
+/// dispatch function calls `builtin_raise_undef` instead.
+#[export_name = "__lumen_builtin_resolve"]
+pub extern "C" fn builtin_resolve(
+    module: Term,
+    function: Term,
+    arity: u8,
+) -> Option<DynamicCallee> {
+    let mfa = match (module.decode(), function.decode()) {
+        (Ok(TypedTerm::Atom(module)), Ok(TypedTerm::Atom(function))) => ModuleFunctionArity {
+            module,
+            function,
+            arity,
+        },
+        _ => return None,
+    };
+
+    apply::find_symbol(&mfa)
+}
+
+/// Raises `undef` for a dynamic call to `module:function` with the `arity` arguments at `argv`
+#[export_name = "__lumen_builtin_raise_undef"]
+#[unwind(allowed)]
+pub unsafe extern "C" fn builtin_raise_undef(
+    module: Term,
+    function: Term,
+    arity: usize,
+    argv: *const Term,
+) -> ! {
+    let args = slice::from_raw_parts(argv, arity);
+    let exception = undef(&current_process(), module, function, args).unwrap();
+
+    start_panic(exception)
+}
+
+/// Returns `{error, undef, [{Module, Function, Args, []}]}`, as raised by a call to an
+/// undefined function
+fn undef(process: &Process, module: Term, function: Term, args: &[Term]) -> AllocResult<Term> {
+    let args = process.list_from_slice(args)?;
+    let frame = process.tuple_from_slice(&[module, function, args, Term::NIL])?;
+    let stacktrace = process.list_from_slice(&[frame])?;
+
+    process.tuple_from_slice(&[atom!("error"), atom!("undef"), stacktrace])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use liblumen_alloc::erts::process::{alloc, Priority};
+
+    use super::*;
+
+    fn process() -> Process {
+        let (heap, heap_size) = alloc::default_heap().unwrap();
+
+        Process::new(
+            Priority::Normal,
+            None,
+            Arc::new(ModuleFunctionArity {
+                module: Atom::from_str("test"),
+                function: Atom::from_str("process"),
+                arity: 0,
+            }),
+            heap,
+            heap_size,
+        )
+    }
+
+    #[test]
+    fn resolve_without_atoms_returns_none() {
+        assert!(builtin_resolve(Term::NIL, atom!("function"), 0).is_none());
+        assert!(builtin_resolve(atom!("module"), Term::NIL, 0).is_none());
+    }
+
+    #[test]
+    fn undef_has_the_call_as_its_stacktrace() {
+        let process = process();
+        let module = atom!("module");
+        let function = atom!("function");
+        let args = [process.integer(1).unwrap(), atom!("two")];
+
+        let frame = process
+            .tuple_from_slice(&[
+                module,
+                function,
+                process.list_from_slice(&args).unwrap(),
+                Term::NIL,
+            ])
+            .unwrap();
+        let stacktrace = process.list_from_slice(&[frame]).unwrap();
+
+        assert_eq!(
+            undef(&process, module, function, &args).unwrap(),
+            process
+                .tuple_from_slice(&[atom!("error"), atom!("undef"), stacktrace])
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn undef_without_arguments_has_an_empty_argument_list() {
+        let process = process();
+        let module = atom!("module");
+        let function = atom!("function");
+
+        let frame = process
+            .tuple_from_slice(&[module, function, Term::NIL, Term::NIL])
+            .unwrap();
+        let stacktrace = process.list_from_slice(&[frame]).unwrap();
+
+        assert_eq!(
+            undef(&process, module, function, &[]).unwrap(),
+            process
+                .tuple_from_slice(&[atom!("error"), atom!("undef"), stacktrace])
+                .unwrap()
+        );
+    }
+}
@@ -3,6 +3,7 @@
 #![feature(naked_functions)]
 #![feature(termination_trait_lib)]
 #![feature(thread_local)]
+#![feature(unwind_attributes)]
 
 #[cfg(not(unix))]
 compile_error!("lumen_rt_minimal is only supported on unix targets!");
@@ -23,6 +24,8 @@ mod sys;
 use bus::Bus;
 use log::Level;
 
+use lumen_rt_core::system::{self, ExitStatus};
+
 use self::config::Config;
 use self::scheduler::Scheduler;
 use self::sys::break_handler::{self, Signal};
@@ -34,7 +37,7 @@ fn main() -> impl ::std::process::Termination + 'static {
     main_internal(name, version, Vec::new())
 }
 
-fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()> {
+fn main_internal(name: &str, version: &str, argv: Vec<String>) -> ExitStatus {
     self::env::init_argv_from_slice(std::env::args_os()).unwrap();
     // Load system configuration
     let _config = match Config::from_argv(name.to_string(), version.to_string(), argv) {
@@ -64,27 +67,23 @@ fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()>
         if let Ok(sig) = rx1.try_recv() {
             match sig {
                 // For now, SIGINT initiates a controlled shutdown
-                Signal::INT => {
-                    // If an error occurs, report it before shutdown
-                    if let Err(err) = scheduler.shutdown() {
-                        eprintln!("System error: {}", err);
-                        return Err(());
-                    } else {
-                        break;
-                    }
-                }
+                Signal::INT => break,
                 // Technically, we may never see these signals directly,
                 // we may just be terminated out of hand; but just in case,
                 // we handle them explicitly by immediately terminating, so
                 // that we are good citizens of the operating system
                 sig if sig.should_terminate() => {
-                    return Err(());
+                    return ExitStatus::FAILURE;
                 }
                 // All other signals can be surfaced to other parts of the
                 // system for custom use, e.g. SIGCHLD, SIGALRM, SIGUSR1/2
                 _ => (),
             }
         }
+        // A process called `erlang:halt`, so stop running processes
+        if system::halt_status().is_some() {
+            break;
+        }
         // If the scheduler scheduled a process this cycle, then we're busy
         // and should keep working until we have an idle period
         if scheduled {
@@ -94,11 +93,12 @@ fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()>
         break;
     }
 
+    // If an error occurs, report it before exiting
     match scheduler.shutdown() {
-        Ok(_) => Ok(()),
+        Ok(()) => ExitStatus::from_halt(),
         Err(err) => {
-            eprintln!("{}", err);
-            Err(())
+            eprintln!("System error: {}", err);
+            ExitStatus::FAILURE
         }
     }
 }
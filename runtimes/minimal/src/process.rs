@@ -11,6 +11,7 @@ use liblumen_alloc::erts::process::{Process, ProcessHeap};
 use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::{atom, CloneToProcess, HeapFragment, Monitor};
 
+use lumen_rt_core::ets;
 use lumen_rt_core::process::monitor;
 use lumen_rt_core::registry::*;
 use lumen_rt_core::Scheduler as SchedulerTrait;
@@ -87,6 +88,7 @@ pub fn monitor(process: &Process, monitored_process: &Process) -> AllocResult<Te
 pub fn propagate_exit(process: &Process, exception: &RuntimeException) {
     monitor::propagate_exit(process, exception);
     propagate_exit_to_links(process, exception);
+    ets::delete_owned_by(process.pid());
 }
 
 pub fn propagate_exit_to_links(process: &Process, exception: &RuntimeException) {
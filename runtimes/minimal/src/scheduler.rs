@@ -249,15 +249,28 @@ impl Scheduler {
         // and is responsible for starting/stopping the system in Erlang.
         //
         // If this process exits, the scheduler terminates
+        //
+        // An application is started by the `lumen_init` module the compiler generates for it,
+        // otherwise the program provides `init` itself
         let (init_heap, init_heap_size) = process::alloc::default_heap()?;
-        let init = Arc::new(Process::new_with_stack(
-            Priority::Normal,
-            None,
-            Arc::new(ModuleFunctionArity {
+        let app_init = ModuleFunctionArity {
+            module: Atom::from_str("lumen_init"),
+            function: Atom::from_str("start"),
+            arity: 0,
+        };
+        let init_module_function_arity = if apply::find_symbol(&app_init).is_some() {
+            app_init
+        } else {
+            ModuleFunctionArity {
                 module: Atom::from_str("init"),
                 function: Atom::from_str("start"),
                 arity: 0,
-            }),
+            }
+        };
+        let init = Arc::new(Process::new_with_stack(
+            Priority::Normal,
+            None,
+            Arc::new(init_module_function_arity),
             init_heap,
             init_heap_size,
         )?);
@@ -320,16 +333,56 @@ impl Scheduler {
         self.run_queues.write().stop_waiting(process);
     }
 
-    // TODO: Request application master termination for controlled shutdown
-    // This request will always come from the thread which spawned the application
-    // master, i.e. the "main" scheduler thread
-    //
-    // Returns `Ok(())` if shutdown was successful, `Err(anyhow::Error)` if something
-    // went wrong during shutdown, and it was not able to complete normally
+    /// Steals a runnable process from another scheduler's run queues into this scheduler's run
+    /// queues.  Returns `true` if a process was stolen.
+    pub fn steal(&self) -> bool {
+        let victims: Vec<Arc<Self>> = SCHEDULERS
+            .lock()
+            .values()
+            .filter_map(|s| s.upgrade())
+            .filter(|s| s.id != self.id)
+            .collect();
+
+        victims.iter().any(|victim| self.steal_from(victim))
+    }
+
+    /// Moves one runnable process from `victim`'s run queues to this scheduler's run queues.
+    ///
+    /// The victim's root process is never stolen, as it is the victim's own context, and only
+    /// one run queue lock is held at a time, so that two schedulers stealing from each other
+    /// cannot deadlock.
+    fn steal_from(&self, victim: &Self) -> bool {
+        let victim_root_pid = victim.root.pid();
+        let stolen = victim
+            .run_queues
+            .write()
+            .steal(|process| process.pid() != victim_root_pid);
+
+        match stolen {
+            Some(process) => {
+                process.schedule_with(self.id);
+                self.run_queues.write().enqueue(process);
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Shuts down the system in an orderly way: the scheduler stops running processes, then the
+    /// exit hooks are run.
+    ///
+    /// This is called from the scheduler loop, either after a process called
+    /// `erlang:halt/0,1,2`, or when the system is interrupted.
+    ///
+    /// Returns `Ok(())` if shutdown was successful, `Err(anyhow::Error)` if something
+    /// went wrong during shutdown, and it was not able to complete normally
     pub fn shutdown(&self) -> anyhow::Result<()> {
-        // For now just Ok(()), but this needs to be addressed when proper
-        // system startup/shutdown is in place
+        // If nothing called `halt`, the system still halts, but successfully
+        rt_core::system::request_halt(0);
         CURRENT_PROCESS.with(|cp| cp.replace(None));
+        rt_core::system::run_exit_hooks();
+
         Ok(())
     }
 }
@@ -427,14 +480,18 @@ impl Scheduler {
                     continue;
                 }
                 Run::None if is_root => {
-                    info!("no processes remaining to schedule, exiting loop");
                     // If no processes are available, then the scheduler should steal,
                     // but if it can't/doesn't, then it must terminate, as there is
-                    // nothing we can swap to. When we break here, we're returning
-                    // to the core scheduler loop, which _must_ terminate, if it does
-                    // not, we'll just end up right back here again.
-                    //
-                    // TODO: stealing
+                    // nothing we can swap to.
+                    if self.steal() {
+                        info!("stole process from another scheduler");
+                        continue;
+                    }
+
+                    info!("no processes remaining to schedule, exiting loop");
+                    // When we break here, we're returning to the core scheduler loop,
+                    // which _must_ terminate, if it does not, we'll just end up right
+                    // back here again.
                     break false;
                 }
                 Run::None => unreachable!(),
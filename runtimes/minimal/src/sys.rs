@@ -1,3 +1,4 @@
 pub mod break_handler;
 pub mod cpus;
+pub mod halt;
 pub mod io;
@@ -0,0 +1,65 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use lumen_rt_core::process::current_process;
+use lumen_rt_core::system::{self, Options, Status};
+
+extern "C" {
+    #[link_name = "__lumen_builtin_yield"]
+    fn builtin_yield() -> bool;
+
+    #[link_name = "__lumen_start_panic"]
+    fn start_panic(payload: Term) -> !;
+}
+
+#[export_name = "erlang:halt/0"]
+pub extern "C" fn halt_0() -> Term {
+    halt(Status::Code(0), Default::default())
+}
+
+#[export_name = "erlang:halt/1"]
+pub extern "C" fn halt_1(status: Term) -> Term {
+    match status.try_into() {
+        Ok(status) => halt(status, Default::default()),
+        Err(_) => raise_badarg(),
+    }
+}
+
+#[export_name = "erlang:halt/2"]
+pub extern "C" fn halt_2(status: Term, options: Term) -> Term {
+    let status: Status = match status.try_into() {
+        Ok(status) => status,
+        Err(_) => raise_badarg(),
+    };
+    let options: Options = match options.try_into() {
+        Ok(options) => options,
+        Err(_) => raise_badarg(),
+    };
+
+    halt(status, options)
+}
+
+fn halt(status: Status, options: Options) -> Term {
+    system::halt(status, options);
+
+    // `halt` never returns to Erlang code, so the process waits, and the scheduler loop stops
+    // once it is back in control
+    let process = current_process();
+    loop {
+        process.wait();
+
+        unsafe {
+            builtin_yield();
+        }
+    }
+}
+
+fn raise_badarg() -> ! {
+    let exception = current_process()
+        .tuple_from_slice(&[atom!("error"), atom!("badarg"), Term::NIL])
+        .unwrap();
+
+    unsafe { start_panic(exception) }
+}